@@ -12,4 +12,6 @@ criterion_main!(
     benchmarks::outline::benches,
     //using the outline algorithm with hysteresis and double threshold
     benchmarks::hysteresis::benches,
+    //colored output with the 16 basic ansi colors
+    benchmarks::color::benches,
 );
@@ -0,0 +1,55 @@
+use std::num::NonZeroU32;
+
+use crate::benchmarks::util;
+use artem::config::{ColorMode, DistanceMetric};
+use criterion::{criterion_group, Criterion};
+
+/// Benchmarks for the 16 basic ansi colors.
+///
+/// A large target size is used on the low resolution image, so most of the time is spent
+/// finding the nearest of the 16 colors for each character, instead of resizing the image.
+fn ansi16_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("ansi 16 colors");
+
+    //use lower sample size for faster benchmarking
+    //it should still take long enough to see relevant changes in performance
+    group.sample_size(10);
+
+    //force colors, even if the output is not a terminal
+    std::env::set_var("CLICOLOR_FORCE", "1");
+
+    let mut options = artem::config::ConfigBuilder::new();
+    options
+        .target_size(NonZeroU32::new(400).unwrap())
+        .color_mode(ColorMode::Ansi16);
+
+    //test the different distance metrics
+
+    group.bench_function("rgb distance", |b| {
+        b.iter_batched(
+            util::load_low_res_image,
+            |data| {
+                artem::convert(data, &options.distance_metric(DistanceMetric::Rgb).build()).unwrap()
+            },
+            criterion::BatchSize::LargeInput,
+        );
+    });
+
+    group.bench_function("lab distance", |b| {
+        b.iter_batched(
+            util::load_low_res_image,
+            |data| {
+                artem::convert(
+                    data,
+                    &options.distance_metric(DistanceMetric::CieLab).build(),
+                )
+                .unwrap()
+            },
+            criterion::BatchSize::LargeInput,
+        );
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, ansi16_benchmark);
@@ -6,5 +6,7 @@ pub mod size;
 pub mod outline;
 //outline version with hysteresis
 pub mod hysteresis;
+//colored output with the 16 basic ansi colors
+pub mod color;
 ///Utils for loading different images.
 mod util;
@@ -61,11 +61,8 @@ pub fn load_high_res_image() -> DynamicImage {
 /// let image = load_image("test.png");
 /// ```
 fn load_image(path: impl AsRef<std::path::Path>) -> DynamicImage {
-    let image = image::open(&path);
-
-    if image.is_ok() {
-        image.unwrap()
-    } else {
-        panic!("Failed to load image: {}", path.as_ref().to_str().unwrap())
+    match image::open(&path) {
+        Ok(image) => image,
+        Err(_) => panic!("Failed to load image: {}", path.as_ref().to_str().unwrap()),
     }
 }
@@ -0,0 +1,147 @@
+//! Conversion of pixel blocks to braille characters.
+//!
+//! A braille character (U+2800 - U+28FF) consists of up to 8 dots, which are arranged in a grid of
+//! 2 columns and 4 rows. Each dot can represent its own pixel, so a single character has a lot more detail
+//! than a character of a density ramp, which only represents the brightness of the whole tile.
+
+use image::Rgba;
+
+use crate::config::LuminanceStandard;
+
+/// Number of dot columns of a braille character.
+pub const DOTS_WIDTH: u32 = 2;
+
+/// Number of dot rows of a braille character.
+pub const DOTS_HEIGHT: u32 = 4;
+
+/// The empty braille character, all other characters are created by adding the bits of the dots.
+const BRAILLE_START: u32 = 0x2800;
+
+/// The bit of each dot, indexed by `[x][y]`.
+///
+/// The bits follow the unicode dot numbering, where the first three dots of each column are numbered
+/// from top to bottom, while the dots of the bottom row were added later and have the highest bits.
+const DOT_BITS: [[u8; DOTS_HEIGHT as usize]; DOTS_WIDTH as usize] =
+    [[0x01, 0x02, 0x04, 0x40], [0x08, 0x10, 0x20, 0x80]];
+
+/// Returns the braille character for the given pixel block.
+///
+/// The block has to contain the pixels column by column, so the pixel at `(x, y)` is at
+/// the index `x * tile_height + y`. The block is split into 2x4 parts, one for each dot. A dot is set,
+/// if the average luminance (`0.0` - `1.0`) of its part, using the given standard, is at least the threshold. If `invert` is true, dark
+/// parts set the dots instead. Dots, which do not have any pixels, because the block is smaller than 2x4 pixels,
+/// are never set.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// //a 2x4 block, where only the top left pixel is white
+/// let mut pixels = vec![Rgba::<u8>::from([0, 0, 0, 255]); 8];
+/// pixels[0] = Rgba::<u8>::from([255, 255, 255, 255]);
+/// assert_eq!('⠁', braille_char(&pixels, 2, 4, 0.5, false, LuminanceStandard::Rec709));
+/// ```
+pub fn braille_char(
+    block: &[Rgba<u8>],
+    tile_width: u32,
+    tile_height: u32,
+    threshold: f32,
+    invert: bool,
+    standard: LuminanceStandard,
+) -> char {
+    assert_eq!((tile_width * tile_height) as usize, block.len());
+
+    //sum of the luminances and number of pixels of each dot
+    let mut dots = [[(0f32, 0u32); DOTS_HEIGHT as usize]; DOTS_WIDTH as usize];
+    for x in 0..tile_width {
+        for y in 0..tile_height {
+            let pixel = block[(x * tile_height + y) as usize];
+            let dot = &mut dots[(x * DOTS_WIDTH / tile_width) as usize]
+                [(y * DOTS_HEIGHT / tile_height) as usize];
+            dot.0 += standard.luminance(pixel.0[0], pixel.0[1], pixel.0[2]) / 255f32;
+            dot.1 += 1;
+        }
+    }
+
+    let mut bits = 0;
+    for (column, column_bits) in dots.iter().zip(DOT_BITS) {
+        for ((sum, count), bit) in column.iter().zip(column_bits) {
+            if *count == 0 {
+                continue;
+            }
+            let luminance = sum / *count as f32;
+            let luminance = if invert { 1f32 - luminance } else { luminance };
+            if luminance >= threshold {
+                bits |= bit;
+            }
+        }
+    }
+    //all 256 combinations of the bits are valid braille characters
+    char::from_u32(BRAILLE_START + bits as u32).unwrap_or(' ')
+}
+
+#[cfg(test)]
+mod test_braille_char {
+    use super::*;
+
+    const WHITE: Rgba<u8> = Rgba([255, 255, 255, 255]);
+    const BLACK: Rgba<u8> = Rgba([0, 0, 0, 255]);
+    const STANDARD: LuminanceStandard = LuminanceStandard::Rec709;
+
+    #[test]
+    fn empty_and_full() {
+        assert_eq!(
+            '\u{2800}',
+            braille_char(&[BLACK; 8], 2, 4, 0.5, false, STANDARD)
+        );
+        assert_eq!(
+            '\u{28FF}',
+            braille_char(&[WHITE; 8], 2, 4, 0.5, false, STANDARD)
+        );
+    }
+
+    #[test]
+    fn dot_numbering() {
+        //each pixel of a 2x4 block sets a single dot, column by column
+        let expected = ['⠁', '⠂', '⠄', '⡀', '⠈', '⠐', '⠠', '⢀'];
+        for (index, expected) in expected.into_iter().enumerate() {
+            let mut pixels = [BLACK; 8];
+            pixels[index] = WHITE;
+            assert_eq!(expected, braille_char(&pixels, 2, 4, 0.5, false, STANDARD));
+        }
+    }
+
+    #[test]
+    fn inverted() {
+        assert_eq!(
+            '\u{28FF}',
+            braille_char(&[BLACK; 8], 2, 4, 0.5, true, STANDARD)
+        );
+        assert_eq!(
+            '\u{2800}',
+            braille_char(&[WHITE; 8], 2, 4, 0.5, true, STANDARD)
+        );
+    }
+
+    #[test]
+    fn threshold() {
+        let gray = [Rgba([100, 100, 100, 255]); 8];
+        assert_eq!('\u{28FF}', braille_char(&gray, 2, 4, 0.3, false, STANDARD));
+        assert_eq!('\u{2800}', braille_char(&gray, 2, 4, 0.5, false, STANDARD));
+    }
+
+    #[test]
+    fn larger_block() {
+        //a 4x8 block, with a white left half, sets the dots of the left column
+        let pixels: Vec<_> = (0..32)
+            .map(|index| if index < 16 { WHITE } else { BLACK })
+            .collect();
+        assert_eq!('⡇', braille_char(&pixels, 4, 8, 0.5, false, STANDARD));
+    }
+
+    #[test]
+    fn smaller_block_is_off_outside() {
+        //a single white pixel only covers the first dot, all others are outside of the block
+        assert_eq!('⠁', braille_char(&[WHITE], 1, 1, 0.5, false, STANDARD));
+        //even if inverted, the dots outside are not set
+        assert_eq!('⠁', braille_char(&[BLACK], 1, 1, 0.5, true, STANDARD));
+    }
+}
@@ -1,6 +1,9 @@
 use std::path::PathBuf;
 
-use clap::{builder::PossibleValue, value_parser, Arg, ArgAction, Command, ValueEnum, ValueHint};
+use clap::{
+    builder::{ArgPredicate, PossibleValue},
+    value_parser, Arg, ArgAction, Command, ValueEnum, ValueHint,
+};
 
 /// Get arguments from the command line.
 ///
@@ -27,14 +30,16 @@ pub fn build_cli() -> Command {
                     if cfg!(feature = "web_image")
                     {
                         //special help message with url help 
-                        "Paths or URLs to the target image. If the input is an URL, the image is downloaded and then converted. The original image is NOT altered."
+                        "Paths or URLs to the target image. If the input is an URL, the image is downloaded and then converted. Use - to read the image from the standard input. \
+                        Patterns like *.png are expanded to the matching files. The original image is NOT altered."
                     } else {
                         //normal help text with only paths
-                        "Paths to the target image. The original image is NOT altered."
+                        "Paths to the target image. Use - to read the image from the standard input. Patterns like *.png are expanded to the matching files. \
+                        The original image is NOT altered."
                     }
 
                 )
-                .required(true)
+                .required_unless_present("capabilities")
                 .value_hint(ValueHint::FilePath)
                 //because of web images accept strings, which allows for URLs and files
                 .value_parser(value_parser!(String))
@@ -51,19 +56,72 @@ pub fn build_cli() -> Command {
                 //use "\" to keep this readable but still as a single line string
                 .help("Change the characters that are used to display the image.\
                 The first character should have the highest 'darkness' and the last should have the least (recommended to be a space ' '). \
-                A lower detail map is recommend for smaller images. Included characters can be used with the argument 0 | 1 | 2. If no characters are passed in, the default set will be used."),
+                A lower detail map is recommend for smaller images. Included characters can be used with their name: short (0), standard (1), detailed (2), \
+                blocks, minimal or binary. If no characters are passed in, the default set will be used."),
+        )
+        .arg(
+            Arg::new("mapping-file")
+                .long("mapping-file")
+                .value_parser(value_parser!(PathBuf))
+                .value_hint(ValueHint::FilePath)
+                .conflicts_with("characters")
+                .help("Use a fixed mapping from luminance to characters, loaded from the given file. \
+                Each line of the file has to be a `value:char` entry, where the character is used for all luminance values (0 - 255) \
+                starting at value. The values have to be ascending and the first one has to be 0. This argument is conflicting with --characters."),
+        )
+        .arg(
+            Arg::new("ramp-gradient")
+                .long("ramp-gradient")
+                .value_parser(value_parser!(String))
+                .num_args(1..=2)
+                .value_names(["A|B", "DIRECTION"])
+                .value_hint(ValueHint::Other)
+                .conflicts_with_all(["characters", "mapping-file"])
+                .help("Blend two sets of characters, separated by a '|', by the position in the image. \
+                The output smoothly transitions from the characters A at the start to the characters B at the end of the image. \
+                The direction is either horizontal (from left to right, the default) or vertical (from top to bottom). \
+                This argument is conflicting with --characters and --mapping-file."),
+        )
+        .arg(
+            Arg::new("tonal-ramps")
+                .long("tonal-ramps")
+                .value_parser(value_parser!(String))
+                .value_name("SHADOWS|MIDTONES|HIGHLIGHTS")
+                .value_hint(ValueHint::Other)
+                .conflicts_with_all(["characters", "mapping-file", "ramp-gradient"])
+                .help("Use separate characters, separated by a '|', for the shadows (the darkest third of the luminance), \
+                the midtones and the highlights (the brightest third). Each luminance is mapped to the characters of its band. \
+                This argument is conflicting with --characters, --mapping-file and --ramp-gradient."),
+        )
+        .arg(
+            Arg::new("ramp-rounding")
+                .long("ramp-rounding")
+                .value_parser(["floor", "round", "ceil"])
+                .default_value("floor")
+                .help("How the index of the character is rounded, when mapping the brightness of the image to the characters. \
+                Rounding up (ceil) leans towards lighter, rounding down (floor) towards darker characters."),
+        )
+        .arg(
+            Arg::new("background-char")
+                .long("background-char")
+                .value_parser(parse_char)
+                .value_hint(ValueHint::Other)
+                .conflicts_with_all(["threshold", "mapping-file"])
+                .help("Replace the character of the darkest parts of the image, which is usually a space, with the given character, \
+                for example a dot or a non-breaking space. This keeps the spaces from being removed by editors, \
+                which trim the end of the lines. The colors are not changed."),
         )
         .arg(
             Arg::new("size")
                 .short('s')
                 .long("size")
                 .value_parser(value_parser!(u32))
-                .default_value("80")
                 .value_hint(ValueHint::Other)
-                .conflicts_with_all(["height", "width"])
+                .conflicts_with_all(["height", "width", "fit", "stretch"])
                 .help("Change the size of the output image. \
                 The minimum size is 20. Lower values will be \
-                ignored and changed to 20. This argument is conflicting with --width and --height."),
+                ignored and changed to 20. By default, the width of the terminal is used, \
+                or 80 if stdout is not a terminal or the output is written to a file. This argument is conflicting with --width and --height."),
         )
         .arg(
             Arg::new("height")
@@ -81,14 +139,54 @@ pub fn build_cli() -> Command {
                 .help("Use the terminal maximum terminal width to display the image. \
                 This argument is conflicting with --size and --height."),
         )
+        .arg(
+            Arg::new("fit")
+                .long("fit")
+                .value_name("COLUMNSxROWS")
+                .value_parser(parse_fit)
+                .num_args(0..=1)
+                .value_hint(ValueHint::Other)
+                .conflicts_with_all(["height", "width"])
+                .help("Fit the image into the given number of columns and rows, for example 120x40, while keeping the aspect ratio. \
+                Without a value, the size of the terminal is used. This argument is conflicting with --size, --width and --height."),
+        )
+        .arg(
+            Arg::new("stretch")
+                .long("stretch")
+                .value_name("COLUMNSxROWS")
+                .value_parser(parse_fit)
+                .value_hint(ValueHint::Other)
+                .conflicts_with_all(["height", "width", "fit"])
+                .help("Stretch the image to exactly the given number of columns and rows, for example 120x40, without keeping the aspect ratio. \
+                The image is distorted, if it has a different aspect ratio. This argument is conflicting with --size, --width, --height and --fit."),
+        )
+        .arg(
+            Arg::new("crop")
+                .long("crop")
+                .value_name("WIDTHxHEIGHT+X+Y")
+                .value_parser(parse_crop)
+                .value_hint(ValueHint::Other)
+                .help("Only convert the given region of the image, for example 200x100+50+20 for a region of 200x100 pixels, \
+                whose top left corner is 50 pixels from the left and 20 pixels from the top. The position is optional and defaults to +0+0. \
+                The size of the output is calculated from the cropped region, a region outside of the image is clamped to the image."),
+        )
         .arg(
             Arg::new("scale")
                 .long("ratio")
                 .value_parser(value_parser!(f32))
                 .default_value("0.42")
                 .value_hint(ValueHint::Other)
-                .help("Change the ratio between height and width, since ASCII characters are a bit higher than long. \
-                The value has to be between 0.1 and 1.0. It is not recommend to change this setting."),
+                .allow_negative_numbers(true)
+                .help("Change the ratio between the width and height of a character, since characters are higher than wide. \
+                The value is the width divided by the height of a character cell of the font and has to be a positive number. \
+                The default of 0.42 fits most monospace fonts, fonts with a 1:2 cell use 0.5 and fonts with a 3:5 cell use 0.6."),
+        ).arg(
+            Arg::new("filter")
+                .long("filter")
+                .value_parser(["thumbnail", "nearest", "triangle", "catmull-rom", "gaussian", "lanczos3"])
+                .default_value("thumbnail")
+                .help("Filter, which is used to resize the image before it is converted. The default thumbnail method is fast and averages the pixels. \
+                Nearest is the fastest, but may result in aliasing for detailed images, while lanczos3 is the smoothest, but also the slowest."),
         ).arg(
             Arg::new("flipX")
                 .long("flipX")
@@ -99,42 +197,245 @@ pub fn build_cli() -> Command {
                 .long("flipY")
                 .action(ArgAction::SetTrue)
                 .help("Flip the image along the Y-Axis/vertically."),
+        ).arg(
+            Arg::new("rotate")
+                .long("rotate")
+                .value_parser(["0", "90", "180", "270"])
+                .default_value("0")
+                .help("Rotate the image clockwise by the given degrees, for example for photos, which were taken sideways. \
+                The size of the output is calculated from the rotated image, the image is rotated before it is flipped or cropped."),
         ).arg(
             Arg::new("centerX")
                 .long("centerX")
+                .visible_alias("center")
                 .action(ArgAction::SetTrue)
-                .help("Center the image along the X-Axis/horizontally in the terminal."),
+                .help("Center the image along the X-Axis/horizontally in the terminal. The padding is based on the number of characters, \
+                so colors do not change it. If the terminal width is unknown, for example when the output is not a terminal, the image is not centered."),
         ).arg(
             Arg::new("centerY")
                 .long("centerY")
                 .action(ArgAction::SetTrue)
                 .help("Center the image along the Y-Axis/vertically in the terminal."),
         )
+        .arg(
+            Arg::new("rtl")
+                .long("rtl")
+                .action(ArgAction::SetTrue)
+                .help("Output the characters of each line from right to left. In contrast to --flipX, the image itself is not flipped, \
+                only the order of the characters is reversed."),
+        ).arg(
+            Arg::new("reverse-lines")
+                .long("reverse-lines")
+                .action(ArgAction::SetTrue)
+                .help("Output the lines from bottom to top. In contrast to --flipY, the image itself is not flipped, \
+                only the order of the lines is reversed."),
+        )
         .arg(
             Arg::new("output-file")
                 .short('o')
                 .long("output")
                 .value_parser(value_parser!(PathBuf))
                 .value_hint(ValueHint::FilePath)
+                .action(ArgAction::Append)
                 .help("Output file for non-colored ascii. If the output file is a plaintext file, no color will be used. The use color, either use a file with an \
-                .ansi extension, or an .svg/.html file, to convert the output to the respective format. \
+                .ansi extension, or an .svg/.html/.bbcode/.png file, to convert the output to the respective format. \
                 .ansi files will consider environment variables when creating colored output, for example when COLORTERM is not set to truecolor,\
-                the resulting file will fallback to 8-bit colors."),
+                the resulting file will fallback to 8-bit colors. This argument can be used multiple times, to write the same conversion to multiple files/formats."),
+        )
+        .arg(
+            Arg::new("format")
+                .long("format")
+                .value_parser(["text", "json"])
+                .default_value("text")
+                .help("The format of the output. The text format is rendered for the terminal or the extension of the output file. \
+                The json format contains the columns, rows and tile size of the conversion and the cells as an array of rows, \
+                where each cell is an object with its character and the sampled color as r, g and b, independent of the color support of the terminal. \
+                Output files with a .json extension always use the json format. If multiple images are converted, each image is written as its own json line."),
+        )
+        .arg(
+            Arg::new("line-ending")
+                .long("line-ending")
+                .value_parser(["lf", "crlf"])
+                .default_value("lf")
+                .help("The line ending of the output. Lf is used on Linux and macOS, crlf on Windows. \
+                Output files end without a line ending after the last line."),
+        )
+        .arg(
+            Arg::new("output-dir")
+                .long("output-dir")
+                .value_parser(value_parser!(PathBuf))
+                .value_hint(ValueHint::DirPath)
+                .conflicts_with("output-file")
+                .help("Write each image to its own file in the given directory, instead of combining all images. \
+                Each image is written directly after its conversion, so only the images being converted are kept in memory, \
+                which is useful for converting a large number of images. The files are named after the input images, \
+                with the extension given by --output-extension. Images with the same name, for example from different directories, \
+                get a number appended to their name, like image_2.txt. This argument is conflicting with --output."),
+        )
+        .arg(
+            Arg::new("output-extension")
+                .long("output-extension")
+                .value_parser(value_parser!(String))
+                .default_value("txt")
+                .value_hint(ValueHint::Other)
+                .requires("output-dir")
+                .help("The file extension of the files written to the --output-dir, which also determines the format, like for --output. \
+                For example html or ans for colored output."),
+        )
+        .arg(
+            Arg::new("jobs")
+                .short('j')
+                .long("jobs")
+                .value_parser(value_parser!(usize))
+                .default_value("1")
+                .value_hint(ValueHint::Other)
+                .requires("output-dir")
+                .help("The maximum number of images, which are converted at the same time when using --output-dir."),
+        )
+        .arg(
+            Arg::new("fail-fast")
+                .long("fail-fast")
+                .action(ArgAction::SetTrue)
+                .help("Stop at the first input, which can not be converted. By default, the error is reported and the other inputs \
+                are still converted, the program exits with the code of the first error afterwards."),
+        )
+        .arg(
+            Arg::new("sauce")
+                .long("sauce")
+                .action(ArgAction::SetTrue)
+                .help("Append a SAUCE metadata record to .ans/.ansi output files, which describes the title and dimensions of the ansi art \
+                and is used by ansi art viewers. The title can be set with --sauce-title."),
+        )
+        .arg(
+            Arg::new("sauce-title")
+                .long("sauce-title")
+                .value_parser(value_parser!(String))
+                .value_hint(ValueHint::Other)
+                .requires("sauce")
+                .help("The title used in the SAUCE record, at most 35 characters are used. Defaults to the name of the output file."),
+        )
+        .arg(
+            Arg::new("sidecar")
+                .long("sidecar")
+                .action(ArgAction::SetTrue)
+                .help("Write a sidecar file next to each output file, named like the output file with an additional .meta extension. \
+                It contains the columns and rows of the ascii art, the source dimensions and the color mode as JSON, \
+                which can be used by other tools to place the output precisely."),
+        )
+        .arg(
+            Arg::new("css-classes")
+                .long("css-classes")
+                .action(ArgAction::SetTrue)
+                .help("Use css classes instead of inline styles for colored html output files. Each distinct color is defined \
+                once as a class, which results in much smaller files for images with only a few colors. This only affects .html files."),
+        )
+        .arg(
+            Arg::new("html-background")
+                .long("html-background")
+                .value_parser(parse_hex_color)
+                .value_hint(ValueHint::Other)
+                .help("The background color of .html output files as a hex color, for example #FFFFFF. \
+                It is used for the pre element, which contains the ascii art, so it can be embedded into any page."),
+        )
+        .arg(
+            Arg::new("svg-font-family")
+                .long("svg-font-family")
+                .value_hint(ValueHint::Other)
+                .help("The font family of .svg output files, which can be a list of fonts, like in css. The default is monospace."),
+        )
+        .arg(
+            Arg::new("svg-font-size")
+                .long("svg-font-size")
+                .value_parser(value_parser!(f32))
+                .value_hint(ValueHint::Other)
+                .help("The font size of .svg output files in pixels, the default is 14. \
+                Each character is 0.6 times the font size wide and 1.2 times the font size high, which determines the size of the svg."),
         )
         .arg(
             Arg::new("invert-density")
                 .long("invert")
                 .action(ArgAction::SetTrue)
-                .help("Inverts the characters used for the image, so light characters will as dark ones. Can be useful if the image has a dark background."),
+                .help("Inverts the characters used for the image, so bright parts of the image use the light characters and dark parts the dense ones. \
+                This is useful for terminals with a light background. Only the characters are inverted, the colors stay the same."),
+        )
+        .arg(
+            Arg::new("negative")
+                .long("negative")
+                .action(ArgAction::SetTrue)
+                .help("Invert the colors of the image before converting it, like a photo negative. In contrast to --invert, \
+                which only inverts the characters, this changes both the characters and the colors of the output."),
+        )
+        .arg(
+            Arg::new("brightness")
+                .long("brightness")
+                .value_parser(value_parser!(f64))
+                .allow_negative_numbers(true)
+                .value_hint(ValueHint::Other)
+                .help("Change the brightness of the image before converting it (-1.0 - 1.0). \
+                Positive values lighten the image, negative values darken it, 0.0 does not change it."),
+        )
+        .arg(
+            Arg::new("contrast")
+                .long("contrast")
+                .value_parser(value_parser!(f64))
+                .allow_negative_numbers(true)
+                .value_hint(ValueHint::Other)
+                .help("Change the contrast of the image before converting it (-1.0 - 1.0). \
+                Positive values increase the contrast, negative values reduce it, until -1.0 results in a uniform gray image."),
+        )
+        .arg(
+            Arg::new("auto-contrast")
+                .long("auto-contrast")
+                .action(ArgAction::SetTrue)
+                .help("Stretch the brightness of the image between its 1st and 99th percentile to the full range of characters, \
+                which improves low contrast images, like photos taken in fog or at night. The darkest and lightest percent \
+                of the image is clipped, so a few outliers, like a highlight, do not prevent the stretching. \
+                This is independent of --brightness and --contrast."),
+        )
+        .arg(
+            Arg::new("silhouette")
+                .long("silhouette")
+                .action(ArgAction::SetTrue)
+                .help("Convert only the alpha channel of the image, which results in a silhouette of the opaque regions. \
+                The colors are ignored, fully opaque pixels use the densest character and fully transparent pixels the lightest one."),
         )
         .arg(
             Arg::new("background-color")
                 .long("background")
                 .conflicts_with("no-color")
                 .action(ArgAction::SetTrue)
-                .help("Sets the background of the ascii as the color. This will be ignored if the terminal does not support truecolor. \
+                .help("Sets the background of the ascii as the color. Without truecolor support, the nearest of the 256 or 16 colors is used. \
+                Together with a space character, this creates a colored mosaic of the image. \
                 This argument is mutually exclusive with the no-color argument."),
         )
+        .arg(
+            Arg::new("min-contrast")
+                .long("min-contrast")
+                .value_parser(value_parser!(f64))
+                .value_hint(ValueHint::Other)
+                .help("Lighten or darken the character colors, while keeping their hue, to have at least the given contrast ratio \
+                against the terminal background. The ratio is calculated like the WCAG contrast ratio and has to be between 1.0 and 21.0, \
+                for example 4.5 keeps most characters readable. The background can be changed with --contrast-background."),
+        )
+        .arg(
+            Arg::new("contrast-background")
+                .long("contrast-background")
+                .value_parser(parse_hex_color)
+                .default_value("#000000")
+                .value_hint(ValueHint::Other)
+                .requires("min-contrast")
+                .help("The background color of the terminal as a hex color, for example #FFFFFF for a white background, \
+                which is used for the --min-contrast argument. The default is a black background."),
+        )
+        .arg(
+            Arg::new("background-fill")
+                .long("background-fill")
+                .value_parser(parse_hex_color)
+                .value_hint(ValueHint::Other)
+                .help("Blend transparent pixels with the given hex color, for example #FFFFFF for a white background, \
+                before converting the image. Fully transparent parts of the image are converted to spaces. \
+                Without this argument, the colors of the pixels are weighted by their alpha value."),
+        )
         .arg(
             Arg::new("border")
                 .long("border")
@@ -142,12 +443,134 @@ pub fn build_cli() -> Command {
                 .help("Adds a decorative border surrounding the ascii image. This will make the image overall a bit smaller, \
                 since it respects the user given size."),
         )
+        .arg(
+            Arg::new("border-style")
+                .long("border-style")
+                .value_parser(["double", "single", "ascii"])
+                .default_value("double")
+                .requires("border")
+                .help("The style of the --border. double and single use box drawing characters, \
+                ascii only uses +, - and | for terminals and fonts without box drawing characters."),
+        )
         .arg(
             Arg::new("no-color")
                 .long("no-color")
                 .action(ArgAction::SetTrue)
+                .default_value_if("mono-optimize", ArgPredicate::IsPresent, "true")
+                .default_value_if("markdown", ArgPredicate::IsPresent, "true")
+                .default_value_if("whitespace", "compact", "true")
+                .default_value_if("pin-color-mode", "none", "true")
                 .help("Do not use color when printing the image to the terminal."),
         )
+        .arg(
+            Arg::new("force-color")
+                .long("force-color")
+                .visible_alias("color")
+                .action(ArgAction::SetTrue)
+                .conflicts_with("no-color")
+                .help("Use colors, even if stdout is not a terminal, for example when piping the output into less -R. \
+                By default, colors are only used for terminals, like ls and grep do. \
+                This also keeps the ansi colors in plain text output files, which are written without colors by default."),
+        )
+        .arg(
+            Arg::new("color-mode")
+                .long("color-mode")
+                .value_parser(["truecolor", "256", "16"])
+                .default_value_if("pin-color-mode", "truecolor", "truecolor")
+                .default_value_if("pin-color-mode", "256", "256")
+                .default_value_if("pin-color-mode", "16", "16")
+                .help("The color depth of the terminal and .ans output. By default, truecolor is used if the terminal supports it (COLORTERM), \
+                otherwise 256 colors if the TERM environment variable contains 256color, and the 16 basic ansi colors as the last fallback. The 256 colors of the xterm palette are a lot closer to the original colors than \
+                the 16 basic colors, and are supported by many terminals without truecolor support. The 16 colors do not support background colors."),
+        )
+        .arg(
+            Arg::new("pin-color-mode")
+                .long("pin-color-mode")
+                .value_parser(["truecolor", "256", "16", "none"])
+                .conflicts_with_all(["color-mode", "no-color", "force-color"])
+                .help("Pin the color mode of the terminal and .ans output, so the output does not depend on the environment. \
+                Unlike --color-mode, the colors are used even if stdout is not a terminal, and environment variables, \
+                like COLORTERM, TERM, NO_COLOR and CLICOLOR_FORCE, are ignored. none never uses colors. \
+                The same input and arguments then result in byte-identical output, for example for snapshot tests in CI."),
+        )
+        .arg(
+            Arg::new("color-distance")
+                .long("color-distance")
+                .value_parser(["rgb", "lab"])
+                .default_value("rgb")
+                .help("How the nearest of the 16 basic ansi colors is found. Lab compares the colors in the CIELAB color space, \
+                which is closer to the human perception, so browns and dark blues are not replaced by red or black. \
+                It is used by the 16 color mode and the --color-palette."),
+        )
+        .arg(
+            Arg::new("trim")
+                .long("trim")
+                .action(ArgAction::SetTrue)
+                .help("Remove the empty rows and columns at the edges of the ascii art, for example the margins of a logo \
+                on a black or transparent background. Empty rows and columns only contain spaces or the character of the darkest parts. \
+                The ascii art is trimmed before it is centered or a border is added."),
+        )
+        .arg(
+            Arg::new("markdown")
+                .long("markdown")
+                .action(ArgAction::SetTrue)
+                .conflicts_with_all(["background-color", "min-contrast"])
+                .help("Wrap the output in a markdown code block, so the columns stay aligned when pasting the ascii art \
+                into a chat or documentation, which uses a proportional font. This disables color."),
+        )
+        .arg(
+            Arg::new("whitespace")
+                .long("whitespace")
+                .value_parser(["preserve", "compact"])
+                .default_value("preserve")
+                .help("How runs of spaces are written. Compact removes the spaces at the end of each line and replaces \
+                runs of at least two spaces, which end on a tab stop (every 8 columns), with a tab. \
+                This keeps the columns aligned with the usual tab width, but disables color."),
+        )
+        .arg(
+            Arg::new("char-height-compensation")
+                .long("char-height-compensation")
+                .value_parser(value_parser!(f32))
+                .value_name("FACTOR")
+                .value_hint(ValueHint::Other)
+                .help("Stretch the ascii art vertically by the given factor, after it is converted. \
+                Instead of compensating for the height of the characters with --ratio, the rows are repeated, \
+                for example 2 repeats every row and 1.5 every second row. The factor has to be at least 1. \
+                This is useful together with --ratio 1, which converts square parts of the image."),
+        )
+        .arg(
+            Arg::new("compensation-rows")
+                .long("compensation-rows")
+                .value_parser(["duplicate", "blank"])
+                .default_value("duplicate")
+                .requires("char-height-compensation")
+                .help("Rows, which are added by --char-height-compensation. \
+                Duplicate repeats the previous row including its colors, blank adds empty rows."),
+        )
+        .arg(
+            Arg::new("mono-optimize")
+                .long("mono-optimize")
+                .action(ArgAction::SetTrue)
+                .conflicts_with_all(["background-color", "min-contrast"])
+                .help("Optimize the output for monochrome displays, such as e-ink displays or grayscale terminals. \
+                This disables color, stretches the contrast of the image to the full range of characters \
+                and uses dithering to avoid visible bands in smooth gradients."),
+        )
+        .arg(
+            Arg::new("dither")
+                .long("dither")
+                .value_parser(["floyd-steinberg", "bayer4", "bayer8", "stipple"])
+                .num_args(0..=1)
+                .default_missing_value("floyd-steinberg")
+                .conflicts_with("braille")
+                .help("Use dithering when choosing the characters, which avoids visible bands in smooth gradients. \
+                Floyd–Steinberg dithering, which is the default, distributes the difference between the brightness of each part of the image \
+                and the brightness of its character to the following parts. The Bayer modes use ordered dithering with a 4x4 or 8x8 matrix, \
+                which results in a regular pattern, that only depends on the position of each part. \
+                Stipple randomly chooses one of the two characters next to the brightness of each part, which looks like a hand-drawn stipple drawing. \
+                The random numbers are generated from --seed, so the same seed always results in the same output. \
+                This also works with --threshold, which then alternates between the two characters."),
+        )
         .arg(
             Arg::new("outline")
                 .long("outline")
@@ -164,6 +587,235 @@ pub fn build_cli() -> Command {
                 .help("When creating the outline use the hysteresis method, which will remove imperfection, but might not be as good looking in ascii form.\
                  This will require the --outline argument to be present as well."),
         )
+        .arg(
+            Arg::new("graded-edges")
+                .long("graded-edges")
+                .requires("outline")
+                .action(ArgAction::SetTrue)
+                .help("Choose the characters of the outline by the strength of the edges, \
+                faint characters such as '.' for weak edges and line characters matching the direction, such as '|' or '/', for strong edges. \
+                This results in softer looking contours and will require the --outline argument to be present as well."),
+        )
+        .arg(
+            Arg::new("directional")
+                .long("directional")
+                .action(ArgAction::SetTrue)
+                .help("Use directional characters for parts of the image with a clear texture direction, \
+                for example '-' and '=' for horizontal stripes or '|' and '!' for vertical stripes. This is a lighter alternative to --outline."),
+        )
+        .arg(
+            Arg::new("edges")
+                .long("edges")
+                .value_name("threshold")
+                .value_parser(value_parser!(f32))
+                .num_args(0..=1)
+                .default_missing_value("0.3")
+                .value_hint(ValueHint::Other)
+                .conflicts_with_all(["outline", "directional"])
+                .help("Use the line characters '|', '-', '/' and '\\' for parts of the image with a strong edge, depending on the direction of the edge. \
+                The edges are detected with a Sobel operator, parts with an edge strength below the threshold (0.0 - 1.0, 0.3 by default) use the characters as usual. \
+                This improves the recognizability of line art and faces, while the image itself stays visible."),
+        )
+        .arg(
+            Arg::new("braille")
+                .long("braille")
+                .value_name("threshold")
+                .value_parser(value_parser!(f32))
+                .num_args(0..=1)
+                .default_missing_value("0.5")
+                .value_hint(ValueHint::Other)
+                .conflicts_with_all(["directional", "mono-optimize", "edges"])
+                .help("Use braille characters, which have 2x4 dots, instead of the characters. Each dot is set, if its part of the image \
+                is brighter than the threshold (0.0 - 1.0, 0.5 by default), or darker if --invert is used. \
+                This results in 8 times more details, but the brightness is only represented by the number of dots."),
+        )
+        .arg(
+            Arg::new("half-blocks")
+                .long("half-blocks")
+                .action(ArgAction::SetTrue)
+                .conflicts_with_all(["no-color", "braille", "directional", "outline", "background-color", "edges"])
+                .help("Use upper half blocks ('▀') instead of the characters, with the color of the upper pixel as the foreground \
+                and the color of the lower pixel as the background. This doubles the vertical resolution, but requires colors. \
+                Only the terminal and html output show both colors."),
+        )
+        .arg(
+            Arg::new("partial-block")
+                .long("partial-block")
+                .value_parser(["render", "pad"])
+                .default_value("render")
+                .requires("half-blocks")
+                .help("How the last row of --half-blocks is converted, if the image ends in its upper half. \
+                render resizes the image to whole rows, pad keeps the size of the image and fills the lower half \
+                with the --background-fill color or black."),
+        )
+        .arg(
+            Arg::new("only-color")
+                .long("only-color")
+                .action(ArgAction::SetTrue)
+                .conflicts_with_all(["no-color", "mono-optimize", "braille", "half-blocks", "directional", "outline", "edges"])
+                .help("Use solid blocks ('█') instead of the characters, so the image is only shown by the colors, like a mosaic. \
+                With --background-color, spaces with a background color are used instead."),
+        )
+        .arg(
+            Arg::new("gamma")
+                .long("gamma")
+                .value_parser(value_parser!(f64))
+                .num_args(0..=1)
+                .default_missing_value("2.2")
+                .value_hint(ValueHint::Other)
+                .help("Calculate the brightness of each part of the image in linear light and encode it with the given gamma (2.2 by default), \
+                instead of using the average sRGB color. This brightens the midtones, which often improves photographs. \
+                A gamma of 1.0 uses the linear brightness. The colors are not changed."),
+        )
+        .arg(
+            Arg::new("luminance")
+                .long("luminance")
+                .value_parser(["rec709", "rec601", "average"])
+                .default_value("rec709")
+                .help("Weights of the red, green and blue channel, which are used to calculate the brightness of each part of the image. \
+                Rec. 601 weights green less than Rec. 709, so green parts are darker, while average weights all channels equally."),
+        )
+        .arg(
+            Arg::new("channel")
+                .long("channel")
+                .value_parser(["luminance", "red", "green", "blue"])
+                .default_value("luminance")
+                .help("Channel of the colors, which is used to choose the characters. By default the brightness of the colors is used, \
+                a single channel is useful for scientific or false color images, such as single band satellite images."),
+        )
+        .arg(
+            Arg::new("threshold")
+                .long("threshold")
+                .value_parser(value_parser!(f64))
+                .num_args(0..=1)
+                .default_missing_value("0.5")
+                .value_hint(ValueHint::Other)
+                .conflicts_with_all(["characters", "mapping-file", "ramp-gradient", "tonal-ramps", "braille"])
+                .help("Only use two characters, the --on-char for parts of the image, which are at least as bright as the threshold \
+                (0.0 - 1.0, 0.5 by default), and the --off-char for darker parts. This results in pure black and white output, \
+                for example for logos or stencils. The characters are swapped if --invert is used."),
+        )
+        .arg(
+            Arg::new("on-char")
+                .long("on-char")
+                .value_parser(parse_char)
+                .default_value("#")
+                .value_hint(ValueHint::Other)
+                .requires("threshold")
+                .help("Character for the bright parts of the image, when using --threshold."),
+        )
+        .arg(
+            Arg::new("off-char")
+                .long("off-char")
+                .value_parser(parse_char)
+                .default_value(" ")
+                .value_hint(ValueHint::Other)
+                .requires("threshold")
+                .help("Character for the dark parts of the image, when using --threshold."),
+        )
+        .arg(
+            Arg::new("dominant-color")
+                .long("dominant-color")
+                .action(ArgAction::SetTrue)
+                .help("Do not convert the image, instead print its dominant color as a hex color (e.g. #FF8800), \
+                followed by a colored swatch. The swatch can be disabled with --no-color."),
+        )
+        .arg(
+            Arg::new("capabilities")
+                .long("capabilities")
+                .action(ArgAction::SetTrue)
+                .help("Do not convert an image, instead print the detected terminal capabilities: truecolor support, \
+                the terminal width, the TERM, COLORTERM and TERM_PROGRAM environment variables, the color mode, which would be used, \
+                and the detected inline image protocol. \
+                This can help to debug why the colors look wrong."),
+        )
+        .arg(
+            Arg::new("image-protocol")
+                .long("image-protocol")
+                .value_parser(["auto", "kitty", "iterm", "none"])
+                .default_value("none")
+                .conflicts_with_all(["output-file", "output-dir"])
+                .help("Show the resized image itself in the terminal using an inline image protocol, instead of converting it to ascii art. \
+                The image covers the same columns and rows as the ascii art would. Kitty uses the graphics protocol of Kitty (also supported by Ghostty and Konsole), \
+                iterm the inline images of iTerm2 (also supported by WezTerm). Auto detects the protocol from the TERM and TERM_PROGRAM environment variables, \
+                if stdout is a terminal. Ascii art is used as the fallback, if no protocol is supported, and is the default."),
+        )
+        .arg(
+            Arg::new("ink-budget")
+                .long("ink-budget")
+                .value_parser(value_parser!(usize))
+                .value_hint(ValueHint::Other)
+                .help("Limit the number of non-whitespace characters. Only the characters with the highest contrast to their neighbours, \
+                such as edges, are kept, all others are replaced by spaces, which results in a sparse, minimalist ascii art."),
+        )
+        .arg(
+            Arg::new("vignette")
+                .long("vignette")
+                .value_parser(value_parser!(f32))
+                .value_hint(ValueHint::Other)
+                .help("Darken the image towards the edges with the given strength (0.0 - 1.0), so the subject in the center stands out \
+                and the edges fade out. A strength of 1.0 fades the corners completely."),
+        )
+        .arg(
+            Arg::new("mask")
+                .long("mask")
+                .value_parser(value_parser!(PathBuf))
+                .value_hint(ValueHint::FilePath)
+                .help("Weight the image with a grayscale mask image, which should have the same aspect ratio as the image. \
+                Bright regions of the mask keep all details, while dark regions are blanked. This can be used to highlight a region."),
+        )
+        .arg(
+            Arg::new("color-step")
+                .long("color-step")
+                .value_parser(value_parser!(u8).range(1..))
+                .value_hint(ValueHint::Other)
+                .help("Round each color channel to the nearest multiple of the given step (1 - 255), for example 16. \
+                Small changes of the image then do not change the color of every character, which results in cleaner diffs \
+                when the output is committed to version control."),
+        )
+        .arg(
+            Arg::new("color-palette")
+                .long("color-palette")
+                .value_parser(parse_hex_color)
+                .value_delimiter(',')
+                .value_hint(ValueHint::Other)
+                .help("Only use the colors of the given palette, which is a comma separated list of hex colors, \
+                for example #000000,#FFFFFF. Each color is replaced by the nearest color of the palette, \
+                using the --color-distance, which is useful to match the colors of a theme or a retro system."),
+        )
+        .arg(
+            Arg::new("palette")
+                .long("palette")
+                .value_parser(value_parser!(usize))
+                .value_hint(ValueHint::Other)
+                .help("Do not convert the image, instead print a palette of at most the given number of colors as hex colors, \
+                the most common first. The palette is extracted using k-means clustering, which is seeded by --seed, \
+                so the same image and seed always result in the same palette."),
+        )
+        .arg(
+            Arg::new("seed")
+                .long("seed")
+                .value_parser(value_parser!(u64))
+                .default_value("0")
+                .value_hint(ValueHint::Other)
+                .help("Seed for all randomized parts of the conversion, such as the palette extraction and --dither stipple. \
+                Using the same seed with the same input always produces the same output."),
+        )
+        .arg(
+            Arg::new("loop")
+                .long("loop")
+                .action(ArgAction::SetTrue)
+                .help("Repeat animated gifs until the program is stopped. An animated gif, which is the only input and is printed to the terminal, \
+                is played frame by frame using the delays of the gif. Otherwise, for example when the output is piped or redirected to a file, \
+                only the first frame is converted."),
+        )
+        .arg(
+            Arg::new("stats")
+                .long("stats")
+                .action(ArgAction::SetTrue)
+                .help("Print statistics about the conversion to stderr, such as the source dimensions, the number of columns and rows, \
+                the number of distinct colors, the written bytes and the elapsed time. This does not affect the ascii art."),
+        )
         .arg(
             Arg::new("verbosity")
                 .long("verbose")
@@ -172,7 +824,116 @@ pub fn build_cli() -> Command {
                 .help("Choose the verbosity of the logging level. Warnings and errors will always be shown by default. To completely disable them, \
                 use the off argument."),
         )
+        .args(lut_arg())
+        .args(interactive_arg())
+        .args(exif_arg())
+        .args(png_arg())
+}
+
+/// Returns the argument for applying a 3D lut, which is only available with the `lut` feature.
+fn lut_arg() -> Option<Arg> {
+    #[cfg(feature = "lut")]
+    return Some(
+        Arg::new("lut")
+            .long("lut")
+            .value_parser(value_parser!(PathBuf))
+            .value_hint(ValueHint::FilePath)
+            .help("Apply a 3D color lookup table from the given .cube file to the image before converting it. \
+            This can be used to apply a color grading, such as a film look, to the image."),
+    );
+    #[cfg(not(feature = "lut"))]
+    None
+}
+
+/// Returns the argument for transparent png files, which is only available with the `png` feature.
+fn png_arg() -> Option<Arg> {
+    #[cfg(feature = "png")]
+    return Some(
+        Arg::new("transparent")
+            .long("transparent")
+            .action(ArgAction::SetTrue)
+            .help("Use a fully transparent background for .png output files, instead of a black one. \
+            Only the characters are drawn, so the image can be placed on top of other graphics."),
+    );
+    #[cfg(not(feature = "png"))]
+    None
+}
+
+/// Returns the argument for the interactive preview, which is only available with the `interactive` feature.
+fn interactive_arg() -> Option<Arg> {
+    #[cfg(feature = "interactive")]
+    return Some(
+        Arg::new("interactive")
+            .long("interactive")
+            .action(ArgAction::SetTrue)
+            .help("Show an interactive preview of the first image in the terminal, before converting the images. \
+            The arrow keys change the size (up/down) and the ratio (left/right), tab switches the characters. \
+            Enter converts the images with the chosen settings, escape quits without an output."),
+    );
+    #[cfg(not(feature = "interactive"))]
+    None
+}
+
+/// Returns the argument for disabling the EXIF orientation, which is only available with the `exif` feature.
+fn exif_arg() -> Option<Arg> {
+    #[cfg(feature = "exif")]
+    return Some(
+        Arg::new("no-auto-orient")
+            .long("no-auto-orient")
+            .action(ArgAction::SetTrue)
+            .help("Do not rotate and flip the images according to their EXIF orientation. By default, photos, which are stored sideways, \
+            are converted upright, like they are shown by image viewers. The orientation is applied before --rotate."),
+    );
+    #[cfg(not(feature = "exif"))]
+    None
+}
+
+/// Parse a hex color like `#FF8800` or `FF8800` to its red, green and blue values.
+fn parse_hex_color(input: &str) -> Result<(u8, u8, u8), String> {
+    let hex = input.strip_prefix('#').unwrap_or(input);
+    if hex.len() != 6 || !hex.is_ascii() {
+        return Err(format!("{input} is not a hex color like #FF8800"));
+    }
+    let channel = |index: usize| {
+        u8::from_str_radix(&hex[index..index + 2], 16)
+            .map_err(|_| format!("{input} is not a hex color like #FF8800"))
+    };
+    Ok((channel(0)?, channel(2)?, channel(4)?))
+}
+
+/// Parse the maximum columns and rows of the `--fit` argument, which are separated by an `x`, like `120x40`.
+fn parse_fit(input: &str) -> Result<(u32, u32), String> {
+    let error = || format!("{input} is not a size like 120x40");
+    let (columns, rows) = input.split_once(['x', 'X']).ok_or_else(error)?;
+    let parse = |value: &str| value.trim().parse::<u32>().map_err(|_| error());
+    Ok((parse(columns)?, parse(rows)?))
+}
+
+/// Parse the region of the `--crop` argument, which consists of the size and an optional position, like `200x100+50+20`.
+///
+/// The region is returned as the x and y position, followed by the width and height.
+fn parse_crop(input: &str) -> Result<(u32, u32, u32, u32), String> {
+    let error = || format!("{input} is not a region like 200x100+50+20");
+    let mut parts = input.split('+');
+    let (width, height) = parse_fit(parts.next().unwrap_or_default()).map_err(|_| error())?;
+    let mut position = parts.map(|value| value.trim().parse::<u32>().map_err(|_| error()));
+    let (x, y) = match (position.next(), position.next(), position.next()) {
+        (None, None, None) => (0, 0),
+        (Some(x), Some(y), None) => (x?, y?),
+        _ => return Err(error()),
+    };
+    Ok((x, y, width, height))
+}
+
+/// Parse a single character, for example for the characters of the monochrome mode.
+fn parse_char(input: &str) -> Result<char, String> {
+    let mut chars = input.chars();
+    match (chars.next(), chars.next()) {
+        (Some(char), None) => Ok(char),
+        _ => Err(format!("{input} is not a single character")),
+    }
 }
+
 /// Verbosity enum for different logging levels.
 ///
 /// This enum is used for accepting the `--verbose` argument with different logging levels.
@@ -255,6 +1016,81 @@ impl From<Verbosity> for log::LevelFilter {
     }
 }
 
+#[cfg(test)]
+mod test_parse_hex_color {
+    use super::*;
+
+    #[test]
+    fn with_and_without_hash() {
+        assert_eq!(Ok((255, 136, 0)), parse_hex_color("#FF8800"));
+        assert_eq!(Ok((255, 136, 0)), parse_hex_color("ff8800"));
+    }
+
+    #[test]
+    fn invalid_color() {
+        assert!(parse_hex_color("#FF88").is_err());
+        assert!(parse_hex_color("#GG8800").is_err());
+        assert!(parse_hex_color("#FF88001").is_err());
+    }
+}
+
+#[cfg(test)]
+mod test_parse_fit {
+    use super::*;
+
+    #[test]
+    fn valid_size() {
+        assert_eq!(Ok((120, 40)), parse_fit("120x40"));
+        assert_eq!(Ok((80, 24)), parse_fit("80X24"));
+    }
+
+    #[test]
+    fn invalid_size() {
+        assert!(parse_fit("").is_err());
+        assert!(parse_fit("120").is_err());
+        assert!(parse_fit("120x").is_err());
+        assert!(parse_fit("-1x40").is_err());
+    }
+}
+
+#[cfg(test)]
+mod test_parse_crop {
+    use super::*;
+
+    #[test]
+    fn valid_region() {
+        assert_eq!(Ok((50, 20, 200, 100)), parse_crop("200x100+50+20"));
+        //the position is optional
+        assert_eq!(Ok((0, 0, 200, 100)), parse_crop("200x100"));
+    }
+
+    #[test]
+    fn invalid_region() {
+        assert!(parse_crop("").is_err());
+        assert!(parse_crop("200x100+50").is_err());
+        assert!(parse_crop("200x100+50+20+10").is_err());
+        assert!(parse_crop("200x100+-5+20").is_err());
+        assert!(parse_crop("+50+20").is_err());
+    }
+}
+
+#[cfg(test)]
+mod test_parse_char {
+    use super::*;
+
+    #[test]
+    fn single_char() {
+        assert_eq!(Ok('#'), parse_char("#"));
+        assert_eq!(Ok('█'), parse_char("█"));
+    }
+
+    #[test]
+    fn invalid_char() {
+        assert!(parse_char("").is_err());
+        assert!(parse_char("ab").is_err());
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -265,6 +1101,12 @@ mod test {
         assert!(matches.is_err());
     }
 
+    #[test]
+    fn capabilities_without_input() {
+        let matches = build_cli().try_get_matches_from(["artem", "--capabilities"]);
+        assert!(matches.is_ok());
+    }
+
     #[test]
     fn success_input() {
         let matches = build_cli().try_get_matches_from(["artem", "../example/abraham_lincoln.jpg"]);
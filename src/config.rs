@@ -1,4 +1,4 @@
-use std::num::NonZeroU32;
+use std::{num::NonZeroU32, str::FromStr};
 
 ///Preferred image resize direction
 ///
@@ -18,6 +18,18 @@ pub enum ResizingDimension {
     #[default]
     Width,
     Height,
+    /// Fit the output into the target size as the maximum number of columns and the given maximum number of rows,
+    /// while keeping the aspect ratio.
+    Both {
+        /// Maximum number of rows.
+        rows: u32,
+    },
+    /// Stretch the output to exactly the target size as the number of columns and the given number of rows,
+    /// without keeping the aspect ratio.
+    Stretch {
+        /// Number of rows.
+        rows: u32,
+    },
 }
 
 impl ResizingDimension {
@@ -27,15 +39,25 @@ impl ResizingDimension {
     /// values based on a target_size. It returns them as a tuple, the elements are in the previously named order.
     /// The dimension property can be used to change what dimension will be scaled. Since terminal character are a bit higher the wide,
     /// Width and Height of the output needs to be based on either one, so the other can be calculated.
+    /// Both calculates the dimensions based on the width and the height and uses the smaller ones, so neither of them is exceeded.
+    /// Stretch uses exactly the given columns and rows, so the image is distorted, if it has a different aspect ratio.
+    ///
+    /// # Errors
+    /// Returns [`ArtemError::ZeroTargetSize`], if the target size (or the maximum number of rows of [`ResizingDimension::Both`]) is 0,
+    /// and [`ArtemError::InvalidDimensions`], if the image is empty. Returns [`ArtemError::InvalidStretch`], if the rows of
+    /// [`ResizingDimension::Stretch`] are 0 or the columns or rows are larger than [`crate::MAX_PRESCALED_SIZE`].
     ///
     /// # Examples
     /// ```
-    /// use artem::config::{ResizingDimension};
+    /// use artem::config::{ArtemError, ResizingDimension};
     ///
     /// assert_eq!(
-    /// (100, 46, 5, 11),
+    /// Ok((100, 46, 5, 11)),
     /// //image with a size of 512x512, split into 100 columns with no border
     /// ResizingDimension::calculate_dimensions(100, 512, 512, 0.42, false, ResizingDimension::Width));
+    /// assert_eq!(
+    /// Err(ArtemError::InvalidDimensions(0, 512)),
+    /// ResizingDimension::calculate_dimensions(100, 512, 0, 0.42, false, ResizingDimension::Width));
     /// ```
     pub fn calculate_dimensions(
         target_size: u32,
@@ -44,7 +66,13 @@ impl ResizingDimension {
         scale: f32,
         border: bool,
         dimension: ResizingDimension,
-    ) -> (u32, u32, u32, u32) {
+    ) -> Result<(u32, u32, u32, u32), ArtemError> {
+        if target_size == 0 {
+            return Err(ArtemError::ZeroTargetSize);
+        }
+        if width == 0 || height == 0 {
+            return Err(ArtemError::InvalidDimensions(width, height));
+        }
         match dimension {
             ResizingDimension::Width => {
                 //calculate dimensions based on columns
@@ -61,26 +89,28 @@ impl ResizingDimension {
 
                 //calculate tiles
                 let tile_width = width / columns;
-                let tile_height = (tile_width as f32 / scale).floor() as u32;
+                //a tile has to be at least a single pixel high, otherwise there is nothing to convert
+                let tile_height = ((tile_width as f32 / scale).floor() as u32).max(1);
 
                 let rows = height / tile_height;
 
                 //.max(1) is used to ensure that the values are at least 1
                 //a value of 0 could cause an error (but not crash) later on
-                (columns.max(1), rows.max(1), tile_width, tile_height)
+                Ok((columns.max(1), rows.max(1), tile_width, tile_height))
             }
 
             ResizingDimension::Height => {
                 let mut rows = if height > target_size {
-                    // minus 1, since the user input line is included
-                    target_size - 1
+                    // minus 1, since the user input line is included, but at least a single row is needed
+                    (target_size - 1).max(1)
                 } else {
                     height
                 };
 
                 //calculate tiles
                 let tile_height = height / rows;
-                let tile_width = (tile_height as f32 * scale).ceil() as u32;
+                //a tile has to be at least a single pixel wide, otherwise there is nothing to convert
+                let tile_width = ((tile_height as f32 * scale).ceil() as u32).max(1);
 
                 let mut columns = width / tile_width;
 
@@ -92,7 +122,52 @@ impl ResizingDimension {
 
                 //.max(1) is used to ensure that the values are at least 1
                 //a value of 0 could cause an error (but not crash) later on
-                (columns.max(1), rows.max(1), tile_width, tile_height)
+                Ok((columns.max(1), rows.max(1), tile_width, tile_height))
+            }
+
+            ResizingDimension::Both { rows } => {
+                let by_width = Self::calculate_dimensions(
+                    target_size,
+                    height,
+                    width,
+                    scale,
+                    border,
+                    ResizingDimension::Width,
+                )?;
+                let by_height = Self::calculate_dimensions(
+                    rows,
+                    height,
+                    width,
+                    scale,
+                    border,
+                    ResizingDimension::Height,
+                )?;
+                //the height already limits the rows, so the width fits, if it does not result in more rows
+                Ok(if by_width.1 <= by_height.1 {
+                    by_width
+                } else {
+                    by_height
+                })
+            }
+
+            ResizingDimension::Stretch { rows } => {
+                if rows == 0
+                    || target_size > crate::MAX_PRESCALED_SIZE
+                    || rows > crate::MAX_PRESCALED_SIZE
+                {
+                    return Err(ArtemError::InvalidStretch(target_size, rows));
+                }
+                let (mut columns, mut rows) = (target_size, rows);
+                if border {
+                    //remove a bit of space for the border
+                    columns = columns.saturating_sub(2).max(1);
+                    rows = rows.saturating_sub(2).max(1);
+                }
+
+                //the tiles do not keep the aspect ratio, an image smaller than the output is enlarged
+                let tile_width = (width / columns).max(1);
+                let tile_height = (height / rows).max(1);
+                Ok((columns, rows, tile_width, tile_height))
             }
         }
     }
@@ -133,8 +208,21 @@ pub enum TargetType {
     /// SVG
     ///
     /// Supports both color and background colors.
-    /// The resulting output file may have visual glitches.
+    /// The characters are placed in a grid, so the output scales without pixelation.
     Svg,
+    /// BBCode, as used by many forums
+    ///
+    /// Supports color, but not background colors.
+    BbCode,
+    /// JSON, with the character and color of each cell
+    ///
+    /// Always contains the sampled colors, but no background colors.
+    Json,
+    /// PNG image, with the characters drawn by a bundled font
+    ///
+    /// Supports both color and background colors. The image is rendered by [`crate::Grid::render_image`],
+    /// which requires the `png` feature, as text the characters are not colored.
+    Png,
 }
 
 impl TargetType {
@@ -147,439 +235,3178 @@ impl TargetType {
     /// Returns whether the output supports background colors
     pub(crate) fn supports_background_colors(&self) -> bool {
         match self {
-            TargetType::Shell | TargetType::HtmlFile | TargetType::Svg => true,
-            TargetType::AnsiFile | TargetType::File => false,
+            TargetType::Shell | TargetType::HtmlFile | TargetType::Svg | TargetType::Png => true,
+            TargetType::AnsiFile | TargetType::File | TargetType::BbCode | TargetType::Json => {
+                false
+            }
         }
     }
 }
 
-///Config for the conversion of the image to the ascii image.
-#[derive(Debug, PartialEq)]
-pub struct Config {
-    pub characters: String,
-    pub scale: f32,
-    pub target_size: u32,
-    pub color: bool,
-    pub background_color: bool,
-    pub invert: bool,
-    pub border: bool,
-    pub dimension: ResizingDimension,
-    pub transform_x: bool,
-    pub transform_y: bool,
-    pub center_x: bool,
-    pub center_y: bool,
-    pub outline: bool,
-    pub hysteresis: bool,
-    pub target: TargetType,
+/// Color depth of ansi colored output, which is used for the terminal and `.ans` files.
+///
+/// If no color mode is set in the [`Config`], the best mode supported by the terminal is used,
+/// see [`ColorMode::detect`].
+///
+/// # Examples
+/// ```
+/// use artem::config::{ColorMode, ConfigBuilder};
+///
+/// let config = ConfigBuilder::new().color_mode(ColorMode::Ansi256).build();
+/// assert_eq!(ColorMode::Ansi256, config.resolved_color_mode());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorMode {
+    /// 24-bit colors.
+    Truecolor,
+    /// The 256 colors of the xterm palette.
+    Ansi256,
+    /// The 16 basic ansi colors, which are supported by nearly every terminal.
+    Ansi16,
 }
 
-impl Config {
-    /// Create [`ConfigBuilder`] with default properties.
-    ///
-    /// # Examples
-    /// ```
-    /// use artem::config::Config;
-    ///
-    /// let default = Config::builder();
-    /// ```
-    pub fn builder() -> ConfigBuilder {
-        ConfigBuilder::default()
+impl ColorMode {
+    /// Returns the best color mode, which is supported by the terminal.
+    ///
+    /// Truecolor is used if it is supported (see [`crate::SUPPORTS_TRUECOLOR`]), otherwise the 256 colors,
+    /// if they are supported (see [`crate::supports_256color`]). If neither is supported, the 16 basic ansi colors are used.
+    pub fn detect() -> ColorMode {
+        if *crate::SUPPORTS_TRUECOLOR {
+            ColorMode::Truecolor
+        } else if crate::supports_256color() {
+            ColorMode::Ansi256
+        } else {
+            ColorMode::Ansi16
+        }
     }
+}
 
-    /// Returns whether the output should be colored.
-    ///
-    /// This depends on both if colored output is enabled and the output target supports colored
-    /// output.
-    pub(crate) fn color(&self) -> bool {
-        self.color && self.target.supports_color()
-    }
+/// Metric for the distance between two colors, which is used to find the nearest of the 16 basic ansi colors
+/// or the nearest color of a palette.
+///
+/// # Examples
+/// ```
+/// use artem::config::DistanceMetric;
+///
+/// assert_eq!(DistanceMetric::Rgb, DistanceMetric::default());
+/// ```
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DistanceMetric {
+    /// Squared euclidean distance of the red, green and blue values, which is the default.
+    #[default]
+    Rgb,
+    /// Euclidean distance in the CIELAB color space (CIE76), which is a lot closer to human perception,
+    /// for example dark blues do not collapse into black.
+    CieLab,
+}
 
-    /// Returns whether the output should be colored using background colors.
-    ///
-    /// This depends on both if background color is enabled and the output target supports
-    /// background colored output.
-    pub(crate) fn background_color(&self) -> bool {
-        self.background_color && self.target.supports_background_colors()
+/// Dithering, which is used when choosing the characters.
+///
+/// Dithering alternates between the characters in smooth gradients, so they do not show visible bands.
+/// Floyd–Steinberg dithering diffuses the error of each tile to its neighbours, so the tiles have to be
+/// processed in order, while ordered dithering only depends on the position of each tile.
+///
+/// # Examples
+/// ```
+/// use artem::config::DitherMode;
+///
+/// assert_eq!(DitherMode::None, DitherMode::default());
+/// ```
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DitherMode {
+    /// No dithering, each tile uses the character of its luminance, which is the default.
+    #[default]
+    None,
+    /// Ordered dithering with the 4x4 Bayer matrix, which is repeated over the image.
+    Bayer4,
+    /// Ordered dithering with the 8x8 Bayer matrix, which has more levels, but a larger pattern.
+    Bayer8,
+    /// Floyd–Steinberg error diffusion, which distributes the error of each tile to the following tiles.
+    FloydSteinberg,
+    /// Random dithering, which results in a noisy, hand-drawn stipple look.
+    ///
+    /// Like ordered dithering, but the threshold of each tile is random, so darker tiles only have a higher chance
+    /// of a denser character. The random numbers are generated from the seed of the [`Config`], so the same seed
+    /// always results in the same output.
+    Stipple,
+}
+
+impl DitherMode {
+    /// Returns the size of the Bayer matrix, if this is ordered dithering.
+    pub fn bayer_size(&self) -> Option<usize> {
+        match self {
+            DitherMode::Bayer4 => Some(4),
+            DitherMode::Bayer8 => Some(8),
+            DitherMode::None | DitherMode::FloydSteinberg | DitherMode::Stipple => None,
+        }
     }
 }
 
-impl Default for Config {
-    fn default() -> Self {
-        Self {
-            characters: r#"MWNXK0Okxdolc:;,'...   "#.to_string(),
-            scale: 0.42f32,
-            target_size: 80,
-            color: true,
-            background_color: false,
-            invert: Default::default(),
-            border: Default::default(),
-            dimension: Default::default(),
-            transform_x: Default::default(),
-            transform_y: Default::default(),
-            center_x: Default::default(),
-            center_y: Default::default(),
-            outline: Default::default(),
-            hysteresis: Default::default(),
-            target: Default::default(),
+/// Weights of the red, green and blue channel, which are used to calculate the luminance of a color.
+///
+/// The luminance chooses the characters, so the standard changes how bright colored parts of the image appear,
+/// especially green, which is weighted very differently.
+///
+/// # Examples
+/// ```
+/// use artem::config::LuminanceStandard;
+///
+/// assert_eq!(LuminanceStandard::Rec709, LuminanceStandard::default());
+/// assert_eq!(85f32, LuminanceStandard::Average.luminance(0, 255, 0));
+/// ```
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LuminanceStandard {
+    /// The coefficients of Rec. 709 (sRGB), rounded to `0.21`, `0.72` and `0.07`, which is the default.
+    #[default]
+    Rec709,
+    /// The older coefficients of Rec. 601 (`0.299`, `0.587` and `0.114`), which weight green less and red and blue more.
+    Rec601,
+    /// The plain average of the three channels.
+    Average,
+}
+
+impl LuminanceStandard {
+    /// Returns the weights of the red, green and blue channel, which add up to `1.0`.
+    pub fn weights(&self) -> (f32, f32, f32) {
+        match self {
+            LuminanceStandard::Rec709 => (0.21, 0.72, 0.07),
+            LuminanceStandard::Rec601 => (0.299, 0.587, 0.114),
+            LuminanceStandard::Average => (1f32 / 3f32, 1f32 / 3f32, 1f32 / 3f32),
         }
     }
+
+    /// Returns the luminance (`0.0` - `255.0`) of the given color.
+    pub fn luminance(&self, red: u8, green: u8, blue: u8) -> f32 {
+        let (red_weight, green_weight, blue_weight) = self.weights();
+        (red_weight * red as f32) + (green_weight * green as f32) + (blue_weight * blue as f32)
+    }
 }
 
 #[cfg(test)]
-mod test_option {
+mod test_luminance_standard {
     use super::*;
+
     #[test]
-    fn builder_default() {
-        assert_eq!(
-            ConfigBuilder {
-                characters: r#"MWNXK0Okxdolc:;,'...   "#.to_string(),
-                scale: 0.42f32,
-                target_size: 80,
-                color: true,
-                background_color: false,
-                invert: false,
-                border: false,
-                dimension: ResizingDimension::Width,
-                transform_x: false,
-                transform_y: false,
-                center_x: false,
-                center_y: false,
-                outline: false,
-                hysteresis: false,
-                target: TargetType::default(),
-            },
-            Config::builder()
-        );
+    fn pure_green() {
+        assert_eq!(183.6, LuminanceStandard::Rec709.luminance(0, 255, 0));
+        assert_eq!(149.685, LuminanceStandard::Rec601.luminance(0, 255, 0));
+        assert_eq!(85f32, LuminanceStandard::Average.luminance(0, 255, 0));
+    }
+
+    #[test]
+    fn gray_is_equal() {
+        for standard in [
+            LuminanceStandard::Rec709,
+            LuminanceStandard::Rec601,
+            LuminanceStandard::Average,
+        ] {
+            assert!((standard.luminance(100, 100, 100) - 100f32).abs() < 0.001);
+        }
     }
 }
 
-///A builder to create a [`Config`] struct.
-#[derive(PartialEq, Debug)]
-pub struct ConfigBuilder {
-    characters: String,
-    scale: f32,
-    target_size: u32,
-    color: bool,
-    background_color: bool,
-    invert: bool,
-    border: bool,
-    dimension: ResizingDimension,
-    transform_x: bool,
-    transform_y: bool,
-    center_x: bool,
-    center_y: bool,
-    outline: bool,
-    hysteresis: bool,
-    target: TargetType,
+/// Channel of the colors, which chooses the characters.
+///
+/// By default the luminance of the colors is used. A single channel is useful for scientific or false color images,
+/// such as single band satellite images, where only one of the channels contains the data.
+///
+/// # Examples
+/// ```
+/// use artem::config::{Channel, LuminanceStandard};
+///
+/// assert_eq!(Channel::Luminance, Channel::default());
+/// assert_eq!(255f32, Channel::Blue.value(0, 0, 255, LuminanceStandard::Rec709));
+/// ```
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Channel {
+    /// The luminance of the color, using the [`LuminanceStandard`] of the config, which is the default.
+    #[default]
+    Luminance,
+    /// Only the red channel.
+    Red,
+    /// Only the green channel.
+    Green,
+    /// Only the blue channel.
+    Blue,
 }
 
-impl Default for ConfigBuilder {
-    fn default() -> Self {
-        Self {
-            //these have to be set to custom defaults for the program to work
-            characters: r#"MWNXK0Okxdolc:;,'...   "#.to_string(),
-            scale: 0.42f32,
-            target_size: 80,
-            color: true,
-            background_color: false,
-            invert: Default::default(),
-            border: Default::default(),
-            dimension: Default::default(),
-            transform_x: Default::default(),
-            transform_y: Default::default(),
-            center_x: Default::default(),
-            center_y: Default::default(),
-            outline: Default::default(),
-            hysteresis: Default::default(),
-            target: Default::default(),
+impl Channel {
+    /// Returns the weights of the red, green and blue channel, which add up to `1.0`.
+    pub fn weights(&self, standard: LuminanceStandard) -> (f32, f32, f32) {
+        match self {
+            Channel::Luminance => standard.weights(),
+            Channel::Red => (1f32, 0f32, 0f32),
+            Channel::Green => (0f32, 1f32, 0f32),
+            Channel::Blue => (0f32, 0f32, 1f32),
+        }
+    }
+
+    /// Returns the value (`0.0` - `255.0`) of the channel of the given color.
+    pub fn value(&self, red: u8, green: u8, blue: u8, standard: LuminanceStandard) -> f32 {
+        match self {
+            Channel::Luminance => standard.luminance(red, green, blue),
+            Channel::Red => red as f32,
+            Channel::Green => green as f32,
+            Channel::Blue => blue as f32,
         }
     }
 }
 
-/// Generate a builder property.
+#[cfg(test)]
+mod test_channel {
+    use super::*;
+
+    #[test]
+    fn single_channels() {
+        let standard = LuminanceStandard::default();
+        assert_eq!(10f32, Channel::Red.value(10, 20, 30, standard));
+        assert_eq!(20f32, Channel::Green.value(10, 20, 30, standard));
+        assert_eq!(30f32, Channel::Blue.value(10, 20, 30, standard));
+    }
+
+    #[test]
+    fn luminance_uses_standard() {
+        assert_eq!(
+            85f32,
+            Channel::Luminance.value(0, 255, 0, LuminanceStandard::Average)
+        );
+        assert_eq!(
+            LuminanceStandard::Rec601.weights(),
+            Channel::Luminance.weights(LuminanceStandard::Rec601)
+        );
+    }
+}
+
+/// Clockwise rotation of the image, which is applied before it is converted.
 ///
-/// This macro generates a function, which sets the specified property of the Builder.
-/// It does NOT check the value passed in, so for example it would be possible to pass in an empty string, which could lead to
-/// errors later this. This should be done before setting the value.
+/// Rotating by 90 or 270 degrees swaps the width and height of the image, so the size of the ascii art is
+/// calculated from the rotated image.
 ///
 /// # Examples
-///
-/// ``` compile_fail, just an internal example code
-/// property!{
-/// ///Example doc
-/// ///This generates a name setter function.
-/// => name, String
-/// }
 /// ```
-/// ## Generated function
-/// ``` compile_fail, just an internal example code
-/// ///Example doc
-/// ///This generates a name setter function.
-/// pub fn name<'a>(&'a mut self, name: String) -> &'a Self {
-///     self.name = name;
-///     self
-/// }
+/// use artem::config::Rotation;
+///
+/// assert_eq!(Rotation::None, Rotation::default());
+/// assert_eq!(Some(Rotation::Rotate90), Rotation::from_degrees(90));
+/// assert_eq!(None, Rotation::from_degrees(45));
 /// ```
-macro_rules! property {
-    ($(#[$attr:meta])* => $field:ident, $field_type:ty) => {
-        $(#[$attr])*
-        pub fn $field(&mut self, $field: $field_type) -> &mut Self {
-            self.$field = $field;
-            self
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rotation {
+    /// The image is not rotated, which is the default.
+    #[default]
+    None,
+    /// Rotate the image by 90 degrees clockwise.
+    Rotate90,
+    /// Rotate the image by 180 degrees.
+    Rotate180,
+    /// Rotate the image by 270 degrees clockwise, which is 90 degrees counterclockwise.
+    Rotate270,
+}
+
+impl Rotation {
+    /// Returns the rotation for the given clockwise degrees, if they are a multiple of 90.
+    pub fn from_degrees(degrees: u32) -> Option<Self> {
+        match degrees % 360 {
+            0 => Some(Rotation::None),
+            90 => Some(Rotation::Rotate90),
+            180 => Some(Rotation::Rotate180),
+            270 => Some(Rotation::Rotate270),
+            _ => None,
         }
-    };
-    ($(#[$attr:meta])* => $field:ident, $field_type:ty, $func:ident) => {
-        $(#[$attr])*
-        pub fn $field(&mut self, $field: $field_type) -> &mut Self {
-            self.$field = $field.$func();
-            self
+    }
+
+    /// Returns the rotated image.
+    pub fn apply(&self, image: image::DynamicImage) -> image::DynamicImage {
+        match self {
+            Rotation::None => image,
+            Rotation::Rotate90 => image.rotate90(),
+            Rotation::Rotate180 => image.rotate180(),
+            Rotation::Rotate270 => image.rotate270(),
         }
-    };
+    }
 }
 
-impl ConfigBuilder {
-    ///Create a new ConfigBuilder.
+#[cfg(test)]
+mod test_rotation {
+    use image::{DynamicImage, GenericImageView};
+
+    use super::*;
+
+    #[test]
+    fn degrees() {
+        assert_eq!(Some(Rotation::None), Rotation::from_degrees(0));
+        assert_eq!(Some(Rotation::Rotate180), Rotation::from_degrees(180));
+        assert_eq!(Some(Rotation::Rotate270), Rotation::from_degrees(270));
+        assert_eq!(Some(Rotation::Rotate90), Rotation::from_degrees(450));
+        assert_eq!(None, Rotation::from_degrees(100));
+    }
+
+    #[test]
+    fn swaps_dimensions() {
+        let image = DynamicImage::new_rgb8(40, 10);
+        assert_eq!(
+            (10, 40),
+            Rotation::Rotate90.apply(image.clone()).dimensions()
+        );
+        assert_eq!(
+            (40, 10),
+            Rotation::Rotate180.apply(image.clone()).dimensions()
+        );
+        assert_eq!((10, 40), Rotation::Rotate270.apply(image).dimensions());
+    }
+}
+
+/// Style of the border, which surrounds the ascii art, if the border is enabled.
+///
+/// # Examples
+/// ```
+/// use artem::config::BorderStyle;
+///
+/// assert_eq!(BorderStyle::Double, BorderStyle::default());
+/// assert_eq!("┌──┐", BorderStyle::Single.top(2));
+/// assert_eq!("+--+", BorderStyle::Ascii.bottom(2));
+/// ```
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BorderStyle {
+    /// Double box drawing lines (`╔═╗║╚╝`), which is the default.
+    #[default]
+    Double,
+    /// Single box drawing lines (`┌─┐│└┘`).
+    Single,
+    /// Only ascii characters (`+-|`), for terminals and fonts without box drawing characters.
+    Ascii,
+}
+
+impl BorderStyle {
+    /// Returns the corners (top left, top right, bottom left, bottom right) and the horizontal line of the border.
+    fn chars(&self) -> ([char; 4], char) {
+        match self {
+            BorderStyle::Double => (['╔', '╗', '╚', '╝'], '═'),
+            BorderStyle::Single => (['┌', '┐', '└', '┘'], '─'),
+            BorderStyle::Ascii => (['+', '+', '+', '+'], '-'),
+        }
+    }
+
+    /// Returns the top line of the border around the given number of columns, without a line break.
+    pub fn top(&self, columns: u32) -> String {
+        let ([left, right, _, _], line) = self.chars();
+        format!("{left}{}{right}", line.to_string().repeat(columns as usize))
+    }
+
+    /// Returns the bottom line of the border around the given number of columns, without a line break.
+    pub fn bottom(&self, columns: u32) -> String {
+        let ([_, _, left, right], line) = self.chars();
+        format!("{left}{}{right}", line.to_string().repeat(columns as usize))
+    }
+
+    /// Returns the character on the left and right side of each row.
+    pub fn side(&self) -> char {
+        match self {
+            BorderStyle::Double => '║',
+            BorderStyle::Single => '│',
+            BorderStyle::Ascii => '|',
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_border_style {
+    use super::*;
+
+    #[test]
+    fn double() {
+        assert_eq!("╔═══╗", BorderStyle::Double.top(3));
+        assert_eq!('║', BorderStyle::Double.side());
+        assert_eq!("╚═══╝", BorderStyle::Double.bottom(3));
+    }
+
+    #[test]
+    fn ascii_only() {
+        let style = BorderStyle::Ascii;
+        assert!(style.top(5).is_ascii());
+        assert!(style.bottom(5).is_ascii());
+        assert!(style.side().is_ascii());
+    }
+
+    #[test]
+    fn width_matches_columns() {
+        for style in [BorderStyle::Double, BorderStyle::Single, BorderStyle::Ascii] {
+            //the corners are added to the columns
+            assert_eq!(12, style.top(10).chars().count());
+            assert_eq!(12, style.bottom(10).chars().count());
+        }
+    }
+}
+
+/// Filter, which is used to resize the image to the size of the tiles, before it is converted.
+///
+/// The default is the fast thumbnail method, which averages the pixels covered by each resulting pixel.
+/// Since every tile averages its pixels anyway, this is usually good enough and a lot faster than the other filters.
+/// The other filters are the ones of [`image::imageops::FilterType`], where [`ResizeFilter::Nearest`] is the fastest,
+/// but results in aliasing for detailed images, while [`ResizeFilter::Lanczos3`] is the smoothest, but also the slowest.
+///
+/// # Examples
+/// ```
+/// use artem::config::ResizeFilter;
+///
+/// assert_eq!(ResizeFilter::Thumbnail, ResizeFilter::default());
+/// assert_eq!(
+///     Some(image::imageops::FilterType::Lanczos3),
+///     ResizeFilter::Lanczos3.filter_type()
+/// );
+/// ```
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResizeFilter {
+    /// Fast averaging of the pixels, which is the default.
+    #[default]
+    Thumbnail,
+    /// Nearest neighbor, which uses a single pixel.
+    Nearest,
+    /// Linear filter.
+    Triangle,
+    /// Cubic filter.
+    CatmullRom,
+    /// Gaussian filter.
+    Gaussian,
+    /// Lanczos filter with a window of 3.
+    Lanczos3,
+}
+
+impl ResizeFilter {
+    /// Returns the filter type of the image crate, or `None` for the thumbnail method, which does not use one.
+    pub fn filter_type(&self) -> Option<image::imageops::FilterType> {
+        use image::imageops::FilterType;
+        match self {
+            ResizeFilter::Thumbnail => None,
+            ResizeFilter::Nearest => Some(FilterType::Nearest),
+            ResizeFilter::Triangle => Some(FilterType::Triangle),
+            ResizeFilter::CatmullRom => Some(FilterType::CatmullRom),
+            ResizeFilter::Gaussian => Some(FilterType::Gaussian),
+            ResizeFilter::Lanczos3 => Some(FilterType::Lanczos3),
+        }
+    }
+}
+
+/// Named presets of characters, from the densest to the lightest character.
+///
+/// Each preset has a name, which can be resolved with [`CharacterRamp::from_name`]. The shorter names and
+/// numbers of the original presets (`s`/`0` for short, `f`/`1` for standard and `l`/`2` for detailed) are accepted as well.
+///
+/// # Examples
+/// ```
+/// use artem::config::CharacterRamp;
+///
+/// assert_eq!(Some(CharacterRamp::Binary), CharacterRamp::from_name("binary"));
+/// assert_eq!("# ", CharacterRamp::resolve("binary"));
+/// //unknown names are used as characters
+/// assert_eq!("xo ", CharacterRamp::resolve("xo "));
+/// ```
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CharacterRamp {
+    /// The density map of jp2a, which is the default.
+    #[default]
+    Standard,
+    /// A short ramp of letters, digits and symbols.
+    Short,
+    /// The 70 characters of Paul Bourke's ramp.
+    Detailed,
+    /// Unicode shade blocks.
+    Blocks,
+    /// Only three characters and a space.
+    Minimal,
+    /// A single character and a space, which results in black and white output.
+    Binary,
+}
+
+impl CharacterRamp {
+    /// All presets, in the order of their numbers.
+    pub const ALL: [CharacterRamp; 6] = [
+        CharacterRamp::Short,
+        CharacterRamp::Standard,
+        CharacterRamp::Detailed,
+        CharacterRamp::Blocks,
+        CharacterRamp::Minimal,
+        CharacterRamp::Binary,
+    ];
+
+    /// Returns the characters of the preset, from the densest to the lightest character.
+    pub fn characters(&self) -> &'static str {
+        match self {
+            CharacterRamp::Standard => r#"MWNXK0Okxdolc:;,'...   "#,
+            CharacterRamp::Short => r#"Ñ@#W$9876543210?!abc;:+=-,._ "#,
+            CharacterRamp::Detailed => {
+                r#"$@B%8&WM#*oahkbdpqwmZO0QLCJUYXzcvunxrjft/\|()1{}[]?-_+~<>i!lI;:,"^`'. "#
+            }
+            CharacterRamp::Blocks => "█▓▒░ ",
+            CharacterRamp::Minimal => "#:. ",
+            CharacterRamp::Binary => "# ",
+        }
+    }
+
+    /// Returns the preset with the given name, or `None` if there is no such preset.
+    pub fn from_name(name: &str) -> Option<CharacterRamp> {
+        match name {
+            "standard" | "flat" | "f" | "1" => Some(CharacterRamp::Standard),
+            "short" | "s" | "0" => Some(CharacterRamp::Short),
+            "detailed" | "long" | "l" | "2" => Some(CharacterRamp::Detailed),
+            "blocks" => Some(CharacterRamp::Blocks),
+            "minimal" => Some(CharacterRamp::Minimal),
+            "binary" => Some(CharacterRamp::Binary),
+            _ => None,
+        }
+    }
+
+    /// Returns the characters of the preset with the given name, or the input itself, if it is not the name of a preset.
+    pub fn resolve(input: &str) -> &str {
+        CharacterRamp::from_name(input).map_or(input, |ramp| ramp.characters())
+    }
+}
+
+#[cfg(test)]
+mod test_character_ramp {
+    use super::*;
+
+    #[test]
+    fn presets_resolve_to_characters() {
+        assert_eq!(
+            r#"MWNXK0Okxdolc:;,'...   "#,
+            CharacterRamp::resolve("standard")
+        );
+        assert_eq!(70, CharacterRamp::resolve("detailed").chars().count());
+        assert!(CharacterRamp::resolve("detailed").starts_with("$@B%8&WM#*"));
+        assert_eq!("█▓▒░ ", CharacterRamp::resolve("blocks"));
+        assert_eq!("#:. ", CharacterRamp::resolve("minimal"));
+        assert_eq!("# ", CharacterRamp::resolve("binary"));
+    }
+
+    #[test]
+    fn numbers_and_short_names() {
+        assert_eq!(CharacterRamp::resolve("short"), CharacterRamp::resolve("0"));
+        assert_eq!(
+            CharacterRamp::resolve("standard"),
+            CharacterRamp::resolve("f")
+        );
+        assert_eq!(
+            CharacterRamp::resolve("detailed"),
+            CharacterRamp::resolve("long")
+        );
+    }
+
+    #[test]
+    fn unknown_name_is_custom_ramp() {
+        assert_eq!(None, CharacterRamp::from_name("Standard"));
+        assert_eq!("@%. ", CharacterRamp::resolve("@%. "));
+    }
+
+    #[test]
+    fn default_config_uses_standard() {
+        assert_eq!(
+            CharacterRamp::Standard.characters(),
+            Config::default().characters
+        );
+    }
+}
+
+/// Font of svg files.
+///
+/// Each character is placed in a cell, which is `0.6` times the font size wide and `1.2` times the font size high,
+/// which matches most monospace fonts. The size of the svg is calculated from the columns and rows of the ascii art.
+///
+/// # Examples
+/// ```
+/// use artem::config::SvgFont;
+///
+/// let font = SvgFont::default();
+/// assert_eq!("monospace", font.family);
+/// assert_eq!(14f32, font.size);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SvgFont {
+    /// Font family, which can be a list of fonts, like in css.
+    pub family: String,
+    /// Font size in pixels.
+    pub size: f32,
+}
+
+impl Default for SvgFont {
+    fn default() -> Self {
+        Self {
+            family: String::from("monospace"),
+            size: 14f32,
+        }
+    }
+}
+
+/// A fixed mapping from luminance values to characters.
+///
+/// Instead of linearly mapping the luminance to the characters, each entry maps a luminance
+/// bucket to a specific character. An entry `(value, char)` is used for all luminance values starting at
+/// `value` (0 - 255), until the next entry begins. This allows for a more precise control
+/// than the linear mapping of the characters.
+///
+/// The entries have to be strictly ascending and the first entry has to start at 0, so that
+/// every luminance value is covered.
+///
+/// A mapping can be parsed from a string, which contains one `value:char` entry per line.
+///
+/// # Examples
+/// ```
+/// use artem::config::CharacterMapping;
+///
+/// let mapping: CharacterMapping = "0: \n128:#".parse().unwrap();
+/// assert_eq!(' ', mapping.char_for(127));
+/// assert_eq!('#', mapping.char_for(128));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CharacterMapping {
+    entries: Vec<(u8, char)>,
+}
+
+impl CharacterMapping {
+    /// Create a new mapping from the given entries.
+    ///
+    /// # Errors
+    /// Returns an error message, if the entries are empty, do not start at 0 or are not strictly ascending.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::CharacterMapping;
+    ///
+    /// assert!(CharacterMapping::new(vec![(0, ' '), (200, '#')]).is_ok());
+    /// assert!(CharacterMapping::new(vec![(200, '#'), (0, ' ')]).is_err());
+    /// ```
+    pub fn new(entries: Vec<(u8, char)>) -> Result<Self, String> {
+        match entries.first() {
+            None => return Err("The character mapping is empty".to_string()),
+            Some((value, _)) if *value != 0 => {
+                return Err(format!(
+                    "The character mapping has to start at 0, but starts at {value}"
+                ))
+            }
+            _ => {}
+        }
+
+        if let Some(window) = entries.windows(2).find(|window| window[0].0 >= window[1].0) {
+            return Err(format!(
+                "The character mapping has to be strictly ascending, but {} is followed by {}",
+                window[0].0, window[1].0
+            ));
+        }
+
+        Ok(Self { entries })
+    }
+
+    /// Returns the character for the given luminance value (0 - 255).
+    pub fn char_for(&self, luminance: u8) -> char {
+        //entries are sorted, so use the last entry which starts before the luminance
+        let index = self
+            .entries
+            .partition_point(|(value, _)| *value <= luminance);
+        //the first entry always starts at 0, so the index is at least 1
+        self.entries[index - 1].1
+    }
+}
+
+impl FromStr for CharacterMapping {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let entries = s
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| {
+                let (value, char) = line
+                    .split_once(':')
+                    .ok_or_else(|| format!("Line {}: expected `value:char`", index + 1))?;
+                let value = value.trim().parse::<u8>().map_err(|_| {
+                    format!(
+                        "Line {}: {value} is not a value between 0 and 255",
+                        index + 1
+                    )
+                })?;
+                let mut chars = char.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(char), None) => Ok((value, char)),
+                    _ => Err(format!("Line {}: expected a single character", index + 1)),
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        CharacterMapping::new(entries)
+    }
+}
+
+#[cfg(test)]
+mod test_character_mapping {
+    use super::*;
+
+    #[test]
+    fn parse_mapping() {
+        assert_eq!(
+            Ok(CharacterMapping {
+                entries: vec![(0, ' '), (100, '.'), (200, '#')]
+            }),
+            "0: \n100:.\n\n200:#\n".parse::<CharacterMapping>()
+        );
+    }
+
+    #[test]
+    fn empty_mapping() {
+        assert!("".parse::<CharacterMapping>().is_err());
+    }
+
+    #[test]
+    fn mapping_not_starting_at_zero() {
+        assert!("10: \n200:#".parse::<CharacterMapping>().is_err());
+    }
+
+    #[test]
+    fn unordered_mapping() {
+        assert!("0: \n200:#\n100:.".parse::<CharacterMapping>().is_err());
+    }
+
+    #[test]
+    fn duplicate_value() {
+        assert!("0: \n100:#\n100:.".parse::<CharacterMapping>().is_err());
+    }
+
+    #[test]
+    fn invalid_lines() {
+        assert!("0 #".parse::<CharacterMapping>().is_err());
+        assert!("0:ab".parse::<CharacterMapping>().is_err());
+        assert!("256:#".parse::<CharacterMapping>().is_err());
+    }
+
+    #[test]
+    fn char_for_bucket() {
+        let mapping = CharacterMapping::new(vec![(0, ' '), (100, '.'), (200, '#')]).unwrap();
+        assert_eq!(' ', mapping.char_for(0));
+        assert_eq!(' ', mapping.char_for(99));
+        assert_eq!('.', mapping.char_for(100));
+        assert_eq!('.', mapping.char_for(199));
+        assert_eq!('#', mapping.char_for(200));
+        assert_eq!('#', mapping.char_for(255));
+    }
+}
+
+/// A minimum contrast of the character colors against a known background color.
+///
+/// The contrast is calculated as the WCAG contrast ratio, which ranges from `1.0` (no contrast)
+/// to `21.0` (black on white). Colors with a lower contrast are lightened or darkened, while
+/// keeping their hue, so the characters stay readable on the given background.
+///
+/// # Examples
+/// ```
+/// use artem::config::MinContrast;
+///
+/// //at least a contrast of 4.5 against a black terminal background
+/// let contrast = MinContrast {
+///     background: (0, 0, 0),
+///     ratio: 4.5,
+/// };
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MinContrast {
+    /// Background color (red, green, blue) the characters are displayed on.
+    pub background: (u8, u8, u8),
+    /// Minimum contrast ratio between `1.0` and `21.0`.
+    pub ratio: f64,
+}
+
+/// Rounding of the character index, when mapping a luminance to the characters.
+///
+/// The luminance is mapped to a fractional index of the characters, which is then rounded.
+/// Since the characters are ordered from dark to light, rounding up leans towards lighter characters
+/// and rounding down towards darker characters.
+///
+/// # Examples
+/// ```
+/// use artem::config::RampRounding;
+///
+/// assert_eq!(RampRounding::Floor, RampRounding::default());
+/// ```
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RampRounding {
+    /// Round down, which is the default.
+    #[default]
+    Floor,
+    /// Round to the nearest index.
+    Round,
+    /// Round up.
+    Ceil,
+}
+
+/// How the last row of half blocks is converted, if the image only covers its upper half.
+///
+/// The rows are calculated like for the other modes, so an image, which keeps its aspect ratio, can end
+/// in the middle of a row of half blocks, for example with an odd height.
+///
+/// # Examples
+/// ```
+/// use artem::config::PartialBlock;
+///
+/// assert_eq!(PartialBlock::Render, PartialBlock::default());
+/// ```
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PartialBlock {
+    /// Render the partial row from the available pixels, by resizing the image to whole rows, which is the default.
+    #[default]
+    Render,
+    /// Add the partial row and pad its lower half with the background,
+    /// which is the color of [`ConfigBuilder::background_fill`] or black.
+    Pad,
+}
+
+/// How runs of spaces are written in output without colors.
+///
+/// In [`Whitespace::Compact`] mode the following rule is applied to each line, which keeps every
+/// character in the same column, when viewed with the usual tab stops of 8 columns:
+/// - spaces at the end of a line are removed
+/// - a run of at least two spaces, which ends on a tab stop, is replaced by a single tab
+///
+/// Only spaces are collapsed, other light characters are always kept.
+///
+/// # Examples
+/// ```
+/// use artem::config::Whitespace;
+///
+/// assert_eq!(Whitespace::Preserve, Whitespace::default());
+/// ```
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Whitespace {
+    /// Keep all spaces, which is the default.
+    #[default]
+    Preserve,
+    /// Remove trailing spaces and replace runs of spaces with tabs.
+    Compact,
+}
+
+/// Rows, which are added to stretch the ascii art vertically with [`Config::char_height_compensation`].
+///
+/// # Examples
+/// ```
+/// use artem::config::CompensationRows;
+///
+/// assert_eq!(CompensationRows::Duplicate, CompensationRows::default());
+/// ```
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompensationRows {
+    /// Repeat the previous row, including its colors, which is the default.
+    #[default]
+    Duplicate,
+    /// Add an empty row, which only contains the border and spaces.
+    Blank,
+}
+
+/// Line break, which ends the lines of the output.
+///
+/// # Examples
+/// ```
+/// use artem::config::LineEnding;
+///
+/// assert_eq!(LineEnding::Lf, LineEnding::default());
+/// assert_eq!("\r\n", LineEnding::Crlf.as_str());
+/// ```
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineEnding {
+    /// A single line feed (`\n`), which is used on Linux and macOS, and the default.
+    #[default]
+    Lf,
+    /// A carriage return followed by a line feed (`\r\n`), which is used on Windows.
+    Crlf,
+}
+
+impl LineEnding {
+    /// Returns the characters of the line ending.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::Crlf => "\r\n",
+        }
+    }
+
+    /// Returns the text with each line feed replaced by the line ending.
+    ///
+    /// The text is expected to only contain line feeds, like the output of the conversion before the line ending is applied.
+    pub(crate) fn apply(&self, text: String) -> String {
+        match self {
+            LineEnding::Lf => text,
+            LineEnding::Crlf => text.replace('\n', "\r\n"),
+        }
+    }
+}
+
+/// Direction in which a [`RampGradient`] changes from the start to the end characters.
+///
+/// # Examples
+/// ```
+/// use artem::config::GradientDirection;
+///
+/// assert_eq!(Ok(GradientDirection::Vertical), "vertical".parse());
+/// ```
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GradientDirection {
+    /// From the left to the right side of the image.
+    #[default]
+    Horizontal,
+    /// From the top to the bottom of the image.
+    Vertical,
+}
+
+impl FromStr for GradientDirection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "horizontal" | "h" => Ok(Self::Horizontal),
+            "vertical" | "v" => Ok(Self::Vertical),
+            _ => Err(format!(
+                "{s} is not a valid direction, expected horizontal or vertical"
+            )),
+        }
+    }
+}
+
+/// Two sets of characters, which are blended by the position in the image.
+///
+/// Cells at the start of the image (left or top, depending on the direction) use the start characters,
+/// cells at the end use the end characters. In between, the cells use more and more of the end characters,
+/// so the output smoothly transitions between both sets.
+///
+/// # Examples
+/// ```
+/// use artem::config::{GradientDirection, RampGradient};
+///
+/// let gradient = RampGradient {
+///     start: "#k. ".to_string(),
+///     end: "@o- ".to_string(),
+///     direction: GradientDirection::Horizontal,
+/// };
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RampGradient {
+    /// Characters used at the start of the image, from dark to light.
+    pub start: String,
+    /// Characters used at the end of the image, from dark to light.
+    pub end: String,
+    /// Direction of the transition.
+    pub direction: GradientDirection,
+}
+
+/// Separate characters for the shadows, midtones and highlights of the image.
+///
+/// The luminance range is split into three contiguous bands of equal size, which together cover
+/// the whole range from `0.0` to `1.0`: shadows (below [`TonalRamps::MIDTONES_START`]), midtones and
+/// highlights (from [`TonalRamps::HIGHLIGHTS_START`]). Within its band, a luminance is mapped to the characters
+/// of the band like it would be mapped to the full characters, which allows for finer control of each tonal range.
+///
+/// # Examples
+/// ```
+/// use artem::config::TonalRamps;
+///
+/// let ramps = TonalRamps {
+///     shadows: ":. ".to_string(),
+///     midtones: "o+-".to_string(),
+///     highlights: "@#%".to_string(),
+/// };
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TonalRamps {
+    /// Characters used for the shadows, from dark to light.
+    pub shadows: String,
+    /// Characters used for the midtones, from dark to light.
+    pub midtones: String,
+    /// Characters used for the highlights, from dark to light.
+    pub highlights: String,
+}
+
+impl TonalRamps {
+    /// Luminance at which the midtones start, all lower luminances are shadows.
+    pub const MIDTONES_START: f64 = 1f64 / 3f64;
+    /// Luminance at which the highlights start, all lower luminances are midtones or shadows.
+    pub const HIGHLIGHTS_START: f64 = 2f64 / 3f64;
+}
+
+/// A problem of a [`Config`], which is returned by [`Config::validate`].
+///
+/// The [`std::fmt::Display`] implementation describes the problem and how it can be fixed.
+///
+/// # Examples
+/// ```
+/// use artem::config::ArtemError;
+///
+/// assert_eq!(
+///     "The target size is 0, it has to be at least 1",
+///     ArtemError::ZeroTargetSize.to_string()
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArtemError {
+    /// The characters are empty.
+    EmptyCharacterSet,
+    /// The characters of a ramp gradient are empty.
+    EmptyRampGradient,
+    /// The characters of a tonal band are empty.
+    EmptyTonalRamps,
+    /// The target size is 0.
+    ZeroTargetSize,
+    /// The scale is not a positive, finite number.
+    InvalidScale(f32),
+    /// The minimum contrast ratio is not between `1.0` and `21.0`.
+    InvalidMinContrast(f64),
+    /// The vignette strength is not between `0.0` and `1.0`.
+    InvalidVignette(f32),
+    /// The color step is 0.
+    ZeroColorStep,
+    /// The braille threshold is not between `0.0` and `1.0`.
+    InvalidBrailleThreshold(f32),
+    /// The font size of svg files is not a positive, finite number.
+    InvalidFontSize(f32),
+    /// The gamma is not a positive, finite number.
+    InvalidGamma(f64),
+    /// The threshold of the monochrome mode is not between `0.0` and `1.0`.
+    InvalidThreshold(f64),
+    /// The edge threshold is not between `0.0` and `1.0`.
+    InvalidEdgeThreshold(f32),
+    /// The brightness is not between `-1.0` and `1.0`.
+    InvalidBrightness(f64),
+    /// The contrast is not between `-1.0` and `1.0`.
+    InvalidContrast(f64),
+    /// The width or height of the cropped region is 0, with its width and height.
+    EmptyCrop(u32, u32),
+    /// The color palette is empty.
+    EmptyColorPalette,
+    /// The columns or rows of [`ResizingDimension::Stretch`] are 0 or larger than [`crate::MAX_PRESCALED_SIZE`], with the columns and rows.
+    InvalidStretch(u32, u32),
+    /// The char height compensation is not a finite number of at least `1.0`.
+    InvalidCharHeightCompensation(f32),
+    /// The first option only has an effect together with the second option.
+    MissingRequirement(&'static str, &'static str),
+    /// The two options can not be used together.
+    Conflict(&'static str, &'static str),
+    /// Background colors are enabled, but not supported by the target or the terminal.
+    UnsupportedBackground,
+    /// Reading the image or writing the output failed, with the message of the underlying error.
+    Io(String),
+    /// The image could not be decoded, because its format is not supported or the data is invalid.
+    UnsupportedFormat(String),
+    /// The image format, the first value, is only decoded if artem is built with the feature of the second value.
+    MissingFormatFeature(&'static str, &'static str),
+    /// The image is empty or too large to be converted, with its width and height.
+    InvalidDimensions(u32, u32),
+    /// The option needs all rows of the image, so it can not be used with [`crate::convert_rows`].
+    NotStreamable(&'static str),
+}
+
+impl std::fmt::Display for ArtemError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ArtemError::EmptyCharacterSet => {
+                write!(f, "The characters are empty, at least one character is required")
+            }
+            ArtemError::EmptyRampGradient => write!(
+                f,
+                "The ramp gradient has empty characters, both ends need at least one character"
+            ),
+            ArtemError::EmptyTonalRamps => write!(
+                f,
+                "The tonal ramps have empty characters, each band needs at least one character"
+            ),
+            ArtemError::ZeroTargetSize => write!(f, "The target size is 0, it has to be at least 1"),
+            ArtemError::InvalidScale(scale) => {
+                write!(f, "The scale has to be a positive number, but is {scale}")
+            }
+            ArtemError::InvalidMinContrast(ratio) => write!(
+                f,
+                "The minimum contrast has to be between 1.0 and 21.0, but is {ratio}"
+            ),
+            ArtemError::InvalidFontSize(size) => {
+                write!(f, "The font size has to be a positive number, but is {size}")
+            }
+            ArtemError::InvalidGamma(gamma) => {
+                write!(f, "The gamma has to be a positive number, but is {gamma}")
+            }
+            ArtemError::InvalidThreshold(threshold) => write!(
+                f,
+                "The threshold has to be between 0.0 and 1.0, but is {threshold}"
+            ),
+            ArtemError::InvalidEdgeThreshold(threshold) => write!(
+                f,
+                "The edge threshold has to be between 0.0 and 1.0, but is {threshold}"
+            ),
+            ArtemError::InvalidBrightness(brightness) => write!(
+                f,
+                "The brightness has to be between -1.0 and 1.0, but is {brightness}"
+            ),
+            ArtemError::InvalidContrast(contrast) => write!(
+                f,
+                "The contrast has to be between -1.0 and 1.0, but is {contrast}"
+            ),
+            ArtemError::EmptyCrop(width, height) => write!(
+                f,
+                "The cropped region has to be at least 1x1 pixels, but is {width}x{height}"
+            ),
+            ArtemError::EmptyColorPalette => write!(
+                f,
+                "The color palette is empty, at least one color is required"
+            ),
+            ArtemError::InvalidStretch(columns, rows) => write!(
+                f,
+                "The stretched size has to be between 1x1 and {max}x{max}, but is {columns}x{rows}",
+                max = crate::MAX_PRESCALED_SIZE
+            ),
+            ArtemError::InvalidCharHeightCompensation(factor) => write!(
+                f,
+                "The char height compensation has to be at least 1.0, but is {factor}"
+            ),
+            ArtemError::InvalidBrailleThreshold(threshold) => write!(
+                f,
+                "The braille threshold has to be between 0.0 and 1.0, but is {threshold}"
+            ),
+            ArtemError::InvalidVignette(strength) => write!(
+                f,
+                "The vignette strength has to be between 0.0 and 1.0, but is {strength}"
+            ),
+            ArtemError::ZeroColorStep => write!(
+                f,
+                "The color step is 0, it has to be at least 1 (which does not change the colors)"
+            ),
+            ArtemError::MissingRequirement(option, required) => {
+                write!(f, "The {option} option requires the {required} option to be enabled")
+            }
+            ArtemError::Conflict(option, other) => {
+                write!(f, "The {option} option can not be used together with the {other} option")
+            }
+            ArtemError::UnsupportedBackground => write!(
+                f,
+                "Background colors are not supported by the target or the terminal, disable the background_color option"
+            ),
+            ArtemError::Io(message) => write!(f, "Failed to read or write: {message}"),
+            ArtemError::UnsupportedFormat(message) => {
+                write!(f, "Failed to decode the image: {message}")
+            }
+            ArtemError::MissingFormatFeature(format, feature) => write!(
+                f,
+                "{format} images require enabling the `{feature}` feature, rebuild artem with `--features {feature}`"
+            ),
+            ArtemError::InvalidDimensions(width, height) => {
+                write!(f, "The image size {width}x{height} can not be converted")
+            }
+            ArtemError::NotStreamable(option) => write!(
+                f,
+                "The {option} option needs the whole image, so it can not be converted row by row"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ArtemError {}
+
+///Config for the conversion of the image to the ascii image.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Config {
+    pub characters: String,
+    pub scale: f32,
+    pub target_size: u32,
+    pub color: bool,
+    pub background_color: bool,
+    pub invert: bool,
+    pub border: bool,
+    pub dimension: ResizingDimension,
+    pub transform_x: bool,
+    pub transform_y: bool,
+    pub center_x: bool,
+    pub center_y: bool,
+    pub outline: bool,
+    pub hysteresis: bool,
+    pub target: TargetType,
+    pub mapping: Option<CharacterMapping>,
+    pub min_contrast: Option<MinContrast>,
+    pub directional: bool,
+    pub css_classes: bool,
+    pub right_to_left: bool,
+    pub bottom_to_top: bool,
+    pub ink_budget: Option<usize>,
+    pub contrast_stretch: bool,
+    pub dither: DitherMode,
+    pub ramp_gradient: Option<RampGradient>,
+    pub ramp_rounding: RampRounding,
+    pub markdown: bool,
+    pub graded_edges: bool,
+    #[cfg(feature = "lut")]
+    pub lut: Option<crate::lut::Lut>,
+    pub vignette: Option<f32>,
+    pub tonal_ramps: Option<TonalRamps>,
+    pub color_step: Option<u8>,
+    pub negative: bool,
+    pub silhouette: bool,
+    pub whitespace: Whitespace,
+    pub mask: Option<image::GrayImage>,
+    pub color_mode: Option<ColorMode>,
+    pub distance_metric: DistanceMetric,
+    pub braille: Option<f32>,
+    pub html_background: Option<(u8, u8, u8)>,
+    pub svg_font: SvgFont,
+    pub transparent_background: bool,
+    pub half_blocks: bool,
+    pub partial_block: PartialBlock,
+    pub gamma: Option<f64>,
+    pub luminance_standard: LuminanceStandard,
+    pub threshold: Option<f64>,
+    pub on_char: char,
+    pub off_char: char,
+    pub edges: Option<f32>,
+    pub resize_filter: ResizeFilter,
+    pub brightness: f64,
+    pub contrast: f64,
+    pub background_fill: Option<(u8, u8, u8)>,
+    pub channel: Channel,
+    pub border_style: BorderStyle,
+    pub crop: Option<(u32, u32, u32, u32)>,
+    pub rotation: Rotation,
+    pub color_palette: Option<Vec<(u8, u8, u8)>>,
+    pub only_color: bool,
+    pub auto_contrast: bool,
+    pub seed: u64,
+    pub char_height_compensation: f32,
+    pub compensation_rows: CompensationRows,
+    pub background_char: Option<char>,
+    pub line_ending: LineEnding,
+    pub trim: bool,
+}
+
+impl Config {
+    /// Create [`ConfigBuilder`] with default properties.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::Config;
+    ///
+    /// let default = Config::builder();
+    /// ```
+    pub fn builder() -> ConfigBuilder {
+        ConfigBuilder::default()
+    }
+
+    /// Returns whether the output should be colored.
+    ///
+    /// This depends on both if colored output is enabled and the output target supports colored
+    /// output.
+    pub(crate) fn color(&self) -> bool {
+        self.color && self.target.supports_color()
+    }
+
+    /// Returns whether the output should be colored using background colors.
+    ///
+    /// This depends on both if background color is enabled and the output target supports
+    /// background colored output.
+    pub(crate) fn background_color(&self) -> bool {
+        self.background_color && self.target.supports_background_colors()
+    }
+
+    /// Returns the color mode of ansi colored output.
+    ///
+    /// If no color mode is set, the best mode supported by the terminal is detected.
+    pub fn resolved_color_mode(&self) -> ColorMode {
+        self.color_mode.unwrap_or_else(ColorMode::detect)
+    }
+
+    /// Check the config for problems, without converting an image.
+    ///
+    /// This finds invalid values, such as empty characters or a target size of 0, options which depend on or
+    /// contradict each other and background colors, which are not supported by the target or terminal.
+    /// The conversion itself only fails on invalid values, which make it impossible, like empty characters,
+    /// for the other problems the output most likely is not the expected one.
+    ///
+    /// # Errors
+    /// Returns all found problems as [`ArtemError`]s.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::{ArtemError, ConfigBuilder};
+    ///
+    /// assert_eq!(Ok(()), ConfigBuilder::new().build().validate());
+    ///
+    /// let mut config = ConfigBuilder::new().hysteresis(true).build();
+    /// config.target_size = 0;
+    /// assert_eq!(
+    ///     Err(vec![
+    ///         ArtemError::ZeroTargetSize,
+    ///         ArtemError::MissingRequirement("hysteresis", "outline")
+    ///     ]),
+    ///     config.validate()
+    /// );
+    /// ```
+    pub fn validate(&self) -> Result<(), Vec<ArtemError>> {
+        let mut errors = Vec::new();
+
+        //invalid values
+        if self.characters.is_empty() {
+            errors.push(ArtemError::EmptyCharacterSet);
+        }
+        if self
+            .ramp_gradient
+            .as_ref()
+            .is_some_and(|gradient| gradient.start.is_empty() || gradient.end.is_empty())
+        {
+            errors.push(ArtemError::EmptyRampGradient);
+        }
+        if self.tonal_ramps.as_ref().is_some_and(|ramps| {
+            ramps.shadows.is_empty() || ramps.midtones.is_empty() || ramps.highlights.is_empty()
+        }) {
+            errors.push(ArtemError::EmptyTonalRamps);
+        }
+        if self.target_size == 0 || matches!(self.dimension, ResizingDimension::Both { rows: 0 }) {
+            errors.push(ArtemError::ZeroTargetSize);
+        }
+        if !self.scale.is_finite() || self.scale <= 0f32 {
+            errors.push(ArtemError::InvalidScale(self.scale));
+        }
+        if let Some(min_contrast) = self.min_contrast {
+            if !(1f64..=21f64).contains(&min_contrast.ratio) {
+                errors.push(ArtemError::InvalidMinContrast(min_contrast.ratio));
+            }
+        }
+        if let Some(vignette) = self.vignette {
+            if !(0f32..=1f32).contains(&vignette) {
+                errors.push(ArtemError::InvalidVignette(vignette));
+            }
+        }
+        if self.color_step == Some(0) {
+            errors.push(ArtemError::ZeroColorStep);
+        }
+        if let Some(threshold) = self.braille {
+            if !(0f32..=1f32).contains(&threshold) {
+                errors.push(ArtemError::InvalidBrailleThreshold(threshold));
+            }
+        }
+        if !self.svg_font.size.is_finite() || self.svg_font.size <= 0f32 {
+            errors.push(ArtemError::InvalidFontSize(self.svg_font.size));
+        }
+        if let Some(gamma) = self.gamma {
+            if !gamma.is_finite() || gamma <= 0f64 {
+                errors.push(ArtemError::InvalidGamma(gamma));
+            }
+        }
+        if let Some(threshold) = self.threshold {
+            if !(0f64..=1f64).contains(&threshold) {
+                errors.push(ArtemError::InvalidThreshold(threshold));
+            }
+        }
+        if let Some(threshold) = self.edges {
+            if !(0f32..=1f32).contains(&threshold) {
+                errors.push(ArtemError::InvalidEdgeThreshold(threshold));
+            }
+        }
+        if !(-1f64..=1f64).contains(&self.brightness) {
+            errors.push(ArtemError::InvalidBrightness(self.brightness));
+        }
+        if !(-1f64..=1f64).contains(&self.contrast) {
+            errors.push(ArtemError::InvalidContrast(self.contrast));
+        }
+        if let Some((_, _, width, height)) = self.crop {
+            if width == 0 || height == 0 {
+                errors.push(ArtemError::EmptyCrop(width, height));
+            }
+        }
+        if self
+            .color_palette
+            .as_ref()
+            .is_some_and(|palette| palette.is_empty())
+        {
+            errors.push(ArtemError::EmptyColorPalette);
+        }
+        if let ResizingDimension::Stretch { rows } = self.dimension {
+            //a target size of 0 is already reported
+            if rows == 0
+                || self.target_size > crate::MAX_PRESCALED_SIZE
+                || rows > crate::MAX_PRESCALED_SIZE
+            {
+                errors.push(ArtemError::InvalidStretch(self.target_size, rows));
+            }
+        }
+        if !self.char_height_compensation.is_finite() || self.char_height_compensation < 1f32 {
+            errors.push(ArtemError::InvalidCharHeightCompensation(
+                self.char_height_compensation,
+            ));
+        }
+
+        //options depending on other options
+        let requirements = [
+            (self.hysteresis, self.outline, "hysteresis", "outline"),
+            (self.graded_edges, self.outline, "graded_edges", "outline"),
+            (
+                self.background_color,
+                self.color,
+                "background_color",
+                "color",
+            ),
+        ];
+        for (option, required, option_name, required_name) in requirements {
+            if option && !required {
+                errors.push(ArtemError::MissingRequirement(option_name, required_name));
+            }
+        }
+
+        //contradicting options
+        let conflicts = [
+            (self.markdown && self.color, "markdown", "color"),
+            (
+                self.whitespace == Whitespace::Compact && self.color,
+                "whitespace",
+                "color",
+            ),
+            (
+                self.mapping.is_some() && self.ramp_gradient.is_some(),
+                "mapping",
+                "ramp_gradient",
+            ),
+            (
+                self.mapping.is_some() && self.tonal_ramps.is_some(),
+                "mapping",
+                "tonal_ramps",
+            ),
+            (
+                self.ramp_gradient.is_some() && self.tonal_ramps.is_some(),
+                "ramp_gradient",
+                "tonal_ramps",
+            ),
+            (
+                self.braille.is_some() && self.directional,
+                "braille",
+                "directional",
+            ),
+            (
+                self.braille.is_some() && self.dither != DitherMode::None,
+                "braille",
+                "dither",
+            ),
+            (
+                self.half_blocks && self.braille.is_some(),
+                "half_blocks",
+                "braille",
+            ),
+            (
+                self.half_blocks && self.directional,
+                "half_blocks",
+                "directional",
+            ),
+            (
+                self.threshold.is_some() && self.mapping.is_some(),
+                "threshold",
+                "mapping",
+            ),
+            (
+                self.threshold.is_some() && self.ramp_gradient.is_some(),
+                "threshold",
+                "ramp_gradient",
+            ),
+            (
+                self.threshold.is_some() && self.tonal_ramps.is_some(),
+                "threshold",
+                "tonal_ramps",
+            ),
+            (
+                self.threshold.is_some() && self.braille.is_some(),
+                "threshold",
+                "braille",
+            ),
+            (self.edges.is_some() && self.outline, "edges", "outline"),
+            (
+                self.edges.is_some() && self.directional,
+                "edges",
+                "directional",
+            ),
+            (
+                self.edges.is_some() && self.braille.is_some(),
+                "edges",
+                "braille",
+            ),
+            (
+                self.half_blocks && self.edges.is_some(),
+                "half_blocks",
+                "edges",
+            ),
+            (
+                self.only_color && self.half_blocks,
+                "only_color",
+                "half_blocks",
+            ),
+            (
+                self.only_color && self.braille.is_some(),
+                "only_color",
+                "braille",
+            ),
+            (
+                self.only_color && self.directional,
+                "only_color",
+                "directional",
+            ),
+            (
+                self.only_color && self.edges.is_some(),
+                "only_color",
+                "edges",
+            ),
+        ];
+        for (conflict, option, other) in conflicts {
+            if conflict {
+                errors.push(ArtemError::Conflict(option, other));
+            }
+        }
+
+        //background colors are ignored, if the target does not support them
+        if self.color() && self.background_color && !self.target.supports_background_colors() {
+            errors.push(ArtemError::UnsupportedBackground);
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_validate {
+    use super::*;
+
+    #[test]
+    fn default_is_valid() {
+        assert_eq!(Ok(()), Config::default().validate());
+    }
+
+    #[test]
+    fn invalid_values() {
+        let config = Config {
+            characters: String::new(),
+            target_size: 0,
+            scale: 0f32,
+            min_contrast: Some(MinContrast {
+                background: (0, 0, 0),
+                ratio: 30f64,
+            }),
+            vignette: Some(-1f32),
+            color_step: Some(0),
+            braille: Some(2f32),
+            svg_font: SvgFont {
+                family: String::from("monospace"),
+                size: 0f32,
+            },
+            gamma: Some(0f64),
+            threshold: Some(1.5),
+            edges: Some(-0.5),
+            brightness: 2f64,
+            contrast: -1.5,
+            crop: Some((10, 10, 0, 5)),
+            dimension: ResizingDimension::Stretch { rows: 0 },
+            color_palette: Some(Vec::new()),
+            char_height_compensation: 0.5,
+            ..Default::default()
+        };
+        assert_eq!(
+            Err(vec![
+                ArtemError::EmptyCharacterSet,
+                ArtemError::ZeroTargetSize,
+                ArtemError::InvalidScale(0f32),
+                ArtemError::InvalidMinContrast(30f64),
+                ArtemError::InvalidVignette(-1f32),
+                ArtemError::ZeroColorStep,
+                ArtemError::InvalidBrailleThreshold(2f32),
+                ArtemError::InvalidFontSize(0f32),
+                ArtemError::InvalidGamma(0f64),
+                ArtemError::InvalidThreshold(1.5),
+                ArtemError::InvalidEdgeThreshold(-0.5),
+                ArtemError::InvalidBrightness(2f64),
+                ArtemError::InvalidContrast(-1.5),
+                ArtemError::EmptyCrop(0, 5),
+                ArtemError::EmptyColorPalette,
+                ArtemError::InvalidStretch(0, 0),
+                ArtemError::InvalidCharHeightCompensation(0.5),
+                ArtemError::Conflict("threshold", "braille"),
+                ArtemError::Conflict("edges", "braille"),
+            ]),
+            config.validate()
+        );
+    }
+
+    #[test]
+    fn zero_fit_rows() {
+        let config = Config {
+            dimension: ResizingDimension::Both { rows: 0 },
+            ..Default::default()
+        };
+        assert_eq!(Err(vec![ArtemError::ZeroTargetSize]), config.validate());
+    }
+
+    #[test]
+    fn missing_requirements() {
+        let config = Config {
+            hysteresis: true,
+            graded_edges: true,
+            background_color: true,
+            color: false,
+            ..Default::default()
+        };
+        assert_eq!(
+            Err(vec![
+                ArtemError::MissingRequirement("hysteresis", "outline"),
+                ArtemError::MissingRequirement("graded_edges", "outline"),
+                ArtemError::MissingRequirement("background_color", "color"),
+            ]),
+            config.validate()
+        );
+    }
+
+    #[test]
+    fn conflicts() {
+        let config = Config {
+            markdown: true,
+            whitespace: Whitespace::Compact,
+            mapping: Some("0: \n128:#".parse().unwrap()),
+            ramp_gradient: Some(RampGradient {
+                start: String::new(),
+                end: "#".to_string(),
+                direction: GradientDirection::Horizontal,
+            }),
+            tonal_ramps: Some(TonalRamps {
+                shadows: " ".to_string(),
+                midtones: String::new(),
+                highlights: "#".to_string(),
+            }),
+            braille: Some(0.5),
+            directional: true,
+            dither: DitherMode::Bayer4,
+            half_blocks: true,
+            threshold: Some(0.5),
+            edges: Some(0.3),
+            only_color: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            Err(vec![
+                ArtemError::EmptyRampGradient,
+                ArtemError::EmptyTonalRamps,
+                ArtemError::Conflict("markdown", "color"),
+                ArtemError::Conflict("whitespace", "color"),
+                ArtemError::Conflict("mapping", "ramp_gradient"),
+                ArtemError::Conflict("mapping", "tonal_ramps"),
+                ArtemError::Conflict("ramp_gradient", "tonal_ramps"),
+                ArtemError::Conflict("braille", "directional"),
+                ArtemError::Conflict("braille", "dither"),
+                ArtemError::Conflict("half_blocks", "braille"),
+                ArtemError::Conflict("half_blocks", "directional"),
+                ArtemError::Conflict("threshold", "mapping"),
+                ArtemError::Conflict("threshold", "ramp_gradient"),
+                ArtemError::Conflict("threshold", "tonal_ramps"),
+                ArtemError::Conflict("threshold", "braille"),
+                ArtemError::Conflict("edges", "directional"),
+                ArtemError::Conflict("edges", "braille"),
+                ArtemError::Conflict("half_blocks", "edges"),
+                ArtemError::Conflict("only_color", "half_blocks"),
+                ArtemError::Conflict("only_color", "braille"),
+                ArtemError::Conflict("only_color", "directional"),
+                ArtemError::Conflict("only_color", "edges"),
+            ]),
+            config.validate()
+        );
+    }
+
+    #[test]
+    fn unsupported_background() {
+        //ansi files do not support background colors, independent of the terminal
+        let config = Config {
+            background_color: true,
+            target: TargetType::AnsiFile,
+            ..Default::default()
+        };
+        assert_eq!(
+            Err(vec![ArtemError::UnsupportedBackground]),
+            config.validate()
+        );
+
+        let config = Config {
+            background_color: true,
+            target: TargetType::HtmlFile,
+            ..Default::default()
+        };
+        assert_eq!(Ok(()), config.validate());
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            characters: r#"MWNXK0Okxdolc:;,'...   "#.to_string(),
+            scale: 0.42f32,
+            target_size: 80,
+            color: true,
+            background_color: false,
+            invert: Default::default(),
+            border: Default::default(),
+            dimension: Default::default(),
+            transform_x: Default::default(),
+            transform_y: Default::default(),
+            center_x: Default::default(),
+            center_y: Default::default(),
+            outline: Default::default(),
+            hysteresis: Default::default(),
+            target: Default::default(),
+            mapping: None,
+            min_contrast: None,
+            directional: Default::default(),
+            css_classes: Default::default(),
+            right_to_left: Default::default(),
+            bottom_to_top: Default::default(),
+            ink_budget: None,
+            contrast_stretch: false,
+            dither: DitherMode::None,
+            ramp_gradient: None,
+            ramp_rounding: RampRounding::Floor,
+            markdown: false,
+            graded_edges: false,
+            #[cfg(feature = "lut")]
+            lut: None,
+            vignette: None,
+            tonal_ramps: None,
+            color_step: None,
+            negative: false,
+            silhouette: false,
+            whitespace: Whitespace::Preserve,
+            mask: None,
+            color_mode: None,
+            distance_metric: DistanceMetric::Rgb,
+            braille: None,
+            html_background: None,
+            svg_font: SvgFont::default(),
+            transparent_background: false,
+            half_blocks: false,
+            partial_block: PartialBlock::Render,
+            gamma: None,
+            luminance_standard: LuminanceStandard::Rec709,
+            threshold: None,
+            on_char: '#',
+            off_char: ' ',
+            edges: None,
+            resize_filter: ResizeFilter::Thumbnail,
+            brightness: 0f64,
+            contrast: 0f64,
+            background_fill: None,
+            channel: Channel::Luminance,
+            border_style: BorderStyle::Double,
+            crop: None,
+            rotation: Rotation::None,
+            color_palette: None,
+            only_color: false,
+            auto_contrast: false,
+            seed: 0,
+            char_height_compensation: 1f32,
+            compensation_rows: CompensationRows::Duplicate,
+            background_char: None,
+            line_ending: LineEnding::Lf,
+            trim: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_option {
+    use super::*;
+    #[test]
+    fn builder_default() {
+        assert_eq!(
+            ConfigBuilder {
+                characters: r#"MWNXK0Okxdolc:;,'...   "#.to_string(),
+                scale: 0.42f32,
+                target_size: 80,
+                color: true,
+                background_color: false,
+                invert: false,
+                border: false,
+                dimension: ResizingDimension::Width,
+                transform_x: false,
+                transform_y: false,
+                center_x: false,
+                center_y: false,
+                outline: false,
+                hysteresis: false,
+                target: TargetType::default(),
+                mapping: None,
+                min_contrast: None,
+                directional: false,
+                css_classes: false,
+                right_to_left: false,
+                bottom_to_top: false,
+                ink_budget: None,
+                contrast_stretch: false,
+                dither: DitherMode::None,
+                ramp_gradient: None,
+                ramp_rounding: RampRounding::Floor,
+                markdown: false,
+                graded_edges: false,
+                #[cfg(feature = "lut")]
+                lut: None,
+                vignette: None,
+                tonal_ramps: None,
+                color_step: None,
+                negative: false,
+                silhouette: false,
+                whitespace: Whitespace::Preserve,
+                mask: None,
+                color_mode: None,
+                distance_metric: DistanceMetric::Rgb,
+                braille: None,
+                html_background: None,
+                svg_font: SvgFont::default(),
+                transparent_background: false,
+                half_blocks: false,
+                partial_block: PartialBlock::Render,
+                gamma: None,
+                luminance_standard: LuminanceStandard::Rec709,
+                threshold: None,
+                on_char: '#',
+                off_char: ' ',
+                edges: None,
+                resize_filter: ResizeFilter::Thumbnail,
+                brightness: 0f64,
+                contrast: 0f64,
+                background_fill: None,
+                channel: Channel::Luminance,
+                border_style: BorderStyle::Double,
+                crop: None,
+                rotation: Rotation::None,
+                color_palette: None,
+                only_color: false,
+                auto_contrast: false,
+                seed: 0,
+                char_height_compensation: 1f32,
+                compensation_rows: CompensationRows::Duplicate,
+                background_char: None,
+                line_ending: LineEnding::Lf,
+                trim: false,
+            },
+            Config::builder()
+        );
+    }
+}
+
+///A builder to create a [`Config`] struct.
+#[derive(PartialEq, Debug)]
+pub struct ConfigBuilder {
+    characters: String,
+    scale: f32,
+    target_size: u32,
+    color: bool,
+    background_color: bool,
+    invert: bool,
+    border: bool,
+    dimension: ResizingDimension,
+    transform_x: bool,
+    transform_y: bool,
+    center_x: bool,
+    center_y: bool,
+    outline: bool,
+    hysteresis: bool,
+    target: TargetType,
+    mapping: Option<CharacterMapping>,
+    min_contrast: Option<MinContrast>,
+    directional: bool,
+    css_classes: bool,
+    right_to_left: bool,
+    bottom_to_top: bool,
+    ink_budget: Option<usize>,
+    contrast_stretch: bool,
+    dither: DitherMode,
+    ramp_gradient: Option<RampGradient>,
+    ramp_rounding: RampRounding,
+    markdown: bool,
+    graded_edges: bool,
+    #[cfg(feature = "lut")]
+    lut: Option<crate::lut::Lut>,
+    vignette: Option<f32>,
+    tonal_ramps: Option<TonalRamps>,
+    color_step: Option<u8>,
+    negative: bool,
+    silhouette: bool,
+    whitespace: Whitespace,
+    mask: Option<image::GrayImage>,
+    color_mode: Option<ColorMode>,
+    distance_metric: DistanceMetric,
+    braille: Option<f32>,
+    html_background: Option<(u8, u8, u8)>,
+    svg_font: SvgFont,
+    transparent_background: bool,
+    half_blocks: bool,
+    partial_block: PartialBlock,
+    gamma: Option<f64>,
+    luminance_standard: LuminanceStandard,
+    threshold: Option<f64>,
+    on_char: char,
+    off_char: char,
+    edges: Option<f32>,
+    resize_filter: ResizeFilter,
+    brightness: f64,
+    contrast: f64,
+    background_fill: Option<(u8, u8, u8)>,
+    channel: Channel,
+    border_style: BorderStyle,
+    crop: Option<(u32, u32, u32, u32)>,
+    rotation: Rotation,
+    color_palette: Option<Vec<(u8, u8, u8)>>,
+    only_color: bool,
+    auto_contrast: bool,
+    seed: u64,
+    char_height_compensation: f32,
+    compensation_rows: CompensationRows,
+    background_char: Option<char>,
+    line_ending: LineEnding,
+    trim: bool,
+}
+
+impl Default for ConfigBuilder {
+    fn default() -> Self {
+        Self {
+            //these have to be set to custom defaults for the program to work
+            characters: r#"MWNXK0Okxdolc:;,'...   "#.to_string(),
+            scale: 0.42f32,
+            target_size: 80,
+            color: true,
+            background_color: false,
+            invert: Default::default(),
+            border: Default::default(),
+            dimension: Default::default(),
+            transform_x: Default::default(),
+            transform_y: Default::default(),
+            center_x: Default::default(),
+            center_y: Default::default(),
+            outline: Default::default(),
+            hysteresis: Default::default(),
+            target: Default::default(),
+            mapping: None,
+            min_contrast: None,
+            directional: Default::default(),
+            css_classes: Default::default(),
+            right_to_left: Default::default(),
+            bottom_to_top: Default::default(),
+            ink_budget: None,
+            contrast_stretch: false,
+            dither: DitherMode::None,
+            ramp_gradient: None,
+            ramp_rounding: RampRounding::Floor,
+            markdown: false,
+            graded_edges: false,
+            #[cfg(feature = "lut")]
+            lut: None,
+            vignette: None,
+            tonal_ramps: None,
+            color_step: None,
+            negative: false,
+            silhouette: false,
+            whitespace: Whitespace::Preserve,
+            mask: None,
+            color_mode: None,
+            distance_metric: DistanceMetric::Rgb,
+            braille: None,
+            html_background: None,
+            svg_font: SvgFont::default(),
+            transparent_background: false,
+            half_blocks: false,
+            partial_block: PartialBlock::Render,
+            gamma: None,
+            luminance_standard: LuminanceStandard::Rec709,
+            threshold: None,
+            on_char: '#',
+            off_char: ' ',
+            edges: None,
+            resize_filter: ResizeFilter::Thumbnail,
+            brightness: 0f64,
+            contrast: 0f64,
+            background_fill: None,
+            channel: Channel::Luminance,
+            border_style: BorderStyle::Double,
+            crop: None,
+            rotation: Rotation::None,
+            color_palette: None,
+            only_color: false,
+            auto_contrast: false,
+            seed: 0,
+            char_height_compensation: 1f32,
+            compensation_rows: CompensationRows::Duplicate,
+            background_char: None,
+            line_ending: LineEnding::Lf,
+            trim: false,
+        }
+    }
+}
+
+/// Generate a builder property.
+///
+/// This macro generates a function, which sets the specified property of the Builder.
+/// It does NOT check the value passed in, so for example it would be possible to pass in an empty string, which could lead to
+/// errors later this. This should be done before setting the value.
+///
+/// # Examples
+///
+/// ``` compile_fail, just an internal example code
+/// property!{
+/// ///Example doc
+/// ///This generates a name setter function.
+/// => name, String
+/// }
+/// ```
+/// ## Generated function
+/// ``` compile_fail, just an internal example code
+/// ///Example doc
+/// ///This generates a name setter function.
+/// pub fn name<'a>(&'a mut self, name: String) -> &'a Self {
+///     self.name = name;
+///     self
+/// }
+/// ```
+macro_rules! property {
+    ($(#[$attr:meta])* => $field:ident, $field_type:ty) => {
+        $(#[$attr])*
+        pub fn $field(&mut self, $field: $field_type) -> &mut Self {
+            self.$field = $field;
+            self
+        }
+    };
+    ($(#[$attr:meta])* => $field:ident, $field_type:ty, $func:ident) => {
+        $(#[$attr])*
+        pub fn $field(&mut self, $field: $field_type) -> &mut Self {
+            self.$field = $field.$func();
+            self
+        }
+    };
+}
+
+impl ConfigBuilder {
+    ///Create a new ConfigBuilder.
+    ///
+    /// If an option is not specified, the rust default value will be used, unless specified otherwise.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::ConfigBuilder;
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// ```
+    pub fn new() -> ConfigBuilder {
+        Config::builder()
+    }
+
+    /// Set the characters.
+    ///
+    /// The characters will determine how 'visible'/light/dark a character will be perceived.
+    /// They are ordered from the densest to the lightest character, the luminance of each tile is mapped
+    /// onto them, so any number of characters can be used, for example the 70 characters of Paul Bourke's ramp.
+    ///
+    /// # Errors
+    /// When the given characters are empty, the characters will not be changed.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::ConfigBuilder;
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.characters("Mkl. ");
+    /// builder.characters("@%#*+=-:. ".to_string());
+    /// ```
+    pub fn characters(&mut self, characters: impl Into<String>) -> &mut Self {
+        let characters = characters.into();
+        if !characters.is_empty() {
+            self.characters = characters;
+        }
+        self
+    }
+
+    property! {
+    /// Set a fixed mapping from luminance to characters.
+    ///
+    /// When set, the mapping overrides the characters entirely.
+    /// See [`CharacterMapping`] for more information.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::{CharacterMapping, ConfigBuilder};
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.mapping("0: \n128:#".parse::<CharacterMapping>().unwrap());
+    /// ```
+    => mapping, CharacterMapping, into
+    }
+
+    property! {
+    /// Set a minimum contrast of the character colors against a background color.
+    ///
+    /// Colors which do not have the minimum contrast are lightened or darkened, so the
+    /// characters stay readable. See [`MinContrast`] for more information.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::{ConfigBuilder, MinContrast};
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.min_contrast(MinContrast {
+    ///     background: (0, 0, 0),
+    ///     ratio: 4.5,
+    /// });
+    /// ```
+    => min_contrast, MinContrast, into
+    }
+
+    property! {
+    /// Set the scale.
+    ///
+    /// Used to change the ratio between width and height of an character.
+    /// Since a char is a bit higher than wide, the scale should compensate for that.
+    /// It is the width divided by the height of a character cell of the font, the default of `0.42` fits most
+    /// monospace fonts, fonts with a 1:2 cell use `0.5` and fonts with a 3:5 cell use `0.6`.
+    /// The scale has to be a positive, finite number, otherwise [`Config::validate`] returns [`ArtemError::InvalidScale`].
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::ConfigBuilder;
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.scale(0.42f32);
+    /// ```
+       => scale, f32
+    }
+
+    property! {
+    /// Set the target_size.
+    ///
+    /// This is the number of characters that the resulting ascii image will be high/wide.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::ConfigBuilder;
+    /// use core::num::NonZeroU32;
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.target_size(NonZeroU32::new(80).unwrap());
+    /// ```
+    => target_size, NonZeroU32, get
+    }
+
+    property! {
+    /// Render the output in color.
+    ///
+    /// If the chosen [`TargetType`] does not support color, it is ignored.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::ConfigBuilder;
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.color(true);
+    /// ```
+    => color, bool
+    }
+
+    property! {
+    /// Render the background of the output in color.
+    ///
+    /// If the chosen [`TargetType`] does not support background colors, it is ignored.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::ConfigBuilder;
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.color(true);
+    /// ```
+    => background_color, bool
+    }
+
+    property! {
+    /// Invert to density map/characters.
+    ///
+    /// This inverts the mapping from light to dark characters. It can be useful when
+    /// the image has a dark background. It defaults to false.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::ConfigBuilder;
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.invert(true);
+    /// ```
+    => invert, bool
+    }
+
+    property! {
+    /// Enable a border surrounding the image.
+    ///
+    /// The border will take reduced the space of the ascii image, since it will still
+    /// use the same target size.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::ConfigBuilder;
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.border(true);
+    /// ```
+    => border, bool
+    }
+
+    property! {
+    /// Set which dimension should be scaled first.
+    ///
+    /// See [`ResizingDimension`] for more information.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::ConfigBuilder;
+    /// use artem::config::ResizingDimension;
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.dimension(ResizingDimension::Height);
+    /// ```
+    => dimension, ResizingDimension
+    }
+
+    property! {
+    /// Flip the image along the X-axis
+    ///
+    /// This will flip the image horizontally by reversing reading of the horizontal part of the image.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::ConfigBuilder;
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.transform_x(true);
+    /// ```
+    => transform_x, bool
+    }
+
+    property! {
+    /// Flip the image along the Y-axis
+    ///
+    /// This will flip the image vertically by reversing reading of the vertical part of the image.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::ConfigBuilder;
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.transform_y(true);
+    /// ```
+    => transform_y, bool
+    }
+
+    property! {
+    /// Center the image horizontally in the terminal
+    ///
+    /// It will center the image by adding spaces in front of the text.
+    /// Since the terminal might have an uneven width, and only monospace chars are allowed,
+    /// the spacing might not always be accurate.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::ConfigBuilder;
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.center_x(true);
+    /// ```
+    => center_x, bool
+    }
+
+    property! {
+    /// Center the image vertically in the terminal
+    ///
+    /// It will center the image by adding new lines  above and below the text.
+    /// Since the terminal might have an uneven height, and only monospace chars are allowed,
+    /// the spacing might not always be accurate.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::ConfigBuilder;
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.center_y(true);
+    /// ```
+    => center_y, bool
+    }
+
+    property! {
+    /// Convert the image to it's outline
+    ///
+    /// This will use gaussian blur and sobel operators to only it's outline,
+    /// it might not produce the best result on all images.
+    /// Caution, this will take some additional time.
+    /// Defaults to false.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::ConfigBuilder;
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.outline(true);
+    /// ```
+    => outline, bool
+    }
+
+    property! {
+    /// When converting the image to an outline, also use double threshold and hysteresis
+    ///
+    /// It will remove small imperfection in the outline for the cost of smaller/thinner lines, which can make
+    /// the resulting ascii looking less visible, which might not be desired.
+    ///
+    /// It will only be used when outlining is set to true.
+    /// # Examples
+    /// ```
+    /// use artem::config::ConfigBuilder;
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.hysteresis(true);
+    /// ```
+    => hysteresis, bool
+    }
+
+    property! {
+    /// Use directional characters for tiles with a dominant texture direction.
+    ///
+    /// Tiles which mostly change their brightness from top to bottom, such as horizontal stripes,
+    /// will use horizontal characters (`=`, `-`), while tiles which mostly change from left to right
+    /// will use vertical characters (`|`, `!`). This is a lighter alternative to the outline.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::ConfigBuilder;
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.directional(true);
+    /// ```
+    => directional, bool
+    }
+
+    property! {
+    /// Use css classes instead of inline styles for colored html files.
+    ///
+    /// Each distinct color gets a css class, which is defined once in a `<style>` element,
+    /// so flat images with only a few colors result in much smaller files.
+    /// It is only used, when the target is a [`TargetType::HtmlFile`] and color is enabled.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::{ConfigBuilder, TargetType};
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.target(TargetType::HtmlFile).css_classes(true);
+    /// ```
+    => css_classes, bool
+    }
+
+    property! {
+    /// Output the characters of each line from right to left.
+    ///
+    /// In contrast to [`ConfigBuilder::transform_x`], the image itself is not flipped,
+    /// only the order of the converted characters is reversed, which can be useful for
+    /// right-to-left display pipelines.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::ConfigBuilder;
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.right_to_left(true);
+    /// ```
+    => right_to_left, bool
+    }
+
+    property! {
+    /// Output the lines from bottom to top.
+    ///
+    /// Like [`ConfigBuilder::right_to_left`], this only reverses the order of the converted lines,
+    /// the image itself is not flipped.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::ConfigBuilder;
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.bottom_to_top(true);
+    /// ```
+    => bottom_to_top, bool
+    }
+
+    property! {
+    /// Limit the number of non-whitespace characters.
+    ///
+    /// Only the characters with the highest gradient magnitude, such as edges, are kept,
+    /// all others are replaced by spaces, which results in a sparse, minimalist ascii art.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::ConfigBuilder;
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.ink_budget(500);
+    /// ```
+    => ink_budget, usize, into
+    }
+
+    property! {
+    /// Stretch the luminances of the image to the full range.
+    ///
+    /// The darkest part of the image is mapped to the darkest character and the
+    /// lightest part to the lightest character, which improves low contrast images.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::ConfigBuilder;
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.contrast_stretch(true);
+    /// ```
+    => contrast_stretch, bool
+    }
+
+    property! {
+    /// Stretch the luminances of the image between its 1st and 99th percentile to the full range.
+    ///
+    /// Like [`ConfigBuilder::contrast_stretch`], this improves low contrast images, but a few very dark or light parts,
+    /// like a highlight, do not prevent the stretching, since they are clipped to the darkest or lightest character.
+    /// It is independent of the brightness and contrast of the image and takes precedence over the contrast stretching.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::ConfigBuilder;
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.auto_contrast(true);
+    /// ```
+    => auto_contrast, bool
+    }
+
+    property! {
+    /// Use dithering when choosing the characters, see [`DitherMode`].
+    ///
+    /// With Floyd–Steinberg dithering, the difference between the luminance of a tile and the luminance represented by its character
+    /// is distributed to the neighbouring tiles. Ordered dithering instead offsets the luminance of each tile by the threshold
+    /// of a Bayer matrix at its position. Both avoid visible bands in smooth gradients. Stippling uses a random threshold instead,
+    /// which is generated from the [`ConfigBuilder::seed`].
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::{ConfigBuilder, DitherMode};
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.dither(DitherMode::Bayer4);
+    /// ```
+    => dither, DitherMode
+    }
+
+    property! {
+    /// Blend two sets of characters by the position in the image.
+    ///
+    /// This replaces the characters for all cells. See [`RampGradient`] for more information.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::{ConfigBuilder, GradientDirection, RampGradient};
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.ramp_gradient(RampGradient {
+    ///     start: "#k. ".to_string(),
+    ///     end: "@o- ".to_string(),
+    ///     direction: GradientDirection::Vertical,
+    /// });
+    /// ```
+    => ramp_gradient, RampGradient, into
+    }
+
+    property! {
+    /// Set how the character index is rounded, when mapping a luminance to the characters.
+    ///
+    /// See [`RampRounding`] for more information. It defaults to [`RampRounding::Floor`].
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::{ConfigBuilder, RampRounding};
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.ramp_rounding(RampRounding::Round);
+    /// ```
+    => ramp_rounding, RampRounding
+    }
+
+    property! {
+    /// Wrap the output in a markdown code block.
+    ///
+    /// Pasting ascii art into a context with a proportional font, such as a chat or documentation,
+    /// misaligns the columns. A fenced code block is rendered with a monospace font instead.
+    /// This is only used for the shell and plain text files, since colors can not be shown in a code block.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::ConfigBuilder;
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.markdown(true);
+    /// ```
+    => markdown, bool
+    }
+
+    property! {
+    /// Use characters graded by the edge strength for the outline.
+    ///
+    /// This only has an effect together with the `outline` option. Instead of mapping the edges
+    /// to the characters like any other image, weak edges use faint characters such as `.`
+    /// and strong edges use line characters matching their direction, such as `|` or `/`,
+    /// which results in softer looking contours.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::ConfigBuilder;
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.outline(true).graded_edges(true);
+    /// ```
+    => graded_edges, bool
+    }
+
+    property! {
+    /// Set a 3D color lookup table, which is applied to each pixel before the conversion.
+    ///
+    /// This can be used to apply a color grading to the image. The lut is applied before any other
+    /// processing, so the outline and the characters are based on the graded colors.
+    /// This is only available with the `lut` feature.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::ConfigBuilder;
+    /// use artem::lut::Lut;
+    ///
+    /// let lut: Lut = "LUT_3D_SIZE 2\n1 1 1\n0 1 1\n1 0 1\n0 0 1\n1 1 0\n0 1 0\n1 0 0\n0 0 0"
+    ///     .parse()
+    ///     .unwrap();
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.lut(Some(lut));
+    /// ```
+    #[cfg(feature = "lut")]
+    => lut, Option<crate::lut::Lut>
+    }
+
+    property! {
+    /// Darken the image towards the edges with the given strength.
+    ///
+    /// The luminance of each cell is multiplied with a radial falloff, which is centered on the image.
+    /// A strength of `0.0` does not change the image, while a strength of `1.0` fades the corners
+    /// completely to the darkest character, which makes the subject in the center stand out.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::ConfigBuilder;
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.vignette(0.5);
+    /// ```
+    => vignette, f32, into
+    }
+
+    property! {
+    /// Use separate characters for the shadows, midtones and highlights.
+    ///
+    /// When set, the tonal ramps are used instead of the characters.
+    /// See [`TonalRamps`] for more information.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::{ConfigBuilder, TonalRamps};
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.tonal_ramps(TonalRamps {
+    ///     shadows: ":. ".to_string(),
+    ///     midtones: "o+-".to_string(),
+    ///     highlights: "@#%".to_string(),
+    /// });
+    /// ```
+    => tonal_ramps, TonalRamps, into
+    }
+
+    property! {
+    /// Round the colors of the characters to multiples of the given step.
+    ///
+    /// Each color channel is rounded to the nearest multiple of the step, so small changes of the image
+    /// do not change the color of every character. This results in much smaller diffs, when the output
+    /// is committed to version control. A step of `1` does not change the colors.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::ConfigBuilder;
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.color_step(16);
+    /// ```
+    => color_step, u8, into
+    }
+
+    property! {
+    /// Invert the colors of the image before the conversion, like a photo negative.
+    ///
+    /// In contrast to the `invert` option, which only inverts the mapping from the luminance to the characters,
+    /// this inverts the pixels themselves, so both the characters and the colors of the output are inverted.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::ConfigBuilder;
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.negative(true);
+    /// ```
+    => negative, bool
+    }
+
+    property! {
+    /// Convert only the alpha channel of the image, which results in a silhouette of the opaque regions.
+    ///
+    /// The colors of the image are ignored, fully opaque pixels use the densest character
+    /// and fully transparent pixels the lightest one.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::ConfigBuilder;
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.silhouette(true);
+    /// ```
+    => silhouette, bool
+    }
+
+    property! {
+    /// Set how runs of spaces are written, when the output is not colored.
+    ///
+    /// See [`Whitespace`] for the exact rule. It defaults to [`Whitespace::Preserve`].
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::{ConfigBuilder, Whitespace};
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.whitespace(Whitespace::Compact);
+    /// ```
+    => whitespace, Whitespace
+    }
+
+    property! {
+    /// Weight the image with a grayscale mask, to emphasize a region of the image.
+    ///
+    /// The mask is resized to the size of the ascii art and the luminance of each cell is multiplied with
+    /// the brightness of the mask. White regions keep all details, while black regions use the lightest character.
+    /// The mask should have the same aspect ratio as the image, it is flipped together with the image.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::ConfigBuilder;
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.mask(image::GrayImage::from_pixel(4, 3, image::Luma([255])));
+    /// ```
+    => mask, image::GrayImage, into
+    }
+
+    property! {
+    /// Set the color depth of ansi colored output.
+    ///
+    /// When it is not set, the best mode supported by the terminal is used. See [`ColorMode`] for more information.
+    /// When it is set, the environment is not consulted for the color depth, so the same image and config always result in
+    /// the same output. Only whether escape sequences are written at all still follows the `colored` crate, which can be pinned
+    /// with `colored::control::set_override`.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::{ColorMode, ConfigBuilder};
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.color_mode(ColorMode::Ansi256);
+    /// ```
+    => color_mode, ColorMode, into
+    }
+
+    property! {
+    /// Set the metric, which is used to find the nearest of the 16 basic ansi colors.
+    ///
+    /// It is only used for [`ColorMode::Ansi16`]. It defaults to [`DistanceMetric::Rgb`].
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::{ConfigBuilder, DistanceMetric};
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.distance_metric(DistanceMetric::CieLab);
+    /// ```
+    => distance_metric, DistanceMetric
+    }
+
+    property! {
+    /// Use braille characters with the given threshold, instead of the characters.
+    ///
+    /// Each braille character has 2x4 dots, which are set, if the luminance (`0.0` - `1.0`) of their part
+    /// of the image is at least the threshold. This results in a lot more details, since every character
+    /// represents 8 parts of the image instead of a single one. If inverted, the dark parts set the dots.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::ConfigBuilder;
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.braille(0.5);
+    /// ```
+    => braille, f32, into
+    }
+
+    property! {
+    /// Set the background color of html files.
+    ///
+    /// The color is used for the pre element, which contains the ascii art, so the colored characters
+    /// can be shown on a matching background, independent of the page it is embedded into.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::ConfigBuilder;
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.html_background((255, 255, 255));
+    /// ```
+    => html_background, (u8, u8, u8), into
+    }
+
+    property! {
+    /// Set the font of svg files.
+    ///
+    /// The size of the svg depends on the font size, see [`SvgFont`] for more information.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::{ConfigBuilder, SvgFont};
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.svg_font(SvgFont {
+    ///     family: String::from("Fira Code, monospace"),
+    ///     size: 12f32,
+    /// });
+    /// ```
+    => svg_font, SvgFont
+    }
+
+    property! {
+    /// Make the background of png images fully transparent.
+    ///
+    /// Only the characters are drawn, so the image can be placed on top of other graphics.
+    /// Background colors are still drawn behind their characters. This only has an effect for the png target.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::ConfigBuilder;
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.transparent_background(true);
+    /// ```
+    => transparent_background, bool
+    }
+
+    property! {
+    /// Use upper half blocks (`▀`), which show two pixels per character.
+    ///
+    /// The upper pixel is used as the foreground color and the lower pixel as the background color,
+    /// which doubles the vertical resolution. Both colors are only used for the terminal and html files,
+    /// the other targets only use the upper color. If the image ends in the middle of a row,
+    /// the last row is converted according to [`ConfigBuilder::partial_block`].
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::ConfigBuilder;
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.half_blocks(true);
+    /// ```
+    => half_blocks, bool
+    }
+
+    property! {
+    /// Change how the last row of half blocks is converted, if the image only covers its upper half.
+    ///
+    /// See [`PartialBlock`] for more information. It defaults to [`PartialBlock::Render`]
+    /// and is only used together with [`ConfigBuilder::half_blocks`].
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::{ConfigBuilder, PartialBlock};
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.partial_block(PartialBlock::Pad);
+    /// ```
+    => partial_block, PartialBlock
+    }
+
+    property! {
+    /// Calculate the luminance of the tiles in linear light, with the given gamma.
+    ///
+    /// By default, the luminance, which chooses the characters, is calculated from the average sRGB color of a tile,
+    /// which makes midtones look too dark. With a gamma, the pixels are linearized with the sRGB transfer function
+    /// (`((c + 0.055) / 1.055)^2.4`, or `c / 12.92` for values up to `0.04045`), averaged and weighted in linear light,
+    /// and then encoded again as `luminance^(1 / gamma)`. A gamma of `1.0` uses the linear luminance, while `2.2`
+    /// roughly matches the sRGB encoding. The colors of the output are not changed.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::ConfigBuilder;
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.gamma(2.2);
+    /// ```
+    => gamma, f64, into
+    }
+
+    property! {
+    /// Set the standard of the weights, which are used to calculate the luminance of the colors.
+    ///
+    /// The luminance chooses the characters, see [`LuminanceStandard`] for more information.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::{ConfigBuilder, LuminanceStandard};
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.luminance_standard(LuminanceStandard::Rec601);
+    /// ```
+    => luminance_standard, LuminanceStandard
+    }
+
+    property! {
+    /// Use only two characters, depending on whether the luminance is above the threshold (`0.0` - `1.0`).
+    ///
+    /// Tiles with a luminance of at least the threshold use the on character, all other tiles the off character,
+    /// see [`ConfigBuilder::on_char`] and [`ConfigBuilder::off_char`]. If inverted, the characters are swapped.
+    /// This creates pure black and white output, for example for stencils or simple sprites.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::ConfigBuilder;
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.threshold(0.5);
+    /// ```
+    => threshold, f64, into
+    }
+
+    property! {
+    /// Set the character of the monochrome mode, which is used for tiles with a luminance of at least the threshold.
+    ///
+    /// It is only used together with [`ConfigBuilder::threshold`], the default is `#`.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::ConfigBuilder;
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.on_char('@');
+    /// ```
+    => on_char, char
+    }
+
+    property! {
+    /// Set the character of the monochrome mode, which is used for tiles with a luminance below the threshold.
+    ///
+    /// It is only used together with [`ConfigBuilder::threshold`], the default is a space.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::ConfigBuilder;
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.off_char('.');
+    /// ```
+    => off_char, char
+    }
+
+    property! {
+    /// Use line characters for strong edges, with the given threshold (`0.0` - `1.0`) of the edge strength.
     ///
-    /// If an option is not specified, the rust default value will be used, unless specified otherwise.
+    /// A Sobel operator is applied to the image, if the strongest gradient magnitude of a tile is at least the threshold,
+    /// the tile uses `|`, `-`, `/` or `\` depending on the direction of the edge. All other tiles use the characters as usual.
+    /// In contrast to the outline option, the image itself is still visible.
     ///
     /// # Examples
     /// ```
     /// use artem::config::ConfigBuilder;
     ///
     /// let mut builder = ConfigBuilder::new();
+    /// builder.edges(0.3);
     /// ```
-    pub fn new() -> ConfigBuilder {
-        Config::builder()
+    => edges, f32, into
     }
 
-    /// Set the characters.
+    property! {
+    /// Set the filter, which is used to resize the image before it is converted.
     ///
-    /// The characters will determine how 'visible'/light/dark a character will be perceived.
+    /// See [`ResizeFilter`] for the available filters.
     ///
-    /// # Errors
-    /// When the given characters are empty, the characters will not be changed.
+    /// # Examples
+    /// ```
+    /// use artem::config::{ConfigBuilder, ResizeFilter};
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.resize_filter(ResizeFilter::Lanczos3);
+    /// ```
+    => resize_filter, ResizeFilter
+    }
+
+    property! {
+    /// Change the brightness of the image before it is converted, between `-1.0` and `1.0`.
+    ///
+    /// The value is scaled to the range of a color channel and added to each channel,
+    /// so a positive brightness lightens and a negative brightness darkens the image. The channels are clamped
+    /// to `0` - `255`. The default of `0.0` does not change the image.
     ///
     /// # Examples
     /// ```
     /// use artem::config::ConfigBuilder;
     ///
     /// let mut builder = ConfigBuilder::new();
-    /// builder.characters("Mkl. ".to_string());
+    /// builder.brightness(0.2);
     /// ```
-    pub fn characters(&mut self, characters: String) -> &mut Self {
-        if !characters.is_empty() {
-            self.characters = characters;
-        }
-        self
+    => brightness, f64
     }
 
     property! {
-    /// Set the scale.
+    /// Change the contrast of the image before it is converted, between `-1.0` and `1.0`.
     ///
-    /// Used to change the ratio between width and height of an character.
-    /// Since a char is a bit higher than wide, the scale should compensate for that.
+    /// A positive contrast pushes the channels away from the middle gray, while a negative contrast
+    /// pulls them towards it, `-1.0` results in a uniform gray image. The channels are clamped
+    /// to `0` - `255`. The default of `0.0` does not change the image.
     ///
     /// # Examples
     /// ```
     /// use artem::config::ConfigBuilder;
     ///
     /// let mut builder = ConfigBuilder::new();
-    /// builder.scale(0.42f32);
+    /// builder.contrast(0.5);
     /// ```
-       => scale, f32
+    => contrast, f64
     }
 
     property! {
-    /// Set the target_size.
+    /// Blend transparent pixels with the given background color, before the image is converted.
     ///
-    /// This is the number of characters that the resulting ascii image will be high/wide.
+    /// Without a background color, each pixel is weighted by its alpha value, so transparent pixels do not contribute to the color
+    /// of their tile and only the opaque part of a tile counts for its luminance.
+    /// With a background color, each pixel is blended with it by its alpha value, so half transparent red over white
+    /// results in pink. Tiles, which are fully transparent, use a space, so the terminal background shows through.
     ///
     /// # Examples
     /// ```
     /// use artem::config::ConfigBuilder;
-    /// use core::num::NonZeroU32;
     ///
     /// let mut builder = ConfigBuilder::new();
-    /// builder.target_size(NonZeroU32::new(80).unwrap());
+    /// //white background
+    /// builder.background_fill((255, 255, 255));
     /// ```
-    => target_size, NonZeroU32, get
+    => background_fill, (u8, u8, u8), into
     }
 
     property! {
-    /// Render the output in color.
+    /// Set the channel of the colors, which chooses the characters.
     ///
-    /// If the chosen [`TargetType`] does not support color, it is ignored.
+    /// See [`Channel`] for more information, by default the luminance is used.
     ///
     /// # Examples
     /// ```
-    /// use artem::config::ConfigBuilder;
+    /// use artem::config::{Channel, ConfigBuilder};
     ///
     /// let mut builder = ConfigBuilder::new();
-    /// builder.color(true);
+    /// builder.channel(Channel::Red);
     /// ```
-    => color, bool
+    => channel, Channel
     }
 
     property! {
-    /// Render the background of the output in color.
+    /// Set the style of the border.
     ///
-    /// If the chosen [`TargetType`] does not support background colors, it is ignored.
+    /// The style is only used, if the border is enabled, see [`BorderStyle`]. By default, double lines are used.
     ///
     /// # Examples
     /// ```
-    /// use artem::config::ConfigBuilder;
+    /// use artem::config::{BorderStyle, ConfigBuilder};
     ///
     /// let mut builder = ConfigBuilder::new();
-    /// builder.color(true);
+    /// builder.border(true).border_style(BorderStyle::Ascii);
     /// ```
-    => background_color, bool
+    => border_style, BorderStyle
     }
 
     property! {
-    /// Invert to density map/characters.
+    /// Set the region of the image, which is converted.
     ///
-    /// This inverts the mapping from light to dark characters. It can be useful when
-    /// the image has a dark background. It defaults to false.
+    /// The region is given as the x and y position of its top left corner, followed by its width and height, in pixels
+    /// of the input image. The image is cropped before the size of the ascii art is calculated, so the region is
+    /// converted like a smaller image. A region, which is partially or fully outside of the image, is clamped to the image.
     ///
     /// # Examples
     /// ```
     /// use artem::config::ConfigBuilder;
     ///
     /// let mut builder = ConfigBuilder::new();
-    /// builder.invert(true);
+    /// //the top left 100x50 pixels
+    /// builder.crop((0, 0, 100, 50));
     /// ```
-    => invert, bool
+    => crop, (u32, u32, u32, u32), into
     }
 
     property! {
-    /// Enable a border surrounding the image.
+    /// Set the clockwise rotation of the image.
     ///
-    /// The border will take reduced the space of the ascii image, since it will still
-    /// use the same target size.
+    /// The image is rotated before it is cropped and before the size of the ascii art is calculated, see [`Rotation`].
     ///
     /// # Examples
     /// ```
-    /// use artem::config::ConfigBuilder;
+    /// use artem::config::{ConfigBuilder, Rotation};
     ///
     /// let mut builder = ConfigBuilder::new();
-    /// builder.border(true);
+    /// builder.rotation(Rotation::Rotate90);
     /// ```
-    => border, bool
+    => rotation, Rotation
     }
 
     property! {
-    /// Set which dimension should be scaled first.
+    /// Limit the colors of the characters to the given palette, for example the colors of a terminal theme.
     ///
-    /// See [`ResizingDimension`] for more information.
+    /// Each color is replaced by the nearest color of the palette, using the `distance_metric`,
+    /// see [`crate::palette::nearest_color`]. The palette must not be empty.
     ///
     /// # Examples
     /// ```
     /// use artem::config::ConfigBuilder;
-    /// use artem::config::ResizingDimension;
     ///
     /// let mut builder = ConfigBuilder::new();
-    /// builder.dimension(ResizingDimension::Height);
+    /// builder.color_palette(vec![(0, 0, 0), (255, 255, 255)]);
     /// ```
-    => dimension, ResizingDimension
+    => color_palette, Vec<(u8, u8, u8)>, into
     }
 
     property! {
-    /// Flip the image along the X-axis
+    /// Use solid blocks (`█`), which only show the color of the tiles.
     ///
-    /// This will flip the image horizontally by reversing reading of the horizontal part of the image.
+    /// The characters are not chosen by the luminance, instead the brightness is only shown by the color,
+    /// which results in a mosaic of colored blocks. If background colors are used, the blocks are spaces with a background color.
+    /// Without colors, every character is the same block.
     ///
     /// # Examples
     /// ```
     /// use artem::config::ConfigBuilder;
     ///
     /// let mut builder = ConfigBuilder::new();
-    /// builder.transform_x(true);
+    /// builder.only_color(true);
     /// ```
-    => transform_x, bool
+    => only_color, bool
     }
 
     property! {
-    /// Flip the image along the Y-axis
+    /// Seed for the randomized parts of the conversion, which is `0` by default.
     ///
-    /// This will flip the image vertically by reversing reading of the vertical part of the image.
+    /// It is used by [`DitherMode::Stipple`], the same seed and image always result in the same ascii art.
     ///
     /// # Examples
     /// ```
-    /// use artem::config::ConfigBuilder;
+    /// use artem::config::{ConfigBuilder, DitherMode};
     ///
     /// let mut builder = ConfigBuilder::new();
-    /// builder.transform_y(true);
+    /// builder.dither(DitherMode::Stipple).seed(42);
     /// ```
-    => transform_y, bool
+    => seed, u64
     }
 
     property! {
-    /// Center the image horizontally in the terminal
+    /// Stretch the ascii art vertically by the given factor, after it is converted.
     ///
-    /// It will center the image by adding spaces in front of the text.
-    /// Since the terminal might have an uneven width, and only monospace chars are allowed,
-    /// the spacing might not always be accurate.
+    /// Instead of changing the size of the tiles with the scale, the rows are repeated to compensate for the height
+    /// of the characters. A factor of `2.0` repeats every row, a factor of `1.5` every second row, the added rows are
+    /// chosen by [`ConfigBuilder::compensation_rows`]. The factor defaults to `1.0`, which does not add any rows,
+    /// and has to be a finite number of at least `1.0`, otherwise [`Config::validate`] returns
+    /// [`ArtemError::InvalidCharHeightCompensation`]. Svg and json files are not stretched.
     ///
     /// # Examples
     /// ```
     /// use artem::config::ConfigBuilder;
     ///
     /// let mut builder = ConfigBuilder::new();
-    /// builder.center_x(true);
+    /// builder.scale(1f32).char_height_compensation(2f32);
     /// ```
-    => center_x, bool
+    => char_height_compensation, f32
     }
 
     property! {
-    /// Center the image vertically in the terminal
+    /// Set the rows, which are added by [`ConfigBuilder::char_height_compensation`].
     ///
-    /// It will center the image by adding new lines  above and below the text.
-    /// Since the terminal might have an uneven height, and only monospace chars are allowed,
-    /// the spacing might not always be accurate.
+    /// It defaults to [`CompensationRows::Duplicate`].
     ///
     /// # Examples
     /// ```
-    /// use artem::config::ConfigBuilder;
+    /// use artem::config::{CompensationRows, ConfigBuilder};
     ///
     /// let mut builder = ConfigBuilder::new();
-    /// builder.center_y(true);
+    /// builder.char_height_compensation(2f32).compensation_rows(CompensationRows::Blank);
     /// ```
-    => center_y, bool
+    => compensation_rows, CompensationRows
     }
 
     property! {
-    /// Convert the image to it's outline
+    /// Set the character, which replaces the character of the darkest luminances.
     ///
-    /// This will use gaussian blur and sobel operators to only it's outline,
-    /// it might not produce the best result on all images.
-    /// Caution, this will take some additional time.
-    /// Defaults to false.
+    /// Only the character, which is used for a luminance of `0.0`, is replaced, which is usually a space,
+    /// so the rest of the characters does not have to be changed. This is useful for editors, which remove the spaces at the end of the lines.
+    /// With tonal ramps, the darkest character of the shadows is replaced, with a ramp gradient the darkest
+    /// character of both ramps. The colors are not affected. It is not used together with a threshold, which has
+    /// its own [`ConfigBuilder::off_char`], or with a character mapping.
     ///
     /// # Examples
     /// ```
     /// use artem::config::ConfigBuilder;
     ///
     /// let mut builder = ConfigBuilder::new();
-    /// builder.outline(true);
+    /// builder.background_char('.');
     /// ```
-    => outline, bool
+    => background_char, char, into
     }
 
     property! {
-    /// When converting the image to an outline, also use double threshold and hysteresis
+    /// Set the line ending, which ends each line of the output.
     ///
-    /// It will remove small imperfection in the outline for the cost of smaller/thinner lines, which can make
-    /// the resulting ascii looking less visible, which might not be desired.
+    /// It is used for all lines, including the lines around the ascii art, like the border or the start and end of
+    /// html files. It defaults to [`LineEnding::Lf`].
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::{ConfigBuilder, LineEnding};
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.line_ending(LineEnding::Crlf);
+    /// ```
+    => line_ending, LineEnding
+    }
+
+    property! {
+    /// Remove the empty rows and columns at the edges of the ascii art, after it is converted.
+    ///
+    /// A row or column is empty, if all of its characters are whitespace or the character of the darkest luminance,
+    /// for example the margins of a logo on a black or transparent background. The colors are ignored.
+    /// The ascii art is trimmed before it is centered or a border is added, the columns and rows of the
+    /// [`crate::Stats`] are the trimmed ones. Each frame of an animation is trimmed on its own,
+    /// and an ascii art without any other characters is not trimmed at all.
     ///
-    /// It will only be used when outlining is set to true.
     /// # Examples
     /// ```
     /// use artem::config::ConfigBuilder;
     ///
     /// let mut builder = ConfigBuilder::new();
-    /// builder.hysteresis(true);
+    /// builder.trim(true);
     /// ```
-    => hysteresis, bool
+    => trim, bool
     }
 
     property! {
@@ -631,6 +3458,59 @@ impl ConfigBuilder {
             outline: self.outline,
             hysteresis: self.hysteresis,
             target: self.target,
+            mapping: self.mapping.clone(),
+            min_contrast: self.min_contrast,
+            directional: self.directional,
+            css_classes: self.css_classes,
+            right_to_left: self.right_to_left,
+            bottom_to_top: self.bottom_to_top,
+            ink_budget: self.ink_budget,
+            contrast_stretch: self.contrast_stretch,
+            dither: self.dither,
+            ramp_gradient: self.ramp_gradient.clone(),
+            ramp_rounding: self.ramp_rounding,
+            markdown: self.markdown,
+            graded_edges: self.graded_edges,
+            #[cfg(feature = "lut")]
+            lut: self.lut.clone(),
+            vignette: self.vignette,
+            tonal_ramps: self.tonal_ramps.clone(),
+            color_step: self.color_step,
+            negative: self.negative,
+            silhouette: self.silhouette,
+            whitespace: self.whitespace,
+            mask: self.mask.clone(),
+            color_mode: self.color_mode,
+            distance_metric: self.distance_metric,
+            braille: self.braille,
+            html_background: self.html_background,
+            svg_font: self.svg_font.clone(),
+            transparent_background: self.transparent_background,
+            half_blocks: self.half_blocks,
+            partial_block: self.partial_block,
+            gamma: self.gamma,
+            luminance_standard: self.luminance_standard,
+            threshold: self.threshold,
+            on_char: self.on_char,
+            off_char: self.off_char,
+            edges: self.edges,
+            resize_filter: self.resize_filter,
+            brightness: self.brightness,
+            contrast: self.contrast,
+            background_fill: self.background_fill,
+            channel: self.channel,
+            border_style: self.border_style,
+            crop: self.crop,
+            rotation: self.rotation,
+            color_palette: self.color_palette.clone(),
+            only_color: self.only_color,
+            auto_contrast: self.auto_contrast,
+            seed: self.seed,
+            char_height_compensation: self.char_height_compensation,
+            compensation_rows: self.compensation_rows,
+            background_char: self.background_char,
+            line_ending: self.line_ending,
+            trim: self.trim,
         }
     }
 }
@@ -657,6 +3537,59 @@ mod test_conversion_configuration_builder {
                 outline: false,
                 hysteresis: false,
                 target: TargetType::default(),
+                mapping: None,
+                min_contrast: None,
+                directional: false,
+                css_classes: false,
+                right_to_left: false,
+                bottom_to_top: false,
+                ink_budget: None,
+                contrast_stretch: false,
+                dither: DitherMode::None,
+                ramp_gradient: None,
+                ramp_rounding: RampRounding::Floor,
+                markdown: false,
+                graded_edges: false,
+                #[cfg(feature = "lut")]
+                lut: None,
+                vignette: None,
+                tonal_ramps: None,
+                color_step: None,
+                negative: false,
+                silhouette: false,
+                whitespace: Whitespace::Preserve,
+                mask: None,
+                color_mode: None,
+                distance_metric: DistanceMetric::Rgb,
+                braille: None,
+                html_background: None,
+                svg_font: SvgFont::default(),
+                transparent_background: false,
+                half_blocks: false,
+                partial_block: PartialBlock::Render,
+                gamma: None,
+                luminance_standard: LuminanceStandard::Rec709,
+                threshold: None,
+                on_char: '#',
+                off_char: ' ',
+                edges: None,
+                resize_filter: ResizeFilter::Thumbnail,
+                brightness: 0f64,
+                contrast: 0f64,
+                background_fill: None,
+                channel: Channel::Luminance,
+                border_style: BorderStyle::Double,
+                crop: None,
+                rotation: Rotation::None,
+                color_palette: None,
+                only_color: false,
+                auto_contrast: false,
+                seed: 0,
+                char_height_compensation: 1f32,
+                compensation_rows: CompensationRows::Duplicate,
+                background_char: None,
+                line_ending: LineEnding::Lf,
+                trim: false,
             },
             ConfigBuilder::new().build()
         );
@@ -667,10 +3600,59 @@ mod test_conversion_configuration_builder {
 mod test_calculate_dimensions {
     use super::*;
 
+    #[test]
+    fn calculate_dimensions_both_wide_img() {
+        //a wide image is limited by the width
+        let (columns, rows, _, _) = ResizingDimension::calculate_dimensions(
+            80,
+            200,
+            1600,
+            0.42,
+            false,
+            ResizingDimension::Both { rows: 24 },
+        )
+        .unwrap();
+        assert_eq!(80, columns);
+        assert!(rows <= 24);
+    }
+
+    #[test]
+    fn calculate_dimensions_both_tall_img() {
+        //a tall image is limited by the height
+        let (columns, rows, _, _) = ResizingDimension::calculate_dimensions(
+            80,
+            1600,
+            200,
+            0.42,
+            false,
+            ResizingDimension::Both { rows: 24 },
+        )
+        .unwrap();
+        assert!(columns <= 80);
+        assert!(rows <= 24);
+        assert!(rows >= 20);
+    }
+
+    #[test]
+    fn calculate_dimensions_both_square_img() {
+        //with the width, a square image would need 46 rows
+        assert_eq!(
+            Ok((51, 23, 10, 22)),
+            ResizingDimension::calculate_dimensions(
+                80,
+                512,
+                512,
+                0.42,
+                false,
+                ResizingDimension::Both { rows: 24 }
+            )
+        );
+    }
+
     #[test]
     fn calculate_dimensions_width() {
         assert_eq!(
-            (100, 46, 5, 11),
+            Ok((100, 46, 5, 11)),
             ResizingDimension::calculate_dimensions(
                 100,
                 512,
@@ -685,7 +3667,7 @@ mod test_calculate_dimensions {
     #[test]
     fn calculate_dimensions_width_119() {
         assert_eq!(
-            (119, 56, 4, 9),
+            Ok((119, 56, 4, 9)),
             ResizingDimension::calculate_dimensions(
                 119,
                 512,
@@ -700,7 +3682,7 @@ mod test_calculate_dimensions {
     #[test]
     fn calculate_dimensions_height() {
         assert_eq!(
-            (170, 99, 3, 5),
+            Ok((170, 99, 3, 5)),
             ResizingDimension::calculate_dimensions(
                 100,
                 512,
@@ -715,7 +3697,7 @@ mod test_calculate_dimensions {
     #[test]
     fn calculate_dimensions_height_1x1_img() {
         assert_eq!(
-            (1, 1, 1, 1),
+            Ok((1, 1, 1, 1)),
             ResizingDimension::calculate_dimensions(
                 100,
                 1,
@@ -730,7 +3712,7 @@ mod test_calculate_dimensions {
     #[test]
     fn calculate_dimensions_width_1x1_img() {
         assert_eq!(
-            (1, 1, 1, 2),
+            Ok((1, 1, 1, 2)),
             ResizingDimension::calculate_dimensions(
                 100,
                 1,
@@ -743,53 +3725,145 @@ mod test_calculate_dimensions {
     }
 
     #[test]
-    #[should_panic]
+    fn calculate_dimensions_width_1x1_img_large_scale() {
+        assert_eq!(
+            Ok((1, 1, 1, 1)),
+            ResizingDimension::calculate_dimensions(
+                100,
+                1,
+                1,
+                1.5,
+                false,
+                ResizingDimension::Width
+            )
+        );
+    }
+
+    #[test]
+    fn calculate_dimensions_height_1x1_img_border() {
+        assert_eq!(
+            Ok((1, 1, 1, 1)),
+            ResizingDimension::calculate_dimensions(
+                100,
+                1,
+                1,
+                0.42,
+                true,
+                ResizingDimension::Height
+            )
+        );
+    }
+
+    #[test]
     fn calculate_dimensions_height_zero() {
-        ResizingDimension::calculate_dimensions(
-            0,
-            512,
-            512,
-            0.42,
-            false,
-            ResizingDimension::Height,
+        assert_eq!(
+            Err(ArtemError::ZeroTargetSize),
+            ResizingDimension::calculate_dimensions(
+                0,
+                512,
+                512,
+                0.42,
+                false,
+                ResizingDimension::Height,
+            )
         );
     }
 
     #[test]
-    #[should_panic]
     fn calculate_dimensions_width_zero() {
-        ResizingDimension::calculate_dimensions(0, 512, 512, 0.42, false, ResizingDimension::Width);
+        assert_eq!(
+            Err(ArtemError::ZeroTargetSize),
+            ResizingDimension::calculate_dimensions(
+                0,
+                512,
+                512,
+                0.42,
+                false,
+                ResizingDimension::Width
+            )
+        );
+    }
+
+    #[test]
+    fn calculate_dimensions_height_one() {
+        //the input line is subtracted, but a single row is still needed
+        assert_eq!(
+            Ok((2, 1, 216, 512)),
+            ResizingDimension::calculate_dimensions(
+                1,
+                512,
+                512,
+                0.42,
+                false,
+                ResizingDimension::Height,
+            )
+        );
+    }
+
+    #[test]
+    fn calculate_dimensions_both_zero_rows() {
+        assert_eq!(
+            Err(ArtemError::ZeroTargetSize),
+            ResizingDimension::calculate_dimensions(
+                80,
+                512,
+                512,
+                0.42,
+                false,
+                ResizingDimension::Both { rows: 0 },
+            )
+        );
     }
 
     #[test]
-    #[should_panic]
     fn calculate_dimensions_img_width_zero() {
-        ResizingDimension::calculate_dimensions(100, 512, 0, 0.42, false, ResizingDimension::Width);
+        assert_eq!(
+            Err(ArtemError::InvalidDimensions(0, 512)),
+            ResizingDimension::calculate_dimensions(
+                100,
+                512,
+                0,
+                0.42,
+                false,
+                ResizingDimension::Width
+            )
+        );
     }
 
     #[test]
-    #[should_panic]
     fn calculate_dimensions_img_height_zero() {
-        ResizingDimension::calculate_dimensions(
-            100,
-            0,
-            512,
-            0.42,
-            false,
-            ResizingDimension::Height,
+        assert_eq!(
+            Err(ArtemError::InvalidDimensions(512, 0)),
+            ResizingDimension::calculate_dimensions(
+                100,
+                0,
+                512,
+                0.42,
+                false,
+                ResizingDimension::Height,
+            )
         );
     }
 
     #[test]
-    #[should_panic]
     fn calculate_dimensions_img_width_height_zero() {
-        ResizingDimension::calculate_dimensions(100, 0, 0, 0.42, false, ResizingDimension::Height);
+        assert_eq!(
+            Err(ArtemError::InvalidDimensions(0, 0)),
+            ResizingDimension::calculate_dimensions(
+                100,
+                0,
+                0,
+                0.42,
+                false,
+                ResizingDimension::Height
+            )
+        );
     }
 
     #[test]
     fn calculate_dimensions_scale_zero() {
         assert_eq!(
-            (100, 1, 5, 4294967295),
+            Ok((100, 1, 5, 4294967295)),
             ResizingDimension::calculate_dimensions(
                 100,
                 512,
@@ -804,7 +3878,7 @@ mod test_calculate_dimensions {
     #[test]
     fn calculate_border_smaller_columns() {
         assert_eq!(
-            (98, 1, 5, 4294967295),
+            Ok((98, 1, 5, 4294967295)),
             ResizingDimension::calculate_dimensions(
                 100,
                 512,
@@ -815,4 +3889,89 @@ mod test_calculate_dimensions {
             )
         );
     }
+
+    #[test]
+    fn calculate_border_fits_size() {
+        for dimension in [ResizingDimension::Width, ResizingDimension::Height] {
+            let (columns, rows, _, _) =
+                ResizingDimension::calculate_dimensions(40, 512, 640, 0.42, false, dimension)
+                    .unwrap();
+            let (border_columns, border_rows, _, _) =
+                ResizingDimension::calculate_dimensions(40, 512, 640, 0.42, true, dimension)
+                    .unwrap();
+            //the border takes up two columns, and for the height also two rows
+            assert_eq!(columns, border_columns + 2, "{dimension:?}");
+            if dimension == ResizingDimension::Height {
+                assert_eq!(rows, border_rows + 2);
+            }
+        }
+    }
+
+    #[test]
+    fn calculate_scale_changes_rows() {
+        let rows = |scale| {
+            ResizingDimension::calculate_dimensions(
+                80,
+                512,
+                640,
+                scale,
+                false,
+                ResizingDimension::Width,
+            )
+            .unwrap()
+            .1
+        };
+        //a wider character cell needs more rows for the same image
+        assert_eq!(26, rows(0.42));
+        assert_eq!(32, rows(0.5));
+    }
+
+    #[test]
+    fn calculate_dimensions_stretch_square_img() {
+        //the square image is distorted to exactly the columns and rows
+        assert_eq!(
+            Ok((50, 10, 10, 51)),
+            ResizingDimension::calculate_dimensions(
+                50,
+                512,
+                512,
+                0.42,
+                false,
+                ResizingDimension::Stretch { rows: 10 }
+            )
+        );
+    }
+
+    #[test]
+    fn calculate_dimensions_stretch_border_and_small_img() {
+        //the border is included in the size, tiles are at least a single pixel
+        assert_eq!(
+            Ok((48, 18, 1, 1)),
+            ResizingDimension::calculate_dimensions(
+                50,
+                10,
+                10,
+                0.42,
+                true,
+                ResizingDimension::Stretch { rows: 20 }
+            )
+        );
+    }
+
+    #[test]
+    fn calculate_dimensions_stretch_invalid() {
+        for (columns, rows) in [(50, 0), (50, 5000), (5000, 10)] {
+            assert_eq!(
+                Err(ArtemError::InvalidStretch(columns, rows)),
+                ResizingDimension::calculate_dimensions(
+                    columns,
+                    512,
+                    512,
+                    0.42,
+                    false,
+                    ResizingDimension::Stretch { rows }
+                )
+            );
+        }
+    }
 }
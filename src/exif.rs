@@ -0,0 +1,288 @@
+//! Support for the EXIF orientation of images.
+//!
+//! Cameras and phones usually store the pixels in the orientation of the sensor and only record the
+//! orientation, in which the photo was taken, in the EXIF metadata. Image viewers rotate and flip the image
+//! according to this orientation, so it has to be applied before the image is converted as well.
+//! The orientation is read from the EXIF data of jpeg and png files, the specification can be found at
+//! <https://www.cipa.jp/std/documents/e/DC-008-2012_E.pdf>.
+
+use image::DynamicImage;
+
+/// The tag of the orientation in the first image file directory of the EXIF data.
+const ORIENTATION_TAG: u16 = 0x0112;
+
+/// The signature at the start of every png file.
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+/// Returns the EXIF orientation (`1` - `8`) of the encoded image, if it has one.
+///
+/// The bytes only have to contain the start of the image, which includes the metadata.
+/// Images without EXIF data, with an invalid orientation or in an unsupported format return `None`.
+///
+/// # Examples
+/// ```
+/// //a jpeg file without any metadata
+/// assert_eq!(None, artem::exif::orientation(&[0xFF, 0xD8, 0xFF, 0xD9]));
+/// ```
+pub fn orientation(bytes: &[u8]) -> Option<u16> {
+    let tiff = if bytes.starts_with(&[0xFF, 0xD8]) {
+        jpeg_exif(bytes)?
+    } else if bytes.starts_with(PNG_SIGNATURE) {
+        png_exif(bytes)?
+    } else {
+        return None;
+    };
+    tiff_orientation(tiff).filter(|orientation| (1..=8).contains(orientation))
+}
+
+/// Returns the image rotated and flipped according to the EXIF orientation, so it is shown upright.
+///
+/// The orientation describes how the stored pixels have to be transformed, for example `6` is rotated
+/// by 90 degrees clockwise and `2` is mirrored horizontally. Unknown orientations do not change the image.
+///
+/// # Examples
+/// ```
+/// let image = image::DynamicImage::new_rgb8(40, 10);
+/// let oriented = artem::exif::orient(image, 6);
+/// assert_eq!((10, 40), (oriented.width(), oriented.height()));
+/// ```
+pub fn orient(image: DynamicImage, orientation: u16) -> DynamicImage {
+    match orientation {
+        2 => image.fliph(),
+        3 => image.rotate180(),
+        4 => image.flipv(),
+        //transposed, the rows become the columns
+        5 => image.rotate90().fliph(),
+        6 => image.rotate90(),
+        7 => image.rotate270().fliph(),
+        8 => image.rotate270(),
+        _ => image,
+    }
+}
+
+/// Returns the EXIF data of a jpeg file, which is stored in an APP1 segment before the image data.
+fn jpeg_exif(bytes: &[u8]) -> Option<&[u8]> {
+    //skip the start of image marker
+    let mut index = 2;
+    while let [0xFF, marker, high, low, ..] = bytes[index.min(bytes.len())..] {
+        //the metadata is always stored before the start of the scan
+        if marker == 0xDA || marker == 0xD9 {
+            return None;
+        }
+        //the length includes the two bytes of the length itself
+        let length = u16::from_be_bytes([high, low]) as usize;
+        let segment = bytes.get(index + 4..index + 2 + length)?;
+        if marker == 0xE1 {
+            if let Some(tiff) = segment.strip_prefix(b"Exif\0\0") {
+                return Some(tiff);
+            }
+        }
+        index += 2 + length;
+    }
+    None
+}
+
+/// Returns the EXIF data of a png file, which is stored in an `eXIf` chunk before the image data.
+fn png_exif(bytes: &[u8]) -> Option<&[u8]> {
+    let mut index = PNG_SIGNATURE.len();
+    while let Some(header) = bytes.get(index..index + 8) {
+        let length = u32::from_be_bytes([header[0], header[1], header[2], header[3]]) as usize;
+        match &header[4..] {
+            b"eXIf" => return bytes.get(index + 8..index + 8 + length),
+            b"IDAT" | b"IEND" => return None,
+            //the chunk consists of the length, type, data and a checksum
+            _ => index += 12 + length,
+        }
+    }
+    None
+}
+
+/// Returns the orientation of the first image file directory of the EXIF data, which has the format of a tiff file.
+fn tiff_orientation(tiff: &[u8]) -> Option<u16> {
+    let big_endian = match tiff.get(..2)? {
+        b"MM" => true,
+        b"II" => false,
+        _ => return None,
+    };
+    let u16_at = |index: usize| {
+        let bytes = [*tiff.get(index)?, *tiff.get(index + 1)?];
+        Some(if big_endian {
+            u16::from_be_bytes(bytes)
+        } else {
+            u16::from_le_bytes(bytes)
+        })
+    };
+    let u32_at = |index: usize| {
+        let bytes = [
+            *tiff.get(index)?,
+            *tiff.get(index + 1)?,
+            *tiff.get(index + 2)?,
+            *tiff.get(index + 3)?,
+        ];
+        Some(if big_endian {
+            u32::from_be_bytes(bytes)
+        } else {
+            u32::from_le_bytes(bytes)
+        })
+    };
+
+    if u16_at(2)? != 42 {
+        return None;
+    }
+    let directory = u32_at(4)? as usize;
+    let entries = u16_at(directory)? as usize;
+    //each entry consists of the tag, type, count and value with 12 bytes in total
+    (0..entries)
+        .map(|entry| directory + 2 + entry * 12)
+        .find(|&entry| u16_at(entry) == Some(ORIENTATION_TAG))
+        .and_then(|entry| u16_at(entry + 8))
+}
+
+#[cfg(test)]
+mod test_orientation {
+    use super::*;
+
+    /// Returns the EXIF data with the given orientation, in little or big endian.
+    fn tiff(orientation: u16, big_endian: bool) -> Vec<u8> {
+        let u16_bytes = |value: u16| {
+            if big_endian {
+                value.to_be_bytes()
+            } else {
+                value.to_le_bytes()
+            }
+        };
+        let mut tiff = if big_endian {
+            b"MM".to_vec()
+        } else {
+            b"II".to_vec()
+        };
+        tiff.extend(u16_bytes(42));
+        tiff.extend(if big_endian {
+            8u32.to_be_bytes()
+        } else {
+            8u32.to_le_bytes()
+        });
+        //a single entry with a short value, followed by the offset of the next directory
+        tiff.extend(u16_bytes(1));
+        tiff.extend(u16_bytes(ORIENTATION_TAG));
+        tiff.extend(u16_bytes(3));
+        tiff.extend(if big_endian {
+            1u32.to_be_bytes()
+        } else {
+            1u32.to_le_bytes()
+        });
+        tiff.extend(u16_bytes(orientation));
+        tiff.extend([0; 6]);
+        tiff
+    }
+
+    /// Returns the start of a jpeg file with the given EXIF data.
+    fn jpeg(tiff: &[u8]) -> Vec<u8> {
+        let mut jpeg = vec![0xFF, 0xD8];
+        //an APP0 segment, which comes before the EXIF data in many files
+        jpeg.extend([0xFF, 0xE0, 0, 4, 0, 0]);
+        jpeg.extend([0xFF, 0xE1]);
+        jpeg.extend((tiff.len() as u16 + 8).to_be_bytes());
+        jpeg.extend(b"Exif\0\0");
+        jpeg.extend(tiff);
+        jpeg.extend([0xFF, 0xDA]);
+        jpeg
+    }
+
+    #[test]
+    fn jpeg_orientation() {
+        assert_eq!(Some(6), orientation(&jpeg(&tiff(6, false))));
+        assert_eq!(Some(2), orientation(&jpeg(&tiff(2, true))));
+    }
+
+    #[test]
+    fn png_orientation() {
+        let tiff = tiff(8, true);
+        let mut png = PNG_SIGNATURE.to_vec();
+        png.extend((tiff.len() as u32).to_be_bytes());
+        png.extend(b"eXIf");
+        png.extend(&tiff);
+        png.extend([0; 4]);
+        assert_eq!(Some(8), orientation(&png));
+    }
+
+    #[test]
+    fn invalid_orientation() {
+        assert_eq!(None, orientation(&jpeg(&tiff(0, false))));
+        assert_eq!(None, orientation(&jpeg(&tiff(9, false))));
+    }
+
+    #[test]
+    fn without_exif() {
+        assert_eq!(None, orientation(&[0xFF, 0xD8, 0xFF, 0xDA]));
+        assert_eq!(None, orientation(PNG_SIGNATURE));
+        assert_eq!(None, orientation(b"GIF89a"));
+        //the data ends in the middle of the EXIF data
+        let jpeg = jpeg(&tiff(6, false));
+        assert_eq!(None, orientation(&jpeg[..jpeg.len() - 8]));
+    }
+}
+
+#[cfg(test)]
+mod test_orient {
+    use image::{GenericImageView, ImageBuffer, Rgb};
+
+    use super::*;
+
+    const RED: Rgb<u8> = Rgb([255, 0, 0]);
+    const BLUE: Rgb<u8> = Rgb([0, 0, 255]);
+
+    /// A 2x1 image with a red left and a blue right pixel.
+    fn image() -> DynamicImage {
+        DynamicImage::ImageRgb8(ImageBuffer::from_fn(
+            2,
+            1,
+            |x, _| if x == 0 { RED } else { BLUE },
+        ))
+    }
+
+    fn pixels(image: &DynamicImage) -> Vec<[u8; 3]> {
+        image.to_rgb8().pixels().map(|pixel| pixel.0).collect()
+    }
+
+    #[test]
+    fn rotate_90_clockwise() {
+        let oriented = orient(image(), 6);
+        assert_eq!((1, 2), oriented.dimensions());
+        //the left pixel is now on top
+        assert_eq!(vec![RED.0, BLUE.0], pixels(&oriented));
+    }
+
+    #[test]
+    fn mirror_horizontal() {
+        let oriented = orient(image(), 2);
+        assert_eq!((2, 1), oriented.dimensions());
+        assert_eq!(vec![BLUE.0, RED.0], pixels(&oriented));
+    }
+
+    #[test]
+    fn all_orientations() {
+        //a 2x2 image with the pixels a, b in the top and c, d in the bottom row
+        let [a, b, c, d] = [[255, 0, 0], [0, 255, 0], [0, 0, 255], [255, 255, 255]];
+        let image = DynamicImage::ImageRgb8(ImageBuffer::from_fn(2, 2, |x, y| {
+            Rgb([[a, b], [c, d]][y as usize][x as usize])
+        }));
+        let expected = [
+            [a, b, c, d],
+            [b, a, d, c],
+            [d, c, b, a],
+            [c, d, a, b],
+            [a, c, b, d],
+            [c, a, d, b],
+            [d, b, c, a],
+            [b, d, a, c],
+        ];
+        for (orientation, expected) in (1..=8).zip(expected) {
+            assert_eq!(
+                expected.to_vec(),
+                pixels(&orient(image.clone(), orientation)),
+                "orientation {orientation}"
+            );
+        }
+    }
+}
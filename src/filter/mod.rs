@@ -29,6 +29,279 @@ pub fn edge_detection_filter(img: DynamicImage, hysteresis: bool) -> DynamicImag
     }
 }
 
+/// Convert the alpha channel of the image to a grayscale image, ignoring the colors.
+///
+/// Opaque pixels are white and transparent pixels are black, so the result is a silhouette of the opaque regions.
+/// Images with more than 8 bits per channel keep 16 bits per channel, see [`is_high_bit_depth`].
+///
+/// # Example
+/// ```compile_fail, compile will fail, this is an internal example
+///  let silhouette = alpha_silhouette(img);
+/// ```
+pub fn alpha_silhouette(img: DynamicImage) -> DynamicImage {
+    if is_high_bit_depth(&img) {
+        let img = img.into_rgba16();
+        return DynamicImage::ImageLuma16(ImageBuffer::from_fn(
+            img.width(),
+            img.height(),
+            |x, y| image::Luma([img.get_pixel(x, y).0[3]]),
+        ));
+    }
+    let img = img.into_rgba8();
+    DynamicImage::ImageLuma8(ImageBuffer::from_fn(img.width(), img.height(), |x, y| {
+        image::Luma([img.get_pixel(x, y).0[3]])
+    }))
+}
+
+/// Change the brightness and the contrast of the image, which are both between `-1.0` and `1.0`.
+///
+/// The brightness is scaled to the range of a color channel and added to each channel, afterwards the distance of each
+/// channel to the middle gray is multiplied by `(1 + contrast)^2`. The channels are clamped after each step, so they can not wrap around.
+/// The alpha channel is not changed. Images with more than 8 bits per channel keep 16 bits per channel,
+/// see [`is_high_bit_depth`].
+///
+/// # Example
+/// ```compile_fail, compile will fail, this is an internal example
+///  let adjusted = brightness_contrast(img, 0.2, -0.1);
+/// ```
+pub fn brightness_contrast(img: DynamicImage, brightness: f64, contrast: f64) -> DynamicImage {
+    let factor = (1f64 + contrast).powi(2);
+    let adjust = |value: f64| {
+        let value = (value + brightness).clamp(0f64, 1f64);
+        ((value - 0.5) * factor + 0.5).clamp(0f64, 1f64)
+    };
+    if is_high_bit_depth(&img) {
+        let mut img = img.into_rgba16();
+        for pixel in img.pixels_mut() {
+            for channel in &mut pixel.0[..3] {
+                *channel = (adjust(*channel as f64 / 65535f64) * 65535f64).round() as u16;
+            }
+        }
+        return DynamicImage::ImageRgba16(img);
+    }
+    let mut img = img.into_rgba8();
+    for pixel in img.pixels_mut() {
+        for channel in &mut pixel.0[..3] {
+            *channel = (adjust(*channel as f64 / 255f64) * 255f64).round() as u8;
+        }
+    }
+    DynamicImage::ImageRgba8(img)
+}
+
+/// Returns whether the image has more than 8 bits per channel, like 16 bit png or tiff files.
+///
+/// These images are converted with their full precision, instead of reducing them to 8 bits per channel.
+///
+/// # Example
+/// ```compile_fail, compile will fail, this is an internal example
+/// assert!(is_high_bit_depth(&DynamicImage::new_luma16(1, 1)));
+/// assert!(!is_high_bit_depth(&DynamicImage::new_rgb8(1, 1)));
+/// ```
+pub fn is_high_bit_depth(img: &DynamicImage) -> bool {
+    let color_type = img.color();
+    color_type.bytes_per_pixel() > color_type.channel_count()
+}
+
+/// Crop the image to the rectangle with the given position, width and height.
+///
+/// The rectangle is clamped to the bounds of the image, so it contains at least a single pixel of a non-empty image,
+/// even if it is partially or fully outside of the image.
+///
+/// # Example
+/// ```compile_fail, compile will fail, this is an internal example
+/// let cropped = crop(DynamicImage::new_rgb8(100, 100), (50, 50, 100, 100));
+/// assert_eq!((50, 50), cropped.dimensions());
+/// ```
+pub fn crop(img: DynamicImage, (x, y, width, height): (u32, u32, u32, u32)) -> DynamicImage {
+    let x = x.min(img.width().saturating_sub(1));
+    let y = y.min(img.height().saturating_sub(1));
+    let width = width.clamp(1, (img.width() - x).max(1));
+    let height = height.clamp(1, (img.height() - y).max(1));
+    img.crop_imm(x, y, width, height)
+}
+
+#[cfg(test)]
+mod test_crop {
+    use super::*;
+
+    #[test]
+    fn inside() {
+        let img = crop(DynamicImage::new_rgb8(100, 80), (10, 20, 30, 40));
+        assert_eq!((30, 40), (img.width(), img.height()));
+    }
+
+    #[test]
+    fn clamped_to_bounds() {
+        let img = crop(DynamicImage::new_rgb8(100, 80), (50, 60, 100, 100));
+        assert_eq!((50, 20), (img.width(), img.height()));
+        //a rectangle outside of the image keeps the last pixel
+        let img = crop(DynamicImage::new_rgb8(100, 80), (200, 200, 10, 10));
+        assert_eq!((1, 1), (img.width(), img.height()));
+    }
+}
+
+/// Blend the colors of the image with the given background color, using the alpha channel of each pixel.
+///
+/// The alpha channel itself is not changed, so fully transparent regions can still be recognized.
+/// Images with more than 8 bits per channel keep 16 bits per channel, see [`is_high_bit_depth`].
+///
+/// # Example
+/// ```compile_fail, compile will fail, this is an internal example
+///  let blended = fill_background(img, (255, 255, 255));
+/// ```
+pub fn fill_background(img: DynamicImage, background: (u8, u8, u8)) -> DynamicImage {
+    let background = [background.0, background.1, background.2];
+    if is_high_bit_depth(&img) {
+        let mut img = img.into_rgba16();
+        for pixel in img.pixels_mut() {
+            let alpha = pixel.0[3] as u64;
+            for (channel, background) in pixel.0[..3].iter_mut().zip(background) {
+                //scale the background to 16 bits, 255 * 257 = 65535
+                let background = background as u64 * 257;
+                *channel = ((*channel as u64 * alpha + background * (65535 - alpha) + 32767)
+                    / 65535) as u16;
+            }
+        }
+        return DynamicImage::ImageRgba16(img);
+    }
+    let mut img = img.into_rgba8();
+    for pixel in img.pixels_mut() {
+        let alpha = pixel.0[3] as u32;
+        for (channel, background) in pixel.0[..3].iter_mut().zip(background) {
+            *channel =
+                ((*channel as u32 * alpha + background as u32 * (255 - alpha) + 127) / 255) as u8;
+        }
+    }
+    DynamicImage::ImageRgba8(img)
+}
+
+#[cfg(test)]
+mod test_fill_background {
+    use super::*;
+
+    fn fill(color: [u8; 4], background: (u8, u8, u8)) -> [u8; 4] {
+        let img = DynamicImage::ImageRgba8(ImageBuffer::from_pixel(1, 1, image::Rgba(color)));
+        fill_background(img, background)
+            .into_rgba8()
+            .get_pixel(0, 0)
+            .0
+    }
+
+    #[test]
+    fn half_transparent_red_is_pink() {
+        assert_eq!(
+            [255, 127, 127, 128],
+            fill([255, 0, 0, 128], (255, 255, 255))
+        );
+    }
+
+    #[test]
+    fn opaque_and_transparent() {
+        assert_eq!([10, 20, 30, 255], fill([10, 20, 30, 255], (255, 255, 255)));
+        assert_eq!([0, 128, 255, 0], fill([10, 20, 30, 0], (0, 128, 255)));
+    }
+
+    #[test]
+    fn sixteen_bit_keeps_precision() {
+        let img = DynamicImage::ImageRgba16(ImageBuffer::from_fn(2, 1, |x, _| {
+            image::Rgba([32768 + x as u16 * 100, 0, 65535, 32768])
+        }));
+        let filled = fill_background(img, (0, 0, 0));
+        assert!(is_high_bit_depth(&filled));
+        let filled = filled.into_rgba16();
+        assert_eq!([16384, 0, 32768, 32768], filled.get_pixel(0, 0).0);
+        assert_eq!([16434, 0, 32768, 32768], filled.get_pixel(1, 0).0);
+    }
+}
+
+#[cfg(test)]
+mod test_brightness_contrast {
+    use super::*;
+
+    fn adjust(color: [u8; 4], brightness: f64, contrast: f64) -> [u8; 4] {
+        let img = DynamicImage::ImageRgba8(ImageBuffer::from_pixel(1, 1, image::Rgba(color)));
+        brightness_contrast(img, brightness, contrast)
+            .into_rgba8()
+            .get_pixel(0, 0)
+            .0
+    }
+
+    #[test]
+    fn no_change() {
+        assert_eq!([250, 128, 5, 100], adjust([250, 128, 5, 100], 0f64, 0f64));
+    }
+
+    #[test]
+    fn brightness_is_clamped() {
+        assert_eq!([255, 255, 133, 255], adjust([250, 128, 5, 255], 0.5, 0f64));
+        assert_eq!([122, 0, 0, 255], adjust([250, 128, 5, 255], -0.5, 0f64));
+    }
+
+    #[test]
+    fn contrast_is_clamped() {
+        assert_eq!([255, 130, 0, 255], adjust([250, 128, 5, 255], 0f64, 1f64));
+        //without any contrast, only the middle gray is left
+        assert_eq!(
+            [128, 128, 128, 255],
+            adjust([250, 128, 5, 255], 0f64, -1f64)
+        );
+    }
+
+    #[test]
+    fn sixteen_bit_image() {
+        let img =
+            DynamicImage::ImageRgb16(ImageBuffer::from_pixel(1, 1, image::Rgb([65535, 0, 0])));
+        let adjusted = brightness_contrast(img, -1f64, 0f64);
+        assert_eq!([0, 0, 0, 255], adjusted.into_rgba8().get_pixel(0, 0).0);
+    }
+
+    #[test]
+    fn sixteen_bit_keeps_precision() {
+        let img = DynamicImage::ImageLuma16(ImageBuffer::from_fn(2, 1, |x, _| {
+            image::Luma([32768 + x as u16 * 100])
+        }));
+        let adjusted = brightness_contrast(img, 0f64, 0.5);
+        assert!(is_high_bit_depth(&adjusted));
+        let adjusted = adjusted.into_rgba16();
+        assert!(adjusted.get_pixel(0, 0).0[0] < adjusted.get_pixel(1, 0).0[0]);
+    }
+}
+
+#[cfg(test)]
+mod test_alpha_silhouette {
+    use super::*;
+
+    #[test]
+    fn alpha_to_luminance() {
+        let img = DynamicImage::ImageRgba8(ImageBuffer::from_fn(3, 1, |x, _| {
+            image::Rgba([255, 0, 0, [0, 128, 255][x as usize]])
+        }));
+        let desired_result = DynamicImage::ImageLuma8(ImageBuffer::from_fn(3, 1, |x, _| {
+            image::Luma([[0, 128, 255][x as usize]])
+        }));
+        assert_eq!(desired_result, alpha_silhouette(img));
+    }
+
+    #[test]
+    fn opaque_image() {
+        let img = DynamicImage::new_rgb8(2, 2);
+        let desired_result =
+            DynamicImage::ImageLuma8(ImageBuffer::from_pixel(2, 2, image::Luma([255])));
+        assert_eq!(desired_result, alpha_silhouette(img));
+    }
+
+    #[test]
+    fn sixteen_bit_image() {
+        let img = DynamicImage::ImageRgba16(ImageBuffer::from_fn(2, 1, |x, _| {
+            image::Rgba([0, 0, 0, 32768 + x as u16])
+        }));
+        let desired_result = DynamicImage::ImageLuma16(ImageBuffer::from_fn(2, 1, |x, _| {
+            image::Luma([32768 + x as u16])
+        }));
+        assert_eq!(desired_result, alpha_silhouette(img));
+    }
+}
+
 ///Blur the given image using an gaussian blur, based on the given sigma.
 ///
 /// This returns a new (blurred) image.
@@ -481,6 +754,7 @@ mod test_edge_detection {
     }
 
     #[test]
+    #[allow(clippy::if_same_then_else)]
     fn weak_pixel_with_strong_neighbor_is_converted() {
         //there is method to use raw pixel data, but it is not good enough documented, so I couldn't figure out how to use it
         let img = DynamicImage::ImageLuma8(ImageBuffer::from_fn(3, 3, |x, y| {
@@ -0,0 +1,317 @@
+//! Support for the inline image protocols of terminals.
+//!
+//! Some terminals can show real images between the lines of text. Instead of converting the image to ascii art,
+//! the resized image is encoded as png and sent to the terminal inside an escape sequence, which also contains
+//! the number of columns and rows the image should cover.
+//! The Kitty graphics protocol is described at <https://sw.kovidgoyal.net/kitty/graphics-protocol/>,
+//! the inline images of iTerm2 at <https://iterm2.com/documentation-images.html>.
+
+use std::io::Cursor;
+
+use image::{DynamicImage, ImageFormat};
+
+use crate::config::{ArtemError, Config, ResizingDimension};
+
+/// The maximum width of the part of the image, which is shown in a single cell, in pixels.
+///
+/// Terminal cells are rarely larger than this, so larger images would only increase the size of the escape sequence.
+const MAX_CELL_WIDTH: u32 = 16;
+
+/// The maximum height of the part of the image, which is shown in a single cell, in pixels.
+const MAX_CELL_HEIGHT: u32 = 32;
+
+/// The maximum number of base64 characters in a single escape sequence of the Kitty graphics protocol.
+const KITTY_CHUNK_SIZE: usize = 4096;
+
+/// The characters used by base64 for each group of 6 bits.
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Protocol of a terminal to show inline images.
+///
+/// # Examples
+/// ```
+/// use artem::graphics::ImageProtocol;
+///
+/// //the protocols are only detected in supporting terminals
+/// if let Some(protocol) = ImageProtocol::detect() {
+///     println!("Using {protocol:?}");
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageProtocol {
+    /// The graphics protocol of Kitty, which is also supported by other terminals, like Ghostty and Konsole.
+    Kitty,
+    /// The inline images of iTerm2, which are also supported by WezTerm.
+    Iterm,
+}
+
+impl ImageProtocol {
+    /// Returns the image protocol of the terminal, if it supports one.
+    ///
+    /// The terminal is detected using the `TERM` and `TERM_PROGRAM` environment variables,
+    /// since there is no reliable way to query the support without waiting for a response of the terminal.
+    pub fn detect() -> Option<ImageProtocol> {
+        let term = std::env::var("TERM").ok();
+        let term_program = std::env::var("TERM_PROGRAM").ok();
+        Self::from_env(term.as_deref(), term_program.as_deref())
+    }
+
+    /// Returns the image protocol for the given values of `TERM` and `TERM_PROGRAM`.
+    fn from_env(term: Option<&str>, term_program: Option<&str>) -> Option<ImageProtocol> {
+        match (term.unwrap_or_default(), term_program.unwrap_or_default()) {
+            (_, "iTerm.app" | "WezTerm") => Some(ImageProtocol::Iterm),
+            ("xterm-kitty" | "xterm-ghostty", _) | (_, "ghostty") => Some(ImageProtocol::Kitty),
+            _ => None,
+        }
+    }
+}
+
+/// Takes an image and returns the escape sequence, which shows it as an inline image in the terminal.
+///
+/// The image is resized like the ascii art of [`crate::convert`], so it covers the same number of columns and rows,
+/// and the filters of the [`Config`], like the brightness or flipping the image, are applied as well.
+/// Options, which only affect the characters or colors of the ascii art, are ignored.
+/// The escape sequence does not end with a line break.
+///
+/// # Errors
+/// Returns the same errors as [`crate::convert`], for example if the image is empty.
+///
+/// # Examples
+/// ```
+/// use artem::config::ConfigBuilder;
+/// use artem::graphics::{inline_image, ImageProtocol};
+///
+/// let img = image::DynamicImage::new_rgb8(512, 512);
+/// let sequence = inline_image(img, &ConfigBuilder::new().build(), ImageProtocol::Iterm).unwrap();
+/// assert!(sequence.starts_with("\x1b]1337;File=inline=1;"));
+/// ```
+pub fn inline_image(
+    image: DynamicImage,
+    config: &Config,
+    protocol: ImageProtocol,
+) -> Result<String, ArtemError> {
+    crate::check_input(&image, config)?;
+    let image = crate::rotate_and_crop(image, config);
+    let (columns, rows, tile_width, tile_height) = ResizingDimension::calculate_dimensions(
+        config.target_size,
+        image.height(),
+        image.width(),
+        config.scale,
+        config.border,
+        config.dimension,
+    )
+    .expect("the image is not empty and the target size is not 0");
+    log::debug!("Columns: {columns}");
+    log::debug!("Rows: {rows}");
+
+    let (width, height) = image_size(columns, rows, tile_width, tile_height);
+    let image = crate::resize(&crate::preprocess(image, config), width, height, config);
+
+    let mut png = Vec::new();
+    image
+        .to_rgba8()
+        .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+        .expect("writing a png to memory does not fail");
+    log::debug!("Encoded png with {} bytes", png.len());
+
+    let data = base64(&png);
+    Ok(match protocol {
+        ImageProtocol::Kitty => kitty_sequence(&data, columns, rows),
+        ImageProtocol::Iterm => format!(
+            "\x1b]1337;File=inline=1;size={};width={columns};height={rows};preserveAspectRatio=0:{data}\x07",
+            png.len()
+        ),
+    })
+}
+
+/// Returns the width and height of the image, which is shown in the columns and rows.
+///
+/// The image keeps the size of the tiles, unless they are larger than [`MAX_CELL_WIDTH`] and [`MAX_CELL_HEIGHT`].
+/// Then the tiles are shrunk, while keeping their aspect ratio.
+fn image_size(columns: u32, rows: u32, tile_width: u32, tile_height: u32) -> (u32, u32) {
+    let factor = (MAX_CELL_WIDTH as f64 / tile_width as f64)
+        .min(MAX_CELL_HEIGHT as f64 / tile_height as f64)
+        .min(1f64);
+    let width = ((columns * tile_width) as f64 * factor).round().max(1f64) as u32;
+    let height = ((rows * tile_height) as f64 * factor).round().max(1f64) as u32;
+    (width, height)
+}
+
+/// Returns the escape sequences of the Kitty graphics protocol, which show the base64 encoded png.
+///
+/// The data is split into chunks, each of them is sent in its own escape sequence.
+/// All but the last chunk are marked with `m=1`. The responses of the terminal are suppressed with `q=2`,
+/// since they would otherwise be written to the input of the shell.
+fn kitty_sequence(data: &str, columns: u32, rows: u32) -> String {
+    let chunks = data.as_bytes().chunks(KITTY_CHUNK_SIZE).collect::<Vec<_>>();
+    let mut sequence = String::with_capacity(data.len() + chunks.len() * 16 + 32);
+    for (index, chunk) in chunks.iter().enumerate() {
+        let more = u8::from(index + 1 < chunks.len());
+        //the first sequence contains the options of the image
+        if index == 0 {
+            sequence.push_str(&format!(
+                "\x1b_Ga=T,f=100,q=2,c={columns},r={rows},m={more};"
+            ));
+        } else {
+            sequence.push_str(&format!("\x1b_Gm={more};"));
+        }
+        //base64 only consists of ascii characters
+        sequence.push_str(std::str::from_utf8(chunk).unwrap_or_default());
+        sequence.push_str("\x1b\\");
+    }
+    sequence
+}
+
+/// Returns the bytes encoded as base64 with padding.
+fn base64(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for group in bytes.chunks(3) {
+        let bits = group.iter().enumerate().fold(0u32, |bits, (index, byte)| {
+            bits | (*byte as u32) << (16 - index * 8)
+        });
+        //each group of 3 bytes becomes 4 characters, missing bytes are replaced by padding
+        for index in 0..4 {
+            if index <= group.len() {
+                encoded.push(BASE64_ALPHABET[(bits >> (18 - index * 6) & 0x3F) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod test_base64 {
+    use super::*;
+
+    #[test]
+    fn rfc_examples() {
+        for (input, expected) in [
+            ("", ""),
+            ("f", "Zg=="),
+            ("fo", "Zm8="),
+            ("foo", "Zm9v"),
+            ("foob", "Zm9vYg=="),
+            ("fooba", "Zm9vYmE="),
+            ("foobar", "Zm9vYmFy"),
+        ] {
+            assert_eq!(expected, base64(input.as_bytes()));
+        }
+    }
+
+    #[test]
+    fn all_characters() {
+        assert_eq!("+/8=", base64(&[0xFB, 0xFF]));
+    }
+}
+
+#[cfg(test)]
+mod test_from_env {
+    use super::*;
+
+    #[test]
+    fn kitty() {
+        assert_eq!(
+            Some(ImageProtocol::Kitty),
+            ImageProtocol::from_env(Some("xterm-kitty"), None)
+        );
+        assert_eq!(
+            Some(ImageProtocol::Kitty),
+            ImageProtocol::from_env(Some("xterm-256color"), Some("ghostty"))
+        );
+    }
+
+    #[test]
+    fn iterm() {
+        assert_eq!(
+            Some(ImageProtocol::Iterm),
+            ImageProtocol::from_env(Some("xterm-256color"), Some("iTerm.app"))
+        );
+        assert_eq!(
+            Some(ImageProtocol::Iterm),
+            ImageProtocol::from_env(Some("xterm-256color"), Some("WezTerm"))
+        );
+    }
+
+    #[test]
+    fn unsupported() {
+        assert_eq!(None, ImageProtocol::from_env(None, None));
+        assert_eq!(
+            None,
+            ImageProtocol::from_env(Some("xterm-256color"), Some("Apple_Terminal"))
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_inline_image {
+    use std::num::NonZeroU32;
+
+    use crate::config::ConfigBuilder;
+
+    use super::*;
+
+    #[test]
+    fn kitty_chunks() {
+        //a noisy image, which does not compress well, needs multiple chunks
+        let img = DynamicImage::ImageRgb8(image::ImageBuffer::from_fn(200, 200, |x, y| {
+            image::Rgb([((x * 7) ^ (y * 13)) as u8, (x * y) as u8, (x + y * 3) as u8])
+        }));
+        let config = ConfigBuilder::new()
+            .target_size(NonZeroU32::new(40).unwrap())
+            .build();
+        let sequence = inline_image(img, &config, ImageProtocol::Kitty).unwrap();
+        assert!(sequence.starts_with("\x1b_Ga=T,f=100,q=2,c=40,r=18,m=1;iVBOR"));
+        assert!(sequence.contains("\x1b\\\x1b_Gm=1;"));
+        assert!(sequence.ends_with("\x1b\\"));
+        assert_eq!(1, sequence.matches("m=0;").count());
+    }
+
+    #[test]
+    fn iterm_cells() {
+        let img = DynamicImage::new_rgb8(100, 50);
+        let config = ConfigBuilder::new()
+            .target_size(NonZeroU32::new(20).unwrap())
+            .build();
+        let sequence = inline_image(img, &config, ImageProtocol::Iterm).unwrap();
+        assert!(sequence.contains(";width=20;height=4;"));
+        assert!(sequence.ends_with('\x07'));
+    }
+
+    #[test]
+    fn invalid_input() {
+        let config = ConfigBuilder::new().build();
+        assert_eq!(
+            Err(ArtemError::InvalidDimensions(0, 50)),
+            inline_image(DynamicImage::new_rgb8(0, 50), &config, ImageProtocol::Kitty)
+        );
+        let config = ConfigBuilder::new().color_step(0).build();
+        assert_eq!(
+            Err(ArtemError::ZeroColorStep),
+            inline_image(
+                DynamicImage::new_rgb8(10, 10),
+                &config,
+                ImageProtocol::Iterm
+            )
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_image_size {
+    use super::*;
+
+    #[test]
+    fn small_tiles_are_kept() {
+        assert_eq!((80, 68), image_size(10, 4, 8, 17));
+    }
+
+    #[test]
+    fn large_tiles_are_shrunk() {
+        //the height of the tiles limits the size, the width is shrunk by the same factor
+        assert_eq!((100, 128), image_size(10, 4, 40, 128));
+        assert_eq!((160, 200), image_size(10, 10, 400, 500));
+    }
+}
@@ -0,0 +1,346 @@
+use std::io::{self, Write};
+
+use artem::config::{Config, TargetType};
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    execute, queue, terminal,
+};
+use image::DynamicImage;
+
+/// Number of steps of the scale per `1.0`, each key press changes the scale by one step.
+const SCALE_STEPS: f32 = 20.0;
+
+/// The range of the scale in the preview, which includes the ratios of all common fonts.
+const SCALE_RANGE: std::ops::RangeInclusive<f32> = 0.1..=1.0;
+
+/// A key, which is used to control the interactive preview.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    /// Increase the size.
+    Up,
+    /// Decrease the size.
+    Down,
+    /// Decrease the scale.
+    Left,
+    /// Increase the scale.
+    Right,
+    /// Use the next characters.
+    NextRamp,
+    /// Use the previous characters.
+    PreviousRamp,
+    /// Use the current parameters for the final output.
+    Confirm,
+    /// Quit without an output.
+    Cancel,
+}
+
+impl Key {
+    /// Returns the [`Key`] for the given key event, if it controls the preview.
+    fn from_event(event: KeyEvent) -> Option<Key> {
+        match event.code {
+            KeyCode::Up => Some(Key::Up),
+            KeyCode::Down => Some(Key::Down),
+            KeyCode::Left => Some(Key::Left),
+            KeyCode::Right => Some(Key::Right),
+            KeyCode::Tab | KeyCode::Char('r') => Some(Key::NextRamp),
+            KeyCode::BackTab | KeyCode::Char('R') => Some(Key::PreviousRamp),
+            KeyCode::Enter => Some(Key::Confirm),
+            //raw mode does not send a signal for ctrl+c
+            KeyCode::Char('c') if event.modifiers.contains(KeyModifiers::CONTROL) => {
+                Some(Key::Cancel)
+            }
+            KeyCode::Esc | KeyCode::Char('q') => Some(Key::Cancel),
+            _ => None,
+        }
+    }
+}
+
+/// The parameters of the interactive preview, which can be adjusted with the [`Key`]s.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Preview {
+    config: Config,
+    ramps: Vec<String>,
+    ramp: usize,
+}
+
+impl Preview {
+    /// Create a new preview, starting with the given config.
+    ///
+    /// The ramps are the characters, which can be selected. The characters of the config are
+    /// always selectable and used first.
+    pub fn new(config: Config, ramps: &[&str]) -> Preview {
+        let mut all_ramps = vec![config.characters.clone()];
+        for ramp in ramps {
+            if !ramp.is_empty() && !all_ramps.iter().any(|existing| existing == ramp) {
+                all_ramps.push(ramp.to_string());
+            }
+        }
+        Preview {
+            config,
+            ramps: all_ramps,
+            ramp: 0,
+        }
+    }
+
+    /// Returns the config with the current parameters.
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Change the parameters according to the key.
+    ///
+    /// Returns if a parameter was changed, in which case the preview has to be rendered again.
+    /// Keys that do not change a parameter, such as [`Key::Confirm`] or the size at its minimum, return false.
+    pub fn handle(&mut self, key: Key) -> bool {
+        let old = (self.config.target_size, self.config.scale, self.ramp);
+        match key {
+            Key::Up => self.config.target_size = self.config.target_size.saturating_add(1),
+            Key::Down => self.config.target_size = self.config.target_size.saturating_sub(1).max(1),
+            Key::Left => self.change_scale(-1.0),
+            Key::Right => self.change_scale(1.0),
+            Key::NextRamp => self.ramp = (self.ramp + 1) % self.ramps.len(),
+            Key::PreviousRamp => self.ramp = (self.ramp + self.ramps.len() - 1) % self.ramps.len(),
+            Key::Confirm | Key::Cancel => {}
+        }
+        self.config.characters = self.ramps[self.ramp].clone();
+        old != (self.config.target_size, self.config.scale, self.ramp)
+    }
+
+    /// Change the scale by the given number of steps, while staying in the [`SCALE_RANGE`].
+    ///
+    /// A scale outside of the range, for example from the `--ratio` argument, is not clamped to the range,
+    /// but the steps can only move it towards the range.
+    fn change_scale(&mut self, steps: f32) {
+        //round to whole steps, to avoid accumulating float errors
+        let scale = ((self.config.scale * SCALE_STEPS).round() + steps) / SCALE_STEPS;
+        let lower = SCALE_RANGE.start().min(self.config.scale);
+        let upper = SCALE_RANGE.end().max(self.config.scale);
+        self.config.scale = scale.clamp(lower, upper);
+    }
+
+    /// Returns a single line with the current parameters and the available keys.
+    pub fn status(&self) -> String {
+        format!(
+            "size: {}, ratio: {:.2}, characters: {}/{} | arrow keys: size/ratio, tab: characters, enter: confirm, esc: quit",
+            self.config.target_size,
+            self.config.scale,
+            self.ramp + 1,
+            self.ramps.len(),
+        )
+    }
+
+    /// Render the preview of the image with the current parameters for the terminal.
+    ///
+    /// If the image can not be converted, for example because it is empty, the error is shown instead.
+    pub fn render(&self, image: &DynamicImage) -> String {
+        let config = Config {
+            target: TargetType::Shell,
+            ..self.config.clone()
+        };
+        artem::convert(image.clone(), &config).unwrap_or_else(|err| err.to_string())
+    }
+}
+
+/// Run the preview for the given keys, until a key confirms or cancels it.
+///
+/// The preview is drawn once at the start and again after every change of the parameters.
+/// Returns true if the preview was confirmed. If the keys end without confirming, it is treated
+/// as cancelled.
+pub fn run(
+    image: &DynamicImage,
+    preview: &mut Preview,
+    keys: impl IntoIterator<Item = Key>,
+    mut draw: impl FnMut(&Preview, &str),
+) -> bool {
+    draw(preview, &preview.render(image));
+    for key in keys {
+        match key {
+            Key::Confirm => return true,
+            Key::Cancel => return false,
+            key => {
+                if preview.handle(key) {
+                    log::debug!("Preview changed: {}", preview.status());
+                    draw(preview, &preview.render(image));
+                }
+            }
+        }
+    }
+    false
+}
+
+/// Run the interactive preview in the terminal, which reads the keys in raw mode.
+///
+/// The preview is shown on the alternate screen, so the terminal content is restored afterwards.
+/// Returns true if the user confirmed the parameters.
+///
+/// # Errors
+/// Returns an error, if the terminal could not be set up or read from.
+pub fn run_terminal(image: &DynamicImage, preview: &mut Preview) -> io::Result<bool> {
+    let mut stdout = io::stdout();
+    terminal::enable_raw_mode()?;
+    execute!(stdout, terminal::EnterAlternateScreen, cursor::Hide)?;
+
+    let mut read_error = None;
+    let keys = std::iter::from_fn(|| loop {
+        match event::read() {
+            Ok(Event::Key(event)) if event.kind == KeyEventKind::Press => {
+                if let Some(key) = Key::from_event(event) {
+                    return Some(key);
+                }
+            }
+            Ok(_) => {}
+            Err(err) => {
+                read_error = Some(err);
+                return None;
+            }
+        }
+    });
+    let mut draw_error = None;
+    let confirmed = run(image, preview, keys, |preview, output| {
+        if let Err(err) = draw(&mut stdout, preview, output) {
+            draw_error.get_or_insert(err);
+        }
+    });
+
+    //always restore the terminal, even if an error occurred
+    execute!(stdout, cursor::Show, terminal::LeaveAlternateScreen)?;
+    terminal::disable_raw_mode()?;
+    match read_error.or(draw_error) {
+        Some(err) => Err(err),
+        None => Ok(confirmed),
+    }
+}
+
+/// Draw the output and the status line at the top of the terminal.
+fn draw(stdout: &mut impl Write, preview: &Preview, output: &str) -> io::Result<()> {
+    queue!(
+        stdout,
+        cursor::MoveTo(0, 0),
+        terminal::Clear(terminal::ClearType::All)
+    )?;
+    //raw mode does not return the cursor to the start of the line
+    for line in output.lines() {
+        write!(stdout, "{line}\r\n")?;
+    }
+    write!(stdout, "{}", preview.status())?;
+    stdout.flush()
+}
+
+#[cfg(test)]
+mod test_preview {
+    use super::*;
+    use artem::config::ConfigBuilder;
+
+    fn test_image() -> DynamicImage {
+        DynamicImage::ImageRgb8(image::ImageBuffer::from_fn(120, 80, |x, y| {
+            image::Rgb([(x * 2) as u8, (y * 3) as u8, 128])
+        }))
+    }
+
+    fn base_config() -> Config {
+        ConfigBuilder::new()
+            .color(false)
+            .characters("#*. ".to_string())
+            .target_size(std::num::NonZeroU32::new(20).unwrap())
+            .scale(0.5)
+            .build()
+    }
+
+    #[test]
+    fn adjusts_parameters() {
+        let mut preview = Preview::new(base_config(), &["@%=-", "#*. "]);
+        assert!(preview.handle(Key::Up));
+        assert!(preview.handle(Key::Right));
+        assert!(preview.handle(Key::NextRamp));
+        assert!(!preview.handle(Key::Confirm));
+
+        assert_eq!(21, preview.config().target_size);
+        assert_eq!(0.55, preview.config().scale);
+        assert_eq!("@%=-", preview.config().characters);
+        //the duplicated characters of the config are not added again
+        assert!(preview.handle(Key::NextRamp));
+        assert_eq!("#*. ", preview.config().characters);
+    }
+
+    #[test]
+    fn stays_in_range() {
+        let mut config = base_config();
+        config.target_size = 1;
+        config.scale = 1.0;
+        let mut preview = Preview::new(config, &[]);
+        assert!(!preview.handle(Key::Down));
+        assert!(!preview.handle(Key::Right));
+        assert!(!preview.handle(Key::NextRamp));
+        assert_eq!(1, preview.config().target_size);
+        assert_eq!(1.0, preview.config().scale);
+    }
+
+    #[test]
+    fn keeps_ratio_outside_of_range() {
+        let mut config = base_config();
+        config.scale = 2.0;
+        let mut preview = Preview::new(config, &[]);
+        //the ratio is not clamped by a key press, it only moves towards the range
+        assert!(!preview.handle(Key::Right));
+        assert_eq!(2.0, preview.config().scale);
+        assert!(preview.handle(Key::Left));
+        assert_eq!(1.95, preview.config().scale);
+
+        let mut config = base_config();
+        config.scale = 0.05;
+        let mut preview = Preview::new(config, &[]);
+        assert!(!preview.handle(Key::Left));
+        assert_eq!(0.05, preview.config().scale);
+        assert!(preview.handle(Key::Right));
+        assert_eq!(0.1, preview.config().scale);
+    }
+
+    #[test]
+    fn renders_again_on_change() {
+        let image = test_image();
+        let mut preview = Preview::new(base_config(), &["@%=-"]);
+        let mut drawn = Vec::new();
+        //the confirm key ends the input, the remaining keys are not used
+        let keys = [
+            Key::Up,
+            Key::Up,
+            Key::Down,
+            Key::Left,
+            Key::NextRamp,
+            Key::Confirm,
+            Key::Up,
+        ];
+        let confirmed = run(&image, &mut preview, keys, |_, output| {
+            drawn.push(output.to_string())
+        });
+        assert!(confirmed);
+        //once at the start and once for every change
+        assert_eq!(6, drawn.len());
+
+        let expected_config = ConfigBuilder::new()
+            .color(false)
+            .characters("@%=-".to_string())
+            .target_size(std::num::NonZeroU32::new(21).unwrap())
+            .scale(0.45)
+            .build();
+        let expected = artem::convert(image, &expected_config).unwrap();
+        assert_eq!(&expected, drawn.last().unwrap());
+        assert_eq!(expected_config.target_size, preview.config().target_size);
+        assert_eq!(expected_config.scale, preview.config().scale);
+        assert_eq!(expected_config.characters, preview.config().characters);
+    }
+
+    #[test]
+    fn cancel_and_end_of_input() {
+        let image = test_image();
+        let mut preview = Preview::new(base_config(), &[]);
+        assert!(!run(
+            &image,
+            &mut preview,
+            [Key::Up, Key::Cancel],
+            |_, _| {}
+        ));
+        assert!(!run(&image, &mut preview, [Key::Up], |_, _| {}));
+    }
+}
@@ -10,37 +10,440 @@
 //! ```
 //! # let path = "./assets/images/standard_test_img.png";
 //! let image = image::open(path).expect("Failed to open image");
-//! let ascii_art = artem::convert(image, &artem::config::ConfigBuilder::new().build());
+//! let ascii_art = artem::convert(image, &artem::config::ConfigBuilder::new().build()).unwrap();
 //! ```
+//!
+//! The conversion functions only return the ascii art, they never print it or exit the program, so they can be used
+//! inside other applications, for example a TUI. Only [`print_image`] writes directly to the terminal.
+//! Options, which come from user input, can be checked using [`crate::config::Config::validate`] before converting the image.
 
 //condense all arguments into a single struct
 pub mod config;
 
+//SAUCE metadata for ansi art files
+pub mod sauce;
+
+//color palette extraction
+pub mod palette;
+
+//inline images for terminals with a graphics protocol
+pub mod graphics;
+
+//EXIF orientation of photos
+#[cfg(feature = "exif")]
+pub mod exif;
+
+//3D color lookup tables
+#[cfg(feature = "lut")]
+pub mod lut;
+
 //functions for working with pixels
 mod pixel;
 
 //outlining filter
 mod filter;
+//braille characters with 2x4 dots
+mod braille;
 //functions for dealing with output targets/files
 mod target;
+//convenience functions for printing images
+mod print;
 
-use image::{DynamicImage, GenericImageView};
+use std::{
+    collections::HashSet,
+    io::Write,
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+use image::{DynamicImage, GenericImage, GenericImageView, Rgba};
 use once_cell::sync::Lazy;
 
 pub use crate::config::ConfigBuilder;
-use crate::config::{Config, ResizingDimension, TargetType};
+use crate::config::{
+    ArtemError, CompensationRows, Config, ResizingDimension, TargetType, Whitespace,
+};
+pub use crate::pixel::{dominant_color, glyph_for_luminance, Cell};
+pub use crate::print::{print_image, print_image_to, ImageSource};
+pub use crate::target::ansi::visible_len;
+pub use crate::target::json::JsonGrid;
 
 /// Takes an image and returns it as an ascii art string.
 ///
-/// The result can be changed using the [`crate::config::Config`] argument
+/// The result can be changed using the [`crate::config::Config`] argument. Instead of panicking or producing an unexpected output,
+/// an invalid config or an empty image is returned as an error, so the crate can be embedded in other applications,
+/// even if the config is created from user input.
+///
+/// # Errors
+/// Returns the first error of [`Config::validate`], which makes the conversion impossible, like
+/// [`ArtemError::EmptyCharacterSet`] or [`ArtemError::ZeroTargetSize`]. Conflicts, missing requirements and
+/// unsupported background colors do not fail, the affected options are simply ignored.
+/// Returns [`ArtemError::InvalidDimensions`], if the image is empty.
+///
+/// # Examples
+/// ```
+/// use artem::config::{ArtemError, ConfigBuilder};
+///
+/// let img = image::DynamicImage::new_rgb8(512, 512);
+/// let mut config = ConfigBuilder::new().build();
+/// assert!(artem::convert(img.clone(), &config).is_ok());
+///
+/// config.characters = String::new();
+/// assert_eq!(Err(ArtemError::EmptyCharacterSet), artem::convert(img, &config));
+/// ```
+pub fn convert(image: DynamicImage, config: &Config) -> Result<String, ArtemError> {
+    check_input(&image, config)?;
+    Ok(grid(image, config, None).render(config))
+}
+
+/// Returns the first error of the config, which makes the conversion impossible,
+/// or [`ArtemError::InvalidDimensions`], if the image is empty.
+///
+/// Conflicts, missing requirements and unsupported backgrounds are only reported by [`Config::validate`],
+/// the conversion simply ignores the affected options.
+pub(crate) fn check_input(image: &DynamicImage, config: &Config) -> Result<(), ArtemError> {
+    if let Err(errors) = config.validate() {
+        if let Some(error) = errors.into_iter().find(|error| {
+            matches!(
+                error,
+                ArtemError::EmptyCharacterSet
+                    | ArtemError::EmptyRampGradient
+                    | ArtemError::EmptyTonalRamps
+                    | ArtemError::ZeroTargetSize
+                    | ArtemError::InvalidScale(_)
+                    | ArtemError::ZeroColorStep
+                    | ArtemError::EmptyCrop(_, _)
+                    | ArtemError::EmptyColorPalette
+                    | ArtemError::InvalidStretch(_, _)
+                    | ArtemError::InvalidCharHeightCompensation(_)
+            )
+        }) {
+            return Err(error);
+        }
+    }
+    if image.width() == 0 || image.height() == 0 {
+        return Err(ArtemError::InvalidDimensions(image.width(), image.height()));
+    }
+    Ok(())
+}
+
+/// Statistics about a conversion.
+///
+/// See [`convert_with_stats`] for more information.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    /// Width of the input image in pixels, after it was cropped.
+    pub source_width: u32,
+    /// Height of the input image in pixels, after it was cropped.
+    pub source_height: u32,
+    /// Number of columns of the ascii art, without the border.
+    pub columns: u32,
+    /// Number of rows of the ascii art, without the border.
+    pub rows: u32,
+    /// Width of the part of the resized image, which is converted to a single character, in pixels.
+    pub tile_width: u32,
+    /// Height of the part of the resized image, which is converted to a single character, in pixels.
+    pub tile_height: u32,
+    /// Number of distinct colors used by the converted characters.
+    pub distinct_colors: usize,
+}
+
+/// Takes an image and returns it as an ascii art string, together with [`Stats`] about the conversion.
+///
+/// This is the same as [`convert`], but additionally returns information about the conversion,
+/// such as the number of columns and rows, which would otherwise have to be derived from the output.
+///
+/// # Errors
+/// Returns the same errors as [`convert`].
+///
+/// # Examples
+/// ```
+/// use artem::config::ConfigBuilder;
+///
+/// let img = image::DynamicImage::new_rgb8(512, 512);
+/// let (ascii_art, stats) = artem::convert_with_stats(img, &ConfigBuilder::new().build()).unwrap();
+/// assert_eq!(80, stats.columns);
+/// assert_eq!(6, stats.tile_width);
+/// assert_eq!(1, stats.distinct_colors);
+/// ```
+pub fn convert_with_stats(
+    image: DynamicImage,
+    config: &Config,
+) -> Result<(String, Stats), ArtemError> {
+    let grid = convert_to_grid(image, config)?;
+    Ok((grid.render(config), grid.stats()))
+}
+
+/// A converted image, consisting of a grid of [`Cell`]s.
+///
+/// The grid can be rendered to the different targets, without having to convert the image again.
+/// See [`convert_to_grid`] for more information.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid {
+    cells: Vec<Cell>,
+    /// Background colors of the half blocks, which is empty if half blocks are not used.
+    lower_colors: Vec<(u8, u8, u8)>,
+    stats: Stats,
+}
+
+/// Takes an image and returns it as a [`Grid`] of converted cells.
+///
+/// In contrast to [`convert`], the grid is not yet rendered to a string. It can be rendered
+/// multiple times using [`Grid::render`], for example to create both a plain text and a html file of
+/// the same image, while only converting it once. Only the options, which affect the rendering, such as
+/// the target, are used by the rendering, all other options have to be set before the conversion.
+///
+/// # Errors
+/// Returns the same errors as [`convert`].
+///
+/// # Examples
+/// ```
+/// use artem::config::{ConfigBuilder, TargetType};
+///
+/// let img = image::DynamicImage::new_rgb8(512, 512);
+/// let mut config = ConfigBuilder::new().build();
+/// let grid = artem::convert_to_grid(img, &config).unwrap();
+///
+/// let text = grid.render(&config);
+/// config.target = TargetType::HtmlFile;
+/// let html = grid.render(&config);
+/// assert_eq!(80, grid.stats().columns);
+/// ```
+pub fn convert_to_grid(image: DynamicImage, config: &Config) -> Result<Grid, ArtemError> {
+    check_input(&image, config)?;
+    Ok(grid(image, config, None))
+}
+
+/// Takes an image and returns it as a [`Grid`], while reporting the progress of the conversion.
+///
+/// This is the same as [`convert_to_grid`], but the callback is called with the number of converted rows
+/// and the total number of rows after each row is finished, so it is called exactly once for every row.
+/// This can be used to show a progress bar for large images. With the `parallel` feature, the rows are converted
+/// on multiple threads, so the callback is called from these threads and the calls may not be in order,
+/// but each number of converted rows is reported once.
+///
+/// # Errors
+/// Returns the same errors as [`convert`], in which case the callback is never called.
+///
+/// # Examples
+/// ```
+/// use std::sync::atomic::{AtomicU32, Ordering};
+/// use artem::config::ConfigBuilder;
+///
+/// let img = image::DynamicImage::new_rgb8(512, 512);
+/// let calls = AtomicU32::new(0);
+/// let grid = artem::convert_to_grid_with_progress(img, &ConfigBuilder::new().build(), |done, total| {
+///     assert!(done <= total);
+///     calls.fetch_add(1, Ordering::Relaxed);
+/// })
+/// .unwrap();
+/// assert_eq!(grid.stats().rows, calls.into_inner());
+/// ```
+pub fn convert_to_grid_with_progress(
+    image: DynamicImage,
+    config: &Config,
+    progress: impl Fn(u32, u32) + Sync,
+) -> Result<Grid, ArtemError> {
+    check_input(&image, config)?;
+    Ok(grid(image, config, Some(&progress)))
+}
+
+/// Rotate, crop and convert the image to a [`Grid`], without checking the image and the config first.
+fn grid(
+    image: DynamicImage,
+    config: &Config,
+    progress: Option<&(dyn Fn(u32, u32) + Sync)>,
+) -> Grid {
+    let image = rotate_and_crop(image, config);
+    let (dimensions, padded) = tile_dimensions(&image, config);
+    grid_with_dimensions(image, dimensions, padded, config, progress)
+}
+
+/// Takes the frames of an animation and returns each of them as an ascii art string.
+///
+/// The size of the ascii art is calculated only once from the first frame, so all frames have the
+/// same number of columns and rows, even if the frames would otherwise be resized differently.
+/// Frames, which have a different size than the first one, are stretched to its size.
+///
+/// # Errors
+/// Returns the same errors as [`convert`], if any of the frames is empty or the config is invalid.
+///
+/// # Examples
+/// ```
+/// use artem::config::ConfigBuilder;
+///
+/// let frames = vec![image::DynamicImage::new_rgb8(512, 512); 3];
+/// let ascii_frames = artem::convert_frames(frames, &ConfigBuilder::new().build()).unwrap();
+/// assert_eq!(3, ascii_frames.len());
+/// ```
+pub fn convert_frames(
+    frames: Vec<DynamicImage>,
+    config: &Config,
+) -> Result<Vec<String>, ArtemError> {
+    for frame in &frames {
+        check_input(frame, config)?;
+    }
+    let frames = frames
+        .into_iter()
+        .map(|frame| rotate_and_crop(frame, config))
+        .collect::<Vec<_>>();
+    let Some(first) = frames.first() else {
+        return Ok(Vec::new());
+    };
+    let (dimensions, padded) = tile_dimensions(first, config);
+    log::debug!("Converting {} frames", frames.len());
+
+    Ok(frames
+        .into_iter()
+        .map(|frame| grid_with_dimensions(frame, dimensions, padded, config, None).render(config))
+        .collect())
+}
+
+/// Takes an image and returns an iterator, which converts the ascii art one row at a time.
+///
+/// In contrast to [`convert`], the whole ascii art is never held in memory. The image is resized like for [`convert`],
+/// but each row is only converted and rendered, when the iterator reaches it, so the rows can be written
+/// and dropped one after another. The rows are returned from top to bottom, each of them ending with the line ending of the config.
+/// Only the rows of the ascii art are returned, without the parts around them, like the top and bottom of the border,
+/// a markdown code block or the start and end of a html file. Without these, joining the rows results in the same
+/// output as [`convert`]. With [`Config::char_height_compensation`], a row contains all of its repeated lines.
+///
+/// # Errors
+/// Returns the same errors as [`convert`]. Options, which choose the characters from the luminances of all tiles,
+/// like dithering or stretching the contrast, return [`ArtemError::NotStreamable`],
+/// as well as svg and json files and the css classes of html files.
+///
 /// # Examples
-/// ```no_run
+/// ```
 /// use artem::config::ConfigBuilder;
 ///
-/// let img = image::open("examples/abraham_lincoln.jpg").unwrap();
-/// let converted_image = artem::convert(img, &ConfigBuilder::new().build());
+/// let img = image::DynamicImage::new_rgb8(512, 512);
+/// let config = ConfigBuilder::new().build();
+/// let rows = artem::convert_rows(img.clone(), &config).unwrap();
+/// assert_eq!(artem::convert(img, &config).unwrap(), rows.collect::<String>());
 /// ```
-pub fn convert(image: DynamicImage, config: &Config) -> String {
+pub fn convert_rows(image: DynamicImage, config: &Config) -> Result<Rows<'_>, ArtemError> {
+    check_input(&image, config)?;
+    if let Some(option) = streaming_conflict(config) {
+        return Err(ArtemError::NotStreamable(option));
+    }
+
+    let image = rotate_and_crop(image, config);
+    let (dimensions @ (columns, _, _, _), padded) = tile_dimensions(&image, config);
+    let input_size = image.dimensions();
+    let image = resize_tiles(&preprocess(image, config), dimensions, padded, config);
+    Ok(Rows {
+        image,
+        input_size,
+        dimensions,
+        renderer: RowRenderer::new(columns, None, config),
+        row: 0,
+    })
+}
+
+/// Returns the name of the first option, which needs all rows of the image, see [`convert_rows`].
+fn streaming_conflict(config: &Config) -> Option<&'static str> {
+    let options = [
+        (config.contrast_stretch, "contrast_stretch"),
+        (config.auto_contrast, "auto_contrast"),
+        (config.dither != config::DitherMode::None, "dither"),
+        (config.ramp_gradient.is_some(), "ramp_gradient"),
+        (config.vignette.is_some(), "vignette"),
+        (config.mask.is_some(), "mask"),
+        (config.ink_budget.is_some(), "ink_budget"),
+        (config.bottom_to_top, "bottom_to_top"),
+        (config.trim, "trim"),
+        (
+            matches!(config.target, TargetType::Svg | TargetType::Json),
+            "target",
+        ),
+        (
+            config.target == TargetType::HtmlFile && config.color() && config.css_classes,
+            "css_classes",
+        ),
+    ];
+    options
+        .into_iter()
+        .find_map(|(conflict, option)| conflict.then_some(option))
+}
+
+/// An iterator over the rendered rows of an ascii art.
+///
+/// It is created by [`convert_rows`], see its documentation for more information.
+pub struct Rows<'a> {
+    /// The resized image, which contains all tiles.
+    image: DynamicImage,
+    /// Width and height of the input image, after it was cropped.
+    input_size: (u32, u32),
+    /// Columns, rows, tile width and tile height.
+    dimensions: (u32, u32, u32, u32),
+    renderer: RowRenderer<'a>,
+    /// Index of the next row.
+    row: u32,
+}
+
+impl Iterator for Rows<'_> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (columns, rows, tile_width, tile_height) = self.dimensions;
+        if self.row >= rows {
+            return None;
+        }
+        log::trace!("Converting row {}", self.row);
+        //only the pixels of the row are copied, with one more pixel above and below it as neighbours for the edges
+        let y = self.row * tile_height;
+        let top = y.saturating_sub(1);
+        let bottom = (y + tile_height + 1).min(self.image.height());
+        let strip = self
+            .image
+            .crop_imm(0, top, self.image.width(), bottom - top);
+        let grid = grid_from_tiles(
+            &strip,
+            self.input_size,
+            (columns, 1, tile_width, tile_height),
+            y - top,
+            self.renderer.config,
+            None,
+        );
+        let line = self.renderer.render(
+            &grid.cells,
+            (!grid.lower_colors.is_empty()).then_some(grid.lower_colors.as_slice()),
+            self.row,
+        );
+        self.row += 1;
+        Some(self.renderer.config.line_ending.apply(line))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.dimensions.1 - self.row) as usize;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for Rows<'_> {}
+
+/// Rotate the image and crop it to the region of the config, if they are set.
+///
+/// Both change the dimensions of the image, so they are applied before the dimensions are calculated.
+/// The region is cropped from the rotated image, and converted like a smaller image.
+fn rotate_and_crop(image: DynamicImage, config: &Config) -> DynamicImage {
+    if config.rotation != config::Rotation::None {
+        log::info!("Rotating image by {:?}", config.rotation);
+    }
+    let image = config.rotation.apply(image);
+    match config.crop {
+        //an empty image can not be cropped, it is reported by the conversion
+        Some(region) if image.width() != 0 && image.height() != 0 => {
+            log::info!("Cropping image to {region:?}");
+            filter::crop(image, region)
+        }
+        _ => image,
+    }
+}
+
+/// Returns the columns, rows, tile width and tile height, which are used to convert the image,
+/// and if the lower half of the last row is padded, see [`config::PartialBlock::Pad`].
+fn tile_dimensions(image: &DynamicImage, config: &Config) -> ((u32, u32, u32, u32), bool) {
     log::debug!("Using inverted color: {}", config.invert);
     //get img dimensions
     let input_width = image.width();
@@ -49,21 +452,185 @@ pub fn convert(image: DynamicImage, config: &Config) -> String {
     log::debug!("Input Image Height: {input_height}");
 
     //calculate the needed dimensions
-    let (columns, rows, tile_width, tile_height) = ResizingDimension::calculate_dimensions(
-        config.target_size,
-        input_height,
-        input_width,
-        config.scale,
-        config.border,
-        config.dimension,
-    );
+    let (columns, mut rows, mut tile_width, mut tile_height) =
+        ResizingDimension::calculate_dimensions(
+            config.target_size,
+            input_height,
+            input_width,
+            config.scale,
+            config.border,
+            config.dimension,
+        )
+        .expect("the image is not empty and the target size is not 0");
+    //the rows keep the aspect ratio, so the image can end in the upper half of a row below the last one
+    let padded = config.half_blocks
+        && config.partial_block == config::PartialBlock::Pad
+        && !matches!(config.dimension, ResizingDimension::Stretch { .. })
+        && input_height * 2 / tile_height == rows * 2 + 1;
+    if padded {
+        log::debug!("Padding the lower half of the last row");
+        rows += 1;
+    }
+    if config.braille.is_some() {
+        //the columns and rows keep the aspect ratio, but each dot of a braille character is a single pixel
+        tile_width = braille::DOTS_WIDTH;
+        tile_height = braille::DOTS_HEIGHT;
+    } else if config.half_blocks {
+        //each half of a block is a single pixel
+        tile_width = 1;
+        tile_height = 2;
+    }
     log::debug!("Columns: {columns}");
     log::debug!("Rows: {rows}");
     log::debug!("Tile Width: {tile_width}");
     log::debug!("Tile Height: {tile_height}");
+    ((columns, rows, tile_width, tile_height), padded)
+}
+
+/// Resize the image to the given dimensions and convert it to a [`Grid`].
+///
+/// The progress is reported after each row, see [`convert_to_grid_with_progress`].
+fn grid_with_dimensions(
+    image: DynamicImage,
+    dimensions: (u32, u32, u32, u32),
+    padded: bool,
+    config: &Config,
+    progress: Option<&(dyn Fn(u32, u32) + Sync)>,
+) -> Grid {
+    let (input_width, input_height) = image.dimensions();
+    let input_img = preprocess(image, config);
+
+    let source_img = resize_tiles(&input_img, dimensions, padded, config);
+
+    grid_from_tiles(
+        &source_img,
+        (input_width, input_height),
+        dimensions,
+        0,
+        config,
+        progress,
+    )
+}
+
+/// Resize the image to the size of the tiles.
+///
+/// If the last row is padded, the image is resized to one pixel less and the missing pixel row
+/// is filled with the background of [`config::PartialBlock::Pad`].
+fn resize_tiles(
+    image: &DynamicImage,
+    (columns, rows, tile_width, tile_height): (u32, u32, u32, u32),
+    padded: bool,
+    config: &Config,
+) -> DynamicImage {
+    let (width, height) = (columns * tile_width, rows * tile_height);
+    if !padded {
+        return resize(image, width, height, config);
+    }
+    let resized = resize(image, width, height - 1, config);
+    let (red, green, blue) = config.background_fill.unwrap_or((0, 0, 0));
+    let mut padded_img = DynamicImage::new(width, height, resized.color());
+    padded_img
+        .copy_from(&resized, 0, 0)
+        .expect("the resized image is smaller than the padded image");
+    for x in 0..width {
+        padded_img.put_pixel(x, height - 1, Rgba([red, green, blue, u8::MAX]));
+    }
+    padded_img
+}
+
+/// Resize the image to exactly the given width and height, using the resize filter of the config.
+fn resize(image: &DynamicImage, width: u32, height: u32, config: &Config) -> DynamicImage {
+    log::info!("Resizing image to fit new dimensions");
+    let resized = match config.resize_filter.filter_type() {
+        Some(filter) => {
+            log::debug!("Resizing with {filter:?} filter");
+            image.resize_exact(width, height, filter)
+        }
+        //use the thumbnail method, since its way faster, it may result in artifacts, but the ascii art will be pixelate anyway
+        None => image.thumbnail_exact(width, height),
+    };
+
+    log::debug!("Resized Image Width: {}", resized.width());
+    log::debug!("Resized Image Height: {}", resized.height());
+    resized
+}
+
+/// The maximum number of columns and rows of an image passed to [`convert_prescaled`].
+pub const MAX_PRESCALED_SIZE: u32 = 4096;
+
+/// Takes an image, which already has the size of the ascii art, and returns it as an ascii art string.
+///
+/// Each pixel of the image is converted to a single character, so the image is not resized and the
+/// size options of the [`Config`] (`target_size`, `scale` and `dimension`) are ignored. This can be used if
+/// the image was already resized, for example by the caller, to avoid resizing it a second time.
+///
+/// # Errors
+/// Returns the same errors as [`convert`], or [`ArtemError::InvalidDimensions`],
+/// if the image has more than [`MAX_PRESCALED_SIZE`] columns or rows.
+///
+/// # Examples
+/// ```
+/// use artem::config::ConfigBuilder;
+///
+/// let img = image::DynamicImage::new_rgb8(40, 20);
+/// let ascii_art = artem::convert_prescaled(img, &ConfigBuilder::new().color(false).build()).unwrap();
+/// assert_eq!(20, ascii_art.lines().count());
+/// ```
+pub fn convert_prescaled(image: DynamicImage, config: &Config) -> Result<String, ArtemError> {
+    check_input(&image, config)?;
+    let (columns, rows) = image.dimensions();
+    if columns == 0 || rows == 0 || columns > MAX_PRESCALED_SIZE || rows > MAX_PRESCALED_SIZE {
+        return Err(ArtemError::InvalidDimensions(columns, rows));
+    }
+    log::debug!("Columns: {columns}");
+    log::debug!("Rows: {rows}");
 
+    let source_img = preprocess(image, config);
+    Ok(grid_from_tiles(
+        &source_img,
+        (columns, rows),
+        (columns, rows, 1, 1),
+        0,
+        config,
+        None,
+    )
+    .render(config))
+}
+
+/// Apply the filters and transformations of the config to the image.
+fn preprocess(image: DynamicImage, config: &Config) -> DynamicImage {
     let mut input_img = image;
 
+    if let Some(background) = config.background_fill {
+        log::info!("Blending transparent pixels with {background:?}");
+        input_img = filter::fill_background(input_img, background);
+    }
+
+    #[cfg(feature = "lut")]
+    if let Some(lut) = &config.lut {
+        log::info!("Applying color lookup table");
+        input_img = lut.apply(input_img);
+    }
+
+    if config.silhouette {
+        log::info!("Converting alpha channel to a silhouette");
+        input_img = filter::alpha_silhouette(input_img);
+    }
+
+    if config.negative {
+        log::info!("Inverting image colors");
+        input_img.invert();
+    }
+
+    if config.brightness != 0f64 || config.contrast != 0f64 {
+        log::info!(
+            "Changing image brightness by {} and contrast by {}",
+            config.brightness,
+            config.contrast
+        );
+        input_img = filter::brightness_contrast(input_img, config.brightness, config.contrast);
+    }
+
     if config.outline {
         //create an outline using an algorithm loosely based on the canny edge algorithm
         input_img = filter::edge_detection_filter(input_img, config.hysteresis);
@@ -79,181 +646,2588 @@ pub fn convert(image: DynamicImage, config: &Config) -> String {
         input_img = input_img.flipv();
     }
 
-    log::info!("Resizing image to fit new dimensions");
-    //use the thumbnail method, since its way faster, it may result in artifacts, but the ascii art will be pixelate anyway
-    let source_img = input_img.thumbnail_exact(columns * tile_width, rows * tile_height);
-
-    log::debug!("Resized Image Width: {}", source_img.width());
-    log::debug!("Resized Image Height: {}", source_img.height());
+    input_img
+}
 
-    //output string
-    let mut output = String::with_capacity((tile_width * tile_height) as usize);
-    log::trace!("Created output string");
+/// Convert the already resized image to a [`Grid`].
+///
+/// The image has to have the size of `columns * tile_width` and `rows * tile_height`,
+/// each tile of the image is converted to a single cell. The cell is calculated from every pixel
+/// of the `tile_width * tile_height` region, the tile is never point sampled.
+/// The tiles start at the y coordinate `top`, the pixels above and below the tiles are only used as neighbours.
+/// The progress is called with the number of converted rows after each row.
+fn grid_from_tiles(
+    source_img: &DynamicImage,
+    (input_width, input_height): (u32, u32),
+    (columns, rows, tile_width, tile_height): (u32, u32, u32, u32),
+    top: u32,
+    config: &Config,
+    progress: Option<&(dyn Fn(u32, u32) + Sync)>,
+) -> Grid {
+    log::info!("Starting conversion to ascii");
+    //the luminances are only calculated, if they are needed to choose the characters again
+    let adjust_luminances = config.contrast_stretch
+        || config.auto_contrast
+        || config.dither != config::DitherMode::None
+        || config.ramp_gradient.is_some()
+        || config.vignette.is_some()
+        || config.mask.is_some();
+    //images with more than 8 bits per channel keep their precision for the luminance and color of the tiles
+    let precise_img = filter::is_high_bit_depth(source_img).then(|| {
+        log::debug!(
+            "Using the full precision of the {:?} image",
+            source_img.color()
+        );
+        source_img.to_rgba16()
+    });
+    //each tile is converted independently, which allows to convert them in parallel
+    let convert_tile = |index: u32| {
+        let x = (index % columns) * tile_width;
+        let y = top + (index / columns) * tile_height;
+        //pre-allocate vector with the with space for all pixels in the tile
+        let mut pixels = Vec::with_capacity((tile_height * tile_width) as usize);
 
-    if config.target == TargetType::HtmlFile {
-        log::trace!("Adding html top part");
-        output.push_str(&target::html::html_top());
-    }
+        //get all pixel of the tile, so the whole area is averaged
+        for p_x in 0..tile_width {
+            for p_y in 0..tile_height {
+                pixels.push(unsafe { source_img.unsafe_get_pixel(x + p_x, y + p_y) })
+            }
+        }
 
-    log::trace!("Calculating horizontal spacing");
-    let horizontal_spacing = if config.center_x {
-        spacing_horizontal(if config.border {
-            //two columns are missing because the border takes up two lines
-            columns + 2
+        //convert pixels to a cell
+        let (mut cell, precise_luminance) = match &precise_img {
+            Some(precise_img) => {
+                let mut precise_pixels = Vec::with_capacity(pixels.len());
+                for p_x in 0..tile_width {
+                    for p_y in 0..tile_height {
+                        precise_pixels.push(*precise_img.get_pixel(x + p_x, y + p_y));
+                    }
+                }
+                let (color, luminance) = pixel::precise_color_luminance(&precise_pixels, config);
+                (
+                    pixel::cell_for_tile(color, luminance, config),
+                    Some(luminance),
+                )
+            }
+            None => (pixel::correlating_cell(&pixels, config), None),
+        };
+        //half blocks use the colors of both halves of the tile, instead of the average color
+        let lower_color = config.half_blocks.then(|| {
+            let ((red, green, blue), lower) =
+                pixel::half_block_colors(&pixels, tile_width, tile_height);
+            (cell.red, cell.green, cell.blue) = (red, green, blue);
+            lower
+        });
+        let luminance = if let Some(luminance) = precise_luminance {
+            luminance as f32
+        } else if adjust_luminances {
+            let color = pixel::tile_color(&pixels, config);
+            pixel::tile_luminance(&pixels, color, config) as f32
         } else {
-            columns
-        })
-    } else {
-        String::with_capacity(0)
+            0f32
+        };
+        let fixed_char = if config.half_blocks {
+            Some(target::ansi::HALF_BLOCK)
+        } else if config.background_fill.is_some() && pixels.iter().all(|pixel| pixel.0[3] == 0) {
+            //the background shows through fully transparent tiles
+            Some(' ')
+        } else if config.only_color {
+            //the background color fills the whole space
+            Some(if config.color() && config.background_color() {
+                ' '
+            } else {
+                target::ansi::FULL_BLOCK
+            })
+        } else if config.outline && config.graded_edges {
+            Some(pixel::edge_glyph(&pixels, tile_width, tile_height))
+        } else if config.directional {
+            pixel::directional_char(&pixels, tile_width, tile_height, config)
+        } else if let Some(threshold) = config.edges {
+            pixel::sobel_char(
+                source_img,
+                (x, y, tile_width, tile_height),
+                threshold,
+                config.luminance_standard,
+            )
+        } else {
+            config.braille.map(|threshold| {
+                braille::braille_char(
+                    &pixels,
+                    tile_width,
+                    tile_height,
+                    threshold,
+                    config.invert,
+                    config.luminance_standard,
+                )
+            })
+        };
+        (cell, ((luminance, fixed_char), lower_color))
+    };
+
+    //the rows are counted, since they may be finished in any order, if they are converted in parallel
+    let converted_rows = AtomicU32::new(0);
+    let convert_row = |row: u32| {
+        let tiles = (row * columns..(row + 1) * columns)
+            .map(convert_tile)
+            .collect::<Vec<_>>();
+        if let Some(progress) = progress {
+            progress(converted_rows.fetch_add(1, Ordering::Relaxed) + 1, rows);
+        }
+        tiles
     };
 
-    if config.center_y && config.target == TargetType::Shell {
-        log::trace!("Adding vertical top spacing");
-        output.push_str(&spacing_vertical(if config.border {
-            //two rows are missing because the border takes up two lines
-            rows + 2
+    #[cfg(feature = "parallel")]
+    let tiles = {
+        use rayon::prelude::*;
+        log::debug!("Converting rows in parallel");
+        (0..rows)
+            .into_par_iter()
+            .flat_map_iter(convert_row)
+            .collect::<Vec<_>>()
+    };
+    #[cfg(not(feature = "parallel"))]
+    let tiles = (0..rows).flat_map(convert_row).collect::<Vec<_>>();
+    let (mut cells, ((mut luminances, fixed_chars), lower_colors)): (
+        Vec<_>,
+        ((Vec<_>, Vec<_>), Vec<_>),
+    ) = tiles.into_iter().unzip();
+    let mut lower_colors = lower_colors.into_iter().flatten().collect::<Vec<_>>();
+
+    //choose the characters again, now that the luminances of all cells are known
+    if adjust_luminances {
+        if config.auto_contrast {
+            pixel::auto_contrast(&mut luminances);
+        } else if config.contrast_stretch {
+            pixel::stretch_contrast(&mut luminances);
+        }
+        if let Some(strength) = config.vignette {
+            pixel::apply_vignette(&mut luminances, (columns as usize, rows as usize), strength);
+        }
+        if let Some(mask) = &config.mask {
+            log::debug!("Applying {}x{} mask", mask.width(), mask.height());
+            let mut mask = image::imageops::thumbnail(mask, columns, rows);
+            //the image has already been flipped, so the mask has to follow
+            if config.transform_x {
+                image::imageops::flip_horizontal_in_place(&mut mask);
+            }
+            if config.transform_y {
+                image::imageops::flip_vertical_in_place(&mut mask);
+            }
+            pixel::apply_mask(&mut luminances, &mask);
+        }
+        let chars = if let Some(size) = config.dither.bayer_size() {
+            pixel::ordered_glyphs(&luminances, columns as usize, size, config)
+        } else if config.dither == config::DitherMode::FloydSteinberg {
+            pixel::dithered_glyphs(&luminances, columns as usize, config)
+        } else if config.dither == config::DitherMode::Stipple {
+            log::debug!("Stippling with seed {}", config.seed);
+            pixel::stippled_glyphs(&luminances, columns as usize, config.seed, config)
         } else {
-            rows
-        }));
+            let (columns, rows) = (columns as usize, rows as usize);
+            luminances
+                .iter()
+                .enumerate()
+                .map(|(index, luminance)| {
+                    pixel::cell_glyph(
+                        *luminance as f64,
+                        (index % columns, index / columns),
+                        (columns, rows),
+                        config,
+                    )
+                })
+                .collect()
+        };
+        for (cell, char) in cells.iter_mut().zip(chars) {
+            cell.char = char;
+        }
     }
 
-    if config.border {
-        //add spacing for centering
-        if config.center_x {
-            output.push_str(&horizontal_spacing);
+    //edge and directional characters take precedence over the luminance
+    for (cell, char) in cells.iter_mut().zip(fixed_chars) {
+        if let Some(char) = char {
+            cell.char = char;
         }
+    }
 
-        //add top part of border before conversion
-        log::trace!("Adding top part of border");
-        output.push('╔');
-        output.push_str(&"═".repeat(columns as usize));
-        output.push_str("╗\n");
+    if let Some(budget) = config.ink_budget {
+        log::debug!("Ink budget: {budget}");
+        pixel::apply_ink_budget(
+            &mut cells,
+            columns as usize,
+            budget,
+            config.luminance_standard,
+        );
     }
 
-    log::info!("Starting conversion to ascii");
-    let width = source_img.width();
-
-    //convert source img to a target string
-    let target = source_img
-        .pixels()
-        .step_by(tile_width as usize)
-        .filter(|(x, y, _)| y % tile_height == 0 && x % tile_width == 0)
-        .map(|(x, y, _)| {
-            //pre-allocate vector with the with space for all pixels in the tile
-            let mut pixels = Vec::with_capacity((tile_height * tile_width) as usize);
-
-            //get all pixel of the tile
-            for p_x in 0..tile_width {
-                for p_y in 0..tile_height {
-                    pixels.push(unsafe { source_img.unsafe_get_pixel(x + p_x, y + p_y) })
-                }
-            }
+    if let Some(step) = config.color_step {
+        log::debug!("Color step: {step}");
+        for cell in cells.iter_mut() {
+            cell.red = pixel::quantize_channel(cell.red, step);
+            cell.green = pixel::quantize_channel(cell.green, step);
+            cell.blue = pixel::quantize_channel(cell.blue, step);
+        }
+        for (red, green, blue) in lower_colors.iter_mut() {
+            *red = pixel::quantize_channel(*red, step);
+            *green = pixel::quantize_channel(*green, step);
+            *blue = pixel::quantize_channel(*blue, step);
+        }
+    }
 
-            //convert pixels to a char/string
-            let mut ascii_char = pixel::correlating_char(&pixels, config);
+    if let Some(colors) = config.color_palette.as_deref() {
+        log::debug!("Color palette: {} colors", colors.len());
+        //the palette is only converted once for all cells
+        let points = palette::palette_points(colors, config.distance_metric);
+        let nearest = |color: (u8, u8, u8)| {
+            colors[palette::nearest_center(
+                &palette::color_point(color, config.distance_metric),
+                &points,
+            )]
+        };
+        for cell in cells.iter_mut() {
+            (cell.red, cell.green, cell.blue) = nearest((cell.red, cell.green, cell.blue));
+        }
+        for color in lower_colors.iter_mut() {
+            *color = nearest(*color);
+        }
+    }
 
-            //add border at the start
-            //this cannot be done in single if-else, since the image might only be a single pixel wide
-            if x == 0 {
-                //add outer border (left)
-                if config.border {
-                    ascii_char.insert(0, '║');
-                }
+    //reverse the output order, without flipping the image itself
+    if config.right_to_left {
+        cells
+            .chunks_mut(columns as usize)
+            .for_each(|row| row.reverse());
+        lower_colors
+            .chunks_mut(columns as usize)
+            .for_each(|row| row.reverse());
+    }
+    if config.bottom_to_top {
+        //reversing all cells also reverses the rows, so reverse them back
+        cells.reverse();
+        cells
+            .chunks_mut(columns as usize)
+            .for_each(|row| row.reverse());
+        lower_colors.reverse();
+        lower_colors
+            .chunks_mut(columns as usize)
+            .for_each(|row| row.reverse());
+        //the upper half of each block is now the lower half
+        for (cell, (red, green, blue)) in cells.iter_mut().zip(lower_colors.iter_mut()) {
+            std::mem::swap(&mut cell.red, red);
+            std::mem::swap(&mut cell.green, green);
+            std::mem::swap(&mut cell.blue, blue);
+        }
+    }
 
-                //add spacing for centering the image
-                if config.center_x {
-                    ascii_char.insert_str(0, &horizontal_spacing);
-                }
-            }
+    let (columns, rows) = if config.trim {
+        trim_cells(&mut cells, &mut lower_colors, (columns, rows), config)
+    } else {
+        (columns, rows)
+    };
 
-            //add a break at line end
-            if x == width - tile_width {
-                //add outer border (right)
-                if config.border {
-                    ascii_char.push('║');
-                }
+    let stats = Stats {
+        source_width: input_width,
+        source_height: input_height,
+        columns,
+        rows,
+        tile_width,
+        tile_height,
+        distinct_colors: cells
+            .iter()
+            .map(|cell| (cell.red, cell.green, cell.blue))
+            .chain(lower_colors.iter().copied())
+            .collect::<HashSet<_>>()
+            .len(),
+    };
+    log::debug!("Distinct colors: {}", stats.distinct_colors);
 
-                ascii_char.push('\n');
-            }
+    Grid {
+        cells,
+        lower_colors,
+        stats,
+    }
+}
 
-            ascii_char
-        })
-        .collect::<String>();
+/// Remove the rows and columns at the edges of the cells, which only contain empty characters.
+///
+/// A character is empty, if it is whitespace or the character of the darkest luminance.
+/// The lower colors of half blocks are removed together with their cells.
+/// Returns the remaining columns and rows, the cells are not changed, if all of them are empty.
+fn trim_cells(
+    cells: &mut Vec<Cell>,
+    lower_colors: &mut Vec<(u8, u8, u8)>,
+    (columns, rows): (u32, u32),
+    config: &Config,
+) -> (u32, u32) {
+    let background = glyph_for_luminance(0f64, config);
+    let (columns, rows) = (columns as usize, rows as usize);
+    let filled = |column: usize, row: usize| {
+        let char = cells[row * columns + column].char;
+        !char.is_whitespace() && char != background
+    };
+    let filled_row = |row: &usize| (0..columns).any(|column| filled(column, *row));
+    let (Some(top), Some(bottom)) = ((0..rows).find(filled_row), (0..rows).rfind(filled_row))
+    else {
+        log::debug!("Not trimming empty ascii art");
+        return (columns as u32, rows as u32);
+    };
+    let filled_column = |column: &usize| (top..=bottom).any(|row| filled(*column, row));
+    //there is at least one filled cell, so there is also a filled column
+    let left = (0..columns).find(filled_column).unwrap_or(0);
+    let right = (0..columns).rfind(filled_column).unwrap_or(columns - 1);
+    log::debug!("Trimming to columns {left}..={right} and rows {top}..={bottom}");
 
-    output.push_str(&target);
+    //keep only the cells inside the trimmed rows and columns
+    let keep = |index: usize| {
+        (left..=right).contains(&(index % columns)) && (top..=bottom).contains(&(index / columns))
+    };
+    *cells = (0..cells.len())
+        .filter(|index| keep(*index))
+        .map(|index| cells[index])
+        .collect();
+    *lower_colors = (0..lower_colors.len())
+        .filter(|index| keep(*index))
+        .map(|index| lower_colors[index])
+        .collect();
+    ((right - left + 1) as u32, (bottom - top + 1) as u32)
+}
 
-    if config.border {
-        //add spacing for centering
-        if config.center_x {
-            output.push_str(&horizontal_spacing);
-        }
+/// Renders the rows of cells to lines, which is shared by [`Grid::render`] and [`Rows`].
+struct RowRenderer<'a> {
+    /// Spacing in front of each line for centering the image, which is empty if it is not centered.
+    horizontal_spacing: String,
+    /// Line added by [`CompensationRows::Blank`], which is empty for other rows.
+    blank_line: String,
+    /// Css classes of colored html files.
+    palette: Option<target::html::CssPalette>,
+    config: &'a Config,
+}
 
-        //add bottom part of border after conversion
-        log::trace!("Adding bottom border");
-        output.push('╚');
-        output.push_str(&"═".repeat(columns as usize));
-        output.push('╝');
+impl<'a> RowRenderer<'a> {
+    fn new(columns: u32, palette: Option<target::html::CssPalette>, config: &'a Config) -> Self {
+        log::trace!("Calculating horizontal spacing");
+        let horizontal_spacing = if config.center_x {
+            spacing_horizontal(if config.border {
+                //two columns are missing because the border takes up two lines
+                columns + 2
+            } else {
+                columns
+            })
+        } else {
+            String::with_capacity(0)
+        };
+        let blank_line = if config.compensation_rows == CompensationRows::Blank {
+            blank_line(columns, &horizontal_spacing, config)
+        } else {
+            String::with_capacity(0)
+        };
+        RowRenderer {
+            horizontal_spacing,
+            blank_line,
+            palette,
+            config,
+        }
     }
 
-    //compare it, ignoring the enum value such as true, true
-    if config.target == TargetType::HtmlFile {
-        log::trace!("Adding html bottom part");
-        output.push_str(&target::html::html_bottom());
-    }
+    /// Returns the row with the given index as a line, including the spacing for centering and the sides of the border.
+    ///
+    /// The rows added by the char height compensation are part of the returned string, so it may consist of multiple lines.
+    fn render(&self, row: &[Cell], lower_row: Option<&[(u8, u8, u8)]>, index: u32) -> String {
+        let config = self.config;
+        //add spacing for centering the image
+        let mut output = self.horizontal_spacing.clone();
+
+        //add outer border (left)
+        if config.border {
+            output.push(config.border_style.side());
+        }
 
-    if config.center_y && config.target == TargetType::Shell {
-        log::trace!("Adding vertical bottom spacing");
-        output.push_str(&spacing_vertical(if config.border {
-            //two rows are missing because the border takes up two lines
-            rows + 2
+        let mut line = if config.color() {
+            render_line(row, lower_row, self.palette.as_ref(), config)
         } else {
-            rows
-        }));
-    }
+            //without colors, the characters are used directly, instead of rendering each cell to its own string
+            plain_line(row)
+        };
+
+        //add outer border (right)
+        if config.border {
+            line.push(config.border_style.side());
+        }
 
-    output
+        if config.whitespace == Whitespace::Compact && !config.color() {
+            //the centering spacing is part of the line, so it affects the tab stops
+            line = compact_whitespace(&line, self.horizontal_spacing.len());
+        }
+
+        //add a break at line end
+        target::ansi::terminate_line(&mut line);
+        output.push_str(&line);
+
+        //stretch the ascii art vertically
+        let repeats = compensated_rows(index + 1, config.char_height_compensation)
+            - compensated_rows(index, config.char_height_compensation);
+        if repeats > 1 {
+            let added_line = match config.compensation_rows {
+                CompensationRows::Duplicate => output.clone(),
+                CompensationRows::Blank => self.blank_line.clone(),
+            };
+            for _ in 1..repeats {
+                output.push_str(&added_line);
+            }
+        }
+        output
+    }
 }
 
-/// Return a spacer string, which can be used to center the ascii image in the middle of the terminal.
+/// Returns the cells of a row as a line for the target of the config.
 ///
-/// When the terminal width is not existing, for example when the output is not a terminal, the returned string will be empty.
-fn spacing_horizontal(width: u32) -> String {
-    let term_width = terminal_size::terminal_size()
-        .map(|dimensions| dimensions.0 .0 as u32)
-        .unwrap_or_default();
-    " ".repeat(term_width.saturating_sub(width).saturating_div(2) as usize)
+/// The lower colors are the background colors of half blocks, the palette is used for the css classes of html files.
+fn render_line(
+    row: &[Cell],
+    lower_row: Option<&[(u8, u8, u8)]>,
+    palette: Option<&target::html::CssPalette>,
+    config: &Config,
+) -> String {
+    if config.target == TargetType::BbCode && config.color() {
+        //consecutive characters with the same color share a tag
+        target::bbcode::colored_line(row)
+    } else if let Some(lower_row) = lower_row {
+        row.iter()
+            .zip(lower_row)
+            .map(|(cell, lower)| cell.render_half_block(*lower, config))
+            .collect::<String>()
+    } else {
+        row.iter()
+            .map(|cell| match palette {
+                Some(palette) => palette.colored_char(cell.red, cell.green, cell.blue, cell.char),
+                None => cell.render(config),
+            })
+            .collect::<String>()
+    }
 }
 
-/// Return a spacer string, which can be used to center the ascii image in the middle of the terminal.
+/// Returns the number of rows, which the given number of converted rows take up after the char height compensation.
 ///
-/// When the terminal height is not existing, for example when the output is not a terminal, the returned string will be empty.
-fn spacing_vertical(height: u32) -> String {
-    let term_height = terminal_size::terminal_size()
-        .map(|dimensions| dimensions.1 .0 as u32)
-        .unwrap_or_default();
-    log::trace!("H: {term_height}, h: {height}");
-    "\n".repeat(term_height.saturating_sub(height).saturating_div(2) as usize)
+/// Each converted row is repeated at least once, so the rows are never shrunk.
+fn compensated_rows(rows: u32, factor: f32) -> u32 {
+    ((rows as f64 * factor as f64).round() as u32).max(rows)
 }
 
-/// Returns if the terminal supports truecolor mode.
-///
-/// It checks the `COLORTERM` environment variable,
-/// if it is either set to
-/// `truecolor` or `24bit` true is returned.
-///
-/// In all other cases false will be returned.
-///
-/// # Examples
-/// ```
-/// use artem::SUPPORTS_TRUECOLOR;
-/// # use std::env;
+/// Returns an empty line for [`CompensationRows::Blank`], including the spacing for centering and the border.
+fn blank_line(columns: u32, horizontal_spacing: &str, config: &Config) -> String {
+    let mut line = " ".repeat(columns as usize);
+    if config.border {
+        line.insert(0, config.border_style.side());
+        line.push(config.border_style.side());
+    }
+    if config.whitespace == Whitespace::Compact && !config.color() {
+        line = compact_whitespace(&line, horizontal_spacing.len());
+    }
+    format!("{horizontal_spacing}{line}\n")
+}
+
+/// Returns the characters of the cells of a row as a line without colors.
 ///
-/// # env::set_var("COLORTERM", "truecolor");
-/// //only true when run in a shell that supports true color
-/// let color_support = *SUPPORTS_TRUECOLOR;
-/// assert!(color_support);
-/// ```
-pub static SUPPORTS_TRUECOLOR: Lazy<bool> = Lazy::new(|| {
-    std::env::var("COLORTERM")
+/// This is the same as [`render_line`] without colors, but the characters are pushed into a single string,
+/// instead of rendering each cell to its own string, which is a lot faster for large or many images.
+fn plain_line(row: &[Cell]) -> String {
+    //the border is added to the line afterwards
+    let mut line = String::with_capacity(row.len() + 2 * '║'.len_utf8());
+    line.extend(row.iter().map(|cell| cell.char));
+    line
+}
+
+/// Start of a markdown code block, with a language hint for plain text.
+const MARKDOWN_FENCE_START: &str = "```text\n";
+
+/// End of a markdown code block.
+const MARKDOWN_FENCE_END: &str = "```\n";
+
+impl Grid {
+    /// Returns the [`Stats`] of the conversion.
+    pub fn stats(&self) -> Stats {
+        self.stats
+    }
+
+    /// Returns all cells of the grid, row by row.
+    pub fn cells(&self) -> &[Cell] {
+        &self.cells
+    }
+
+    /// Render the grid as an ascii art string for the target of the given config.
+    ///
+    /// Besides the target, the config is used for the colors, the border and centering of the output,
+    /// as well as the line ending of each line.
+    pub fn render(&self, config: &Config) -> String {
+        config.line_ending.apply(self.render_target(config))
+    }
+
+    /// Render the grid for the target of the config, with line feeds as line endings.
+    fn render_target(&self, config: &Config) -> String {
+        if config.target == TargetType::Svg {
+            return self.render_svg(config);
+        }
+        if config.target == TargetType::Json {
+            return self.render_json();
+        }
+
+        let Stats { columns, rows, .. } = self.stats;
+        let cells = &self.cells;
+
+        //output string
+        let mut output = String::with_capacity(cells.len() * 2);
+        log::trace!("Created output string");
+
+        //css classes are only used for colored html files
+        let palette =
+            if config.target == TargetType::HtmlFile && config.color() && config.css_classes {
+                Some(target::html::CssPalette::new(
+                    cells
+                        .iter()
+                        .map(|cell| ((cell.red, cell.green, cell.blue), cell.char)),
+                    config.background_color(),
+                ))
+            } else {
+                None
+            };
+
+        if config.target == TargetType::HtmlFile {
+            log::trace!("Adding html top part");
+            match (&palette, config.html_background) {
+                (None, None) => output.push_str(&target::html::html_top()),
+                (palette, background) => output.push_str(&target::html::html_top_with_style(
+                    &palette
+                        .as_ref()
+                        .map(|palette| palette.style())
+                        .unwrap_or_default(),
+                    background,
+                )),
+            }
+        }
+
+        if config.target == TargetType::BbCode {
+            log::trace!("Adding bbcode code block start");
+            output.push_str(target::bbcode::CODE_START);
+        }
+
+        let renderer = RowRenderer::new(columns, palette, config);
+
+        //the same spacing is added above and below the ascii art
+        let vertical_spacing = if config.center_y && config.target == TargetType::Shell {
+            log::trace!("Adding vertical spacing");
+            spacing_vertical(rendered_height(rows, config))
+        } else {
+            String::new()
+        };
+        output.push_str(&vertical_spacing);
+
+        //markdown code blocks are only useful for plain text
+        let markdown =
+            config.markdown && matches!(config.target, TargetType::Shell | TargetType::File);
+        if markdown {
+            log::trace!("Adding markdown code block start");
+            output.push_str(MARKDOWN_FENCE_START);
+        }
+
+        if config.border {
+            //add spacing for centering
+            output.push_str(&renderer.horizontal_spacing);
+
+            //add top part of border before conversion
+            log::trace!("Adding top part of border");
+            output.push_str(&config.border_style.top(columns));
+            output.push('\n');
+        }
+
+        //convert the cells to a target string
+        let mut lower_rows = self.lower_colors.chunks(columns as usize);
+        for (index, row) in (0..).zip(cells.chunks(columns as usize)) {
+            output.push_str(&renderer.render(row, lower_rows.next(), index));
+        }
+
+        if config.border {
+            //add spacing for centering
+            output.push_str(&renderer.horizontal_spacing);
+
+            //add bottom part of border after conversion
+            log::trace!("Adding bottom border");
+            output.push_str(&config.border_style.bottom(columns));
+        }
+
+        if markdown {
+            log::trace!("Adding markdown code block end");
+            //the bottom border does not end with a line break
+            if !output.ends_with('\n') {
+                output.push('\n');
+            }
+            output.push_str(MARKDOWN_FENCE_END);
+        }
+
+        if config.target == TargetType::BbCode {
+            log::trace!("Adding bbcode code block end");
+            //the bottom border does not end with a line break
+            if !output.ends_with('\n') {
+                output.push('\n');
+            }
+            output.push_str(target::bbcode::CODE_END);
+        }
+
+        //compare it, ignoring the enum value such as true, true
+        if config.target == TargetType::HtmlFile {
+            log::trace!("Adding html bottom part");
+            output.push_str(&target::html::html_bottom());
+        }
+
+        output.push_str(&vertical_spacing);
+
+        output
+    }
+
+    /// Render the grid like [`Grid::render`] and write it to the given writer, for example a file.
+    ///
+    /// In contrast to [`print_image`], the colors of the config are used as they are, even if the writer is not a terminal.
+    ///
+    /// # Errors
+    /// Returns [`ArtemError::Io`], if the output can not be written.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::ConfigBuilder;
+    ///
+    /// let config = ConfigBuilder::new().color(false).build();
+    /// let grid = artem::convert_to_grid(image::DynamicImage::new_rgb8(20, 20), &config).unwrap();
+    /// let mut output = Vec::new();
+    /// grid.write_to(&mut output, &config).unwrap();
+    /// assert_eq!(grid.render(&config).as_bytes(), output);
+    /// ```
+    pub fn write_to(&self, mut writer: impl Write, config: &Config) -> Result<(), ArtemError> {
+        writer
+            .write_all(self.render(config).as_bytes())
+            .and_then(|_| writer.flush())
+            .map_err(|err| ArtemError::Io(err.to_string()))
+    }
+
+    /// Render the grid as json, see [`JsonGrid`].
+    ///
+    /// The json contains the cells as they were converted, so the rendering options, like the border, are not used.
+    fn render_json(&self) -> String {
+        let Stats {
+            columns,
+            rows,
+            tile_width,
+            tile_height,
+            ..
+        } = self.stats;
+        JsonGrid {
+            columns,
+            rows,
+            tile_width,
+            tile_height,
+            cells: self
+                .cells
+                .chunks(columns as usize)
+                .map(<[Cell]>::to_vec)
+                .collect(),
+        }
+        .to_json()
+    }
+
+    /// Render the grid as a svg file, with a text element for every row.
+    ///
+    /// The size of the svg is calculated from the columns and rows, including the border.
+    /// Centering is not used, since the svg has exactly the size of the ascii art.
+    fn render_svg(&self, config: &Config) -> String {
+        let Stats { columns, rows, .. } = self.stats;
+        let border = if config.border { 2 } else { 0 };
+        let (color, background_color) = (config.color(), config.background_color());
+        let font = &config.svg_font;
+
+        let mut output = target::svg::svg_top(columns + border, rows + border, font);
+        let mut row_index = 0;
+        if config.border {
+            let top = config.border_style.top(columns);
+            output.push_str(&target::svg::text_row(
+                row_index,
+                (&top, &[], ""),
+                font,
+                color,
+                background_color,
+            ));
+            row_index += 1;
+        }
+
+        let side = if config.border {
+            config.border_style.side().to_string()
+        } else {
+            String::new()
+        };
+        for row in self.cells.chunks(columns as usize) {
+            output.push_str(&target::svg::text_row(
+                row_index,
+                (&side, row, &side),
+                font,
+                color,
+                background_color,
+            ));
+            row_index += 1;
+        }
+
+        if config.border {
+            let bottom = config.border_style.bottom(columns);
+            output.push_str(&target::svg::text_row(
+                row_index,
+                (&bottom, &[], ""),
+                font,
+                color,
+                background_color,
+            ));
+        }
+        output.push_str(target::svg::SVG_BOTTOM);
+        output
+    }
+
+    /// Render the grid as an image, with the characters drawn by a bundled monospace font.
+    ///
+    /// The image always supports colors, so the colors and background colors of the config are used regardless of its target.
+    /// Half blocks use the lower color as the background of their cell. The background of the image is black,
+    /// or fully transparent, if [`Config::transparent_background`] is set. The border is drawn like the uncolored characters.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::ConfigBuilder;
+    ///
+    /// let config = ConfigBuilder::new().transparent_background(true).build();
+    /// let grid = artem::convert_to_grid(image::DynamicImage::new_rgb8(20, 20), &config).unwrap();
+    /// let image = grid.render_image(&config);
+    /// assert_eq!(0, image.get_pixel(0, 0).0[3]);
+    /// ```
+    #[cfg(feature = "png")]
+    pub fn render_image(&self, config: &Config) -> image::RgbaImage {
+        use target::png::{PngCell, FOREGROUND};
+
+        let Stats { columns, .. } = self.stats;
+        let text = |text: &str| {
+            text.chars()
+                .map(|char| PngCell {
+                    char,
+                    color: FOREGROUND,
+                    background: None,
+                })
+                .collect::<Vec<_>>()
+        };
+        let side = if config.border {
+            config.border_style.side().to_string()
+        } else {
+            String::new()
+        };
+
+        let mut rows = Vec::with_capacity(self.stats.rows as usize + 2);
+        if config.border {
+            rows.push(text(&config.border_style.top(columns)));
+        }
+        let mut lower_rows = self.lower_colors.chunks(columns as usize);
+        for row in self.cells.chunks(columns as usize) {
+            let lower_row = lower_rows.next();
+            let mut png_row = text(&side);
+            png_row.extend(row.iter().enumerate().map(|(index, cell)| {
+                let cell_color = (cell.red, cell.green, cell.blue);
+                let lower = lower_row.map(|lower_row| lower_row[index]);
+                match (config.color, config.background_color, lower) {
+                    (false, _, _) => PngCell {
+                        char: cell.char,
+                        color: FOREGROUND,
+                        background: None,
+                    },
+                    (true, _, Some(lower)) => PngCell {
+                        char: cell.char,
+                        color: cell_color,
+                        background: Some(lower),
+                    },
+                    (true, true, None) => PngCell {
+                        char: cell.char,
+                        color: FOREGROUND,
+                        background: Some(cell_color),
+                    },
+                    (true, false, None) => PngCell {
+                        char: cell.char,
+                        color: cell_color,
+                        background: None,
+                    },
+                }
+            }));
+            png_row.extend(text(&side));
+            rows.push(png_row);
+        }
+        if config.border {
+            rows.push(text(&config.border_style.bottom(columns)));
+        }
+        target::png::render(&rows, config.transparent_background)
+    }
+}
+
+/// Compact the spaces of a line, which starts at the given column, as described in [`Whitespace::Compact`].
+///
+/// Trailing spaces are removed and every run of at least two spaces, which ends on a tab stop, is replaced by a tab.
+/// Shorter runs, and runs that do not reach the next tab stop, are kept as spaces, so each character stays in its column.
+fn compact_whitespace(line: &str, start_column: usize) -> String {
+    const TAB_WIDTH: usize = 8;
+    let line = line.trim_end_matches(' ');
+    let mut compacted = String::with_capacity(line.len());
+    //number of spaces, which have not been written yet
+    let mut spaces = 0;
+    for (column, char) in (start_column..).zip(line.chars()) {
+        if char == ' ' {
+            spaces += 1;
+            //the space ends right before the next tab stop
+            if (column + 1) % TAB_WIDTH == 0 {
+                compacted.push_str(if spaces > 1 { "\t" } else { " " });
+                spaces = 0;
+            }
+            continue;
+        }
+        compacted.push_str(&" ".repeat(spaces));
+        spaces = 0;
+        compacted.push(char);
+    }
+    compacted
+}
+
+/// Return a spacer string, which can be used to center the ascii image in the middle of the terminal.
+///
+/// When the terminal width is not existing, for example when the output is not a terminal, the returned string will be empty.
+fn spacing_horizontal(width: u32) -> String {
+    let term_width = terminal_size::terminal_size().map(|dimensions| dimensions.0 .0 as u32);
+    " ".repeat(center_padding(term_width, width))
+}
+
+/// Returns the number of spaces, which center the given number of visible columns in the available width.
+///
+/// The width has to be the number of characters, not bytes, since escape sequences do not take up any space.
+/// Without an available width, no padding is needed.
+fn center_padding(available: Option<u32>, width: u32) -> usize {
+    available.map_or(0, |available| available.saturating_sub(width) as usize / 2)
+}
+
+#[cfg(test)]
+mod test_center_padding {
+    use super::*;
+
+    #[test]
+    fn centers_in_available_width() {
+        assert_eq!(40, center_padding(Some(100), 20));
+        assert_eq!(40, center_padding(Some(101), 20));
+    }
+
+    #[test]
+    fn wider_than_available() {
+        assert_eq!(0, center_padding(Some(10), 20));
+    }
+
+    #[test]
+    fn unknown_width_is_noop() {
+        assert_eq!(0, center_padding(None, 20));
+    }
+}
+
+/// Returns the number of lines of the ascii art with the given rows, which is used to center it vertically.
+///
+/// The rows added by the char height compensation are part of the height, as well as the two lines of the border.
+fn rendered_height(rows: u32, config: &Config) -> u32 {
+    let stretched_rows = compensated_rows(rows, config.char_height_compensation);
+    if config.border {
+        stretched_rows + 2
+    } else {
+        stretched_rows
+    }
+}
+
+/// Return a spacer string, which can be used to center the ascii image in the middle of the terminal.
+///
+/// When the terminal height is not existing, for example when the output is not a terminal, the returned string will be empty.
+fn spacing_vertical(height: u32) -> String {
+    let term_height = terminal_size::terminal_size()
+        .map(|dimensions| dimensions.1 .0 as u32)
+        .unwrap_or_default();
+    log::trace!("H: {term_height}, h: {height}");
+    "\n".repeat(term_height.saturating_sub(height).saturating_div(2) as usize)
+}
+
+/// Returns if the terminal supports truecolor mode.
+///
+/// It checks the `COLORTERM` environment variable,
+/// if it is either set to
+/// `truecolor` or `24bit` true is returned.
+///
+/// In all other cases false will be returned.
+///
+/// # Examples
+/// ```
+/// use artem::SUPPORTS_TRUECOLOR;
+/// # use std::env;
+///
+/// # env::set_var("COLORTERM", "truecolor");
+/// //only true when run in a shell that supports true color
+/// let color_support = *SUPPORTS_TRUECOLOR;
+/// assert!(color_support);
+/// ```
+pub static SUPPORTS_TRUECOLOR: Lazy<bool> = Lazy::new(|| {
+    std::env::var("COLORTERM")
         .is_ok_and(|value| value.contains("truecolor") || value.contains("24bit"))
 });
+
+/// Returns if the terminal supports the 256 colors of the xterm palette.
+///
+/// It checks the `TERM` environment variable, if it contains `256color`, for example
+/// `xterm-256color` or `screen-256color`, true is returned.
+///
+/// In all other cases, including an unset `TERM`, false will be returned.
+///
+/// # Examples
+/// ```
+/// # use std::env;
+/// # env::set_var("TERM", "xterm-256color");
+/// //only true when run in a shell that supports 256 colors
+/// assert!(artem::supports_256color());
+/// ```
+pub fn supports_256color() -> bool {
+    std::env::var("TERM").is_ok_and(|term| term.contains("256color"))
+}
+
+/// Returns if colors should be used for the output on stdout.
+///
+/// An explicit choice always wins, `Some(true)` forces the colors and `Some(false)` disables them.
+/// Otherwise, colors are only used if stdout is a terminal, like `ls` and `grep` do, so the escape sequences
+/// do not end up in files or pagers. The `CLICOLOR_FORCE` environment variable (if not `0`) enables the colors anyway.
+///
+/// # Examples
+/// ```
+/// assert!(artem::should_use_color(Some(true)));
+/// assert!(!artem::should_use_color(Some(false)));
+/// ```
+pub fn should_use_color(force: Option<bool>) -> bool {
+    use std::io::IsTerminal;
+    let clicolor_force = std::env::var("CLICOLOR_FORCE").is_ok_and(|value| value != "0");
+    use_color(force, std::io::stdout().is_terminal(), clicolor_force)
+}
+
+/// Returns if colors should be used, depending on the explicit choice, if stdout is a terminal and `CLICOLOR_FORCE`.
+fn use_color(force: Option<bool>, is_terminal: bool, clicolor_force: bool) -> bool {
+    force.unwrap_or(is_terminal || clicolor_force)
+}
+
+#[cfg(test)]
+mod test_should_use_color {
+    use super::*;
+
+    #[test]
+    fn terminal_uses_color() {
+        assert!(use_color(None, true, false));
+    }
+
+    #[test]
+    fn pipe_disables_color() {
+        assert!(!use_color(None, false, false));
+    }
+
+    #[test]
+    fn clicolor_force_enables_color() {
+        assert!(use_color(None, false, true));
+    }
+
+    #[test]
+    fn explicit_choice_wins() {
+        for (is_terminal, clicolor_force) in
+            [(true, true), (true, false), (false, true), (false, false)]
+        {
+            assert!(use_color(Some(true), is_terminal, clicolor_force));
+            assert!(!use_color(Some(false), is_terminal, clicolor_force));
+        }
+    }
+}
+
+/// The target size, which is used if the width of the terminal is unknown.
+pub const DEFAULT_TARGET_SIZE: u32 = 80;
+
+/// Returns the default target size for the output on stdout, which is the width of the terminal.
+///
+/// The border is part of the target size, so the ascii art fits into the terminal with or without it.
+/// If stdout is not a terminal, for example when the output is piped into a file, [`DEFAULT_TARGET_SIZE`] is used.
+///
+/// # Examples
+/// ```
+/// //the width of the terminal, or 80 if the output is not a terminal
+/// let target_size = artem::default_target_size();
+/// assert!(target_size > 0);
+/// ```
+pub fn default_target_size() -> u32 {
+    target_size_for_terminal(terminal_size::terminal_size().map(|(width, _)| width.0 as u32))
+}
+
+/// Returns the target size for a terminal with the given width, or [`DEFAULT_TARGET_SIZE`] if the width is unknown.
+///
+/// A width of 0, which some terminals report if they do not know their size, is treated as unknown.
+fn target_size_for_terminal(terminal_width: Option<u32>) -> u32 {
+    terminal_width
+        .filter(|width| *width > 0)
+        .unwrap_or(DEFAULT_TARGET_SIZE)
+}
+
+#[cfg(test)]
+mod test_default_target_size {
+    use super::*;
+
+    #[test]
+    fn uses_terminal_width() {
+        assert_eq!(120, target_size_for_terminal(Some(120)));
+        assert_eq!(43, target_size_for_terminal(Some(43)));
+    }
+
+    #[test]
+    fn falls_back_without_terminal() {
+        assert_eq!(DEFAULT_TARGET_SIZE, target_size_for_terminal(None));
+        assert_eq!(DEFAULT_TARGET_SIZE, target_size_for_terminal(Some(0)));
+    }
+}
+
+#[cfg(test)]
+mod test_convert {
+    use image::{ImageBuffer, Rgb};
+
+    use super::*;
+
+    /// Returns the final bytes of all CSI escape sequences in the given string.
+    fn escape_sequence_endings(input: &str) -> Vec<char> {
+        input
+            .split('\x1b')
+            .skip(1)
+            .filter_map(|sequence| {
+                sequence
+                    .strip_prefix('[')?
+                    .chars()
+                    .find(|char| !char.is_ascii_digit() && *char != ';')
+            })
+            .collect()
+    }
+
+    #[test]
+    fn prescaled_keeps_dimensions() {
+        let img = DynamicImage::ImageRgb8(ImageBuffer::from_fn(37, 11, |x, _| {
+            if x % 2 == 0 {
+                Rgb([255, 255, 255])
+            } else {
+                Rgb([0, 0, 0])
+            }
+        }));
+        let config = ConfigBuilder::new().color(false).build();
+        let output = convert_prescaled(img, &config).unwrap();
+        assert_eq!(11, output.lines().count());
+        for line in output.lines() {
+            assert_eq!(37, line.chars().count());
+            //each pixel is its own character
+            assert!(line.starts_with("M M M"));
+        }
+    }
+
+    #[test]
+    fn prescaled_invalid_dimensions() {
+        let config = ConfigBuilder::new().build();
+        assert_eq!(
+            Err(ArtemError::InvalidDimensions(0, 10)),
+            convert_prescaled(DynamicImage::new_rgb8(0, 10), &config)
+        );
+        assert_eq!(
+            Err(ArtemError::InvalidDimensions(MAX_PRESCALED_SIZE + 1, 1)),
+            convert_prescaled(DynamicImage::new_rgb8(MAX_PRESCALED_SIZE + 1, 1), &config)
+        );
+    }
+
+    #[test]
+    fn convert_errors() {
+        let img = DynamicImage::new_rgb8(40, 40);
+        assert_eq!(
+            Err(ArtemError::ZeroColorStep),
+            convert(img.clone(), &ConfigBuilder::new().color_step(0).build())
+        );
+        assert_eq!(
+            Err(ArtemError::InvalidDimensions(0, 40)),
+            convert(DynamicImage::new_rgb8(0, 40), &ConfigBuilder::new().build())
+        );
+        assert!(convert(img, &ConfigBuilder::new().build()).is_ok());
+    }
+
+    #[test]
+    fn convert_ignores_conflicts() {
+        let img = DynamicImage::new_rgb8(40, 40);
+        for config in [
+            ConfigBuilder::new().markdown(true).build(),
+            ConfigBuilder::new().whitespace(Whitespace::Compact).build(),
+            ConfigBuilder::new().hysteresis(true).build(),
+        ] {
+            assert!(config.validate().is_err());
+            assert!(convert(img.clone(), &config).is_ok());
+        }
+    }
+
+    #[test]
+    fn entry_points_return_errors() {
+        let empty = DynamicImage::new_rgb8(0, 40);
+        let config = ConfigBuilder::new().build();
+        let error = ArtemError::InvalidDimensions(0, 40);
+        assert_eq!(Err(error.clone()), convert_to_grid(empty.clone(), &config));
+        assert_eq!(
+            Err(error.clone()),
+            convert_with_stats(empty.clone(), &config)
+        );
+        assert_eq!(
+            Err(error.clone()),
+            convert_to_grid_with_progress(empty.clone(), &config, |_, _| {
+                panic!("no rows are converted")
+            })
+        );
+        assert_eq!(
+            Err(error.clone()),
+            convert_frames(vec![DynamicImage::new_rgb8(40, 40), empty.clone()], &config)
+        );
+        assert_eq!(Err(error), convert_prescaled(empty, &config));
+
+        let img = DynamicImage::new_rgb8(40, 40);
+        let mut config = ConfigBuilder::new().build();
+        config.characters = String::new();
+        let error = ArtemError::EmptyCharacterSet;
+        assert_eq!(Err(error.clone()), convert_to_grid(img.clone(), &config));
+        assert_eq!(Err(error.clone()), convert_with_stats(img.clone(), &config));
+        assert_eq!(
+            Err(error.clone()),
+            convert_frames(vec![img.clone()], &config)
+        );
+        assert_eq!(Err(error), convert_prescaled(img, &config));
+    }
+
+    #[test]
+    fn grid_renders_like_convert() {
+        let img = DynamicImage::ImageRgb8(ImageBuffer::from_fn(40, 40, |x, y| {
+            Rgb([(x * 6) as u8, (y * 6) as u8, 128])
+        }));
+        let mut config = ConfigBuilder::new()
+            .target_size(std::num::NonZeroU32::new(20).unwrap())
+            .border(true)
+            .build();
+        let grid = convert_to_grid(img.clone(), &config).unwrap();
+        assert_eq!(
+            (grid.stats().columns * grid.stats().rows) as usize,
+            grid.cells().len()
+        );
+
+        for target in [TargetType::File, TargetType::HtmlFile, TargetType::AnsiFile] {
+            config.target = target;
+            assert_eq!(convert(img.clone(), &config).unwrap(), grid.render(&config));
+        }
+    }
+
+    #[test]
+    fn ink_budget_limits_characters() {
+        let img = DynamicImage::ImageRgb8(ImageBuffer::from_fn(100, 100, |x, y| {
+            Rgb([(x * 2) as u8, (y * 2) as u8, 128])
+        }));
+        let config = ConfigBuilder::new()
+            .target_size(std::num::NonZeroU32::new(20).unwrap())
+            .color(false)
+            .ink_budget(25)
+            .build();
+        let output = convert(img, &config).unwrap();
+        assert_eq!(
+            25,
+            output.chars().filter(|char| !char.is_whitespace()).count()
+        );
+    }
+
+    #[test]
+    fn single_pixel_image() {
+        let img = DynamicImage::ImageRgb8(ImageBuffer::from_pixel(1, 1, Rgb([255, 0, 0])));
+        let config = ConfigBuilder::new().color(false).build();
+        let output = convert(img, &config).unwrap();
+        assert_eq!(".\n", output);
+    }
+
+    #[test]
+    fn single_pixel_image_colored() {
+        let img = DynamicImage::ImageRgb8(ImageBuffer::from_pixel(1, 1, Rgb([255, 0, 0])));
+        let config = ConfigBuilder::new()
+            .target(TargetType::HtmlFile)
+            .color(true)
+            .build();
+        let output = convert(img, &config).unwrap();
+        assert_eq!(
+            format!(
+                "{}<span style=\"color: #FF0000\">.</span>\n{}",
+                target::html::html_top(),
+                target::html::html_bottom()
+            ),
+            output
+        );
+    }
+
+    #[test]
+    fn html_spans_use_truecolor() {
+        let colors = [[255, 0, 0], [0, 255, 0], [0, 0, 255], [18, 52, 86]];
+        let img = DynamicImage::ImageRgb8(ImageBuffer::from_fn(2, 2, |x, y| {
+            Rgb(colors[(y * 2 + x) as usize])
+        }));
+        //the color mode of the terminal does not limit the colors of html files
+        let config = ConfigBuilder::new()
+            .target(TargetType::HtmlFile)
+            .color_mode(config::ColorMode::Ansi16)
+            .characters("#")
+            .html_background((0, 0, 0))
+            .build();
+        let output = convert_prescaled(img, &config).unwrap();
+
+        assert_eq!(4, output.matches("<span ").count());
+        assert!(output.contains(
+            "<pre style=\"background-color: #000000\"><span style=\"color: #FF0000\">#</span><span style=\"color: #00FF00\">#</span>\n\
+            <span style=\"color: #0000FF\">#</span><span style=\"color: #123456\">#</span>\n"
+        ));
+    }
+
+    #[test]
+    fn single_pixel_image_border() {
+        let img = DynamicImage::ImageRgb8(ImageBuffer::from_pixel(1, 1, Rgb([255, 0, 0])));
+        let config = ConfigBuilder::new().color(false).border(true).build();
+        let output = convert(img, &config).unwrap();
+        assert_eq!("╔═╗\n║.║\n╚═╝", output);
+    }
+
+    #[test]
+    fn uneven_dimensions_keep_last_row_and_column() {
+        //the image is resized to fit the tiles exactly, so no partial row/column is dropped
+        let img = DynamicImage::ImageRgb8(ImageBuffer::from_fn(101, 97, |x, y| {
+            if x >= 96 || y >= 88 {
+                Rgb([255, 255, 255])
+            } else {
+                Rgb([0, 0, 0])
+            }
+        }));
+        let config = ConfigBuilder::new()
+            .target_size(std::num::NonZeroU32::new(20).unwrap())
+            .color(false)
+            .build();
+        let output = convert(img, &config).unwrap();
+        let lines = output.lines().collect::<Vec<_>>();
+        //the bright last row and column are still visible
+        assert!(lines.last().unwrap().chars().all(|char| char != ' '));
+        assert!(lines.iter().all(|line| !line.ends_with(' ')));
+    }
+
+    #[test]
+    fn directional_horizontal_stripes() {
+        //the image already has the size of the tiles, so the stripes are not blurred by resizing
+        let img = DynamicImage::ImageRgb8(ImageBuffer::from_fn(100, 99, |_, y| {
+            if y % 2 == 0 {
+                Rgb([255, 255, 255])
+            } else {
+                Rgb([0, 0, 0])
+            }
+        }));
+        let config = ConfigBuilder::new()
+            .target_size(std::num::NonZeroU32::new(20).unwrap())
+            .color(false)
+            .directional(true)
+            .build();
+        let output = convert(img, &config).unwrap();
+        assert!(output
+            .lines()
+            .flat_map(|line| line.chars())
+            .all(|char| char == '=' || char == '-'));
+    }
+
+    #[test]
+    fn css_classes_for_distinct_colors() {
+        //left half red, right half green
+        let img = DynamicImage::ImageRgb8(ImageBuffer::from_fn(100, 100, |x, _| {
+            if x < 50 {
+                Rgb([255, 0, 0])
+            } else {
+                Rgb([0, 255, 0])
+            }
+        }));
+        let config = ConfigBuilder::new()
+            .target_size(std::num::NonZeroU32::new(20).unwrap())
+            .target(TargetType::HtmlFile)
+            .css_classes(true)
+            .build();
+        let (output, stats) = convert_with_stats(img, &config).unwrap();
+
+        assert!(output.contains("<style>.c0 { color: #FF0000 }.c1 { color: #00FF00 }</style>"));
+        assert_eq!(stats.distinct_colors, output.matches(" { color: #").count());
+        //every character references a class instead of an inline style
+        assert!(!output.contains("style=\""));
+        assert_eq!(
+            (stats.columns * stats.rows) as usize,
+            output.matches("<span class=\"c0\">").count()
+                + output.matches("<span class=\"c1\">").count()
+        );
+    }
+
+    #[test]
+    fn stats_dimensions_and_colors() {
+        //left half red, right half blue
+        let img = DynamicImage::ImageRgb8(ImageBuffer::from_fn(100, 100, |x, _| {
+            if x < 50 {
+                Rgb([255, 0, 0])
+            } else {
+                Rgb([0, 0, 255])
+            }
+        }));
+        let config = ConfigBuilder::new()
+            .target_size(std::num::NonZeroU32::new(20).unwrap())
+            .color(false)
+            .build();
+        let (output, stats) = convert_with_stats(img, &config).unwrap();
+        assert_eq!(
+            Stats {
+                source_width: 100,
+                source_height: 100,
+                columns: 20,
+                rows: 9,
+                tile_width: 5,
+                tile_height: 11,
+                distinct_colors: 2,
+            },
+            stats
+        );
+        assert_eq!(stats.rows as usize, output.lines().count());
+        assert!(output
+            .lines()
+            .all(|line| line.chars().count() == stats.columns as usize));
+    }
+
+    /// Forces colored output until it is dropped, this is not printed to the terminal anyways.
+    ///
+    /// The override is global, so the tests using it are serialized by holding a lock.
+    struct ForceColor {
+        _lock: std::sync::MutexGuard<'static, ()>,
+    }
+
+    impl ForceColor {
+        fn new() -> Self {
+            static LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+            //a panicking test only poisons the lock, the override is still reset on drop
+            let lock = LOCK.lock().unwrap_or_else(|err| err.into_inner());
+            colored::control::set_override(true);
+            ForceColor { _lock: lock }
+        }
+    }
+
+    impl Drop for ForceColor {
+        fn drop(&mut self) {
+            colored::control::unset_override();
+        }
+    }
+
+    /// Split a colored line into the strings of its cells, each ending with a reset.
+    fn colored_cells(line: &str) -> Vec<&str> {
+        line.split_inclusive(target::ansi::RESET).collect()
+    }
+
+    #[test]
+    fn right_to_left_reverses_rows() {
+        let _color = ForceColor::new();
+        let img = DynamicImage::ImageRgb8(ImageBuffer::from_fn(40, 40, |x, y| {
+            Rgb([(x * 6) as u8, (y * 6) as u8, 128])
+        }));
+        let mut builder = ConfigBuilder::new();
+        builder.target_size(std::num::NonZeroU32::new(20).unwrap());
+        let left_to_right = convert(img.clone(), &builder.build()).unwrap();
+        let right_to_left = convert(img.clone(), &builder.right_to_left(true).build()).unwrap();
+
+        assert_eq!(left_to_right.lines().count(), right_to_left.lines().count());
+        for (ltr, rtl) in left_to_right.lines().zip(right_to_left.lines()) {
+            let mut reversed = colored_cells(ltr);
+            reversed.reverse();
+            assert_eq!(reversed, colored_cells(rtl));
+        }
+
+        //reversing the lines as well
+        let reversed_lines = convert(img, &builder.bottom_to_top(true).build()).unwrap();
+        for (ltr, reversed) in left_to_right.lines().zip(reversed_lines.lines().rev()) {
+            let mut cells = colored_cells(ltr);
+            cells.reverse();
+            assert_eq!(cells, colored_cells(reversed));
+        }
+    }
+
+    #[test]
+    fn ansi_output_resets_every_line() {
+        let _color = ForceColor::new();
+        let img = DynamicImage::ImageRgb8(ImageBuffer::from_fn(40, 40, |x, y| {
+            Rgb([(x * 6) as u8, (y * 6) as u8, 128])
+        }));
+        let config = ConfigBuilder::new()
+            .target_size(std::num::NonZeroU32::new(20).unwrap())
+            .border(true)
+            .build();
+        let output = convert(img, &config).unwrap();
+
+        //only color escape sequences are used, no cursor movements or line clearing
+        assert!(escape_sequence_endings(&output)
+            .iter()
+            .all(|ending| *ending == 'm'));
+
+        for line in output.lines().filter(|line| line.contains('\x1b')) {
+            let last_escape = line.rfind('\x1b').unwrap();
+            assert!(line[last_escape..].starts_with(target::ansi::RESET));
+        }
+    }
+
+    #[test]
+    fn color_step_emits_multiples() {
+        let img = DynamicImage::ImageRgb8(ImageBuffer::from_fn(300, 200, |x, y| {
+            Rgb([(x % 256) as u8, (y % 256) as u8, ((x * y) % 256) as u8])
+        }));
+        let config = ConfigBuilder::new()
+            .target(TargetType::HtmlFile)
+            .color_step(16)
+            .build();
+        let output = convert(img, &config).unwrap();
+        let colors = output
+            .match_indices("color: #")
+            .map(|(index, pattern)| &output[index + pattern.len()..index + pattern.len() + 6])
+            .collect::<Vec<_>>();
+        assert!(!colors.is_empty());
+        for color in colors {
+            for channel in 0..3 {
+                let value = u8::from_str_radix(&color[channel * 2..channel * 2 + 2], 16).unwrap();
+                assert_eq!(0, value % 16, "{color} is not a multiple of 16");
+            }
+        }
+    }
+
+    #[test]
+    fn compact_whitespace_rule() {
+        //runs ending on a tab stop become tabs, trailing spaces are removed
+        assert_eq!("##\t\t  #", compact_whitespace("##                #   ", 0));
+        //single spaces are never replaced
+        assert_eq!("####### #", compact_whitespace("####### #", 0));
+        //runs not reaching the next tab stop are kept
+        assert_eq!("#  #", compact_whitespace("#  #", 0));
+        //the start column moves the tab stops
+        assert_eq!("\t#", compact_whitespace("     #", 3));
+        assert_eq!("", compact_whitespace("    ", 0));
+    }
+
+    #[test]
+    fn compact_and_preserved_rows() {
+        //bright on the first two and the second to last column, dark everywhere else
+        let img = DynamicImage::ImageRgb8(ImageBuffer::from_fn(200, 40, |x, _| {
+            if x < 20 || (180..190).contains(&x) {
+                Rgb([255, 255, 255])
+            } else {
+                Rgb([0, 0, 0])
+            }
+        }));
+        let mut builder = ConfigBuilder::new();
+        builder
+            .color(false)
+            .characters("# ".to_string())
+            .target_size(std::num::NonZeroU32::new(20).unwrap());
+        let preserved = convert(img.clone(), &builder.build()).unwrap();
+        builder.whitespace(Whitespace::Compact);
+        let compact = convert(img, &builder.build()).unwrap();
+
+        assert!(preserved.lines().all(|line| line == "##                # "));
+        //the last character stays in the same column, when expanding the tabs
+        assert!(compact.lines().all(|line| line == "##\t\t  #"));
+        assert_eq!(preserved.lines().count(), compact.lines().count());
+    }
+
+    #[test]
+    fn mask_blanks_dark_half() {
+        let img = DynamicImage::ImageRgb8(ImageBuffer::from_pixel(200, 100, Rgb([200, 200, 200])));
+        //same aspect as the image, but a different size
+        let mask =
+            image::GrayImage::from_fn(40, 20, |x, _| image::Luma([if x < 20 { 255 } else { 0 }]));
+        let mut builder = ConfigBuilder::new();
+        builder
+            .color(false)
+            .characters("#. ".to_string())
+            .target_size(std::num::NonZeroU32::new(20).unwrap());
+        let plain = convert(img.clone(), &builder.build()).unwrap();
+        let masked = convert(img, &builder.mask(mask).build()).unwrap();
+
+        for (plain, masked) in plain.lines().zip(masked.lines()) {
+            let (left, right) = masked.split_at(10);
+            assert_eq!(&plain[..10], left);
+            assert_eq!(" ".repeat(10), right);
+        }
+    }
+
+    #[test]
+    fn negative_inverts_colors() {
+        let red = DynamicImage::ImageRgb8(ImageBuffer::from_pixel(40, 40, Rgb([255, 0, 0])));
+        let cyan = DynamicImage::ImageRgb8(ImageBuffer::from_pixel(40, 40, Rgb([0, 255, 255])));
+        let negative =
+            convert_to_grid(red.clone(), &ConfigBuilder::new().negative(true).build()).unwrap();
+        let plain = convert_to_grid(red, &ConfigBuilder::new().build()).unwrap();
+
+        let cell = negative.cells()[0];
+        assert_eq!((0, 255, 255), (cell.red, cell.green, cell.blue));
+        //the brightness is inverted as well, so the characters are the ones of a cyan image
+        let cyan = convert_to_grid(cyan, &ConfigBuilder::new().build()).unwrap();
+        assert_eq!(cyan.cells()[0].char, cell.char);
+        assert_ne!(plain.cells()[0].char, cell.char);
+    }
+
+    #[test]
+    fn silhouette_of_circle() {
+        //an opaque, dark circle over transparent, bright pixels
+        let img = DynamicImage::ImageRgba8(ImageBuffer::from_fn(200, 200, |x, y| {
+            let distance = (x as f32 - 100f32).hypot(y as f32 - 100f32);
+            if distance < 60f32 {
+                image::Rgba([10, 10, 40, 255])
+            } else {
+                image::Rgba([255, 255, 255, 0])
+            }
+        }));
+        let config = ConfigBuilder::new()
+            .color(false)
+            .characters("#. ".to_string())
+            .silhouette(true)
+            .target_size(std::num::NonZeroU32::new(40).unwrap())
+            .build();
+        let output = convert(img, &config).unwrap();
+        let lines = output.lines().collect::<Vec<_>>();
+        let (rows, columns) = (lines.len(), lines[0].chars().count());
+
+        //the circle uses the densest character, the rest is empty
+        assert_eq!(Some('#'), lines[rows / 2].chars().nth(columns / 2));
+        assert!(lines[0].chars().all(|char| char == ' '));
+        assert!(lines[rows - 1].chars().all(|char| char == ' '));
+        assert!(lines
+            .iter()
+            .all(|line| line.starts_with(' ') && line.ends_with(' ')));
+    }
+
+    #[test]
+    fn bbcode_red_cells() {
+        let red = DynamicImage::ImageRgb8(ImageBuffer::from_pixel(40, 40, Rgb([255, 0, 0])));
+        let config = ConfigBuilder::new()
+            .characters("x".to_string())
+            .target(TargetType::BbCode)
+            .target_size(std::num::NonZeroU32::new(4).unwrap())
+            .build();
+        let output = convert(red, &config).unwrap();
+
+        assert!(output.starts_with("[code]\n"));
+        assert!(output.ends_with("[/code]\n"));
+        //the same colored characters share a single tag
+        for line in output.lines().skip(1).take_while(|line| *line != "[/code]") {
+            assert_eq!("[color=#ff0000]xxxx[/color]", line);
+        }
+    }
+
+    #[test]
+    fn vignette_darkens_corners() {
+        let characters = "#%*+=-:. ";
+        let img = DynamicImage::ImageRgb8(ImageBuffer::from_pixel(400, 300, Rgb([230, 230, 230])));
+        let config = ConfigBuilder::new()
+            .color(false)
+            .characters(characters.to_string())
+            .vignette(0.8)
+            .build();
+        let output = convert(img, &config).unwrap();
+        let lines = output.lines().collect::<Vec<_>>();
+        //later characters of the ramp are used for darker cells
+        let darkness =
+            |line: &str, column: usize| characters.find(line.chars().nth(column).unwrap()).unwrap();
+        let (rows, columns) = (lines.len(), lines[0].chars().count());
+        let center = darkness(lines[rows / 2], columns / 2);
+        assert!(darkness(lines[0], 0) > center);
+        assert!(darkness(lines[rows - 1], columns - 1) > center);
+    }
+
+    #[test]
+    fn braille_keeps_dimensions() {
+        let img = DynamicImage::ImageRgb8(ImageBuffer::from_fn(300, 200, |x, _| {
+            Rgb(if x < 150 { [255, 255, 255] } else { [0, 0, 0] })
+        }));
+        let mut builder = ConfigBuilder::new();
+        builder
+            .color(false)
+            .target_size(std::num::NonZeroU32::new(30).unwrap());
+        let plain = convert_to_grid(img.clone(), &builder.build()).unwrap();
+        let braille = convert_to_grid(img.clone(), &builder.braille(0.5).build()).unwrap();
+        let (plain_stats, braille_stats) = (plain.stats(), braille.stats());
+        assert_eq!(
+            (plain_stats.columns, plain_stats.rows),
+            (braille_stats.columns, braille_stats.rows)
+        );
+        //each dot is a single pixel
+        assert_eq!(
+            (2, 4),
+            (braille_stats.tile_width, braille_stats.tile_height)
+        );
+
+        //the white half sets all dots, the black half none
+        let output = braille.render(&builder.build());
+        for line in output.lines() {
+            assert_eq!(format!("{}{}", "⣿".repeat(15), "⠀".repeat(15)), line);
+        }
+        let inverted = convert(img, &builder.invert(true).build()).unwrap();
+        for line in inverted.lines() {
+            assert_eq!(format!("{}{}", "⠀".repeat(15), "⣿".repeat(15)), line);
+        }
+    }
+
+    #[test]
+    fn braille_small_image() {
+        //the image is smaller than the dots, but still uses the full characters
+        let img = DynamicImage::ImageRgb8(ImageBuffer::from_pixel(3, 3, Rgb([255, 255, 255])));
+        let config = ConfigBuilder::new().color(false).braille(0.5).build();
+        let output = convert(img, &config).unwrap();
+        assert!(output.chars().all(|char| char == '⣿' || char == '\n'));
+    }
+
+    #[test]
+    fn invert_only_changes_characters() {
+        let _color = ForceColor::new();
+        let img = DynamicImage::ImageRgb8(ImageBuffer::from_fn(40, 40, |x, y| {
+            Rgb([(x * 6) as u8, (y * 6) as u8, 128])
+        }));
+        let mut builder = ConfigBuilder::new();
+        builder
+            .characters("#*. ")
+            .target_size(std::num::NonZeroU32::new(10).unwrap());
+        for color_mode in [
+            config::ColorMode::Truecolor,
+            config::ColorMode::Ansi256,
+            config::ColorMode::Ansi16,
+        ] {
+            let plain = convert(
+                img.clone(),
+                &builder.color_mode(color_mode).invert(false).build(),
+            )
+            .unwrap();
+            let inverted = convert(img.clone(), &builder.invert(true).build()).unwrap();
+
+            //the escape sequences are the same, only the characters differ
+            let strip_chars = |output: &str| output.replace(['#', '*', '.', ' '], "");
+            assert_eq!(strip_chars(&plain), strip_chars(&inverted));
+            assert_ne!(plain, inverted);
+        }
+    }
+
+    #[test]
+    fn svg_has_text_per_row() {
+        let img = DynamicImage::ImageRgb8(ImageBuffer::from_pixel(4, 3, Rgb([255, 0, 0])));
+        let config = ConfigBuilder::new()
+            .target(TargetType::Svg)
+            .characters("#")
+            .border(true)
+            .build();
+        let output = convert_prescaled(img, &config).unwrap();
+
+        //3 rows and the top and bottom border
+        assert_eq!(5, output.matches("<text ").count());
+        //6 columns of 8.4 and 5 rows of 16.8 with the default font
+        assert!(output.starts_with(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"50.4\" height=\"84\" viewBox=\"0 0 50.4 84\""
+        ));
+        assert!(output.contains("<tspan fill=\"#FF0000\">####</tspan>"));
+        assert!(output.ends_with("</svg>\n"));
+    }
+
+    #[test]
+    fn frames_share_dimensions() {
+        //the second frame has a different aspect ratio, but is resized like the first one
+        let frames = vec![
+            DynamicImage::ImageRgb8(ImageBuffer::from_pixel(100, 50, Rgb([255, 255, 255]))),
+            DynamicImage::ImageRgb8(ImageBuffer::from_pixel(50, 100, Rgb([0, 0, 0]))),
+        ];
+        let config = ConfigBuilder::new()
+            .color(false)
+            .target_size(std::num::NonZeroU32::new(20).unwrap())
+            .build();
+        let output = convert_frames(frames.clone(), &config).unwrap();
+
+        assert_eq!(2, output.len());
+        assert_eq!(convert(frames[0].clone(), &config).unwrap(), output[0]);
+        assert_eq!(
+            output[0]
+                .lines()
+                .map(|line| line.chars().count())
+                .collect::<Vec<_>>(),
+            output[1]
+                .lines()
+                .map(|line| line.chars().count())
+                .collect::<Vec<_>>()
+        );
+        assert_ne!(output[0], output[1]);
+    }
+
+    #[test]
+    fn no_frames() {
+        assert!(convert_frames(Vec::new(), &ConfigBuilder::new().build())
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "parallel")]
+    fn parallel_is_identical_to_sequential() {
+        let img = image::open("assets/images/standard_test_img.png").unwrap();
+        let config = ConfigBuilder::new()
+            .target_size(std::num::NonZeroU32::new(120).unwrap())
+            .contrast_stretch(true)
+            .directional(true)
+            .build();
+        //a single thread converts the tiles in order, like the sequential conversion
+        let convert_with_threads = |threads: usize| {
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap()
+                .install(|| convert(img.clone(), &config).unwrap())
+        };
+        assert_eq!(convert_with_threads(1), convert_with_threads(8));
+    }
+
+    #[test]
+    fn solid_red_background() {
+        std::env::set_var("CLICOLOR_FORCE", "1");
+        let img = DynamicImage::ImageRgb8(ImageBuffer::from_pixel(4, 4, Rgb([255, 0, 0])));
+        let expected = [
+            (config::ColorMode::Truecolor, "\x1b[48;2;255;0;0m"),
+            (config::ColorMode::Ansi256, "\x1b[48;5;196m"),
+            (config::ColorMode::Ansi16, "\x1b[101m"),
+        ];
+        for (color_mode, background) in expected {
+            let config = ConfigBuilder::new()
+                .color_mode(color_mode)
+                .background_color(true)
+                .characters(" ")
+                .build();
+            let output = convert_prescaled(img.clone(), &config).unwrap();
+            //every cell has a red background
+            assert_eq!(16, output.matches(background).count(), "{color_mode:?}");
+        }
+    }
+
+    #[test]
+    fn half_blocks_use_both_colors() {
+        std::env::set_var("CLICOLOR_FORCE", "1");
+        //alternating red and blue rows
+        let img = DynamicImage::ImageRgb8(ImageBuffer::from_fn(4, 4, |_, y| {
+            if y % 2 == 0 {
+                Rgb([255, 0, 0])
+            } else {
+                Rgb([0, 0, 255])
+            }
+        }));
+        let config = ConfigBuilder::new()
+            .target_size(std::num::NonZeroU32::new(4).unwrap())
+            .color_mode(config::ColorMode::Truecolor)
+            .half_blocks(true)
+            .build();
+        let (output, stats) = convert_with_stats(img, &config).unwrap();
+        //each character covers a red and a blue pixel
+        assert_eq!(stats.rows as usize, output.lines().count());
+        assert_eq!(
+            (stats.columns * stats.rows) as usize,
+            output.matches("\x1b[48;2;0;0;255;38;2;255;0;0m▀").count()
+        );
+    }
+
+    #[test]
+    fn half_blocks_odd_height() {
+        //5 pixel rows can not be split into pairs, the last row is white
+        let img = DynamicImage::ImageRgb8(ImageBuffer::from_fn(4, 5, |_, y| {
+            if y == 4 {
+                Rgb([255, 255, 255])
+            } else {
+                Rgb([0, 0, 0])
+            }
+        }));
+        let config = ConfigBuilder::new()
+            .target_size(std::num::NonZeroU32::new(4).unwrap())
+            .dimension(config::ResizingDimension::Stretch { rows: 3 })
+            .half_blocks(true)
+            .build();
+        let grid = convert_to_grid(img, &config).unwrap();
+        assert_eq!(3, grid.stats().rows);
+        //the image is resized to two pixels per row, so the white row is rendered instead of being dropped
+        let (upper_rows, last_row) = grid.cells().split_at(8);
+        let (upper_lower_colors, last_lower_colors) = grid.lower_colors.split_at(8);
+        for (cell, lower) in last_row.iter().zip(last_lower_colors) {
+            assert_eq!((255, 255, 255), (cell.red, cell.green, cell.blue));
+            assert_eq!((255, 255, 255), *lower);
+        }
+        assert!(upper_rows.iter().all(|cell| cell.red == 0));
+        assert!(upper_lower_colors.iter().all(|lower| *lower == (0, 0, 0)));
+    }
+
+    #[test]
+    fn half_blocks_partial_row() {
+        //with a scale of 0.5 each row is 2 pixels high, so the 5 pixel rows end in the upper half of a third row
+        let img = DynamicImage::ImageRgb8(ImageBuffer::from_fn(4, 5, |_, y| {
+            if y == 4 {
+                Rgb([255, 255, 255])
+            } else {
+                Rgb([0, 0, 0])
+            }
+        }));
+        let config = |partial_block| {
+            ConfigBuilder::new()
+                .target_size(std::num::NonZeroU32::new(4).unwrap())
+                .scale(0.5)
+                .half_blocks(true)
+                .partial_block(partial_block)
+                .background_fill((0, 0, 255))
+                .build()
+        };
+
+        //the white row is resized into the lower half of the last whole row
+        let grid = convert_to_grid(img.clone(), &config(config::PartialBlock::Render)).unwrap();
+        assert_eq!(2, grid.stats().rows);
+        assert!(grid.lower_colors[4..].iter().all(|lower| lower.0 > 0));
+
+        //the white row keeps its size in the upper half of an additional row, the lower half is the background
+        let grid = convert_to_grid(img, &config(config::PartialBlock::Pad)).unwrap();
+        assert_eq!(3, grid.stats().rows);
+        let (upper_rows, last_row) = grid.cells().split_at(8);
+        let (upper_lower_colors, last_lower_colors) = grid.lower_colors.split_at(8);
+        for (cell, lower) in last_row.iter().zip(last_lower_colors) {
+            assert_eq!((255, 255, 255), (cell.red, cell.green, cell.blue));
+            assert_eq!((0, 0, 255), *lower);
+        }
+        assert!(upper_rows.iter().all(|cell| cell.red == 0));
+        assert!(upper_lower_colors.iter().all(|lower| *lower == (0, 0, 0)));
+    }
+
+    #[test]
+    fn resize_filters_differ() {
+        //a checkerboard of single pixels
+        let img = DynamicImage::ImageRgb8(ImageBuffer::from_fn(64, 64, |x, y| {
+            if (x + y) % 2 == 0 {
+                Rgb([255, 255, 255])
+            } else {
+                Rgb([0, 0, 0])
+            }
+        }));
+        let average_luminance = |filter: config::ResizeFilter| {
+            let config = ConfigBuilder::new()
+                .target_size(std::num::NonZeroU32::new(8).unwrap())
+                .resize_filter(filter)
+                .build();
+            let grid = convert_to_grid(img.clone(), &config).unwrap();
+            let cells = grid.cells();
+            cells
+                .iter()
+                .map(|cell| pixel::luminosity(cell.red, cell.green, cell.blue))
+                .sum::<f32>()
+                / cells.len() as f32
+        };
+        //nearest only samples single pixels, which are either black or white
+        let nearest = average_luminance(config::ResizeFilter::Nearest);
+        //triangle averages the pixels to gray
+        let triangle = average_luminance(config::ResizeFilter::Triangle);
+        assert!(
+            (nearest - triangle).abs() > 10f32,
+            "{nearest} and {triangle} are too close"
+        );
+    }
+
+    #[test]
+    fn brightness_moves_towards_dense_characters() {
+        let gray = DynamicImage::ImageRgb8(ImageBuffer::from_pixel(40, 40, Rgb([128, 128, 128])));
+        let index = |brightness: f64| {
+            let config = ConfigBuilder::new().brightness(brightness).build();
+            let cell = convert_to_grid(gray.clone(), &config).unwrap().cells()[0];
+            config
+                .characters
+                .chars()
+                .position(|char| char == cell.char)
+                .unwrap()
+        };
+        //the characters are ordered from dense to light
+        assert!(index(0.3) < index(0f64));
+        assert!(index(-0.3) > index(0f64));
+    }
+
+    #[test]
+    fn background_fill_blends_transparent_pixels() {
+        //left half transparent, right half half transparent red
+        let img = DynamicImage::ImageRgba8(ImageBuffer::from_fn(80, 40, |x, _| {
+            image::Rgba(if x < 40 {
+                [0, 0, 0, 0]
+            } else {
+                [255, 0, 0, 128]
+            })
+        }));
+        let config = ConfigBuilder::new()
+            .target_size(std::num::NonZeroU32::new(2).unwrap())
+            .background_fill((255, 255, 255))
+            .build();
+        let grid = convert_to_grid(img.clone(), &config).unwrap();
+        let (transparent, red) = (grid.cells()[0], grid.cells()[1]);
+        assert_eq!(' ', transparent.char);
+        //pink, apart from rounding while resizing
+        assert!(red.red > 250 && red.green == red.blue);
+        assert!((120..135).contains(&red.green), "{red:?} is not pink");
+
+        //without a background, the transparent pixels are black and the red is not blended
+        let grid = convert_to_grid(img, &ConfigBuilder::new().build()).unwrap();
+        let red = grid.cells()[grid.cells().len() - 1];
+        assert_eq!((255, 0, 0), (red.red, red.green, red.blue));
+    }
+
+    #[test]
+    fn blue_channel_of_blue_image() {
+        let blue = DynamicImage::ImageRgb8(ImageBuffer::from_pixel(40, 40, Rgb([0, 0, 255])));
+        let char = |channel: config::Channel| {
+            let config = ConfigBuilder::new().channel(channel).build();
+            convert_to_grid(blue.clone(), &config).unwrap().cells()[0].char
+        };
+        let densest = config::CharacterRamp::default().characters().chars().next();
+        assert_eq!(densest, Some(char(config::Channel::Blue)));
+        for channel in [
+            config::Channel::Luminance,
+            config::Channel::Red,
+            config::Channel::Green,
+        ] {
+            assert_ne!(densest, Some(char(channel)), "{channel:?}");
+        }
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let img = DynamicImage::ImageRgb8(ImageBuffer::from_fn(4, 4, |x, y| {
+            Rgb([(x * 60) as u8, (y * 60) as u8, 200])
+        }));
+        //the colors are kept, even if colors are disabled
+        let config = ConfigBuilder::new()
+            .target(TargetType::Json)
+            .target_size(std::num::NonZeroU32::new(4).unwrap())
+            .color(false)
+            .border(true)
+            .build();
+        let grid = convert_to_grid(img, &config).unwrap();
+        let json: JsonGrid = serde_json::from_str(&grid.render(&config)).unwrap();
+
+        let stats = grid.stats();
+        assert_eq!(
+            (
+                stats.columns,
+                stats.rows,
+                stats.tile_width,
+                stats.tile_height
+            ),
+            (json.columns, json.rows, json.tile_width, json.tile_height)
+        );
+        assert_eq!(json.rows as usize, json.cells.len());
+        assert!(json
+            .cells
+            .iter()
+            .all(|row| row.len() == json.columns as usize));
+        assert_eq!(grid.cells(), json.cells.concat());
+        assert!(grid.cells().iter().any(|cell| cell.red != cell.green));
+    }
+
+    #[test]
+    fn tiles_are_area_averaged() {
+        //a single tile, the left half is black and the right half is white
+        let img = DynamicImage::ImageRgb8(ImageBuffer::from_fn(8, 8, |x, _| {
+            if x < 4 {
+                Rgb([0, 0, 0])
+            } else {
+                Rgb([255, 255, 255])
+            }
+        }));
+        let char = |threshold: f64| {
+            //the weight of a single channel is exactly 1, the luminance weights are rounded floats
+            let config = ConfigBuilder::new()
+                .gamma(1.0)
+                .channel(config::Channel::Red)
+                .threshold(threshold)
+                .on_char('#')
+                .off_char(' ')
+                .build();
+            grid_from_tiles(&img, (8, 8), (1, 1, 8, 8), 0, &config, None).cells()[0].char
+        };
+        //exactly half of the light, a point sample would be either black or white
+        assert_eq!('#', char(0.5));
+        assert_eq!(' ', char(0.5 + f64::EPSILON));
+    }
+
+    #[test]
+    fn plain_line_matches_rendered_line() {
+        let img = image::open("assets/images/standard_test_img.png").unwrap();
+        for target in [
+            TargetType::Shell,
+            TargetType::AnsiFile,
+            TargetType::HtmlFile,
+            TargetType::File,
+            TargetType::BbCode,
+        ] {
+            for half_blocks in [false, true] {
+                let config = ConfigBuilder::new()
+                    .target(target)
+                    .color(false)
+                    .half_blocks(half_blocks)
+                    .build();
+                let grid = convert_to_grid(img.clone(), &config).unwrap();
+                let columns = grid.stats().columns as usize;
+                let mut lower_rows = grid.lower_colors.chunks(columns);
+                for row in grid.cells().chunks(columns) {
+                    assert_eq!(
+                        render_line(row, lower_rows.next(), None, &config),
+                        plain_line(row),
+                        "{target:?}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn crop_bottom_right_quadrant() {
+        //red, green, blue and white quadrants
+        let img = DynamicImage::ImageRgb8(ImageBuffer::from_fn(100, 100, |x, y| {
+            match (x < 50, y < 50) {
+                (true, true) => Rgb([255, 0, 0]),
+                (false, true) => Rgb([0, 255, 0]),
+                (true, false) => Rgb([0, 0, 255]),
+                (false, false) => Rgb([255, 255, 255]),
+            }
+        }));
+        let config = ConfigBuilder::new()
+            .target_size(std::num::NonZeroU32::new(10).unwrap())
+            .crop((50, 50, 50, 50))
+            .build();
+        let grid = convert_to_grid(img.clone(), &config).unwrap();
+        assert_eq!(50, grid.stats().source_width);
+        assert!(grid
+            .cells()
+            .iter()
+            .all(|cell| (cell.red, cell.green, cell.blue) == (255, 255, 255)));
+
+        //a larger region is clamped to the quadrant
+        let config = ConfigBuilder::new()
+            .target_size(std::num::NonZeroU32::new(10).unwrap())
+            .crop((50, 50, 500, 500))
+            .build();
+        assert_eq!(grid, convert_to_grid(img, &config).unwrap());
+    }
+
+    #[test]
+    fn rotated_wide_image_is_taller() {
+        let img = DynamicImage::new_rgb8(400, 100);
+        let stats = |rotation| {
+            let config = ConfigBuilder::new()
+                .target_size(std::num::NonZeroU32::new(40).unwrap())
+                .rotation(rotation)
+                .build();
+            convert_to_grid(img.clone(), &config).unwrap().stats()
+        };
+        let wide = stats(config::Rotation::None);
+        assert!(wide.columns > wide.rows);
+        for rotation in [config::Rotation::Rotate90, config::Rotation::Rotate270] {
+            let rotated = stats(rotation);
+            assert_eq!((100, 400), (rotated.source_width, rotated.source_height));
+            assert!(rotated.rows > rotated.columns, "{rotated:?}");
+        }
+        assert_eq!(wide, stats(config::Rotation::Rotate180));
+    }
+
+    #[test]
+    fn progress_is_reported_for_each_row() {
+        let img = DynamicImage::new_rgb8(300, 200);
+        let config = ConfigBuilder::new()
+            .target_size(std::num::NonZeroU32::new(30).unwrap())
+            .build();
+        let calls = std::sync::Mutex::new(Vec::new());
+        let grid = convert_to_grid_with_progress(img.clone(), &config, |done, total| {
+            calls.lock().unwrap().push((done, total));
+        })
+        .unwrap();
+        let mut calls = calls.into_inner().unwrap();
+        let rows = grid.stats().rows;
+        assert_eq!(rows as usize, calls.len());
+        //the rows may be reported in any order, if they are converted in parallel
+        calls.sort_unstable();
+        assert_eq!(
+            (1..=rows).map(|done| (done, rows)).collect::<Vec<_>>(),
+            calls
+        );
+        //the progress does not change the result
+        assert_eq!(convert_to_grid(img, &config).unwrap(), grid);
+    }
+
+    #[test]
+    fn batch_continues_after_error() {
+        //an empty image fails, without affecting the images converted before or after it
+        let config = ConfigBuilder::new()
+            .target_size(std::num::NonZeroU32::new(10).unwrap())
+            .build();
+        let images = [
+            DynamicImage::new_rgb8(100, 100),
+            DynamicImage::new_rgb8(0, 0),
+            DynamicImage::new_rgb8(50, 100),
+        ];
+        let results = images
+            .iter()
+            .map(|image| convert(image.clone(), &config))
+            .collect::<Vec<_>>();
+        assert!(results[0].is_ok());
+        assert_eq!(Err(ArtemError::InvalidDimensions(0, 0)), results[1]);
+        assert!(results[2].is_ok());
+        assert_ne!(results[0], results[2]);
+    }
+
+    #[test]
+    fn color_palette_limits_colors() {
+        let img = DynamicImage::ImageRgb8(ImageBuffer::from_fn(300, 200, |x, y| {
+            Rgb([(x % 256) as u8, (y % 256) as u8, ((x * y) % 256) as u8])
+        }));
+        let palette = vec![(0, 0, 0), (255, 0, 0), (0, 0, 255), (255, 255, 255)];
+        for half_blocks in [false, true] {
+            let config = ConfigBuilder::new()
+                .half_blocks(half_blocks)
+                .color_palette(palette.clone())
+                .build();
+            let grid = convert_to_grid(img.clone(), &config).unwrap();
+            assert!(grid
+                .cells
+                .iter()
+                .map(|cell| (cell.red, cell.green, cell.blue))
+                .chain(grid.lower_colors.iter().copied())
+                .all(|color| palette.contains(&color)));
+        }
+    }
+
+    #[test]
+    fn only_color_uses_solid_blocks() {
+        std::env::set_var("CLICOLOR_FORCE", "1");
+        //a red left and a blue right tile
+        let img = DynamicImage::ImageRgb8(ImageBuffer::from_fn(20, 10, |x, _| {
+            if x < 10 {
+                Rgb([255, 0, 0])
+            } else {
+                Rgb([0, 0, 255])
+            }
+        }));
+        let mut builder = ConfigBuilder::new();
+        builder
+            .target_size(std::num::NonZeroU32::new(2).unwrap())
+            .dimension(config::ResizingDimension::Stretch { rows: 1 })
+            .only_color(true);
+        for (color_mode, red, blue) in [
+            (
+                config::ColorMode::Truecolor,
+                "\x1b[38;2;255;0;0m█",
+                "\x1b[38;2;0;0;255m█",
+            ),
+            (
+                config::ColorMode::Ansi256,
+                "\x1b[38;5;196m█",
+                "\x1b[38;5;21m█",
+            ),
+            (config::ColorMode::Ansi16, "\x1b[91m█", "\x1b[94m█"),
+        ] {
+            let config = builder.color_mode(color_mode).build();
+            let output = convert(img.clone(), &config).unwrap();
+            assert!(
+                output.starts_with(red) && output.contains(blue),
+                "{color_mode:?}: {output:?}"
+            );
+            assert_eq!(2, output.matches('█').count());
+        }
+
+        let config = builder.background_color(true).build();
+        let grid = convert_to_grid(img, &config).unwrap();
+        assert_eq!(
+            vec![(' ', 255, 0, 0), (' ', 0, 0, 255)],
+            grid.cells()
+                .iter()
+                .map(|cell| (cell.char, cell.red, cell.green, cell.blue))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn sixteen_bit_gradient_keeps_precision() {
+        //a dark gradient, which only covers two levels of an 8 bit image
+        let gradient = ImageBuffer::from_fn(256, 16, |x, _| image::Luma([1000 + x as u16]));
+        let mut png = std::io::Cursor::new(Vec::new());
+        DynamicImage::ImageLuma16(gradient)
+            .write_to(&mut png, image::ImageFormat::Png)
+            .unwrap();
+        let img = image::load_from_memory(png.get_ref()).unwrap();
+        assert_eq!(image::ColorType::L16, img.color());
+
+        let config = ConfigBuilder::new()
+            .target_size(std::num::NonZeroU32::new(64).unwrap())
+            .dimension(config::ResizingDimension::Stretch { rows: 1 })
+            .contrast_stretch(true)
+            .color(false)
+            .build();
+        let distinct_chars = |img: DynamicImage| {
+            convert(img, &config)
+                .unwrap()
+                .trim_end()
+                .chars()
+                .collect::<HashSet<_>>()
+                .len()
+        };
+        let eight_bit = distinct_chars(DynamicImage::ImageLuma8(img.to_luma8()));
+        let sixteen_bit = distinct_chars(img);
+        assert!(eight_bit <= 2, "{eight_bit}");
+        assert!(sixteen_bit >= 10, "{sixteen_bit}");
+    }
+
+    #[test]
+    fn auto_contrast_widens_glyphs() {
+        //a dark gradient, which only covers a narrow range of luminances
+        let img = DynamicImage::ImageLuma8(ImageBuffer::from_fn(256, 16, |x, _| {
+            image::Luma([20 + (x / 8) as u8])
+        }));
+        let distinct_chars = |auto_contrast: bool| {
+            let config = ConfigBuilder::new()
+                .target_size(std::num::NonZeroU32::new(64).unwrap())
+                .dimension(config::ResizingDimension::Stretch { rows: 1 })
+                .auto_contrast(auto_contrast)
+                .color(false)
+                .build();
+            convert(img.clone(), &config)
+                .unwrap()
+                .lines()
+                .flat_map(str::chars)
+                .collect::<HashSet<_>>()
+        };
+        let flat = distinct_chars(false);
+        let normalized = distinct_chars(true);
+        assert!(flat.len() <= 3, "{flat:?}");
+        assert!(normalized.len() >= 10, "{normalized:?}");
+        //both ends of the characters are used
+        let characters = ConfigBuilder::new().build().characters;
+        assert!(normalized.contains(&characters.chars().next().unwrap()));
+        assert!(normalized.contains(&characters.chars().last().unwrap()));
+    }
+
+    #[test]
+    fn stipple_is_reproducible() {
+        let img = image::open("assets/images/standard_test_img.png").unwrap();
+        let stipple = |seed: Option<u64>| {
+            let mut builder = ConfigBuilder::new();
+            builder.dither(config::DitherMode::Stipple).color(false);
+            if let Some(seed) = seed {
+                builder.seed(seed);
+            }
+            convert(img.clone(), &builder.build()).unwrap()
+        };
+        //the default seed results in the same output every time
+        assert_eq!(stipple(None), stipple(None));
+        assert_eq!(stipple(None), stipple(Some(0)));
+        assert_ne!(stipple(None), stipple(Some(1)));
+        assert_ne!(
+            stipple(None),
+            convert(img.clone(), &ConfigBuilder::new().color(false).build()).unwrap()
+        );
+    }
+
+    #[test]
+    fn half_transparent_tile_keeps_color() {
+        //every other column is opaque red, the others are transparent
+        let img = DynamicImage::ImageRgba8(ImageBuffer::from_fn(40, 40, |x, _| {
+            image::Rgba(if x % 2 == 0 {
+                [255, 0, 0, 255]
+            } else {
+                [0, 0, 0, 0]
+            })
+        }));
+        let config = ConfigBuilder::new()
+            .target_size(std::num::NonZeroU32::new(20).unwrap())
+            .build();
+        let grid = convert_to_grid(img.clone(), &config).unwrap();
+        for cell in grid.cells() {
+            assert_eq!((255, 0, 0), (cell.red, cell.green, cell.blue));
+        }
+
+        //only half of each tile is covered, so the character is lighter than for an opaque red image
+        let opaque = DynamicImage::ImageRgb8(ImageBuffer::from_pixel(40, 40, Rgb([255, 0, 0])));
+        let index = |char: char| config.characters.chars().position(|c| c == char).unwrap();
+        let opaque_char = convert_to_grid(opaque, &config).unwrap().cells()[0].char;
+        assert!(index(grid.cells()[0].char) > index(opaque_char));
+    }
+
+    #[test]
+    fn char_height_compensation_doubles_rows() {
+        let img = DynamicImage::ImageRgb8(ImageBuffer::from_fn(40, 40, |x, y| {
+            Rgb([(x * 6) as u8, (y * 6) as u8, 128])
+        }));
+        let mut builder = ConfigBuilder::new();
+        builder
+            .target_size(std::num::NonZeroU32::new(8).unwrap())
+            .dimension(config::ResizingDimension::Stretch { rows: 10 })
+            .color(true);
+        let converted = convert(img.clone(), &builder.build()).unwrap();
+        assert_eq!(10, converted.lines().count());
+
+        builder.char_height_compensation(2f32);
+        let stretched = convert(img.clone(), &builder.build()).unwrap();
+        let lines = stretched.lines().collect::<Vec<_>>();
+        assert_eq!(20, lines.len());
+        //each row is repeated including its colors
+        for (row, pair) in converted.lines().zip(lines.chunks(2)) {
+            assert_eq!([row, row], pair);
+        }
+
+        builder.compensation_rows(CompensationRows::Blank);
+        let blank = convert(img, &builder.build()).unwrap();
+        let lines = blank.lines().collect::<Vec<_>>();
+        assert_eq!(20, lines.len());
+        for (row, pair) in converted.lines().zip(lines.chunks(2)) {
+            assert_eq!([row, "        "], pair);
+        }
+    }
+
+    #[test]
+    fn char_height_compensation_centered() {
+        let img = DynamicImage::new_rgb8(40, 40);
+        for border in [false, true] {
+            let config = ConfigBuilder::new()
+                .target_size(std::num::NonZeroU32::new(20).unwrap())
+                .char_height_compensation(1.5)
+                .center_y(true)
+                .border(border)
+                .color(false)
+                .build();
+            let grid = convert_to_grid(img.clone(), &config).unwrap();
+            //the spacing only depends on the terminal height, so it is removed before counting the lines
+            let output = grid.render(&config);
+            assert_eq!(
+                output.trim_matches('\n').lines().count() as u32,
+                rendered_height(grid.stats().rows, &config)
+            );
+        }
+    }
+
+    #[test]
+    fn char_height_compensation_fraction() {
+        assert_eq!(0, compensated_rows(0, 1.5));
+        assert_eq!(15, compensated_rows(10, 1.5));
+        assert_eq!(10, compensated_rows(10, 1f32));
+        //every second row is repeated
+        let repeats = (0..4)
+            .map(|row| compensated_rows(row + 1, 1.5) - compensated_rows(row, 1.5))
+            .collect::<Vec<_>>();
+        assert_eq!(vec![2, 1, 2, 1], repeats);
+
+        let img = DynamicImage::ImageRgb8(ImageBuffer::from_pixel(40, 40, Rgb([255, 255, 255])));
+        let mut builder = ConfigBuilder::new();
+        builder
+            .target_size(std::num::NonZeroU32::new(8).unwrap())
+            .border(true)
+            .color(false);
+        let converted = convert(img.clone(), &builder.build()).unwrap();
+        let rows = converted.lines().count() - 2;
+        let width = converted.lines().next().unwrap().chars().count();
+
+        builder
+            .char_height_compensation(1.5)
+            .compensation_rows(CompensationRows::Blank);
+        let stretched = convert(img, &builder.build()).unwrap();
+        //the border is not repeated, but the blank rows keep the sides of the border
+        assert_eq!(rows * 3 / 2 + 2, stretched.lines().count());
+        let blank = format!("║{}║", " ".repeat(width - 2));
+        assert_eq!(
+            rows / 2,
+            stretched.lines().filter(|line| *line == blank).count()
+        );
+    }
+
+    #[test]
+    fn rows_equal_batch_output() {
+        let img = DynamicImage::ImageRgb8(ImageBuffer::from_fn(120, 90, |x, y| {
+            if (x / 15 + y / 10) % 3 == 0 {
+                Rgb([255, 255, 255])
+            } else {
+                Rgb([(x * 2) as u8, (y * 2) as u8, 90])
+            }
+        }));
+        let size = std::num::NonZeroU32::new(24).unwrap();
+        let configs = [
+            ConfigBuilder::new().target_size(size).build(),
+            ConfigBuilder::new().target_size(size).color(false).build(),
+            ConfigBuilder::new()
+                .target_size(size)
+                .half_blocks(true)
+                .build(),
+            ConfigBuilder::new()
+                .target_size(size)
+                .half_blocks(true)
+                .partial_block(config::PartialBlock::Pad)
+                .build(),
+            ConfigBuilder::new().target_size(size).braille(0.5).build(),
+            //the edges use the pixels of the neighbouring rows
+            ConfigBuilder::new()
+                .target_size(size)
+                .edges(0.1)
+                .color(false)
+                .build(),
+            ConfigBuilder::new()
+                .target_size(size)
+                .right_to_left(true)
+                .char_height_compensation(1.5)
+                .build(),
+        ];
+        for config in configs {
+            let rows = convert_rows(img.clone(), &config).unwrap();
+            let grid = convert_to_grid(img.clone(), &config).unwrap();
+            assert_eq!(grid.stats().rows as usize, rows.len());
+            assert_eq!(
+                convert(img.clone(), &config).unwrap(),
+                rows.collect::<String>()
+            );
+        }
+    }
+
+    #[test]
+    fn rows_not_streamable() {
+        let img = DynamicImage::new_rgb8(40, 40);
+        let result = |config: &Config| convert_rows(img.clone(), config).err();
+        assert_eq!(
+            Some(ArtemError::NotStreamable("dither")),
+            result(
+                &ConfigBuilder::new()
+                    .dither(config::DitherMode::FloydSteinberg)
+                    .build()
+            )
+        );
+        assert_eq!(
+            Some(ArtemError::NotStreamable("contrast_stretch")),
+            result(&ConfigBuilder::new().contrast_stretch(true).build())
+        );
+        assert_eq!(
+            Some(ArtemError::NotStreamable("trim")),
+            result(&ConfigBuilder::new().trim(true).build())
+        );
+        assert_eq!(
+            Some(ArtemError::NotStreamable("target")),
+            result(&ConfigBuilder::new().target(TargetType::Svg).build())
+        );
+        assert_eq!(
+            Some(ArtemError::InvalidDimensions(0, 0)),
+            convert_rows(DynamicImage::new_rgb8(0, 0), &ConfigBuilder::new().build()).err()
+        );
+    }
+
+    #[test]
+    fn background_char_fills_black_image() {
+        let img = DynamicImage::ImageRgb8(ImageBuffer::from_pixel(40, 40, Rgb([0, 0, 0])));
+        let mut builder = ConfigBuilder::new();
+        builder
+            .target_size(std::num::NonZeroU32::new(20).unwrap())
+            .color(false)
+            .background_char('.');
+        let converted = convert(img.clone(), &builder.build()).unwrap();
+        assert!(converted
+            .lines()
+            .all(|line| line.chars().count() == 20 && line.chars().all(|char| char == '.')));
+
+        //the colors are the same as without the background char
+        let red = DynamicImage::ImageRgb8(ImageBuffer::from_pixel(40, 40, Rgb([40, 0, 0])));
+        let with_char = convert_to_grid(red.clone(), &builder.build()).unwrap();
+        builder.background_char(' ');
+        let without_char = convert_to_grid(red, &builder.build()).unwrap();
+        assert_eq!(with_char.stats(), without_char.stats());
+        for (cell, other) in with_char.cells().iter().zip(without_char.cells()) {
+            assert_eq!(
+                (cell.red, cell.green, cell.blue),
+                (other.red, other.green, other.blue)
+            );
+        }
+    }
+
+    #[test]
+    fn crlf_line_ending() {
+        let img = DynamicImage::ImageRgb8(ImageBuffer::from_fn(40, 40, |x, y| {
+            Rgb([(x * 6) as u8, (y * 6) as u8, 128])
+        }));
+        let mut builder = ConfigBuilder::new();
+        builder
+            .target_size(std::num::NonZeroU32::new(10).unwrap())
+            .border(true);
+        for target in [TargetType::Shell, TargetType::HtmlFile, TargetType::Svg] {
+            builder.target(target);
+            let lf = convert(img.clone(), &builder.build()).unwrap();
+            let crlf = convert(
+                img.clone(),
+                &builder.line_ending(config::LineEnding::Crlf).build(),
+            )
+            .unwrap();
+            builder.line_ending(config::LineEnding::Lf);
+            assert!(!lf.contains('\r'));
+            assert_eq!(lf.replace('\n', "\r\n"), crlf);
+        }
+
+        //the streamed rows use the line ending as well
+        let config = builder
+            .target(TargetType::Shell)
+            .line_ending(config::LineEnding::Crlf)
+            .border(false)
+            .build();
+        let rows = convert_rows(img.clone(), &config)
+            .unwrap()
+            .collect::<Vec<_>>();
+        assert!(rows.iter().all(|row| row.ends_with("\r\n")));
+        assert_eq!(convert(img, &config).unwrap(), rows.concat());
+    }
+
+    #[test]
+    fn trim_removes_empty_rows_and_columns() {
+        //a white square with a black margin of 10 pixels on the top and left and 20 pixels on the right
+        let img = DynamicImage::ImageRgb8(ImageBuffer::from_fn(80, 40, |x, y| {
+            if y >= 10 && (10..60).contains(&x) {
+                Rgb([255, 255, 255])
+            } else {
+                Rgb([0, 0, 0])
+            }
+        }));
+        let mut builder = ConfigBuilder::new();
+        builder
+            .target_size(std::num::NonZeroU32::new(8).unwrap())
+            .dimension(config::ResizingDimension::Stretch { rows: 4 })
+            .characters("# ".to_string())
+            .color(false);
+        let converted = convert(img.clone(), &builder.build()).unwrap();
+        assert_eq!(
+            vec!["        ", " #####  ", " #####  ", " #####  "],
+            converted.lines().collect::<Vec<_>>()
+        );
+
+        builder.trim(true);
+        let trimmed = convert(img.clone(), &builder.build()).unwrap();
+        assert_eq!(
+            vec!["#####", "#####", "#####"],
+            trimmed.lines().collect::<Vec<_>>()
+        );
+
+        //the colors do not affect the trimming
+        let grid = convert_to_grid(img, &builder.color(true).build()).unwrap();
+        assert_eq!((5, 3), (grid.stats().columns, grid.stats().rows));
+        assert_eq!(15, grid.cells().len());
+    }
+
+    #[test]
+    fn trim_keeps_empty_art() {
+        let img = DynamicImage::new_rgb8(40, 40);
+        let mut builder = ConfigBuilder::new();
+        builder
+            .target_size(std::num::NonZeroU32::new(8).unwrap())
+            .color(false);
+        let converted = convert(img.clone(), &builder.build()).unwrap();
+        assert_eq!(
+            converted,
+            convert(img, &builder.trim(true).build()).unwrap()
+        );
+    }
+}
@@ -0,0 +1,293 @@
+//! Support for 3D color lookup tables (LUTs).
+//!
+//! A LUT maps each color to another color, which can be used to apply a color grading, such as a film look,
+//! to the image before it is converted. The LUTs are loaded from the `.cube` format, which is described in
+//! the [Cube LUT Specification](https://resolve.cafe/developers/luts/).
+//!
+//! This module is only available with the `lut` feature.
+
+use std::str::FromStr;
+
+use image::{DynamicImage, Rgba};
+
+/// A 3D color lookup table.
+///
+/// Colors between the nodes of the table are interpolated trilinearly.
+///
+/// # Examples
+/// ```
+/// use artem::lut::Lut;
+///
+/// //the smallest possible identity lut
+/// let lut: Lut = "LUT_3D_SIZE 2
+/// 0 0 0
+/// 1 0 0
+/// 0 1 0
+/// 1 1 0
+/// 0 0 1
+/// 1 0 1
+/// 0 1 1
+/// 1 1 1"
+///     .parse()
+///     .unwrap();
+/// assert_eq!([10, 128, 250], lut.map_color([10, 128, 250]));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lut {
+    size: usize,
+    domain_min: [f32; 3],
+    domain_max: [f32; 3],
+    //red changes fastest, followed by green and blue
+    table: Vec<[f32; 3]>,
+}
+
+impl Lut {
+    /// Returns the color of the lookup table for the given (red, green, blue) color.
+    pub fn map_color(&self, color: [u8; 3]) -> [u8; 3] {
+        self.map_normalized(color.map(|value| value as f32 / 255f32))
+            .map(|value| (value * 255f32).round() as u8)
+    }
+
+    /// Returns the color of the lookup table for the given (red, green, blue) color with 16 bits per channel.
+    fn map_color_16(&self, color: [u16; 3]) -> [u16; 3] {
+        self.map_normalized(color.map(|value| value as f32 / 65535f32))
+            .map(|value| (value * 65535f32).round() as u16)
+    }
+
+    /// Returns the color of the lookup table for the given color with channels between 0 and 1.
+    ///
+    /// The channels of the result are clamped between 0 and 1 as well.
+    fn map_normalized(&self, color: [f32; 3]) -> [f32; 3] {
+        //position of the color in the table, from 0 to size - 1 in each channel
+        let max_index = (self.size - 1) as f32;
+        let position: [f32; 3] = std::array::from_fn(|channel| {
+            let value = color[channel];
+            let range = self.domain_max[channel] - self.domain_min[channel];
+            ((value - self.domain_min[channel]) / range * max_index).clamp(0f32, max_index)
+        });
+        let lower = position.map(|value| value.floor() as usize);
+        let upper = lower.map(|index| (index + 1).min(self.size - 1));
+        let fraction: [f32; 3] =
+            std::array::from_fn(|channel| position[channel] - lower[channel] as f32);
+
+        let node = |red: usize, green: usize, blue: usize| {
+            self.table[red + green * self.size + blue * self.size * self.size]
+        };
+        let mix = |a: [f32; 3], b: [f32; 3], t: f32| -> [f32; 3] {
+            std::array::from_fn(|channel| a[channel] + (b[channel] - a[channel]) * t)
+        };
+
+        //interpolate along red, then green, then blue
+        let [r0, g0, b0] = lower;
+        let [r1, g1, b1] = upper;
+        let [fr, fg, fb] = fraction;
+        let c00 = mix(node(r0, g0, b0), node(r1, g0, b0), fr);
+        let c10 = mix(node(r0, g1, b0), node(r1, g1, b0), fr);
+        let c01 = mix(node(r0, g0, b1), node(r1, g0, b1), fr);
+        let c11 = mix(node(r0, g1, b1), node(r1, g1, b1), fr);
+        let c0 = mix(c00, c10, fg);
+        let c1 = mix(c01, c11, fg);
+        mix(c0, c1, fb).map(|value| value.clamp(0f32, 1f32))
+    }
+
+    /// Apply the lookup table to all pixels of the image.
+    ///
+    /// The alpha channel of the image is not changed. Images with more than 8 bits per channel keep
+    /// 16 bits per channel, so they are not less precise than without a lookup table.
+    pub fn apply(&self, image: DynamicImage) -> DynamicImage {
+        if crate::filter::is_high_bit_depth(&image) {
+            let mut image = image.into_rgba16();
+            for pixel in image.pixels_mut() {
+                let [red, green, blue] = self.map_color_16([pixel.0[0], pixel.0[1], pixel.0[2]]);
+                *pixel = Rgba([red, green, blue, pixel.0[3]]);
+            }
+            return DynamicImage::ImageRgba16(image);
+        }
+        let mut image = image.into_rgba8();
+        for pixel in image.pixels_mut() {
+            let [red, green, blue] = self.map_color([pixel.0[0], pixel.0[1], pixel.0[2]]);
+            *pixel = Rgba([red, green, blue, pixel.0[3]]);
+        }
+        DynamicImage::ImageRgba8(image)
+    }
+}
+
+impl FromStr for Lut {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut size = None;
+        let mut domain_min = [0f32; 3];
+        let mut domain_max = [1f32; 3];
+        let mut table = Vec::new();
+
+        let parse_values = |line: usize, values: &[&str]| -> Result<[f32; 3], String> {
+            match values {
+                [red, green, blue] => {
+                    let parse = |value: &str| {
+                        value
+                            .parse::<f32>()
+                            .map_err(|_| format!("Line {line}: {value} is not a number"))
+                    };
+                    Ok([parse(red)?, parse(green)?, parse(blue)?])
+                }
+                _ => Err(format!("Line {line}: expected three values")),
+            }
+        };
+
+        for (index, line) in s.lines().enumerate() {
+            let line_number = index + 1;
+            let line = line.trim();
+            //skip empty lines and comments
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let parts = line.split_whitespace().collect::<Vec<_>>();
+            match parts[0] {
+                //the title is not needed
+                "TITLE" => {}
+                "LUT_1D_SIZE" => {
+                    return Err(format!("Line {line_number}: 1D luts are not supported"))
+                }
+                "LUT_3D_SIZE" => {
+                    let value = parts.get(1).and_then(|value| value.parse::<usize>().ok());
+                    match value {
+                        Some(value) if (2..=256).contains(&value) => size = Some(value),
+                        _ => {
+                            return Err(format!(
+                                "Line {line_number}: the size has to be between 2 and 256"
+                            ))
+                        }
+                    }
+                }
+                "DOMAIN_MIN" => domain_min = parse_values(line_number, &parts[1..])?,
+                "DOMAIN_MAX" => domain_max = parse_values(line_number, &parts[1..])?,
+                _ => table.push(parse_values(line_number, &parts)?),
+            }
+        }
+
+        let Some(size) = size else {
+            return Err("The lut has no LUT_3D_SIZE".to_string());
+        };
+        if table.len() != size * size * size {
+            return Err(format!(
+                "The lut has {} entries, but {} are expected for a size of {size}",
+                table.len(),
+                size * size * size
+            ));
+        }
+        if (0..3).any(|channel| domain_min[channel] >= domain_max[channel]) {
+            return Err("The DOMAIN_MIN has to be less than the DOMAIN_MAX".to_string());
+        }
+
+        Ok(Self {
+            size,
+            domain_min,
+            domain_max,
+            table,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test_lut {
+    use image::{ImageBuffer, Rgb};
+
+    use super::*;
+
+    /// Create the content of a cube file with the given size, mapping each node with the function.
+    fn cube(size: usize, map: impl Fn([f32; 3]) -> [f32; 3]) -> String {
+        let mut content = format!("TITLE \"test\"\n# comment\nLUT_3D_SIZE {size}\n");
+        for blue in 0..size {
+            for green in 0..size {
+                for red in 0..size {
+                    let node = [red, green, blue].map(|value| value as f32 / (size - 1) as f32);
+                    let [red, green, blue] = map(node);
+                    content.push_str(&format!("{red} {green} {blue}\n"));
+                }
+            }
+        }
+        content
+    }
+
+    fn test_image() -> DynamicImage {
+        DynamicImage::ImageRgb8(ImageBuffer::from_fn(16, 16, |x, y| {
+            Rgb([(x * 16) as u8, (y * 16) as u8, ((x + y) * 8) as u8])
+        }))
+    }
+
+    #[test]
+    fn identity() {
+        let lut: Lut = cube(17, |node| node).parse().unwrap();
+        let image = test_image();
+        let applied = lut.apply(image.clone()).into_rgb8();
+        for (pixel, expected) in applied.pixels().zip(image.into_rgb8().pixels()) {
+            for channel in 0..3 {
+                assert!(pixel.0[channel].abs_diff(expected.0[channel]) <= 1);
+            }
+        }
+    }
+
+    #[test]
+    fn inverting() {
+        let lut: Lut = cube(2, |node| node.map(|value| 1f32 - value))
+            .parse()
+            .unwrap();
+        let image = test_image();
+        let applied = lut.apply(image.clone()).into_rgb8();
+        for (pixel, expected) in applied.pixels().zip(image.into_rgb8().pixels()) {
+            for channel in 0..3 {
+                assert!(pixel.0[channel].abs_diff(255 - expected.0[channel]) <= 1);
+            }
+        }
+    }
+
+    #[test]
+    fn keeps_alpha() {
+        let lut: Lut = cube(2, |node| node).parse().unwrap();
+        let image = DynamicImage::ImageRgba8(ImageBuffer::from_pixel(2, 2, Rgba([1, 2, 3, 4])));
+        assert_eq!(
+            Rgba([1, 2, 3, 4]),
+            *lut.apply(image).into_rgba8().get_pixel(0, 0)
+        );
+    }
+
+    #[test]
+    fn sixteen_bit_image() {
+        let lut: Lut = cube(2, |node| node).parse().unwrap();
+        let image = DynamicImage::ImageRgba16(ImageBuffer::from_fn(2, 1, |x, _| {
+            Rgba([32768 + x as u16, 1000, 65535, 1234])
+        }));
+        let applied = lut.apply(image);
+        assert!(crate::filter::is_high_bit_depth(&applied));
+        let applied = applied.into_rgba16();
+        assert_eq!([32768, 1000, 65535, 1234], applied.get_pixel(0, 0).0);
+        assert_eq!([32769, 1000, 65535, 1234], applied.get_pixel(1, 0).0);
+    }
+
+    #[test]
+    fn domain() {
+        let mut content = cube(2, |node| node);
+        content.push_str("DOMAIN_MIN 0 0 0\nDOMAIN_MAX 0.5 0.5 0.5\n");
+        let lut: Lut = content.parse().unwrap();
+        //everything above the domain is clamped to the last node
+        assert_eq!([255, 255, 255], lut.map_color([200, 200, 200]));
+        assert_eq!([128, 0, 0], lut.map_color([64, 0, 0]));
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(
+            Err("The lut has no LUT_3D_SIZE".to_string()),
+            "0 0 0".parse::<Lut>()
+        );
+        assert!("LUT_1D_SIZE 2".parse::<Lut>().is_err());
+        assert!("LUT_3D_SIZE 1".parse::<Lut>().is_err());
+        assert!("LUT_3D_SIZE 2\n0 0 0".parse::<Lut>().is_err());
+        assert!(cube(2, |node| node)
+            .replace("1 1 1", "1 x 1")
+            .parse::<Lut>()
+            .is_err());
+    }
+}
@@ -10,22 +10,37 @@
 //! ```
 //! # let path = "./assets/images/standard_test_img.png";
 //! let image = image::open(path).expect("Failed to open image");
-//! let ascii_art = artem::convert(image, &artem::config::ConfigBuilder::new().build());
+//! let ascii_art = artem::convert(image, &artem::config::ConfigBuilder::new().build()).unwrap();
 //! ```
 
 use std::{
+    collections::HashSet,
     fs::File,
-    io::Write,
+    io::{IsTerminal, Read, Write},
     num::NonZeroU32,
     path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    time::Instant,
 };
 
-use artem::config::{self, ConfigBuilder, TargetType};
+use artem::{
+    config::{self, ConfigBuilder, TargetType},
+    graphics::ImageProtocol,
+};
+use colored::Colorize;
 
 //import cli
 mod cli;
+#[cfg(feature = "interactive")]
+mod interactive;
 
 fn main() {
+    //measure the time of the complete conversion, used for the statistics
+    let now = Instant::now();
+
     //get args from cli
     let matches = cli::build_cli().get_matches();
 
@@ -46,17 +61,38 @@ fn main() {
     //log enabled features
     log::trace!("Feature web_image: {}", cfg!(feature = "web_image"));
 
+    //only print the terminal capabilities, without converting an image
+    if matches.get_flag("capabilities") {
+        let color_mode = (!matches.get_flag("no-color"))
+            .then(|| requested_color_mode(&matches).unwrap_or_else(config::ColorMode::detect));
+        print!("{}", capabilities_report(color_mode));
+        return;
+    }
+
     let mut config_builder = ConfigBuilder::new();
 
-    //at least one input must exist, so its safe to unwrap
-    let input = matches.get_many::<String>("INPUT").unwrap();
+    //errors of single inputs do not abort the conversion of the other inputs, unless --fail-fast is used
+    let failures = Failures::new(matches.get_flag("fail-fast"));
+
+    //at least one input must exist, if the capabilities are not printed, so its safe to unwrap
+    let input = expand_globs(matches.get_many::<String>("INPUT").unwrap());
 
     let mut img_paths = Vec::with_capacity(input.len());
 
     log::info!("Checking inputs");
-    for value in input {
+    for value in &input {
+        if value == STDIN_PATH {
+            //stdin can only be read once
+            if img_paths.iter().any(|path| *path == STDIN_PATH) {
+                fatal_error("The standard input (-) can only be used once", Some(64));
+            }
+            log::debug!("Input {} is the standard input", value);
+            img_paths.push(value);
+            continue;
+        }
+
         #[cfg(feature = "web_image")]
-        if value.starts_with("http") {
+        if is_url(value) {
             log::debug!("Input {} is a URL", value);
             img_paths.push(value);
             continue;
@@ -73,29 +109,182 @@ fn main() {
         img_paths.push(value);
     }
 
+    //rotate photos according to their EXIF orientation, unless it is disabled
+    #[cfg(feature = "exif")]
+    let auto_orient = !matches.get_flag("no-auto-orient");
+    #[cfg(not(feature = "exif"))]
+    let auto_orient = false;
+    log::debug!("Auto orient: {auto_orient}");
+
+    //only print the dominant color, without converting the images
+    if matches.get_flag("dominant-color") {
+        for path in &img_paths {
+            let Some(img) = failures.load_image(path, auto_orient) else {
+                continue;
+            };
+            let (red, green, blue) = artem::dominant_color(&img);
+            let hex = format!("#{red:02X}{green:02X}{blue:02X}");
+            //only prefix the path, if it is needed to distinguish the colors
+            let prefix = if img_paths.len() > 1 {
+                format!("{path}: ")
+            } else {
+                String::new()
+            };
+            if matches.get_flag("no-color") {
+                println!("{prefix}{hex}");
+            } else {
+                println!("{prefix}{hex} {}", "    ".on_truecolor(red, green, blue));
+            }
+        }
+        failures.exit_on_failure();
+        return;
+    }
+
+    //only print the palette, without converting the images
+    if let Some(&colors) = matches.get_one::<usize>("palette") {
+        if colors == 0 {
+            fatal_error("The palette must have at least one color", Some(65));
+        }
+        let seed = *matches.get_one::<u64>("seed").unwrap();
+        log::debug!("Palette seed: {seed}");
+        for path in &img_paths {
+            let Some(img) = failures.load_image(path, auto_orient) else {
+                continue;
+            };
+            let palette = artem::palette::extract_palette(&img, colors, seed);
+            log::debug!(
+                "Extracted palette of {path} in {} iterations",
+                palette.iterations
+            );
+            //only prefix the path, if it is needed to distinguish the palettes
+            let prefix = if img_paths.len() > 1 {
+                format!("{path}: ")
+            } else {
+                String::new()
+            };
+            for (red, green, blue) in palette.colors {
+                let hex = format!("#{red:02X}{green:02X}{blue:02X}");
+                if matches.get_flag("no-color") {
+                    println!("{prefix}{hex}");
+                } else {
+                    println!("{prefix}{hex} {}", "    ".on_truecolor(red, green, blue));
+                }
+            }
+        }
+        failures.exit_on_failure();
+        return;
+    }
+
     //density char map
     let density = match matches
         .get_one::<String>("characters")
         .map(|res| res.as_str())
     {
-        Some("short") | Some("s") | Some("0") => r#"Ñ@#W$9876543210?!abc;:+=-,._ "#,
-        Some("flat") | Some("f") | Some("1") => r#"MWNXK0Okxdolc:;,'...   "#,
-        Some("long") | Some("l") | Some("2") => {
-            r#"$@B%8&WM#*oahkbdpqwmZO0QLCJUYXzcvunxrjft/\|()1{}[]?-_+~<>i!lI;:,"^`'. "#
-        }
-        Some(chars) if !chars.is_empty() => {
+        Some("") => fatal_error(&config::ArtemError::EmptyCharacterSet.to_string(), Some(65)),
+        //presets are resolved by their name, all other values are used as characters
+        Some(chars) => {
             log::debug!("Using user provided characters");
-            chars
+            config::CharacterRamp::resolve(chars)
         }
-        _ => {
-            //density map from jp2a
+        None => {
             log::debug!("Using default characters");
-            r#"MWNXK0Okxdolc:;,'...   "#
+            config::CharacterRamp::default().characters()
         }
     };
     log::debug!("Characters used: '{density}'");
     config_builder.characters(density.to_string());
 
+    if let Some(mapping_file) = matches.get_one::<PathBuf>("mapping-file") {
+        log::debug!("Mapping-file: {}", mapping_file.display());
+        let Ok(content) = std::fs::read_to_string(mapping_file) else {
+            fatal_error(
+                &format!("Could not read mapping file {}", mapping_file.display()),
+                Some(66),
+            );
+        };
+        match content.parse::<config::CharacterMapping>() {
+            Ok(mapping) => {
+                config_builder.mapping(mapping);
+            }
+            Err(err) => fatal_error(&format!("Invalid mapping file: {err}"), Some(65)),
+        }
+    }
+
+    #[cfg(feature = "lut")]
+    if let Some(lut_file) = matches.get_one::<PathBuf>("lut") {
+        log::debug!("Lut: {}", lut_file.display());
+        let Ok(content) = std::fs::read_to_string(lut_file) else {
+            fatal_error(
+                &format!("Could not read lut file {}", lut_file.display()),
+                Some(66),
+            );
+        };
+        match content.parse::<artem::lut::Lut>() {
+            Ok(lut) => {
+                config_builder.lut(Some(lut));
+            }
+            Err(err) => fatal_error(&format!("Invalid lut file: {err}"), Some(65)),
+        }
+    }
+
+    if let Some(mut values) = matches.get_many::<String>("ramp-gradient") {
+        //the first value is always present
+        let ramps = values.next().unwrap();
+        let Some((start, end)) = ramps
+            .split_once('|')
+            .filter(|(start, end)| !start.is_empty() && !end.is_empty())
+        else {
+            fatal_error(
+                &format!("Invalid ramp gradient {ramps}, expected two sets of characters separated by a '|'"),
+                Some(65),
+            );
+        };
+        let direction = values
+            .next()
+            .map_or(Ok(config::GradientDirection::default()), |direction| {
+                direction.parse()
+            })
+            .unwrap_or_else(|err: String| fatal_error(&err, Some(65)));
+        log::debug!("Ramp gradient: '{start}' to '{end}' {direction:?}");
+        config_builder.ramp_gradient(config::RampGradient {
+            start: start.to_string(),
+            end: end.to_string(),
+            direction,
+        });
+    }
+
+    if let Some(ramps) = matches.get_one::<String>("tonal-ramps") {
+        let bands = ramps.split('|').collect::<Vec<_>>();
+        let [shadows, midtones, highlights] = bands[..] else {
+            fatal_error(
+                &format!("Invalid tonal ramps {ramps}, expected three sets of characters separated by a '|'"),
+                Some(65),
+            );
+        };
+        log::debug!("Tonal ramps: '{shadows}', '{midtones}', '{highlights}'");
+        config_builder.tonal_ramps(config::TonalRamps {
+            shadows: shadows.to_string(),
+            midtones: midtones.to_string(),
+            highlights: highlights.to_string(),
+        });
+    }
+
+    let ramp_rounding = match matches
+        .get_one::<String>("ramp-rounding")
+        .map(|res| res.as_str())
+    {
+        Some("round") => config::RampRounding::Round,
+        Some("ceil") => config::RampRounding::Ceil,
+        _ => config::RampRounding::Floor,
+    };
+    log::debug!("Ramp rounding: {ramp_rounding:?}");
+    config_builder.ramp_rounding(ramp_rounding);
+
+    if let Some(background_char) = matches.get_one::<char>("background-char") {
+        log::debug!("Background char: {background_char:?}");
+        config_builder.background_char(*background_char);
+    }
+
     //set the default resizing dimension to width
     config_builder.dimension(config::ResizingDimension::Width);
 
@@ -113,52 +302,161 @@ fn main() {
     let height = matches.get_flag("height");
     //get target size from args
     //only one arg should be present
-    let target_size = if matches.get_flag("width") || height {
+    let target_size = if matches.contains_id("fit") {
+        //without a value the whole terminal is used, except for a row, which is needed for the user input line
+        let (columns, rows) = matches
+            .get_one::<(u32, u32)>("fit")
+            .copied()
+            .unwrap_or_else(|| {
+                (
+                    terminal_size(false),
+                    terminal_size(true).saturating_sub(1).max(1),
+                )
+            });
+        log::debug!("Fitting into {columns}x{rows}");
+        config_builder.dimension(config::ResizingDimension::Both { rows });
+        columns
+    } else if let Some(&(columns, rows)) = matches.get_one::<(u32, u32)>("stretch") {
+        log::debug!("Stretching to {columns}x{rows}");
+        //the target size can not be 0, the other invalid sizes are reported by the validation of the config
+        if columns == 0 {
+            fatal_error(
+                &config::ArtemError::InvalidStretch(columns, rows).to_string(),
+                Some(65),
+            );
+        }
+        config_builder.dimension(config::ResizingDimension::Stretch { rows });
+        columns
+    } else if matches.get_flag("width") || height {
         if height {
             config_builder.dimension(config::ResizingDimension::Height);
         }
         terminal_size(height)
     } else {
-        //use given input size
-        log::trace!("Using user input size as target size");
-        *matches.get_one::<u32>("size").unwrap_or_else(|| {
-            fatal_error(
-                "Failed to read terminal size, STDOUT is not a tty",
-                Some(72),
-            )
-        })
-    }
-    .max(20); //min should be 20 to ensure a somewhat visible picture
+        //use given input size, otherwise the width of the terminal
+        match matches.get_one::<u32>("size") {
+            Some(size) => {
+                log::trace!("Using user input size as target size");
+                *size
+            }
+            //files keep the same size, regardless of the terminal they are written from
+            None if matches.contains_id("output-file") || matches.contains_id("output-dir") => {
+                log::trace!("Using default target size for output files");
+                artem::DEFAULT_TARGET_SIZE
+            }
+            None => {
+                log::trace!("Using terminal width as target size");
+                artem::default_target_size()
+            }
+        }
+    };
+    //min should be 20 to ensure a somewhat visible picture, only a stretched image uses exactly the given size
+    let target_size = if matches.contains_id("stretch") {
+        target_size
+    } else {
+        target_size.max(20)
+    };
 
     log::debug!("Target Size: {target_size}");
-    config_builder.target_size(NonZeroU32::new(target_size).unwrap()); //safe to unwrap, since it is clamped before
+    config_builder.target_size(NonZeroU32::new(target_size).unwrap()); //safe to unwrap, since it is checked before
 
-    //best ratio between height and width is 0.43
-    let Some(scale) = matches.get_one::<f32>("scale").map(|scale| {
-        scale.clamp(
-            0.1f32, //a negative or 0 scale is not allowed
-            1f32,   //even a scale above 0.43 is not looking good
-        )
-    }) else {
+    if let Some(&crop) = matches.get_one::<(u32, u32, u32, u32)>("crop") {
+        config_builder.crop(crop);
+        log::debug!("Crop: {crop:?}");
+    }
+
+    //a scale, which is not positive and finite, is reported by the validation of the config
+    let Some(scale) = matches.get_one::<f32>("scale").copied() else {
         fatal_error("Could not work with ratio input value", Some(65));
     };
     log::debug!("Scale: {scale}");
     config_builder.scale(scale);
 
+    //get filter for resizing the image
+    let resize_filter = match matches
+        .get_one::<String>("filter")
+        .map(|value| value.as_str())
+    {
+        Some("nearest") => config::ResizeFilter::Nearest,
+        Some("triangle") => config::ResizeFilter::Triangle,
+        Some("catmull-rom") => config::ResizeFilter::CatmullRom,
+        Some("gaussian") => config::ResizeFilter::Gaussian,
+        Some("lanczos3") => config::ResizeFilter::Lanczos3,
+        _ => config::ResizeFilter::Thumbnail,
+    };
+    log::debug!("Resize filter: {resize_filter:?}");
+    config_builder.resize_filter(resize_filter);
+
     let invert = matches.get_flag("invert-density");
     log::debug!("Invert is set to: {invert}");
     config_builder.invert(invert);
 
+    let negative = matches.get_flag("negative");
+    log::debug!("Negative is set to: {negative}");
+    config_builder.negative(negative);
+
+    if let Some(brightness) = matches.get_one::<f64>("brightness") {
+        log::debug!("Brightness: {brightness}");
+        config_builder.brightness(*brightness);
+    }
+
+    if let Some(contrast) = matches.get_one::<f64>("contrast") {
+        log::debug!("Contrast: {contrast}");
+        config_builder.contrast(*contrast);
+    }
+
+    let auto_contrast = matches.get_flag("auto-contrast");
+    log::debug!("Auto contrast: {auto_contrast}");
+    config_builder.auto_contrast(auto_contrast);
+
+    let silhouette = matches.get_flag("silhouette");
+    log::debug!("Silhouette is set to: {silhouette}");
+    config_builder.silhouette(silhouette);
+
     let background_color = matches.get_flag("background-color");
     log::debug!("BackgroundColor is set to: {background_color}");
     config_builder.background_color(background_color);
 
+    if let Some(ratio) = matches.get_one::<f64>("min-contrast") {
+        //checked early, before any warnings about the colors are printed
+        if !(1f64..=21f64).contains(ratio) {
+            fatal_error(
+                &config::ArtemError::InvalidMinContrast(*ratio).to_string(),
+                Some(65),
+            );
+        }
+        //safe to unwrap, since it has a default value
+        let background = *matches
+            .get_one::<(u8, u8, u8)>("contrast-background")
+            .unwrap();
+        log::debug!("Minimum contrast: {ratio} against {background:?}");
+        config_builder.min_contrast(config::MinContrast {
+            background,
+            ratio: *ratio,
+        });
+    }
+
+    if let Some(background) = matches.get_one::<(u8, u8, u8)>("background-fill") {
+        log::debug!("Background fill: {background:?}");
+        config_builder.background_fill(*background);
+    }
+
     //check if no colors should be used or the if a output file will be used
     //since text documents don`t support ansi ascii colors
+    let to_stdout = !matches.contains_id("output-file") && !matches.contains_id("output-dir");
+    //a pinned color mode uses the colors independent of the terminal and the environment
+    let pinned = matches.contains_id("pin-color-mode");
+    let force_color = matches.get_flag("force-color") || pinned;
     let color = if matches.get_flag("no-color") {
         //print the "normal" non-colored conversion
         log::info!("Using non-colored ascii");
         false
+    } else if to_stdout && !artem::should_use_color(force_color.then_some(true)) {
+        //like ls or grep, escape sequences are not written to pipes or files
+        log::info!("Stdout is not a terminal, using non-colored ascii");
+        //background colors require colors
+        config_builder.background_color(false);
+        false
     } else {
         if matches.get_flag("outline") {
             log::warn!("Using outline, result will only be in grayscale");
@@ -167,23 +465,65 @@ fn main() {
 
         //print colored terminal conversion, this should already respect truecolor support/use ansi colors if not supported
         log::info!("Using colored ascii");
-        if !*artem::SUPPORTS_TRUECOLOR {
-            if background_color {
-                log::warn!("Background flag will be ignored, since truecolor is not supported.")
+        match requested_color_mode(&matches) {
+            Some(color_mode) => {
+                log::info!("Using {color_mode:?} ascii");
+                config_builder.color_mode(color_mode);
             }
-            log::warn!("Truecolor is not supported. Using ansi color.")
-        } else {
-            log::info!("Using truecolor ascii")
+            None => match config::ColorMode::detect() {
+                config::ColorMode::Truecolor => log::info!("Using truecolor ascii"),
+                config::ColorMode::Ansi256 => {
+                    log::warn!("Truecolor is not supported. Using 256 colors.")
+                }
+                config::ColorMode::Ansi16 => {
+                    log::warn!("Truecolor is not supported. Using ansi color.")
+                }
+            },
+        }
+        if force_color {
+            //the escape sequences are written, even if stdout is not a terminal or NO_COLOR is set
+            log::info!("Forcing colors");
+            colored::control::set_override(true);
         }
         true
     };
     config_builder.color(color);
 
+    //get the metric used to find the nearest of the 16 ansi colors or palette colors
+    let distance_metric = match matches
+        .get_one::<String>("color-distance")
+        .map(|value| value.as_str())
+    {
+        Some("lab") => config::DistanceMetric::CieLab,
+        _ => config::DistanceMetric::Rgb,
+    };
+    config_builder.distance_metric(distance_metric);
+    log::debug!("Using color distance: {distance_metric:?}");
+
     //get flag for border around image
     let border = matches.get_flag("border");
     config_builder.border(border);
     log::info!("Using border: {border}");
 
+    let border_style = match matches
+        .get_one::<String>("border-style")
+        .map(|value| value.as_str())
+    {
+        Some("single") => config::BorderStyle::Single,
+        Some("ascii") => config::BorderStyle::Ascii,
+        _ => config::BorderStyle::Double,
+    };
+    config_builder.border_style(border_style);
+    log::debug!("Border style: {border_style:?}");
+
+    let rotation = matches
+        .get_one::<String>("rotate")
+        .and_then(|degrees| degrees.parse().ok())
+        .and_then(config::Rotation::from_degrees)
+        .unwrap_or_default();
+    config_builder.rotation(rotation);
+    log::debug!("Rotation: {rotation:?}");
+
     //get flags for flipping along x axis
     let transform_x = matches.get_flag("flipX");
     config_builder.transform_x(transform_x);
@@ -194,6 +534,116 @@ fn main() {
     config_builder.transform_y(transform_y);
     log::debug!("Flipping Y-Axis: {transform_y}");
 
+    let trim = matches.get_flag("trim");
+    config_builder.trim(trim);
+    log::debug!("Trim: {trim}");
+
+    let markdown = matches.get_flag("markdown");
+    config_builder.markdown(markdown);
+    log::debug!("Markdown code block: {markdown}");
+
+    let whitespace = match matches
+        .get_one::<String>("whitespace")
+        .map(|res| res.as_str())
+    {
+        Some("compact") => config::Whitespace::Compact,
+        _ => config::Whitespace::Preserve,
+    };
+    log::debug!("Whitespace: {whitespace:?}");
+    config_builder.whitespace(whitespace);
+
+    let line_ending = match matches
+        .get_one::<String>("line-ending")
+        .map(|value| value.as_str())
+    {
+        Some("crlf") => config::LineEnding::Crlf,
+        _ => config::LineEnding::Lf,
+    };
+    log::debug!("Line ending: {line_ending:?}");
+    config_builder.line_ending(line_ending);
+
+    if let Some(factor) = matches.get_one::<f32>("char-height-compensation") {
+        config_builder.char_height_compensation(*factor);
+        log::debug!("Char height compensation: {factor}");
+    }
+
+    let compensation_rows = match matches
+        .get_one::<String>("compensation-rows")
+        .map(|value| value.as_str())
+    {
+        Some("blank") => config::CompensationRows::Blank,
+        _ => config::CompensationRows::Duplicate,
+    };
+    log::debug!("Compensation rows: {compensation_rows:?}");
+    config_builder.compensation_rows(compensation_rows);
+
+    //monochrome displays profit from the full contrast range and dithering
+    let mono_optimize = matches.get_flag("mono-optimize");
+    config_builder.contrast_stretch(mono_optimize);
+    log::debug!("Optimize for monochrome displays: {mono_optimize}");
+
+    let dither = match matches
+        .get_one::<String>("dither")
+        .map(|value| value.as_str())
+    {
+        Some("bayer4") => config::DitherMode::Bayer4,
+        Some("bayer8") => config::DitherMode::Bayer8,
+        Some("stipple") => config::DitherMode::Stipple,
+        Some(_) => config::DitherMode::FloydSteinberg,
+        None if mono_optimize => config::DitherMode::FloydSteinberg,
+        None => config::DitherMode::None,
+    };
+    config_builder.dither(dither);
+    log::debug!("Dither: {dither:?}");
+
+    //safe to unwrap, since it has a default value
+    let seed = *matches.get_one::<u64>("seed").unwrap();
+    log::debug!("Seed: {seed}");
+    config_builder.seed(seed);
+
+    if let Some(ink_budget) = matches.get_one::<usize>("ink-budget") {
+        log::debug!("Ink budget: {ink_budget}");
+        config_builder.ink_budget(*ink_budget);
+    }
+
+    if let Some(vignette) = matches.get_one::<f32>("vignette") {
+        log::debug!("Vignette: {vignette}");
+        config_builder.vignette(*vignette);
+    }
+
+    if let Some(mask) = matches.get_one::<PathBuf>("mask") {
+        log::debug!("Mask: {}", mask.display());
+        match image::open(mask) {
+            Ok(img) => {
+                config_builder.mask(img.to_luma8());
+            }
+            Err(err) => fatal_error(
+                &format!("Could not read mask image {}: {err}", mask.display()),
+                Some(66),
+            ),
+        }
+    }
+
+    if let Some(color_step) = matches.get_one::<u8>("color-step") {
+        log::debug!("Color step: {color_step}");
+        config_builder.color_step(*color_step);
+    }
+
+    if let Some(color_palette) = matches.get_many::<(u8, u8, u8)>("color-palette") {
+        let color_palette: Vec<_> = color_palette.copied().collect();
+        log::debug!("Color palette: {color_palette:?}");
+        config_builder.color_palette(color_palette);
+    }
+
+    //get flags for the output order
+    let right_to_left = matches.get_flag("rtl");
+    config_builder.right_to_left(right_to_left);
+    log::debug!("Right to left: {right_to_left}");
+
+    let bottom_to_top = matches.get_flag("reverse-lines");
+    config_builder.bottom_to_top(bottom_to_top);
+    log::debug!("Bottom to top: {bottom_to_top}");
+
     //get flags for centering the image
     let center_x = matches.get_flag("centerX");
     config_builder.center_x(center_x);
@@ -216,137 +666,1230 @@ fn main() {
         if hysteresis {
             log::warn!("Using hysteresis might result in an worse looking ascii image than only using --outline")
         }
+
+        let graded_edges = matches.get_flag("graded-edges");
+        config_builder.graded_edges(graded_edges);
+        log::debug!("Graded edges: {graded_edges}");
+    }
+
+    //get flag for directional characters
+    let directional = matches.get_flag("directional");
+    config_builder.directional(directional);
+    log::debug!("Directional: {directional}");
+
+    //get threshold for edge characters
+    if let Some(threshold) = matches.get_one::<f32>("edges") {
+        log::debug!("Edge threshold: {threshold}");
+        config_builder.edges(*threshold);
+    }
+
+    //get threshold for braille characters
+    if let Some(threshold) = matches.get_one::<f32>("braille") {
+        log::debug!("Braille threshold: {threshold}");
+        config_builder.braille(*threshold);
+    }
+
+    //get flag for half blocks
+    let half_blocks = matches.get_flag("half-blocks");
+    config_builder.half_blocks(half_blocks);
+    log::debug!("Half blocks: {half_blocks}");
+
+    //get the handling of a partial last row of half blocks
+    let partial_block = match matches
+        .get_one::<String>("partial-block")
+        .map(|value| value.as_str())
+    {
+        Some("pad") => config::PartialBlock::Pad,
+        _ => config::PartialBlock::Render,
+    };
+    config_builder.partial_block(partial_block);
+    log::debug!("Partial block: {partial_block:?}");
+
+    let only_color = matches.get_flag("only-color");
+    config_builder.only_color(only_color);
+    log::debug!("Only color: {only_color}");
+
+    //get gamma for the luminance calculation
+    if let Some(gamma) = matches.get_one::<f64>("gamma") {
+        log::debug!("Gamma: {gamma}");
+        config_builder.gamma(*gamma);
+    }
+
+    //get weights for the luminance calculation
+    let luminance_standard = match matches
+        .get_one::<String>("luminance")
+        .map(|value| value.as_str())
+    {
+        Some("rec601") => config::LuminanceStandard::Rec601,
+        Some("average") => config::LuminanceStandard::Average,
+        _ => config::LuminanceStandard::Rec709,
+    };
+    config_builder.luminance_standard(luminance_standard);
+    log::debug!("Luminance standard: {luminance_standard:?}");
+
+    //get the channel, which chooses the characters
+    let channel = match matches
+        .get_one::<String>("channel")
+        .map(|value| value.as_str())
+    {
+        Some("red") => config::Channel::Red,
+        Some("green") => config::Channel::Green,
+        Some("blue") => config::Channel::Blue,
+        _ => config::Channel::Luminance,
+    };
+    config_builder.channel(channel);
+    log::debug!("Channel: {channel:?}");
+
+    //get threshold and characters for the monochrome mode
+    if let Some(threshold) = matches.get_one::<f64>("threshold") {
+        log::debug!("Threshold: {threshold}");
+        config_builder.threshold(*threshold);
+        if let Some(on_char) = matches.get_one::<char>("on-char") {
+            log::debug!("On char: {on_char}");
+            config_builder.on_char(*on_char);
+        }
+        if let Some(off_char) = matches.get_one::<char>("off-char") {
+            log::debug!("Off char: {off_char}");
+            config_builder.off_char(*off_char);
+        }
     }
 
-    //get output file extension for specific output, default to plain text
-    if let Some(output_file) = matches.get_one::<PathBuf>("output-file") {
-        log::debug!("Output-file: {}", output_file.to_str().unwrap());
+    //get output file extensions for specific output, default to plain text
+    let output_files = matches
+        .get_many::<PathBuf>("output-file")
+        .map(|files| files.collect::<Vec<_>>())
+        .unwrap_or_default();
+    let output_extensions = if let Some(output_dir) = matches.get_one::<PathBuf>("output-dir") {
+        log::debug!("Output-dir: {}", output_dir.display());
 
-        //check file extension
-        let file_extension = output_file.extension().and_then(std::ffi::OsStr::to_str);
-        log::debug!("FileExtension: {:?}", file_extension);
+        //all files in the directory use the same extension
+        vec![matches
+            .get_one::<String>("output-extension")
+            .map(String::as_str)]
+    } else {
+        output_files
+            .iter()
+            .map(|output_file| {
+                log::debug!("Output-file: {}", output_file.display());
+                //check file extension
+                output_file.extension().and_then(std::ffi::OsStr::to_str)
+            })
+            .collect()
+    };
 
-        config_builder.target(match file_extension {
-            Some("html") | Some("htm") => {
-                log::debug!("Target: Html-File");
-                TargetType::HtmlFile
+    //css classes are only used for html files, the config ignores them for other targets
+    let css_classes = matches.get_flag("css-classes");
+    if css_classes
+        && !output_extensions
+            .iter()
+            .any(|extension| matches!(extension, Some("html" | "htm")))
+    {
+        log::warn!("The --css-classes argument is only used for .html output files");
+    }
+    config_builder.css_classes(css_classes);
+    log::debug!("Css classes: {css_classes}");
+
+    if let Some(html_background) = matches.get_one::<(u8, u8, u8)>("html-background") {
+        if !output_extensions
+            .iter()
+            .any(|extension| matches!(extension, Some("html" | "htm")))
+        {
+            log::warn!("The --html-background argument is only used for .html output files");
+        }
+        log::debug!("Html background: {html_background:?}");
+        config_builder.html_background(*html_background);
+    }
+
+    #[cfg(feature = "png")]
+    if matches.get_flag("transparent") {
+        if !output_extensions.contains(&Some("png")) {
+            log::warn!("The --transparent argument is only used for .png output files");
+        }
+        log::debug!("Transparent background: true");
+        config_builder.transparent_background(true);
+    }
+
+    let svg_font_family = matches.get_one::<String>("svg-font-family");
+    let svg_font_size = matches.get_one::<f32>("svg-font-size");
+    if svg_font_family.is_some() || svg_font_size.is_some() {
+        if !output_extensions.contains(&Some("svg")) {
+            log::warn!("The --svg-font-family and --svg-font-size arguments are only used for .svg output files");
+        }
+        let default = config::SvgFont::default();
+        let svg_font = config::SvgFont {
+            family: svg_font_family.cloned().unwrap_or(default.family),
+            size: svg_font_size.copied().unwrap_or(default.size),
+        };
+        log::debug!("Svg font: {svg_font:?}");
+        config_builder.svg_font(svg_font);
+    }
+
+    //the json format replaces the target of all outputs
+    let json = matches.get_one::<String>("format").map(String::as_str) == Some("json");
+    log::debug!("Json format: {json}");
+    let output_targets = output_extensions
+        .iter()
+        .map(|file_extension| {
+            if json {
+                TargetType::Json
+            } else {
+                target_for_extension(*file_extension, &matches)
             }
-            Some("ansi") | Some("ans") => {
-                log::debug!("Target: Ansi-File");
+        })
+        .collect::<Vec<_>>();
+    //the target of the first output, each output file will use its own target
+    config_builder.target(output_targets.first().copied().unwrap_or_else(|| {
+        if json {
+            TargetType::Json
+        } else {
+            log::debug!("Target: Shell");
+            TargetType::Shell
+        }
+    }));
 
-                //by definition ansi file must have colors, only the background color is optional
-                if matches.get_flag("no-color") {
-                    log::warn!("The --no-color argument conflicts with the target file type. Falling back to plain text file without colors.");
-                    TargetType::File
+    let mut config = config_builder.build();
+    if let Err(errors) = config.validate() {
+        let errors = errors
+            .iter()
+            //targets without background colors simply ignore the background
+            .filter(|error| **error != config::ArtemError::UnsupportedBackground)
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        if !errors.is_empty() {
+            fatal_error(&errors.join("\n"), Some(65));
+        }
+    }
+
+    //adjust the parameters using a preview of the first image, before converting all images
+    #[cfg(feature = "interactive")]
+    if matches.get_flag("interactive") {
+        if !std::io::stdin().is_terminal() || !std::io::stdout().is_terminal() {
+            fatal_error("The --interactive argument requires a terminal", Some(64));
+        }
+        let ramps = config::CharacterRamp::ALL.map(|ramp| ramp.characters());
+        let mut preview = interactive::Preview::new(config, &ramps);
+        let img = load_image(img_paths[0], auto_orient)
+            .unwrap_or_else(|(message, code)| fatal_error(&message, Some(code)));
+        match interactive::run_terminal(&img, &mut preview) {
+            Ok(true) => {
+                config = preview.config().clone();
+                log::info!("Using interactive parameters: {}", preview.status());
+                //the image is converted with the chosen settings, instead of loading it again
+                failures.preload(img_paths[0], img);
+            }
+            Ok(false) => {
+                log::info!("Interactive preview was cancelled");
+                return;
+            }
+            Err(err) => fatal_error(&format!("Interactive preview failed: {err}"), Some(74)),
+        }
+    }
+
+    //a SAUCE record is only used for ansi files
+    let sauce = matches.get_flag("sauce");
+    if sauce
+        && !output_extensions
+            .iter()
+            .any(|extension| matches!(extension, Some("ans" | "ansi")))
+    {
+        log::warn!("The --sauce argument is only used for .ans/.ansi output files");
+    }
+    let sauce_title = matches.get_one::<String>("sauce-title");
+
+    //sidecar files are written next to the output files
+    let sidecar = matches.get_flag("sidecar");
+    if sidecar && output_files.is_empty() && !matches.contains_id("output-dir") {
+        log::warn!("The --sidecar argument is only used for output files");
+    }
+
+    if let Some(output_dir) = matches.get_one::<PathBuf>("output-dir") {
+        //safe to unwrap, since both have default values
+        let extension = matches.get_one::<String>("output-extension").unwrap();
+        let jobs = *matches.get_one::<usize>("jobs").unwrap();
+        let (image_stats, bytes_count) = convert_to_dir(
+            &img_paths,
+            output_dir,
+            extension,
+            jobs,
+            &config,
+            &Extras {
+                sauce: sauce && config.target == TargetType::AnsiFile,
+                sauce_title,
+                sidecar,
+                auto_orient,
+            },
+            &failures,
+        );
+        if matches.get_flag("stats") {
+            print_stats(&image_stats, bytes_count, now);
+        }
+        failures.exit_on_failure();
+        return;
+    }
+
+    //show the images using the inline image protocol of the terminal, instead of converting them
+    let image_protocol = match matches
+        .get_one::<String>("image-protocol")
+        .map(|value| value.as_str())
+    {
+        Some("kitty") => Some(ImageProtocol::Kitty),
+        Some("iterm") => Some(ImageProtocol::Iterm),
+        Some("auto") if std::io::stdout().is_terminal() => ImageProtocol::detect(),
+        _ => None,
+    };
+    log::debug!("Image protocol: {image_protocol:?}");
+    if let Some(protocol) = image_protocol {
+        if matches.get_flag("stats") {
+            log::warn!("The --stats argument is only used for ascii art");
+        }
+        for path in &img_paths {
+            let Some(img) = failures.load_image(path, auto_orient) else {
+                continue;
+            };
+            if img.width() == 0 || img.height() == 0 {
+                continue;
+            }
+            if input.len() > 1 {
+                println!("==> {path} <==");
+            }
+            match artem::graphics::inline_image(img, &config, protocol) {
+                Ok(sequence) => println!("{sequence}"),
+                Err(err) => failures.report(&format!("Could not convert {path}: {err}"), 65),
+            }
+        }
+        failures.exit_on_failure();
+        return;
+    }
+
+    //animated gifs are played in the terminal, instead of only printing the first frame,
+    //when the output is piped or redirected, the screen clears and delays would only end up in the output
+    if output_files.is_empty() && img_paths.len() == 1 && std::io::stdout().is_terminal() {
+        if let Some(frames) = load_animation(img_paths[0]) {
+            play_animation(frames, &config, matches.get_flag("loop"));
+            return;
+        }
+    }
+    if matches.get_flag("loop") {
+        log::warn!("The --loop argument is only used for a single animated gif, which is printed to a terminal");
+    }
+
+    //convert the images only once, they are rendered for each output
+    let grids = img_paths
+        .iter()
+        .filter_map(|path| Some((*path, failures.load_image(path, auto_orient)?)))
+        .filter(|(_, img)| img.height() != 0 || img.width() != 0)
+        .filter_map(|(path, img)| match artem::convert_to_grid(img, &config) {
+            Ok(grid) => Some((path, grid)),
+            Err(err) => {
+                failures.report(&format!("Could not convert {path}: {err}"), 65);
+                None
+            }
+        })
+        .collect::<Vec<_>>();
+    let image_stats = grids
+        .iter()
+        .map(|(path, grid)| (*path, grid.stats()))
+        .collect::<Vec<_>>();
+
+    //when printing multiple images, each one starts with a header line containing its path
+    let render = |config: &config::Config, headers: bool| {
+        let mut output = grids
+            .iter()
+            .map(|(path, grid)| {
+                if headers {
+                    format!(
+                        "==> {path} <=={}{}",
+                        config.line_ending.as_str(),
+                        grid.render(config)
+                    )
                 } else {
-                    if !*artem::SUPPORTS_TRUECOLOR {
-                        log::warn!("truecolor is disabled, output file will not use truecolor chars")
-                    }
-                    TargetType::AnsiFile
+                    grid.render(config)
                 }
+            })
+            .collect::<String>();
+
+        remove_last_line_ending(&mut output, config.line_ending);
+        output
+    };
+
+    //create and write to output files
+    let bytes_count = if !output_files.is_empty() {
+        let mut bytes_count = 0;
+        for (output_file, target) in output_files.iter().zip(&output_targets) {
+            log::info!("Writing output to output file {}", output_file.display());
+            config.target = *target;
+            let mut bytes = match config.target {
+                #[cfg(feature = "png")]
+                TargetType::Png => png_bytes(
+                    &grids
+                        .iter()
+                        .map(|(_, grid)| grid.render_image(&config))
+                        .collect::<Vec<_>>(),
+                ),
+                _ => render(&config, false).into_bytes(),
+            };
+
+            let Ok(mut file) = File::create(output_file) else {
+                fatal_error("Could not create output file", Some(73));
+            };
+
+            if sauce && config.target == TargetType::AnsiFile {
+                let columns = image_stats
+                    .iter()
+                    .map(|(_, stats)| stats.columns)
+                    .max()
+                    .unwrap_or_default();
+                bytes.extend(sauce_record(
+                    sauce_title,
+                    output_file,
+                    &bytes,
+                    columns,
+                    config.border,
+                ));
             }
-            Some("svg") => {
-                log::debug!("Target: SVG");
-                TargetType::Svg
-            }
-            _ => {
-                log::debug!("Target: File");
 
-                if !matches.get_flag("no-color") {
-                    //warn user that output is not colored
-                    log::warn!("Filetype does not support using colors. For colored output file please use either .html or .ansi files");
+            log::trace!("Created output file");
+            let Ok(file_bytes_count) = file.write(&bytes) else {
+                fatal_error("Could not write to output file", Some(74));
+            };
+            log::info!("Written ascii chars to output file");
+            println!(
+                "Written {} bytes to {}",
+                file_bytes_count,
+                output_file.display()
+            );
+            bytes_count += file_bytes_count;
+
+            if sidecar {
+                if let Err(message) = write_sidecar(output_file, &image_stats, &config) {
+                    fatal_error(&message, Some(74));
                 }
-                TargetType::File
             }
-        });
+        }
+        bytes_count
     } else {
-        log::debug!("Target: Shell");
-        config_builder.target(TargetType::Shell);
+        //print the ascii img to the terminal
+        log::info!("Printing output");
+        let output = render(&config, input.len() > 1);
+        //end the last line with the same line ending as the other lines
+        print!("{}{}", output, config.line_ending.as_str());
+        output.len() + config.line_ending.as_str().len()
+    };
+
+    if matches.get_flag("stats") {
+        print_stats(&image_stats, bytes_count, now);
     }
+    failures.exit_on_failure();
+}
+
+/// Returns the content of an output file with the target of the config and the statistics of the conversion.
+///
+/// Text files end without a line ending after the last line, png files contain the drawn image.
+fn file_content(
+    img: image::DynamicImage,
+    config: &config::Config,
+) -> Result<(Vec<u8>, artem::Stats), config::ArtemError> {
+    #[cfg(feature = "png")]
+    if config.target == TargetType::Png {
+        let grid = artem::convert_to_grid(img, config)?;
+        return Ok((png_bytes(&[grid.render_image(config)]), grid.stats()));
+    }
+    let (mut output, stats) = artem::convert_with_stats(img, config)?;
+    //remove last linebreak, like for a single output file
+    remove_last_line_ending(&mut output, config.line_ending);
+    Ok((output.into_bytes(), stats))
+}
 
-    let config = config_builder.build();
-    let mut output = img_paths
+/// Returns the images placed below each other, encoded as a png file.
+#[cfg(feature = "png")]
+fn png_bytes(images: &[image::RgbaImage]) -> Vec<u8> {
+    let width = images
         .iter()
-        .map(|path| load_image(path))
-        .filter(|img| img.height() != 0 || img.width() != 0)
-        .map(|img| artem::convert(img, &config))
-        .collect::<String>();
+        .map(|image| image.width())
+        .max()
+        .unwrap_or_default();
+    let height = images.iter().map(|image| image.height()).sum();
+    let mut combined = image::RgbaImage::new(width, height);
+    let mut top = 0;
+    for image in images {
+        image::imageops::replace(&mut combined, image, 0, top);
+        top += image.height() as i64;
+    }
+    let mut bytes = Vec::new();
+    combined
+        .write_to(
+            &mut std::io::Cursor::new(&mut bytes),
+            image::ImageFormat::Png,
+        )
+        .expect("Encoding a png in memory does not fail");
+    bytes
+}
 
-    //remove last linebreak, we cannot use `.trim_end()` here
-    //as it may end up remove whitespace that is part of the image
-    if output.ends_with('\n') {
-        output.remove(output.len() - 1);
+/// Remove the line ending after the last line of the output.
+///
+/// We cannot use `.trim_end()` here, as it may end up remove whitespace that is part of the image.
+fn remove_last_line_ending(output: &mut String, line_ending: config::LineEnding) {
+    if let Some(length) = output.strip_suffix(line_ending.as_str()).map(str::len) {
+        output.truncate(length);
     }
+}
 
-    //create and write to output file
-    if let Some(output_file) = matches.get_one::<PathBuf>("output-file") {
-        log::info!("Writing output to output file");
+/// Returns the target for an output file with the given extension.
+///
+/// Files without a known extension are plain text files, unless colors are forced, then they are ansi files.
+/// Ansi files fall back to plain text files, if no colors should be used.
+fn target_for_extension(file_extension: Option<&str>, matches: &clap::ArgMatches) -> TargetType {
+    log::debug!("FileExtension: {:?}", file_extension);
 
-        let Ok(mut file) = File::create(output_file) else {
-            fatal_error("Could not create output file", Some(73));
-        };
+    match file_extension {
+        Some("html") | Some("htm") => {
+            log::debug!("Target: Html-File");
+            TargetType::HtmlFile
+        }
+        Some("ansi") | Some("ans") => {
+            log::debug!("Target: Ansi-File");
 
-        if config.target == TargetType::Svg {
-            //convert terminal text to svg
-            output = anstyle_svg::Term::new().render_svg(&output);
+            //by definition ansi file must have colors, only the background color is optional
+            if matches.get_flag("no-color") {
+                log::warn!("The --no-color argument conflicts with the target file type. Falling back to plain text file without colors.");
+                TargetType::File
+            } else {
+                if requested_color_mode(matches).unwrap_or_else(config::ColorMode::detect)
+                    != config::ColorMode::Truecolor
+                {
+                    log::warn!("truecolor is disabled, output file will not use truecolor chars")
+                }
+                TargetType::AnsiFile
+            }
+        }
+        Some("svg") => {
+            log::debug!("Target: SVG");
+            TargetType::Svg
+        }
+        Some("bbcode") => {
+            log::debug!("Target: BBCode");
+            TargetType::BbCode
         }
+        Some("json") => {
+            log::debug!("Target: JSON");
+            TargetType::Json
+        }
+        #[cfg(feature = "png")]
+        Some("png") => {
+            log::debug!("Target: PNG");
+            TargetType::Png
+        }
+        _ if matches.get_flag("force-color") && !matches.get_flag("no-color") => {
+            log::debug!("Target: Ansi-File, since colors are forced");
+            TargetType::AnsiFile
+        }
+        _ => {
+            log::debug!("Target: File");
 
-        log::trace!("Created output file");
-        let Ok(bytes_count) = file.write(output.as_bytes()) else {
-            fatal_error("Could not write to output file", Some(74));
-        };
-        log::info!("Written ascii chars to output file");
-        println!("Written {} bytes to {}", bytes_count, output_file.display())
-    } else {
-        //print the ascii img to the terminal
-        log::info!("Printing output");
-        println!("{}", output);
+            if !matches.get_flag("no-color") {
+                //warn user that output is not colored
+                log::warn!("Filetype does not support using colors. For colored output file please use either .html or .ansi files, or --force-color");
+            }
+            TargetType::File
+        }
+    }
+}
+
+/// Print the statistics of the converted images to stderr.
+///
+/// They are printed to stderr, so the statistics do not end up in the ascii art.
+fn print_stats(image_stats: &[(&String, artem::Stats)], bytes_count: usize, start: Instant) {
+    for (path, stats) in image_stats {
+        eprintln!("{path}:");
+        eprintln!(
+            "  Source dimensions: {}x{}",
+            stats.source_width, stats.source_height
+        );
+        eprintln!("  Columns: {}", stats.columns);
+        eprintln!("  Rows: {}", stats.rows);
+        eprintln!("  Tile size: {}x{}", stats.tile_width, stats.tile_height);
+        eprintln!("  Distinct colors: {}", stats.distinct_colors);
     }
+    eprintln!("Bytes written: {bytes_count}");
+    eprintln!("Elapsed time: {} ms", start.elapsed().as_millis());
+}
+
+/// Additional data, which is written together with the output files.
+struct Extras<'a> {
+    /// Whether a SAUCE record is appended to the output files.
+    sauce: bool,
+    /// The title of the SAUCE record, defaults to the name of the output file.
+    sauce_title: Option<&'a String>,
+    /// Whether a sidecar file is written next to the output files.
+    sidecar: bool,
+    /// Whether the images are rotated according to their EXIF orientation.
+    auto_orient: bool,
 }
 
+/// Errors of single inputs, which do not abort the conversion of the other inputs.
+///
+/// The errors are logged when they occur and the program exits with the code of the first error
+/// after all other inputs were converted, see [`Failures::exit_on_failure`].
+struct Failures {
+    /// Whether the first error exits the program immediately, like a [`fatal_error`].
+    fail_fast: bool,
+    /// Exit codes of the errors, in the order they occurred.
+    codes: Mutex<Vec<i32>>,
+    /// An image, which was already loaded, with its path, see [`Failures::preload`].
+    preloaded: Mutex<Option<(String, image::DynamicImage)>>,
+}
+
+impl Failures {
+    fn new(fail_fast: bool) -> Self {
+        Self {
+            fail_fast,
+            codes: Mutex::new(Vec::new()),
+            preloaded: Mutex::new(None),
+        }
+    }
+
+    /// Keep an image, which was already loaded, so the next [`Failures::load_image`] of the same path returns it.
+    ///
+    /// This avoids reading the standard input twice, which fails, or downloading a url again.
+    #[cfg(feature = "interactive")]
+    fn preload(&self, path: &str, img: image::DynamicImage) {
+        *self
+            .preloaded
+            .lock()
+            .expect("Failed to lock preloaded image") = Some((path.to_string(), img));
+    }
+
+    /// Report the error of a single input.
+    fn report(&self, message: &str, code: i32) {
+        if self.fail_fast {
+            fatal_error(message, Some(code));
+        }
+        log::error!("{}", message);
+        self.codes
+            .lock()
+            .expect("Failed to lock failures")
+            .push(code);
+    }
+
+    /// Return the image from the specified path, like [`load_image`], or report the error.
+    fn load_image(&self, path: &str, auto_orient: bool) -> Option<image::DynamicImage> {
+        {
+            let mut preloaded = self
+                .preloaded
+                .lock()
+                .expect("Failed to lock preloaded image");
+            if preloaded
+                .as_ref()
+                .is_some_and(|(preloaded_path, _)| preloaded_path == path)
+            {
+                return preloaded.take().map(|(_, img)| img);
+            }
+        }
+        load_image(path, auto_orient)
+            .map_err(|(message, code)| self.report(&message, code))
+            .ok()
+    }
+
+    /// Exit the program with the code of the first error, if any input failed.
+    fn exit_on_failure(&self) {
+        let codes = self.codes.lock().expect("Failed to lock failures");
+        if let Some(code) = codes.first() {
+            fatal_error(
+                &format!("{} of the inputs could not be converted", codes.len()),
+                Some(*code),
+            );
+        }
+    }
+}
+
+/// Convert each image to its own file in the output directory.
+///
+/// Instead of collecting the output of all images, each image is loaded, converted and written
+/// to `<output_dir>/<file name>.<extension>` one after another, see [`output_file_names`] for the names of the files.
+/// Each worker holds a single image and its output in memory, which are dropped before the worker loads the next image,
+/// so at most `jobs` images are held in memory at the same time, regardless of the number of inputs.
+///
+/// Returns the statistics of each converted image as well as the total number of written bytes.
+fn convert_to_dir<'a>(
+    img_paths: &[&'a String],
+    output_dir: &Path,
+    extension: &str,
+    jobs: usize,
+    config: &config::Config,
+    extras: &Extras,
+    failures: &Failures,
+) -> (Vec<(&'a String, artem::Stats)>, usize) {
+    if let Err(err) = std::fs::create_dir_all(output_dir) {
+        fatal_error(
+            &format!(
+                "Could not create output directory {}: {err}",
+                output_dir.display()
+            ),
+            Some(73),
+        );
+    }
+
+    //the names are chosen before the conversion, so they do not depend on the order in which the workers finish
+    let file_names = output_file_names(img_paths);
+    //index of the next image, which will be converted by one of the workers
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new(Vec::with_capacity(img_paths.len()));
+    //number of images, which are currently held by the workers, and the largest number at any time
+    let held = AtomicUsize::new(0);
+    let peak_held = AtomicUsize::new(0);
+
+    std::thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, img_paths.len().max(1)) {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(path) = img_paths.get(index) else {
+                    break;
+                };
+
+                //declared before the image, so it is dropped after the image and its output
+                let _held = HeldImage::new(&held, &peak_held);
+                let Some(img) = failures.load_image(path, extras.auto_orient) else {
+                    continue;
+                };
+                if img.height() == 0 && img.width() == 0 {
+                    continue;
+                }
+                let (mut bytes, stats) = match file_content(img, config) {
+                    Ok(converted) => converted,
+                    Err(err) => {
+                        failures.report(&format!("Could not convert {path}: {err}"), 65);
+                        continue;
+                    }
+                };
+
+                let output_file = output_dir.join(format!("{}.{extension}", file_names[index]));
+                if extras.sauce {
+                    bytes.extend(sauce_record(
+                        extras.sauce_title,
+                        &output_file,
+                        &bytes,
+                        stats.columns,
+                        config.border,
+                    ));
+                }
+                if let Err(err) = std::fs::write(&output_file, &bytes) {
+                    failures.report(
+                        &format!(
+                            "Could not write to output file {}: {err}",
+                            output_file.display()
+                        ),
+                        74,
+                    );
+                    continue;
+                }
+                println!("Written {} bytes to {}", bytes.len(), output_file.display());
+                //the output file was written, so it is still counted, if only its sidecar file fails
+                if extras.sidecar {
+                    if let Err(message) = write_sidecar(&output_file, &[(*path, stats)], config) {
+                        failures.report(&message, 74);
+                    }
+                }
+
+                //only the small statistics are kept, the output is dropped here
+                results.lock().expect("Failed to lock results").push((
+                    index,
+                    *path,
+                    stats,
+                    bytes.len(),
+                ));
+            });
+        }
+    });
+
+    log::info!(
+        "At most {} images were held in memory at the same time",
+        peak_held.into_inner()
+    );
+
+    let mut results = results.into_inner().expect("Failed to lock results");
+    //keep the order of the inputs, independent of which worker finished first
+    results.sort_unstable_by_key(|(index, ..)| *index);
+    let bytes_count = results.iter().map(|(.., bytes)| bytes).sum();
+    (
+        results
+            .into_iter()
+            .map(|(_, path, stats, _)| (path, stats))
+            .collect(),
+        bytes_count,
+    )
+}
+
+/// Counts an image of [`convert_to_dir`] as held in memory, until it is dropped.
+struct HeldImage<'a> {
+    held: &'a AtomicUsize,
+}
+
+impl<'a> HeldImage<'a> {
+    /// Increase the number of held images and update the largest number of held images.
+    fn new(held: &'a AtomicUsize, peak_held: &AtomicUsize) -> Self {
+        let count = held.fetch_add(1, Ordering::SeqCst) + 1;
+        peak_held.fetch_max(count, Ordering::SeqCst);
+        Self { held }
+    }
+}
+
+impl Drop for HeldImage<'_> {
+    fn drop(&mut self) {
+        self.held.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Returns the name of the output file of each input in the output directory, without the extension.
+///
+/// The file name of the input is used, this also works for most urls. Inputs with the same name, for example
+/// from different directories, would overwrite each other, so all but the first one get a number appended, like `image_2`.
+/// The names are compared ignoring the case, since some file systems do not distinguish them.
+fn output_file_names(img_paths: &[&String]) -> Vec<String> {
+    let mut used = HashSet::new();
+    img_paths
+        .iter()
+        .enumerate()
+        .map(|(index, path)| {
+            let stem = if path.as_str() == STDIN_PATH {
+                String::from("stdin")
+            } else {
+                Path::new(path.as_str()).file_stem().map_or_else(
+                    || format!("image_{index}"),
+                    |stem| stem.to_string_lossy().to_string(),
+                )
+            };
+            let mut name = stem.clone();
+            let mut number = 1;
+            while !used.insert(name.to_lowercase()) {
+                number += 1;
+                name = format!("{stem}_{number}");
+            }
+            if name != stem {
+                log::warn!(
+                    "Another input is also named {stem}, {path} is written to {name} instead"
+                );
+            }
+            name
+        })
+        .collect()
+}
+
+/// Returns the SAUCE record for the ansi art, which will be written to the output file.
+///
+/// If no title is given, the name of the output file is used instead. The number of rows is counted from
+/// the output, the columns include the border.
+fn sauce_record(
+    title: Option<&String>,
+    output_file: &Path,
+    output: &[u8],
+    columns: u32,
+    border: bool,
+) -> Vec<u8> {
+    let title = title.cloned().unwrap_or_else(|| {
+        output_file
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default()
+    });
+    let columns = if border { columns + 2 } else { columns };
+    let rows = output.split(|byte| *byte == b'\n').count();
+    log::debug!("SAUCE record: {title}, {columns}x{rows}");
+    artem::sauce::Sauce::new(
+        &title,
+        columns.try_into().unwrap_or(u16::MAX),
+        rows.try_into().unwrap_or(u16::MAX),
+    )
+    .to_bytes(output.len().try_into().unwrap_or(u32::MAX))
+}
+
+/// Write the sidecar file of the output file, which is named like the output file with an additional `.meta` extension.
+///
+/// # Errors
+/// Returns the error message, if the sidecar file could not be written.
+fn write_sidecar(
+    output_file: &Path,
+    image_stats: &[(&String, artem::Stats)],
+    config: &config::Config,
+) -> Result<(), String> {
+    let mut sidecar_file = output_file.as_os_str().to_owned();
+    sidecar_file.push(".meta");
+    let sidecar_file = PathBuf::from(sidecar_file);
+    log::info!("Writing sidecar file {}", sidecar_file.display());
+    std::fs::write(&sidecar_file, sidecar_content(image_stats, config)).map_err(|err| {
+        format!(
+            "Could not write to sidecar file {}: {err}",
+            sidecar_file.display()
+        )
+    })
+}
+
+/// Returns the color mode of the `--color-mode` argument, if it is present.
+fn requested_color_mode(matches: &clap::ArgMatches) -> Option<config::ColorMode> {
+    matches
+        .get_one::<String>("color-mode")
+        .map(|mode| match mode.as_str() {
+            "truecolor" => config::ColorMode::Truecolor,
+            "256" => config::ColorMode::Ansi256,
+            _ => config::ColorMode::Ansi16,
+        })
+}
+
+/// Returns the name of the color mode, as used in the capabilities report and sidecar files.
+///
+/// No color mode means that the output is not colored.
+fn color_mode_name(color_mode: Option<config::ColorMode>) -> &'static str {
+    match color_mode {
+        None => "none",
+        Some(config::ColorMode::Truecolor) => "truecolor",
+        Some(config::ColorMode::Ansi256) => "ansi256",
+        Some(config::ColorMode::Ansi16) => "ansi",
+    }
+}
+
+/// Returns a report of the detected terminal capabilities, one per line.
+///
+/// The color mode is the one that is used for the terminal, which is `none` if color is disabled.
+fn capabilities_report(color_mode: Option<config::ColorMode>) -> String {
+    let env_var = |name| std::env::var(name).unwrap_or_else(|_| "not set".to_string());
+    let width = terminal_size::terminal_size().map_or_else(
+        || "unknown (not a terminal)".to_string(),
+        |(width, _)| width.0.to_string(),
+    );
+    format!(
+        "Truecolor support: {}\n256 color support: {}\nTerminal width: {width}\nTERM: {}\nCOLORTERM: {}\nTERM_PROGRAM: {}\nColor mode: {}\nImage protocol: {}\n",
+        if *artem::SUPPORTS_TRUECOLOR {
+            "yes"
+        } else {
+            "no"
+        },
+        if artem::supports_256color() {
+            "yes"
+        } else {
+            "no"
+        },
+        env_var("TERM"),
+        env_var("COLORTERM"),
+        env_var("TERM_PROGRAM"),
+        color_mode_name(color_mode),
+        match ImageProtocol::detect() {
+            Some(ImageProtocol::Kitty) => "kitty",
+            Some(ImageProtocol::Iterm) => "iterm",
+            None => "none",
+        },
+    )
+}
+
+/// Returns the content of a sidecar file as JSON.
+///
+/// The columns and rows include the border, so they match the dimensions of the written ascii art.
+/// If the output contains multiple images, they are placed below each other, so the columns are the
+/// maximum and the rows the sum of all images.
+fn sidecar_content(image_stats: &[(&String, artem::Stats)], config: &config::Config) -> String {
+    let border = if config.border { 2 } else { 0 };
+    let color_mode = if !config.color || config.target == TargetType::File {
+        None
+    } else if matches!(
+        config.target,
+        TargetType::HtmlFile
+            | TargetType::BbCode
+            | TargetType::Svg
+            | TargetType::Json
+            | TargetType::Png
+    ) {
+        Some(config::ColorMode::Truecolor)
+    } else {
+        Some(config.resolved_color_mode())
+    };
+    let background = color_mode.is_some()
+        && config.background_color
+        && !matches!(config.target, TargetType::AnsiFile | TargetType::BbCode);
+    let color_mode = color_mode_name(color_mode);
+
+    let images = image_stats
+        .iter()
+        .map(|(path, stats)| {
+            format!(
+                "{{\"path\":\"{}\",\"source_width\":{},\"source_height\":{},\"columns\":{},\"rows\":{}}}",
+                path.replace('\\', "\\\\").replace('"', "\\\""),
+                stats.source_width,
+                stats.source_height,
+                stats.columns + border,
+                stats.rows + border
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+    let columns = image_stats
+        .iter()
+        .map(|(_, stats)| stats.columns + border)
+        .max()
+        .unwrap_or_default();
+    let rows = image_stats
+        .iter()
+        .map(|(_, stats)| stats.rows + border)
+        .sum::<u32>();
+
+    format!(
+        "{{\"columns\":{columns},\"rows\":{rows},\"color_mode\":\"{color_mode}\",\"background\":{background},\"images\":[{images}]}}\n"
+    )
+}
+
+/// Returns true, if the input is a http or https URL.
+///
+/// # Examples
+/// ```
+/// assert!(is_url("https://example.com/image.png"));
+/// assert!(!is_url("http_image.png"));
+/// ```
+#[cfg(feature = "web_image")]
+fn is_url(input: &str) -> bool {
+    input.starts_with("http://") || input.starts_with("https://")
+}
+
+/// Path, which is used to read the image from the standard input.
+const STDIN_PATH: &str = "-";
+
 /// Return the image from the specified path.
 ///
 /// Loads the image from the specified path.
 /// If the path is a url and the web_image feature is enabled,
 /// the image will be downloaded and opened from memory.
+/// If the path is `-`, the image is read from the standard input instead.
+/// In both cases the format is detected from the bytes of the image.
+/// If `auto_orient` is true, the image is rotated and flipped according to its EXIF orientation.
+///
+/// # Errors
+/// Returns the error message together with the exit code, if the image can not be loaded,
+/// so the other images can still be converted.
 ///
 /// # Examples
 /// ```
-/// let image = load_image("../examples/abraham_lincoln.jpg")
+/// let image = load_image("../examples/abraham_lincoln.jpg", true).unwrap()
 /// ```
-fn load_image(path: &str) -> image::DynamicImage {
+fn load_image(path: &str, auto_orient: bool) -> Result<image::DynamicImage, (String, i32)> {
+    if path == STDIN_PATH {
+        log::info!("Reading image from stdin");
+        let mut bytes: Vec<u8> = Vec::new();
+        if let Err(err) = std::io::stdin().lock().read_to_end(&mut bytes) {
+            return Err((format!("Failed to read from stdin: {err}"), 66));
+        }
+        if bytes.is_empty() {
+            return Err((String::from("No image data was received from stdin"), 66));
+        }
+
+        log::debug!("Opening image from stdin with {} bytes", bytes.len());
+        return load_source(artem::ImageSource::from(bytes.as_slice()), auto_orient);
+    }
+
     #[cfg(feature = "web_image")]
-    if path.starts_with("http") {
+    if is_url(path) {
         log::info!("Started to download image from: {}", path);
         let now = std::time::Instant::now();
-        let Ok(resp) = ureq::get(path).call() else {
-            fatal_error(
-                &format!("Failed to load image bytes from {}", path),
-                Some(66),
-            );
+        //redirects are followed by ureq, error status codes are returned as an error
+        let resp = match ureq::get(path).call() {
+            Ok(resp) => resp,
+            Err(ureq::Error::Status(code, resp)) => {
+                return Err((
+                    format!(
+                        "Failed to load image bytes from {}, the server responded with {} {}",
+                        path,
+                        code,
+                        resp.status_text()
+                    ),
+                    66,
+                ))
+            }
+            Err(err) => {
+                return Err((
+                    format!("Failed to load image bytes from {}: {}", path, err),
+                    66,
+                ))
+            }
         };
+        //other successful responses, like 204 No Content, do not contain an image
+        if resp.status() != 200 {
+            return Err((
+                format!(
+                    "Failed to load image bytes from {}, the server responded with {} {}",
+                    path,
+                    resp.status(),
+                    resp.status_text()
+                ),
+                66,
+            ));
+        }
+        //do not try to decode a web page as an image
+        if resp.content_type() == "text/html" {
+            return Err((
+                format!(
+                    "Failed to load image bytes from {}, the server responded with a web page instead of an image",
+                    path
+                ),
+                66,
+            ));
+        }
 
         //get bytes of the images
         let mut bytes: Vec<u8> = Vec::new();
-        resp.into_reader()
-            .read_to_end(&mut bytes)
-            .expect("Failed to read bytes");
+        if let Err(err) = resp.into_reader().read_to_end(&mut bytes) {
+            return Err((
+                format!("Failed to load image bytes from {}: {}", path, err),
+                66,
+            ));
+        }
         log::info!("Downloading took {:3} ms", now.elapsed().as_millis());
 
         log::debug!("Opening downloaded image from memory");
-        return match image::load_from_memory(&bytes) {
-            Ok(img) => img,
-            Err(err) => fatal_error(&err.to_string(), Some(66)),
-        };
+        return load_source(artem::ImageSource::from(bytes.as_slice()), auto_orient);
     }
 
     log::info!("Opening image");
-    match image::open(path) {
-        Ok(img) => img,
+    load_source(artem::ImageSource::from(path), auto_orient)
+}
+
+/// Load the image from the source, using its EXIF orientation if `auto_orient` is true.
+///
+/// # Errors
+/// Returns the error message together with the exit code, if the image can not be loaded.
+#[cfg_attr(not(feature = "exif"), allow(unused_variables))]
+fn load_source(
+    source: artem::ImageSource,
+    auto_orient: bool,
+) -> Result<image::DynamicImage, (String, i32)> {
+    #[cfg(feature = "exif")]
+    let result = if auto_orient {
+        source.load_oriented()
+    } else {
+        source.load()
+    };
+    #[cfg(not(feature = "exif"))]
+    let result = source.load();
+    result.map_err(|err| (err.to_string(), exit_code(&err)))
+}
+
+/// Returns the inputs, where glob patterns like `*.png` are replaced by the matching files.
+///
+/// Most shells already expand the patterns, but for example the Windows command prompt does not.
+/// Inputs, which exist as a file or are no pattern, are kept as they are. The matches of a pattern are sorted by their path.
+/// Exits the program, if a pattern is invalid or does not match any files.
+fn expand_globs<'a>(input: impl Iterator<Item = &'a String>) -> Vec<String> {
+    let mut expanded = Vec::new();
+    for value in input {
+        //urls may contain a query, which is not a pattern
+        #[cfg(feature = "web_image")]
+        if is_url(value) {
+            expanded.push(value.clone());
+            continue;
+        }
+        if !value.contains(['*', '?', '[']) || Path::new(value).exists() {
+            expanded.push(value.clone());
+            continue;
+        }
+        let matches = match glob::glob(value) {
+            Ok(paths) => paths
+                .filter_map(Result::ok)
+                .filter(|path| path.is_file())
+                .map(|path| path.to_string_lossy().to_string())
+                .collect::<Vec<_>>(),
+            Err(err) => fatal_error(&format!("{value} is not a valid pattern: {err}"), Some(64)),
+        };
+        log::debug!("Pattern {} matches {} files", value, matches.len());
+        if matches.is_empty() {
+            fatal_error(&format!("No files match {value}"), Some(66));
+        }
+        expanded.extend(matches);
+    }
+    expanded
+}
+
+/// Returns the exit code for the error.
+///
+/// Errors while loading the image use the same code as a missing input file,
+/// all other errors are caused by invalid arguments or images.
+/// The codes are taken from sysexits, see [`fatal_error`].
+fn exit_code(error: &config::ArtemError) -> i32 {
+    match error {
+        //EX_NOINPUT
+        config::ArtemError::Io(_)
+        | config::ArtemError::UnsupportedFormat(_)
+        | config::ArtemError::MissingFormatFeature(..) => 66,
+        //EX_DATAERR
+        _ => 65,
+    }
+}
+
+/// Return the frames of the animated gif at the specified path.
+///
+/// Returns `None`, if the path is not a gif file or if the gif only has a single frame,
+/// in which case it can be loaded like any other image using [`load_image`].
+/// Only local files are checked, URLs and the standard input always use the first frame.
+fn load_animation(path: &str) -> Option<Vec<image::Frame>> {
+    use image::AnimationDecoder;
+
+    let reader = image::io::Reader::open(path)
+        .and_then(|reader| reader.with_guessed_format())
+        .ok()?;
+    if reader.format() != Some(image::ImageFormat::Gif) {
+        return None;
+    }
+
+    log::info!("Decoding gif frames");
+    let frames = image::codecs::gif::GifDecoder::new(reader.into_inner())
+        .and_then(|decoder| decoder.into_frames().collect_frames());
+    match frames {
+        Ok(frames) if frames.len() > 1 => {
+            log::debug!("Gif has {} frames", frames.len());
+            Some(frames)
+        }
+        Ok(_) => None,
         Err(err) => fatal_error(&err.to_string(), Some(66)),
     }
 }
 
+/// Play the frames of an animation in the terminal.
+///
+/// The screen is cleared before each frame, which is shown for the delay of the frame.
+/// If `repeat` is true, the animation is repeated until the program is stopped.
+fn play_animation(frames: Vec<image::Frame>, config: &config::Config, repeat: bool) {
+    let delays = frames
+        .iter()
+        .map(|frame| match std::time::Duration::from(frame.delay()) {
+            //like browsers, show frames without a delay for 100 ms instead of skipping them
+            delay if delay.is_zero() => std::time::Duration::from_millis(100),
+            delay => delay,
+        })
+        .collect::<Vec<_>>();
+    let frames = artem::convert_frames(
+        frames
+            .into_iter()
+            .map(|frame| image::DynamicImage::ImageRgba8(frame.into_buffer()))
+            .collect(),
+        config,
+    )
+    .unwrap_or_else(|err| {
+        fatal_error(&format!("Could not convert the animation: {err}"), Some(65))
+    });
+
+    log::info!("Playing animation");
+    let mut stdout = std::io::stdout().lock();
+    loop {
+        for (frame, delay) in frames.iter().zip(&delays) {
+            //clear the screen and move the cursor to the top left corner
+            if write!(stdout, "\x1b[2J\x1b[H{frame}")
+                .and_then(|_| stdout.flush())
+                .is_err()
+            {
+                //stdout was closed, for example by a pipe
+                return;
+            }
+            std::thread::sleep(*delay);
+        }
+        if !repeat {
+            break;
+        }
+    }
+}
+
 /// Function for fatal errors.
 ///
 /// A fatal error is an error, from which the program can no recover, meaning the only option left is to print
@@ -0,0 +1,370 @@
+//! Extraction of a color palette from an image and matching colors to a palette.
+//!
+//! The palette is calculated using k-means clustering of the pixel colors. The initial cluster centers
+//! are chosen randomly using a seed, so the same image and seed always result in the same palette.
+
+use image::{DynamicImage, GenericImageView};
+
+use crate::config::DistanceMetric;
+
+/// The maximum number of k-means iterations, which keeps the runtime deterministic.
+pub const MAX_ITERATIONS: usize = 16;
+
+/// A color palette extracted from an image.
+///
+/// See [`extract_palette`] for more information.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Palette {
+    /// The colors of the palette, sorted by the number of pixels they represent, the most common first.
+    pub colors: Vec<(u8, u8, u8)>,
+    /// The number of k-means iterations, which were needed. This is at most [`MAX_ITERATIONS`].
+    pub iterations: usize,
+}
+
+/// Extract a palette with at most the given number of colors from the image.
+///
+/// The colors are clustered using k-means, with the initial centers being chosen by k-means++,
+/// which is seeded with the given seed. Identical images and seeds always result in identical palettes.
+/// The clustering stops, when the clusters no longer change, or after [`MAX_ITERATIONS`] iterations.
+/// Large images are downscaled first and fully transparent pixels are ignored.
+/// The palette can have less colors than requested, if the image does not have enough distinct colors.
+///
+/// # Examples
+/// ```
+/// use artem::palette::extract_palette;
+///
+/// let img = image::DynamicImage::ImageRgb8(image::RgbImage::from_fn(10, 10, |x, _| {
+///     if x < 5 {
+///         image::Rgb([255, 0, 0])
+///     } else {
+///         image::Rgb([0, 0, 255])
+///     }
+/// }));
+/// let palette = extract_palette(&img, 2, 42);
+/// assert_eq!(2, palette.colors.len());
+/// assert!(palette.colors.contains(&(255, 0, 0)));
+/// assert!(palette.colors.contains(&(0, 0, 255)));
+/// ```
+pub fn extract_palette(image: &DynamicImage, colors: usize, seed: u64) -> Palette {
+    let image = if image.width() > 128 || image.height() > 128 {
+        image.thumbnail(128, 128)
+    } else {
+        image.clone()
+    };
+    let pixels = image
+        .pixels()
+        .filter(|(_, _, pixel)| pixel.0[3] != 0)
+        .map(|(_, _, pixel)| [pixel.0[0] as f32, pixel.0[1] as f32, pixel.0[2] as f32])
+        .collect::<Vec<_>>();
+
+    if pixels.is_empty() || colors == 0 {
+        return Palette {
+            colors: Vec::new(),
+            iterations: 0,
+        };
+    }
+
+    let mut rng = SplitMix64::new(seed);
+    let mut centers = initial_centers(&pixels, colors, &mut rng);
+    let mut assignments = vec![usize::MAX; pixels.len()];
+    let mut iterations = 0;
+
+    while iterations < MAX_ITERATIONS {
+        iterations += 1;
+
+        //assign each pixel to the nearest center
+        let mut changed = false;
+        for (pixel, assignment) in pixels.iter().zip(assignments.iter_mut()) {
+            let nearest = nearest_center(pixel, &centers);
+            if nearest != *assignment {
+                *assignment = nearest;
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+
+        //move each center to the mean of its pixels
+        let mut sums = vec![([0f32; 3], 0usize); centers.len()];
+        for (pixel, assignment) in pixels.iter().zip(&assignments) {
+            let (sum, count) = &mut sums[*assignment];
+            for channel in 0..3 {
+                sum[channel] += pixel[channel];
+            }
+            *count += 1;
+        }
+        for (center, (sum, count)) in centers.iter_mut().zip(sums) {
+            //keep empty clusters at their position
+            if count > 0 {
+                *center = sum.map(|channel| channel / count as f32);
+            }
+        }
+    }
+
+    //sort by the number of pixels, the first center wins on equal counts
+    let mut counts = vec![0usize; centers.len()];
+    for pixel in &pixels {
+        counts[nearest_center(pixel, &centers)] += 1;
+    }
+    let mut order = (0..centers.len())
+        .filter(|index| counts[*index] > 0)
+        .collect::<Vec<_>>();
+    order.sort_by(|a, b| counts[*b].cmp(&counts[*a]).then(a.cmp(b)));
+
+    Palette {
+        colors: order
+            .into_iter()
+            .map(|index| {
+                let [red, green, blue] = centers[index].map(|channel| channel.round() as u8);
+                (red, green, blue)
+            })
+            .collect(),
+        iterations,
+    }
+}
+
+/// Choose the initial centers using k-means++.
+///
+/// The first center is a random pixel, each following center is chosen with a probability proportional
+/// to its squared distance to the nearest, already chosen center.
+fn initial_centers(pixels: &[[f32; 3]], colors: usize, rng: &mut SplitMix64) -> Vec<[f32; 3]> {
+    let mut centers = vec![pixels[rng.next_below(pixels.len())]];
+    let mut distances = pixels
+        .iter()
+        .map(|pixel| distance(pixel, &centers[0]))
+        .collect::<Vec<_>>();
+
+    while centers.len() < colors {
+        let total = distances
+            .iter()
+            .map(|distance| *distance as f64)
+            .sum::<f64>();
+        //all pixels are already represented by a center
+        if total == 0f64 {
+            break;
+        }
+
+        let mut target = rng.next_f64() * total;
+        let index = distances
+            .iter()
+            .position(|distance| {
+                target -= *distance as f64;
+                target < 0f64
+            })
+            .unwrap_or(pixels.len() - 1);
+
+        let center = pixels[index];
+        for (distance, pixel) in distances.iter_mut().zip(pixels) {
+            *distance = distance.min(self::distance(pixel, &center));
+        }
+        centers.push(center);
+    }
+    centers
+}
+
+/// Returns the index of the color of the palette, which is nearest to the target color.
+///
+/// The distance is calculated using the given metric, see [`DistanceMetric`]. If multiple colors
+/// have the same distance, the first of them is used. This is also used to find the nearest of the 16 basic ansi colors.
+///
+/// # Panics
+/// Panics, if the palette is empty.
+///
+/// # Examples
+/// ```
+/// use artem::{config::DistanceMetric, palette::nearest_color};
+///
+/// let palette = [(0, 0, 0), (255, 255, 255)];
+/// assert_eq!(0, nearest_color((100, 100, 100), &palette, DistanceMetric::Rgb));
+/// assert_eq!(1, nearest_color((160, 160, 160), &palette, DistanceMetric::Rgb));
+/// ```
+pub fn nearest_color(
+    target: (u8, u8, u8),
+    palette: &[(u8, u8, u8)],
+    metric: DistanceMetric,
+) -> usize {
+    assert!(!palette.is_empty(), "The palette must not be empty");
+    nearest_center(
+        &color_point(target, metric),
+        &palette_points(palette, metric),
+    )
+}
+
+/// Returns the colors of the palette as points of the metric, so they can be reused for multiple colors.
+pub(crate) fn palette_points(palette: &[(u8, u8, u8)], metric: DistanceMetric) -> Vec<[f32; 3]> {
+    palette
+        .iter()
+        .map(|color| color_point(*color, metric))
+        .collect()
+}
+
+/// Returns the color as a point, where the squared euclidean distance is the distance of the metric.
+pub(crate) fn color_point((red, green, blue): (u8, u8, u8), metric: DistanceMetric) -> [f32; 3] {
+    match metric {
+        DistanceMetric::Rgb => [red as f32, green as f32, blue as f32],
+        DistanceMetric::CieLab => crate::target::ansi::rgb_to_lab([red, green, blue]),
+    }
+}
+
+/// Returns the index of the center nearest to the pixel.
+pub(crate) fn nearest_center(pixel: &[f32; 3], centers: &[[f32; 3]]) -> usize {
+    centers
+        .iter()
+        .enumerate()
+        .min_by(|a, b| distance(pixel, a.1).total_cmp(&distance(pixel, b.1)))
+        .map(|(index, _)| index)
+        .expect("There is always at least one center")
+}
+
+/// Returns the squared euclidean distance between two colors.
+fn distance(a: &[f32; 3], b: &[f32; 3]) -> f32 {
+    (0..3)
+        .map(|channel| (a[channel] - b[channel]).powi(2))
+        .sum()
+}
+
+/// A small, seedable pseudo random number generator.
+///
+/// It is not suitable for cryptographic purposes, but it is fast and returns
+/// the same numbers for the same seed on all platforms.
+/// The algorithm is from <https://prng.di.unimi.it/splitmix64.c>
+#[derive(Debug, Clone)]
+pub(crate) struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    /// Create a new generator with the given seed.
+    pub(crate) fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Returns the next random number.
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut value = self.state;
+        value = (value ^ (value >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        value = (value ^ (value >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        value ^ (value >> 31)
+    }
+
+    /// Returns a random number between `0.0` (inclusive) and `1.0` (exclusive).
+    pub(crate) fn next_f64(&mut self) -> f64 {
+        //use the upper 53 bits, which fit exactly into the mantissa
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Returns a random number between `0` (inclusive) and `bound` (exclusive).
+    pub(crate) fn next_below(&mut self, bound: usize) -> usize {
+        (self.next_f64() * bound as f64) as usize
+    }
+}
+
+#[cfg(test)]
+mod test_nearest_color {
+    use super::*;
+
+    #[test]
+    fn mid_gray_black_white() {
+        let palette = [(0, 0, 0), (255, 255, 255)];
+        assert_eq!(
+            0,
+            nearest_color((127, 127, 127), &palette, DistanceMetric::Rgb)
+        );
+        assert_eq!(
+            1,
+            nearest_color((128, 128, 128), &palette, DistanceMetric::Rgb)
+        );
+        //the lightness of CIELAB is not linear, so mid gray is nearer to white
+        assert_eq!(
+            1,
+            nearest_color((127, 127, 127), &palette, DistanceMetric::CieLab)
+        );
+    }
+
+    #[test]
+    fn first_color_on_tie() {
+        let palette = [(10, 0, 0), (0, 10, 0), (0, 0, 0)];
+        assert_eq!(0, nearest_color((5, 5, 0), &palette, DistanceMetric::Rgb));
+    }
+
+    #[test]
+    #[should_panic]
+    fn empty_palette() {
+        nearest_color((0, 0, 0), &[], DistanceMetric::Rgb);
+    }
+}
+
+#[cfg(test)]
+mod test_extract_palette {
+    use image::{ImageBuffer, Rgb, Rgba};
+
+    use super::*;
+
+    fn noisy_image() -> DynamicImage {
+        let mut rng = SplitMix64::new(7);
+        DynamicImage::ImageRgb8(ImageBuffer::from_fn(60, 40, |_, _| {
+            Rgb([
+                rng.next_below(256) as u8,
+                rng.next_below(256) as u8,
+                rng.next_below(256) as u8,
+            ])
+        }))
+    }
+
+    #[test]
+    fn same_seed_same_palette() {
+        let img = noisy_image();
+        let first = extract_palette(&img, 6, 1234);
+        let second = extract_palette(&img, 6, 1234);
+        assert_eq!(first, second);
+        assert_eq!(6, first.colors.len());
+    }
+
+    #[test]
+    fn iterations_are_bounded() {
+        let palette = extract_palette(&noisy_image(), 12, 99);
+        assert!(palette.iterations >= 1);
+        assert!(palette.iterations <= MAX_ITERATIONS);
+    }
+
+    #[test]
+    fn sorted_by_count() {
+        let img = DynamicImage::ImageRgb8(ImageBuffer::from_fn(10, 10, |x, _| {
+            if x < 3 {
+                Rgb([255, 255, 0])
+            } else {
+                Rgb([0, 128, 0])
+            }
+        }));
+        let palette = extract_palette(&img, 2, 0);
+        assert_eq!(vec![(0, 128, 0), (255, 255, 0)], palette.colors);
+    }
+
+    #[test]
+    fn fewer_distinct_colors() {
+        let img = DynamicImage::ImageRgb8(ImageBuffer::from_pixel(10, 10, Rgb([1, 2, 3])));
+        let palette = extract_palette(&img, 5, 0);
+        assert_eq!(vec![(1, 2, 3)], palette.colors);
+    }
+
+    #[test]
+    fn transparent_image() {
+        let img = DynamicImage::ImageRgba8(ImageBuffer::from_pixel(10, 10, Rgba([1, 2, 3, 0])));
+        assert!(extract_palette(&img, 5, 0).colors.is_empty());
+    }
+
+    #[test]
+    fn random_numbers() {
+        let mut rng = SplitMix64::new(0);
+        //reference values of the splitmix64 algorithm
+        assert_eq!(0xE220_A839_7B1D_CDAF, rng.next_u64());
+        assert_eq!(0x6E78_9E6A_A1B9_65F4, rng.next_u64());
+        for _ in 0..100 {
+            let value = rng.next_f64();
+            assert!((0f64..1f64).contains(&value));
+            assert!(rng.next_below(10) < 10);
+        }
+    }
+}
@@ -1,11 +1,101 @@
-use image::Rgba;
+use std::collections::HashMap;
+
+use image::{DynamicImage, GenericImageView, GrayImage, Rgba};
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    config::{self, Config},
+    config::{
+        self, Config, GradientDirection, LuminanceStandard, RampGradient, RampRounding, TonalRamps,
+    },
+    palette::SplitMix64,
     target,
 };
 
-/// Convert a pixel block to a char (as a String) from the given density string.
+/// A single converted tile of the image.
+///
+/// It contains the character representing the luminance of the tile, as well as
+/// the average color of the tile, so that it can be rendered to the different targets.
+///
+/// In the json output, the colors are named `r`, `g` and `b`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Cell {
+    pub char: char,
+    #[serde(rename = "r")]
+    pub red: u8,
+    #[serde(rename = "g")]
+    pub green: u8,
+    #[serde(rename = "b")]
+    pub blue: u8,
+}
+
+impl Cell {
+    /// Returns the cell as a (colored) string for the target of the given config.
+    ///
+    /// To use color, use the `color` option, if only the background should be colored, use the `background_color` option instead.
+    ///
+    /// # Examples
+    ///
+    /// ```compile_fail, compile will fail, this is an internal example
+    /// let cell = Cell { char: '#', red: 0, green: 0, blue: 0 };
+    /// assert_eq!("#", cell.render(&ConfigBuilder::new().color(false).build()));
+    /// ```
+    pub fn render(&self, config: &Config) -> String {
+        //return the correctly formatted/colored string depending on the target
+        match config.target {
+            //if no color, use default case
+            config::TargetType::Shell | config::TargetType::AnsiFile if config.color() => {
+                target::ansi::colored_char(
+                    self.red,
+                    self.green,
+                    self.blue,
+                    self.char,
+                    config.background_color(),
+                    config.resolved_color_mode(),
+                    config.distance_metric,
+                )
+            }
+            config::TargetType::HtmlFile => {
+                if config.color() {
+                    target::html::colored_char(
+                        self.red,
+                        self.green,
+                        self.blue,
+                        self.char,
+                        config.background_color(),
+                    )
+                } else {
+                    self.char.to_string()
+                }
+            }
+            config::TargetType::BbCode if config.color() => {
+                target::bbcode::colored_char(self.red, self.green, self.blue, self.char)
+            }
+            //all other case, including a plain text file and shell without colors
+            _ => self.char.to_string(),
+        }
+    }
+
+    /// Returns the cell as an upper half block, with the lower color as the background.
+    ///
+    /// Only the terminal and html files use both colors, all other targets are rendered like [`Cell::render`].
+    pub(crate) fn render_half_block(&self, lower: (u8, u8, u8), config: &Config) -> String {
+        let upper = (self.red, self.green, self.blue);
+        match config.target {
+            config::TargetType::Shell if config.color() => target::ansi::half_block(
+                upper,
+                lower,
+                config.resolved_color_mode(),
+                config.distance_metric,
+            ),
+            config::TargetType::HtmlFile if config.color() => {
+                target::html::half_block(upper, lower)
+            }
+            _ => self.render(config),
+        }
+    }
+}
+
+/// Convert a pixel block to a [`Cell`] using the characters of the given config.
 ///
 /// # Panics
 ///
@@ -15,7 +105,6 @@ use crate::{
 ///
 /// ```compile_fail, compile will fail, this is an internal example
 /// use image::Rgba;
-/// use artem::config::TargetType;
 ///
 /// //example pixels, use them from the directly if possible
 /// let pixels = vec![
@@ -23,69 +112,403 @@ use crate::{
 ///     Rgba::<u8>::from([0, 0, 0, 255]),
 /// ];
 ///
-/// assert_eq!(".", correlating_char(&pixels, "#k. ", false, TargetType::default()));
+/// let config = ConfigBuilder::new().characters("#k. ".to_string()).build();
+/// assert_eq!('k', correlating_cell(&pixels, &config).char);
 /// ```
 ///
-/// To use color, use the `color` argument, if only the background should be colored, use the `on_background_color` arg instead.
-///
-/// The `invert` arg, inverts the mapping from pixel luminosity to density string.
-pub fn correlating_char(block: &[Rgba<u8>], config: &Config) -> String {
+/// The `invert` option, inverts the mapping from pixel luminosity to density string.
+pub fn correlating_cell(block: &[Rgba<u8>], config: &Config) -> Cell {
     assert!(!block.is_empty());
     assert!(!config.characters.is_empty());
 
-    let (red, green, blue) = average_color(block);
+    let (red, green, blue) = tile_color(block, config);
 
     //calculate luminosity from avg. pixel color
-    let luminosity = luminosity(red, green, blue);
+    let luminance = tile_luminance(block, (red, green, blue), config);
+
+    cell_for_tile((red, green, blue), luminance, config)
+}
+
+/// Returns the [`Cell`] of a tile with the given color and luminance (`0.0` - `1.0`).
+///
+/// The character is chosen by the luminance, see [`glyph_for_luminance`], and the color
+/// is only changed afterwards, if a minimum contrast is set.
+pub fn cell_for_tile((red, green, blue): (u8, u8, u8), luminance: f64, config: &Config) -> Cell {
+    //get correct char from map
+    let char = glyph_for_luminance(luminance, config);
+
+    //only change the color after the char has been chosen, so the density is not affected
+    let (red, green, blue) = match config.min_contrast {
+        Some(contrast) => {
+            with_min_contrast((red, green, blue), contrast.background, contrast.ratio)
+        }
+        None => (red, green, blue),
+    };
+
+    Cell {
+        char,
+        red,
+        green,
+        blue,
+    }
+}
+
+/// Returns the character of the density map, that is used for the given luminance.
+///
+/// The luminance is expected to be normalized between `0.0` (black) and `1.0` (white), values outside
+/// of this range will be clamped. The mapping respects both the characters and the
+/// invert option of the [`Config`], so it can be used to preview/calibrate the conversion
+/// without having to convert an entire image. If a [`crate::config::CharacterMapping`] is set,
+/// it will be used instead of the characters, otherwise the [`TonalRamps`] are used, if they are set.
+/// If a threshold is set, only the on and off characters are used, a luminance of exactly the threshold is on.
+///
+/// # Panics
+///
+/// Panics if the used characters or tonal ramp are empty, which [`Config::validate`] reports as an error.
+///
+/// # Examples
+///
+/// ```
+/// use artem::config::ConfigBuilder;
+///
+/// let config = ConfigBuilder::new().characters("#k. ".to_string()).build();
+/// assert_eq!(' ', artem::glyph_for_luminance(0.0, &config));
+/// assert_eq!('#', artem::glyph_for_luminance(1.0, &config));
+/// ```
+pub fn glyph_for_luminance(luminance: f64, config: &Config) -> char {
+    if let Some(threshold) = config.threshold {
+        return if (luminance >= threshold) != config.invert {
+            config.on_char
+        } else {
+            config.off_char
+        };
+    }
+
+    if let Some(mapping) = &config.mapping {
+        let value = (luminance.clamp(0f64, 1f64) * 255f64).floor() as u8;
+        return mapping.char_for(if config.invert {
+            u8::MAX - value
+        } else {
+            value
+        });
+    }
+
+    if let Some(ramps) = &config.tonal_ramps {
+        let (characters, luminance) = tonal_band(ramps, luminance.clamp(0f64, 1f64));
+        return with_background_char(
+            glyph_from_characters(luminance, characters, config),
+            &ramps.shadows,
+            config,
+        );
+    }
+
+    ramp_glyph(luminance, &config.characters, config)
+}
+
+/// Returns the character of the given characters for the luminance, see [`glyph_from_characters`],
+/// with the darkest character replaced by the background character of the config.
+fn ramp_glyph(luminance: f64, characters: &str, config: &Config) -> char {
+    with_background_char(
+        glyph_from_characters(luminance, characters, config),
+        characters,
+        config,
+    )
+}
+
+/// Returns the background character of the config, if the glyph is the darkest character of the given characters.
+fn with_background_char(glyph: char, characters: &str, config: &Config) -> char {
+    match config.background_char {
+        Some(background) if glyph == glyph_from_characters(0f64, characters, config) => background,
+        _ => glyph,
+    }
+}
+
+/// Returns the characters of the band the luminance belongs to, together with the luminance
+/// relative to the band, so that the band can be mapped like the full luminance range.
+fn tonal_band(ramps: &TonalRamps, luminance: f64) -> (&str, f64) {
+    let (characters, start, end) = if luminance < TonalRamps::MIDTONES_START {
+        (&ramps.shadows, 0f64, TonalRamps::MIDTONES_START)
+    } else if luminance < TonalRamps::HIGHLIGHTS_START {
+        (
+            &ramps.midtones,
+            TonalRamps::MIDTONES_START,
+            TonalRamps::HIGHLIGHTS_START,
+        )
+    } else {
+        (&ramps.highlights, TonalRamps::HIGHLIGHTS_START, 1f64)
+    };
+    (characters, (luminance - start) / (end - start))
+}
+
+/// Returns the character of the given characters, that is used for the given luminance.
+///
+/// The characters are ordered from dark to light, unless the `invert` option of the config is set.
+/// The index of the character is rounded using the `ramp_rounding` option.
+///
+/// # Panics
+///
+/// Panics if the given characters are empty.
+fn glyph_from_characters(luminance: f64, characters: &str, config: &Config) -> char {
+    assert!(!characters.is_empty());
 
     //use chars length to support unicode chars
-    let length = config.characters.chars().count();
+    let length = characters.chars().count();
 
     //swap to range for white to black values
-    //convert from rgb values (0 - 255) to the density string index (0 - string length)
-    let density_index = map_range(
-        (0f32, 255f32),
+    //convert from the luminance (0 - 1) to the density string index (0 - string length)
+    let density_index = map_range_clamped(
+        (0f32, 1f32),
         if config.invert {
             (0f32, length as f32)
         } else {
             (length as f32, 0f32)
         },
-        luminosity,
-    )
-    .floor()
-    .clamp(0f32, length as f32 - 1.0);
+        luminance as f32,
+    );
+    //the end of the range, which is reached by a luminance of exactly 0 or 1, belongs to the last character
+    let density_index = match config.ramp_rounding {
+        RampRounding::Floor => density_index.floor(),
+        RampRounding::Round => density_index.round(),
+        RampRounding::Ceil => density_index.ceil(),
+    }
+    .min(length as f32 - 1.0);
 
     //get correct char from map
     assert!((density_index as usize) < length);
-    let density_char = config
-        .characters
+    characters
         .chars()
         .nth(density_index as usize)
-        .expect("Failed to get char");
-
-    //return the correctly formatted/colored string depending on the target
-    match config.target {
-        //if no color, use default case
-        config::TargetType::Shell | config::TargetType::AnsiFile | config::TargetType::Svg
-            if config.color() =>
-        {
-            target::ansi::colored_char(red, green, blue, density_char, config.background_color())
+        .expect("Failed to get char")
+}
+
+#[cfg(test)]
+mod test_glyph_for_luminance {
+    use crate::{config::CharacterMapping, ConfigBuilder};
+
+    use super::*;
+
+    #[test]
+    fn black_returns_last_char() {
+        let config = ConfigBuilder::new().characters("#k. ".to_owned()).build();
+        assert_eq!(' ', glyph_for_luminance(0f64, &config));
+    }
+
+    #[test]
+    fn white_returns_first_char() {
+        let config = ConfigBuilder::new().characters("#k. ".to_owned()).build();
+        assert_eq!('#', glyph_for_luminance(1f64, &config));
+    }
+
+    #[test]
+    fn medium_luminance() {
+        let config = ConfigBuilder::new().characters("#k. ".to_owned()).build();
+        assert_eq!('k', glyph_for_luminance(0.6f64, &config));
+        assert_eq!('.', glyph_for_luminance(0.4f64, &config));
+    }
+
+    #[test]
+    fn edge_luminances_stay_in_bounds() {
+        //a luminance of exactly 0 or 1 maps to the end of the range, one after the last index
+        for rounding in [RampRounding::Floor, RampRounding::Round, RampRounding::Ceil] {
+            for invert in [false, true] {
+                let config = ConfigBuilder::new()
+                    .characters("#k. ".to_owned())
+                    .ramp_rounding(rounding)
+                    .invert(invert)
+                    .build();
+                let (dark, light) = if invert { ('#', ' ') } else { (' ', '#') };
+                assert_eq!(light, glyph_for_luminance(1f64, &config));
+                assert_eq!(dark, glyph_for_luminance(0f64, &config));
+                //values outside of the range are treated like the nearest end
+                assert_eq!(light, glyph_for_luminance(1.5f64, &config));
+                assert_eq!(dark, glyph_for_luminance(-0.5f64, &config));
+            }
         }
-        config::TargetType::HtmlFile => {
-            if config.color() {
-                target::html::colored_char(
-                    red,
-                    green,
-                    blue,
-                    density_char,
-                    config.background_color(),
-                )
-            } else {
-                density_char.to_string()
+    }
+
+    #[test]
+    fn invert_swaps_mapping() {
+        let config = ConfigBuilder::new()
+            .characters("#k. ".to_owned())
+            .invert(true)
+            .build();
+        assert_eq!('#', glyph_for_luminance(0f64, &config));
+        assert_eq!(' ', glyph_for_luminance(1f64, &config));
+        assert_eq!('.', glyph_for_luminance(0.6f64, &config));
+    }
+
+    #[test]
+    fn rounding_modes() {
+        let rounded = |luminance: f64, rounding: RampRounding| {
+            let config = ConfigBuilder::new()
+                .characters("#k. ".to_owned())
+                .ramp_rounding(rounding)
+                .build();
+            glyph_for_luminance(luminance, &config)
+        };
+        //exactly between the '.' and ' ' buckets
+        assert_eq!('.', rounded(0.375, RampRounding::Floor));
+        assert_eq!(' ', rounded(0.375, RampRounding::Round));
+        assert_eq!(' ', rounded(0.375, RampRounding::Ceil));
+        //a quarter into the bucket of ' '
+        assert_eq!('.', rounded(0.4375, RampRounding::Floor));
+        assert_eq!('.', rounded(0.4375, RampRounding::Round));
+        assert_eq!(' ', rounded(0.4375, RampRounding::Ceil));
+    }
+
+    #[test]
+    fn out_of_range_is_clamped() {
+        let config = ConfigBuilder::new().characters("#k. ".to_owned()).build();
+        assert_eq!(' ', glyph_for_luminance(-3f64, &config));
+        assert_eq!('#', glyph_for_luminance(42f64, &config));
+    }
+
+    #[test]
+    fn long_ramp_stays_in_bounds() {
+        //Paul Bourke's ramp, from the densest to the lightest character
+        let ramp = r#"$@B%8&WM#*oahkbdpqwmZO0QLCJUYXzcvunxrjft/\|()1{}[]?-_+~<>i!lI;:,"^`'. "#;
+        assert_eq!(70, ramp.chars().count());
+        for rounding in [RampRounding::Floor, RampRounding::Round, RampRounding::Ceil] {
+            for invert in [false, true] {
+                let config = ConfigBuilder::new()
+                    .characters(ramp)
+                    .ramp_rounding(rounding)
+                    .invert(invert)
+                    .build();
+                for step in 0..=1000 {
+                    let char = glyph_for_luminance(step as f64 / 1000f64, &config);
+                    assert!(ramp.contains(char));
+                }
+                let (white, black) = if invert { (' ', '$') } else { ('$', ' ') };
+                assert_eq!(white, glyph_for_luminance(1f64, &config));
+                assert_eq!(black, glyph_for_luminance(0f64, &config));
             }
         }
-        //all other case, including a plain text file and shell without colors
-        _ => density_char.to_string(),
+    }
+
+    #[test]
+    fn tonal_ramps_use_band_characters() {
+        let config = ConfigBuilder::new()
+            .tonal_ramps(TonalRamps {
+                shadows: "s".to_owned(),
+                midtones: "m".to_owned(),
+                highlights: "h".to_owned(),
+            })
+            .build();
+        for (luminance, char) in [
+            (0.0, 's'),
+            (0.2, 's'),
+            (0.33, 's'),
+            (0.34, 'm'),
+            (0.5, 'm'),
+            (0.66, 'm'),
+            (0.67, 'h'),
+            (1.0, 'h'),
+        ] {
+            assert_eq!(char, glyph_for_luminance(luminance, &config));
+        }
+    }
+
+    #[test]
+    fn tonal_ramps_are_mapped_within_band() {
+        let config = ConfigBuilder::new()
+            .tonal_ramps(TonalRamps {
+                shadows: ":. ".to_owned(),
+                midtones: "o+-".to_owned(),
+                highlights: "@#%".to_owned(),
+            })
+            .build();
+        //the start and end of each band use the lightest and darkest characters of the band
+        assert_eq!(' ', glyph_for_luminance(0.0, &config));
+        assert_eq!(':', glyph_for_luminance(0.33, &config));
+        assert_eq!('-', glyph_for_luminance(0.34, &config));
+        assert_eq!('o', glyph_for_luminance(0.66, &config));
+        assert_eq!('%', glyph_for_luminance(0.67, &config));
+        assert_eq!('@', glyph_for_luminance(1.0, &config));
+    }
+
+    #[test]
+    fn mapping_overrides_characters() {
+        let config = ConfigBuilder::new()
+            .characters("#k. ".to_owned())
+            .mapping(CharacterMapping::new(vec![(0, 'a'), (64, 'b'), (192, 'c')]).unwrap())
+            .build();
+        assert_eq!('a', glyph_for_luminance(0f64, &config));
+        assert_eq!('b', glyph_for_luminance(0.5f64, &config));
+        assert_eq!('c', glyph_for_luminance(1f64, &config));
+    }
+
+    #[test]
+    fn mapping_inverted() {
+        let config = ConfigBuilder::new()
+            .mapping(CharacterMapping::new(vec![(0, 'a'), (64, 'b'), (192, 'c')]).unwrap())
+            .invert(true)
+            .build();
+        assert_eq!('c', glyph_for_luminance(0f64, &config));
+        assert_eq!('b', glyph_for_luminance(0.5f64, &config));
+        assert_eq!('a', glyph_for_luminance(1f64, &config));
+    }
+
+    #[test]
+    fn unicode_characters() {
+        let config = ConfigBuilder::new().characters("█▓▒░ ".to_owned()).build();
+        assert_eq!('█', glyph_for_luminance(1f64, &config));
+        assert_eq!('▒', glyph_for_luminance(0.5f64, &config));
+        assert_eq!(' ', glyph_for_luminance(0f64, &config));
+    }
+
+    #[test]
+    fn threshold_uses_two_chars() {
+        let config = ConfigBuilder::new()
+            .threshold(0.5)
+            .on_char('@')
+            .off_char('.')
+            .build();
+        assert_eq!('@', glyph_for_luminance(1f64, &config));
+        assert_eq!('@', glyph_for_luminance(0.6f64, &config));
+        assert_eq!('.', glyph_for_luminance(0.4f64, &config));
+        assert_eq!('.', glyph_for_luminance(0f64, &config));
+    }
+
+    #[test]
+    fn threshold_is_on() {
+        let config = ConfigBuilder::new().threshold(0.5).build();
+        assert_eq!('#', glyph_for_luminance(0.5f64, &config));
+        //inverted, the same luminance is consistently on, which uses the off character
+        let config = ConfigBuilder::new().threshold(0.5).invert(true).build();
+        assert_eq!(' ', glyph_for_luminance(0.5f64, &config));
+        assert_eq!('#', glyph_for_luminance(0.4f64, &config));
+    }
+
+    #[test]
+    fn background_char_replaces_darkest() {
+        let mut builder = ConfigBuilder::new();
+        builder.characters("#k. ".to_owned()).background_char('_');
+        let config = builder.build();
+        assert_eq!('_', glyph_for_luminance(0f64, &config));
+        assert_eq!('.', glyph_for_luminance(0.4f64, &config));
+        assert_eq!('#', glyph_for_luminance(1f64, &config));
+
+        //the darkest character is the first one, when the characters are inverted
+        let config = builder.invert(true).build();
+        assert_eq!('_', glyph_for_luminance(0f64, &config));
+        assert_eq!(' ', glyph_for_luminance(1f64, &config));
+    }
+
+    #[test]
+    fn background_char_tonal_ramps() {
+        let config = ConfigBuilder::new()
+            .tonal_ramps(TonalRamps {
+                shadows: ":. ".to_owned(),
+                midtones: "o+ ".to_owned(),
+                highlights: "@#%".to_owned(),
+            })
+            .background_char('_')
+            .build();
+        assert_eq!('_', glyph_for_luminance(0.0, &config));
+        //the space of the midtones is the same character, so it is replaced as well
+        assert_eq!('_', glyph_for_luminance(0.34, &config));
+        assert_eq!('%', glyph_for_luminance(0.67, &config));
     }
 }
 
@@ -97,6 +520,11 @@ mod test_pixel_density {
 
     use super::*;
 
+    /// Convert the pixel block to a rendered string.
+    fn correlating_char(block: &[Rgba<u8>], config: &Config) -> String {
+        correlating_cell(block, config).render(config)
+    }
+
     #[test]
     fn invert_returns_first_instead_of_last_char() {
         let pixels = vec![
@@ -112,6 +540,27 @@ mod test_pixel_density {
         assert_eq!(" ", correlating_char(&pixels, &config));
     }
 
+    #[test]
+    fn invert_keeps_color() {
+        let white = [Rgba::<u8>::from([255, 255, 255, 255])];
+        let black = [Rgba::<u8>::from([0, 0, 0, 255])];
+        let mut builder = ConfigBuilder::new();
+        builder.characters("@%#*+=-:. ");
+
+        //a white pixel uses the densest character for a dark background
+        let cell = correlating_cell(&white, &builder.build());
+        assert_eq!('@', cell.char);
+
+        //inverted, the black pixel uses the densest character, while the colors are not changed
+        let config = builder.invert(true).build();
+        let cell = correlating_cell(&black, &config);
+        assert_eq!('@', cell.char);
+        assert_eq!((0, 0, 0), (cell.red, cell.green, cell.blue));
+        let cell = correlating_cell(&white, &config);
+        assert_eq!(' ', cell.char);
+        assert_eq!((255, 255, 255), (cell.red, cell.green, cell.blue));
+    }
+
     #[test]
     fn medium_density_char() {
         let pixels = vec![
@@ -303,11 +752,23 @@ mod test_pixel_density {
 ///
 /// If the value is outside of the specified range, it will still be
 /// converted as if it was in the range. This means it could be much larger or smaller than expected.
-/// This can be fixed by using the `clamp` function after the remapping.
+/// Use [`map_range_clamped`] to keep the result inside of the target range.
 fn map_range(from_range: (f32, f32), to_range: (f32, f32), value: f32) -> f32 {
     to_range.0 + (value - from_range.0) * (to_range.1 - to_range.0) / (from_range.1 - from_range.0)
 }
 
+/// Remap a value from one range to another, like [`map_range`], but clamp the result to the target range.
+///
+/// The target range can also be descending, a value outside of the specified range results in the nearest end of the target range.
+fn map_range_clamped(from_range: (f32, f32), to_range: (f32, f32), value: f32) -> f32 {
+    let (min, max) = if to_range.0 <= to_range.1 {
+        to_range
+    } else {
+        (to_range.1, to_range.0)
+    };
+    map_range(from_range, to_range, value).clamp(min, max)
+}
+
 #[cfg(test)]
 mod test_map_range {
     use super::*;
@@ -329,6 +790,117 @@ mod test_map_range {
         //remap -1 to -2, since the value will be doubled
         assert_eq!(-2f32, map_range((0f32, 10f32), (0f32, 20f32), -1f32));
     }
+
+    #[test]
+    fn clamped_remap_values() {
+        assert_eq!(4f32, map_range_clamped((0f32, 10f32), (0f32, 20f32), 2f32));
+        assert_eq!(
+            20f32,
+            map_range_clamped((0f32, 10f32), (0f32, 20f32), 21f32)
+        );
+        assert_eq!(0f32, map_range_clamped((0f32, 10f32), (0f32, 20f32), -1f32));
+    }
+
+    #[test]
+    fn clamped_remap_descending_range() {
+        assert_eq!(16f32, map_range_clamped((0f32, 10f32), (20f32, 0f32), 2f32));
+        assert_eq!(0f32, map_range_clamped((0f32, 10f32), (20f32, 0f32), 21f32));
+        assert_eq!(
+            20f32,
+            map_range_clamped((0f32, 10f32), (20f32, 0f32), -1f32)
+        );
+    }
+}
+
+/// Returns the color of a tile, which is the average color of its pixels.
+///
+/// The pixels are weighted by their alpha channel, unless they were already blended with a background color, see [`weights_alpha`].
+///
+/// # Examples
+///
+/// ```compile_fail, compile will fail, this is an internal example
+/// let pixels = vec![Rgba::<u8>::from([10, 20, 30, 255]); 16];
+/// assert_eq!((10, 20, 30), tile_color(&pixels, &ConfigBuilder::new().build()));
+/// ```
+pub fn tile_color(block: &[Rgba<u8>], config: &Config) -> (u8, u8, u8) {
+    if weights_alpha(config) {
+        alpha_average_color(block)
+    } else {
+        average_color(block)
+    }
+}
+
+/// Returns if the pixels of a tile are weighted by their alpha channel.
+///
+/// Transparent pixels do not contribute to the color of a tile, unless they were already blended
+/// with the background color of the config, see [`crate::config::ConfigBuilder::background_fill`].
+fn weights_alpha(config: &Config) -> bool {
+    config.background_fill.is_none()
+}
+
+/// Returns the part of the tile (`0.0` - `1.0`), which is covered by opaque pixels, which is the average alpha value.
+fn coverage(block: &[Rgba<u8>]) -> f64 {
+    let sum = block.iter().map(|pixel| pixel.0[3] as u32).sum::<u32>();
+    sum as f64 / (block.len().max(1) * u8::MAX as usize) as f64
+}
+
+/// Returns the average colors of the upper and the lower half of a tile, which are used for half blocks.
+///
+/// The block has to contain the pixels column by column, like for [`crate::braille`]. If the tile only has
+/// a single row, both colors are the same.
+///
+/// # Examples
+///
+/// ```compile_fail, compile will fail, this is an internal example
+/// let pixels = [Rgba::<u8>::from([255, 0, 0, 255]), Rgba::<u8>::from([0, 0, 255, 255])];
+/// assert_eq!(((255, 0, 0), (0, 0, 255)), half_block_colors(&pixels, 1, 2));
+/// ```
+pub fn half_block_colors(
+    block: &[Rgba<u8>],
+    tile_width: u32,
+    tile_height: u32,
+) -> ((u8, u8, u8), (u8, u8, u8)) {
+    let (mut upper, mut lower) = (Vec::new(), Vec::new());
+    for (index, pixel) in block.iter().enumerate() {
+        let y = index as u32 % tile_height;
+        //the middle row of uneven tiles belongs to both halves
+        if y < tile_height.div_ceil(2) {
+            upper.push(*pixel);
+        }
+        if y >= tile_height / 2 {
+            lower.push(*pixel);
+        }
+    }
+    debug_assert_eq!((tile_width * tile_height) as usize, block.len());
+    (average_color(&upper), average_color(&lower))
+}
+
+#[cfg(test)]
+mod test_half_block_colors {
+    use super::*;
+
+    const RED: Rgba<u8> = Rgba([255, 0, 0, 255]);
+    const BLUE: Rgba<u8> = Rgba([0, 0, 255, 255]);
+
+    #[test]
+    fn upper_and_lower_pixel() {
+        assert_eq!(
+            ((255, 0, 0), (0, 0, 255)),
+            half_block_colors(&[RED, BLUE], 1, 2)
+        );
+    }
+
+    #[test]
+    fn larger_tile() {
+        //2x4 tile, column by column
+        let pixels = [RED, RED, BLUE, BLUE, RED, RED, BLUE, BLUE];
+        assert_eq!(((255, 0, 0), (0, 0, 255)), half_block_colors(&pixels, 2, 4));
+    }
+
+    #[test]
+    fn single_row() {
+        assert_eq!(((255, 0, 0), (255, 0, 0)), half_block_colors(&[RED], 1, 1));
+    }
 }
 
 /// Returns the average rbg color of multiple pixel.
@@ -343,7 +915,7 @@ mod test_map_range {
 /// ```
 ///
 /// The formula for calculating the rbg colors is based an a minutephysics video <https://www.youtube.com/watch?v=LKnqECcg6Gw>
-fn average_color(block: &[Rgba<u8>]) -> (u8, u8, u8) {
+pub fn average_color(block: &[Rgba<u8>]) -> (u8, u8, u8) {
     let sum = block
         .iter()
         .map(|pixel| {
@@ -363,6 +935,33 @@ fn average_color(block: &[Rgba<u8>]) -> (u8, u8, u8) {
     )
 }
 
+/// Returns the average rgb color of multiple pixels, where each pixel is weighted by its alpha value.
+///
+/// Fully transparent pixels do not contribute to the color, so a tile of opaque red and transparent pixels is still red,
+/// instead of being darkened by the usually black color channels of the transparent pixels. If all pixels are
+/// fully transparent, this is the same as [`average_color`].
+///
+/// # Examples
+///
+/// ```compile_fail, compile will fail, this is an internal example
+/// let pixels = [Rgba::<u8>::from([255, 0, 0, 255]), Rgba::<u8>::from([0, 0, 0, 0])];
+/// assert_eq!((255, 0, 0), alpha_average_color(&pixels));
+/// ```
+pub fn alpha_average_color(block: &[Rgba<u8>]) -> (u8, u8, u8) {
+    let weight = block.iter().map(|pixel| pixel.0[3] as f32).sum::<f32>();
+    if weight == 0f32 {
+        return average_color(block);
+    }
+    let [red, green, blue] = [0, 1, 2].map(|channel| {
+        let sum = block
+            .iter()
+            .map(|pixel| pixel.0[3] as f32 * pixel.0[channel] as f32 * pixel.0[channel] as f32)
+            .sum::<f32>();
+        (sum / weight).sqrt() as u8
+    });
+    (red, green, blue)
+}
+
 #[cfg(test)]
 mod test_avg_color {
     use super::*;
@@ -387,6 +986,33 @@ mod test_avg_color {
         assert_eq!((0, 180, 180), average_color(&pixels));
     }
 
+    #[test]
+    fn half_transparent_red_is_red() {
+        let pixels = vec![
+            Rgba::<u8>::from([255, 0, 0, 255]),
+            Rgba::<u8>::from([0, 0, 0, 0]),
+        ];
+        assert_eq!((255, 0, 0), alpha_average_color(&pixels));
+        //without the alpha, the transparent black darkens the red
+        assert_eq!((180, 0, 0), average_color(&pixels));
+    }
+
+    #[test]
+    fn opaque_equals_average() {
+        let pixels = vec![
+            Rgba::<u8>::from([255, 0, 0, 255]),
+            Rgba::<u8>::from([0, 255, 0, 255]),
+            Rgba::<u8>::from([13, 100, 200, 255]),
+        ];
+        assert_eq!(average_color(&pixels), alpha_average_color(&pixels));
+    }
+
+    #[test]
+    fn only_transparent() {
+        let pixels = vec![Rgba::<u8>::from([10, 20, 30, 0]); 4];
+        assert_eq!((10, 20, 30), alpha_average_color(&pixels));
+    }
+
     #[test]
     fn empty_input() {
         let pixels: Vec<Rgba<u8>> = Vec::new();
@@ -413,7 +1039,7 @@ mod test_avg_color {
 ///
 /// The formula/weighting for the colors comes from <http://www.johndcook.com/blog/2009/08/24/algorithms-convert-color-grayscale/>
 pub fn luminosity(red: u8, green: u8, blue: u8) -> f32 {
-    (0.21 * red as f32) + (0.72 * green as f32) + (0.07 * blue as f32)
+    LuminanceStandard::default().luminance(red, green, blue)
 }
 
 #[cfg(test)]
@@ -435,3 +1061,1838 @@ mod tests {
         assert_eq!(97.32f32, luminosity(154, 85, 54))
     }
 }
+
+/// Returns the luminance (`0.0` - `1.0`) of a tile with the given color, which is used to choose its character.
+///
+/// Without a gamma in the config, this is the luminance of the color, using the [`LuminanceStandard`] of the config. With a gamma, the pixels of the tile
+/// are linearized using [`srgb_to_linear`], averaged and weighted in linear light, and then encoded with the gamma.
+/// If a single [`crate::config::Channel`] is set, only this channel is used instead of the luminance.
+/// Transparent pixels are empty, so the luminance is scaled by the part of the tile, which is covered by opaque pixels,
+/// unless they were already blended with a background color.
+///
+/// # Examples
+///
+/// ```compile_fail, compile will fail, this is an internal example
+/// let pixels = [Rgba::<u8>::from([0, 0, 0, 255]), Rgba::<u8>::from([255, 255, 255, 255])];
+/// let config = ConfigBuilder::new().gamma(1.0).build();
+/// assert_eq!(0.5, tile_luminance(&pixels, (180, 180, 180), &config));
+/// ```
+pub fn tile_luminance(
+    block: &[Rgba<u8>],
+    (red, green, blue): (u8, u8, u8),
+    config: &Config,
+) -> f64 {
+    let weights_alpha = weights_alpha(config);
+    let Some(gamma) = config.gamma else {
+        let value = config
+            .channel
+            .value(red, green, blue, config.luminance_standard) as f64
+            / 255f64;
+        //transparent parts of the tile are empty
+        return if weights_alpha {
+            value * coverage(block)
+        } else {
+            value
+        };
+    };
+    let (red_weight, green_weight, blue_weight) = config.channel.weights(config.luminance_standard);
+    let sum = block
+        .iter()
+        .map(|pixel| {
+            let alpha = if weights_alpha {
+                pixel.0[3] as f64 / 255f64
+            } else {
+                1f64
+            };
+            alpha
+                * (red_weight as f64 * srgb_to_linear(pixel.0[0])
+                    + green_weight as f64 * srgb_to_linear(pixel.0[1])
+                    + blue_weight as f64 * srgb_to_linear(pixel.0[2]))
+        })
+        .sum::<f64>();
+    let linear = (sum / block.len().max(1) as f64).clamp(0f64, 1f64);
+    linear.powf(1f64 / gamma)
+}
+
+/// Converts a sRGB channel to linear light (`0.0` - `1.0`), using the sRGB transfer function.
+///
+/// # Examples
+///
+/// ```compile_fail, compile will fail, this is an internal example
+/// assert_eq!(1.0, srgb_to_linear(255));
+/// ```
+pub fn srgb_to_linear(value: u8) -> f64 {
+    linearize(value as f64 / 255f64)
+}
+
+/// Converts a sRGB value (`0.0` - `1.0`) to linear light, see [`srgb_to_linear`].
+fn linearize(value: f64) -> f64 {
+    if value <= 0.04045 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+#[cfg(test)]
+mod test_tile_luminance {
+    use super::*;
+    use crate::config::ConfigBuilder;
+
+    #[test]
+    fn srgb_transfer_function() {
+        assert_eq!(0f64, srgb_to_linear(0));
+        assert_eq!(1f64, srgb_to_linear(255));
+        //sRGB mid-gray is about 21.6% of the light
+        assert!((srgb_to_linear(128) - 0.2158).abs() < 0.0001);
+    }
+
+    #[test]
+    fn without_gamma_is_luminosity() {
+        let pixels = [Rgba([154, 85, 54, 255])];
+        assert_eq!(
+            luminosity(154, 85, 54) as f64 / 255f64,
+            tile_luminance(&pixels, (154, 85, 54), &ConfigBuilder::new().build())
+        );
+    }
+
+    #[test]
+    fn gamma_lifts_midtones() {
+        //a mid-gray tile of black and white pixels
+        let pixels = [Rgba([0, 0, 0, 255]), Rgba([255, 255, 255, 255])];
+        let color = tile_color(&pixels, &ConfigBuilder::new().build());
+        let without = tile_luminance(&pixels, color, &ConfigBuilder::new().build());
+        let with = tile_luminance(&pixels, color, &ConfigBuilder::new().gamma(2.2).build());
+        //half of the light, encoded with the gamma
+        assert!((with - 0.5f64.powf(1f64 / 2.2)).abs() < 0.0001);
+        assert!(with > without, "{with} <= {without}");
+    }
+
+    #[test]
+    fn transparent_pixels_are_empty() {
+        let pixels = [Rgba([255, 255, 255, 255]), Rgba([255, 255, 255, 0])];
+        for config in [
+            ConfigBuilder::new().build(),
+            ConfigBuilder::new().gamma(1.0).build(),
+        ] {
+            let color = tile_color(&pixels, &config);
+            assert_eq!((255, 255, 255), color);
+            //only half of the tile is covered
+            assert!((tile_luminance(&pixels, color, &config) - 0.5).abs() < 0.01);
+        }
+        //with a background, the pixels have already been blended
+        let config = ConfigBuilder::new().background_fill((0, 0, 0)).build();
+        assert!((tile_luminance(&pixels, (255, 255, 255), &config) - 1f64).abs() < 0.01);
+    }
+
+    #[test]
+    fn linear_gamma() {
+        let pixels = [Rgba([128, 128, 128, 255])];
+        let luminance = tile_luminance(
+            &pixels,
+            (128, 128, 128),
+            &ConfigBuilder::new().gamma(1.0).build(),
+        );
+        assert!((luminance - srgb_to_linear(128)).abs() < 0.0001);
+    }
+}
+
+/// Returns the color and luminance (`0.0` - `1.0`) of a tile of an image with 16 bits per channel.
+///
+/// This is the same as [`tile_color`] and [`tile_luminance`], but the pixels are averaged and weighted
+/// with their full precision, so smooth gradients are not reduced to 256 levels. Only the returned color is
+/// rounded to 8 bits per channel, since it is only used for the colors of the output.
+///
+/// # Examples
+///
+/// ```compile_fail, compile will fail, this is an internal example
+/// let pixels = [Rgba::<u16>::from([1000, 1000, 1000, 65535])];
+/// let (color, luminance) = precise_color_luminance(&pixels, &ConfigBuilder::new().build());
+/// assert_eq!((4, 4, 4), color);
+/// ```
+pub fn precise_color_luminance(block: &[Rgba<u16>], config: &Config) -> ((u8, u8, u8), f64) {
+    let pixels = block.len().max(1) as f64;
+    let value = |pixel: &Rgba<u16>, channel: usize| pixel.0[channel] as f64 / u16::MAX as f64;
+    //like for 8 bit images, transparent pixels do not contribute to the color, see alpha_average_color
+    let alphas = block
+        .iter()
+        .map(|pixel| {
+            if weights_alpha(config) {
+                value(pixel, 3)
+            } else {
+                1f64
+            }
+        })
+        .collect::<Vec<_>>();
+    let coverage = alphas.iter().sum::<f64>() / pixels;
+    //fully transparent tiles keep the average of their color channels
+    let color_weights = if coverage == 0f64 {
+        vec![1f64; block.len()]
+    } else {
+        alphas.clone()
+    };
+    let weight = color_weights.iter().sum::<f64>().max(f64::MIN_POSITIVE);
+    //the same root mean square as average_color
+    let [red, green, blue] = [0, 1, 2].map(|channel| {
+        let sum = block
+            .iter()
+            .zip(&color_weights)
+            .map(|(pixel, weight)| weight * value(pixel, channel).powi(2))
+            .sum::<f64>();
+        (sum / weight).sqrt()
+    });
+    let (red_weight, green_weight, blue_weight) = config.channel.weights(config.luminance_standard);
+    let (red_weight, green_weight, blue_weight) =
+        (red_weight as f64, green_weight as f64, blue_weight as f64);
+    let luminance = match config.gamma {
+        None => (red_weight * red + green_weight * green + blue_weight * blue) * coverage,
+        Some(gamma) => {
+            let sum = block
+                .iter()
+                .zip(&alphas)
+                .map(|(pixel, alpha)| {
+                    alpha
+                        * (red_weight * linearize(value(pixel, 0))
+                            + green_weight * linearize(value(pixel, 1))
+                            + blue_weight * linearize(value(pixel, 2)))
+                })
+                .sum::<f64>();
+            (sum / pixels).clamp(0f64, 1f64).powf(1f64 / gamma)
+        }
+    };
+    let to_u8 = |value: f64| (value * 255f64).round() as u8;
+    (
+        (to_u8(red), to_u8(green), to_u8(blue)),
+        luminance.clamp(0f64, 1f64),
+    )
+}
+
+#[cfg(test)]
+mod test_precise_color_luminance {
+    use super::*;
+    use crate::config::ConfigBuilder;
+
+    #[test]
+    fn keeps_precision() {
+        let config = ConfigBuilder::new().build();
+        let dark = [Rgba::<u16>::from([1000, 1000, 1000, u16::MAX])];
+        let darker = [Rgba::<u16>::from([1100, 1100, 1100, u16::MAX])];
+        let (color, luminance) = precise_color_luminance(&dark, &config);
+        //both are the same 8 bit color, but their luminance differs
+        assert_eq!((4, 4, 4), color);
+        assert_eq!(color, precise_color_luminance(&darker, &config).0);
+        assert!(luminance < precise_color_luminance(&darker, &config).1);
+    }
+
+    #[test]
+    fn matches_8_bit_colors() {
+        //8 bit colors are converted to 16 bits by multiplying them with 257
+        let config = ConfigBuilder::new().gamma(2.2).build();
+        let pixels = [[0, 128, 255], [255, 64, 10]];
+        let precise = pixels.map(|[red, green, blue]| {
+            Rgba::<u16>::from([red * 257, green * 257, blue * 257, u16::MAX])
+        });
+        let block = pixels
+            .map(|[red, green, blue]| Rgba::<u8>::from([red as u8, green as u8, blue as u8, 255]));
+        let (color, luminance) = precise_color_luminance(&precise, &config);
+        assert_eq!(average_color(&block), color);
+        assert!((tile_luminance(&block, color, &config) - luminance).abs() < 1e-9);
+    }
+
+    #[test]
+    fn transparent_pixels_are_ignored() {
+        let config = ConfigBuilder::new().build();
+        let pixels = [
+            Rgba::<u16>::from([u16::MAX, 0, 0, u16::MAX]),
+            Rgba::<u16>::from([0, 0, 0, 0]),
+        ];
+        let (color, luminance) = precise_color_luminance(&pixels, &config);
+        assert_eq!((255, 0, 0), color);
+        let opaque = precise_color_luminance(&pixels[..1], &config).1;
+        assert!((luminance - opaque / 2f64).abs() < 1e-9);
+        //a fully transparent tile is empty
+        assert_eq!(0f64, precise_color_luminance(&pixels[1..], &config).1);
+    }
+
+    #[test]
+    fn white_and_black() {
+        let config = ConfigBuilder::new().build();
+        let white = [Rgba::<u16>::from([u16::MAX; 4])];
+        let black = [Rgba::<u16>::from([0, 0, 0, u16::MAX])];
+        assert_eq!(
+            ((255, 255, 255), 1f64),
+            precise_color_luminance(&white, &config)
+        );
+        assert_eq!(((0, 0, 0), 0f64), precise_color_luminance(&black, &config));
+    }
+}
+
+/// Returns the relative luminance of the given rgb color, as defined by the WCAG.
+///
+/// In contrast to [`luminosity`], the color channels are linearized first and the result is
+/// between `0.0` (black) and `1.0` (white).
+///
+/// The formula comes from <https://www.w3.org/TR/WCAG21/#dfn-relative-luminance>
+fn relative_luminance((red, green, blue): (u8, u8, u8)) -> f64 {
+    let linear = |channel: u8| {
+        let channel = channel as f64 / 255f64;
+        if channel <= 0.03928 {
+            channel / 12.92
+        } else {
+            ((channel + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    0.2126 * linear(red) + 0.7152 * linear(green) + 0.0722 * linear(blue)
+}
+
+/// Returns the WCAG contrast ratio between two colors, which ranges from `1.0` to `21.0`.
+fn contrast_ratio(first: (u8, u8, u8), second: (u8, u8, u8)) -> f64 {
+    let first = relative_luminance(first);
+    let second = relative_luminance(second);
+    (first.max(second) + 0.05) / (first.min(second) + 0.05)
+}
+
+/// Returns the color adjusted to have at least the given contrast ratio against the background.
+///
+/// The color is mixed with either white or black, which keeps the hue of the color, but changes
+/// its lightness. It is preferably shifted away from the background in the direction it already is, if the
+/// contrast can not be reached that way, the other direction is used. If the contrast can not
+/// be reached at all, the color with the highest possible contrast is returned.
+///
+/// # Examples
+///
+/// ```compile_fail, compile will fail, this is an internal example
+/// //dark gray on black is lightened
+/// let color = with_min_contrast((20, 20, 20), (0, 0, 0), 4.5);
+/// assert!(contrast_ratio(color, (0, 0, 0)) >= 4.5);
+/// ```
+pub fn with_min_contrast(
+    color: (u8, u8, u8),
+    background: (u8, u8, u8),
+    ratio: f64,
+) -> (u8, u8, u8) {
+    if contrast_ratio(color, background) >= ratio {
+        return color;
+    }
+
+    //mix the color with the target, t = 0 is the color itself, t = 1 the target
+    let mix = |target: u8, t: f64| {
+        let mix_channel =
+            |channel: u8| (channel as f64 + (target as f64 - channel as f64) * t).round() as u8;
+        (
+            mix_channel(color.0),
+            mix_channel(color.1),
+            mix_channel(color.2),
+        )
+    };
+
+    //prefer to keep the color lighter/darker than the background, if it already is
+    let lighten = relative_luminance(color) >= relative_luminance(background);
+    let targets = if lighten { [u8::MAX, 0] } else { [0, u8::MAX] };
+
+    let Some(target) = targets
+        .into_iter()
+        .find(|target| contrast_ratio(mix(*target, 1f64), background) >= ratio)
+    else {
+        //the contrast can not be reached, so use the highest possible one
+        return targets
+            .into_iter()
+            .map(|target| mix(target, 1f64))
+            .max_by(|a, b| {
+                contrast_ratio(*a, background).total_cmp(&contrast_ratio(*b, background))
+            })
+            .expect("There are always two targets");
+    };
+
+    //binary search the smallest mix, which has enough contrast
+    let (mut low, mut high) = (0f64, 1f64);
+    for _ in 0..16 {
+        let middle = (low + high) / 2f64;
+        if contrast_ratio(mix(target, middle), background) >= ratio {
+            high = middle;
+        } else {
+            low = middle;
+        }
+    }
+    mix(target, high)
+}
+
+#[cfg(test)]
+mod test_min_contrast {
+    use crate::{config::MinContrast, ConfigBuilder};
+
+    use super::*;
+
+    #[test]
+    fn contrast_black_white() {
+        assert!((contrast_ratio((0, 0, 0), (255, 255, 255)) - 21f64).abs() < 0.001);
+        assert!((contrast_ratio((255, 255, 255), (0, 0, 0)) - 21f64).abs() < 0.001);
+        assert_eq!(1f64, contrast_ratio((100, 50, 20), (100, 50, 20)));
+    }
+
+    #[test]
+    fn enough_contrast_is_unchanged() {
+        assert_eq!(
+            (200, 180, 20),
+            with_min_contrast((200, 180, 20), (0, 0, 0), 4.5)
+        );
+    }
+
+    #[test]
+    fn near_background_is_lightened() {
+        let color = with_min_contrast((20, 10, 10), (10, 10, 10), 4.5);
+        assert!(contrast_ratio(color, (10, 10, 10)) >= 4.5);
+        //hue is kept, so red is still the dominant channel
+        assert!(color.0 > color.1);
+        assert_eq!(color.1, color.2);
+    }
+
+    #[test]
+    fn near_background_is_darkened() {
+        let color = with_min_contrast((230, 240, 250), (255, 255, 255), 7f64);
+        assert!(contrast_ratio(color, (255, 255, 255)) >= 7f64);
+        assert!(color.0 < color.1 && color.1 < color.2);
+    }
+
+    #[test]
+    fn uses_other_direction() {
+        //a lighter color on a light gray can not reach the contrast by lightening it
+        let color = with_min_contrast((210, 210, 210), (200, 200, 200), 3f64);
+        assert!(contrast_ratio(color, (200, 200, 200)) >= 3f64);
+        assert!(color.0 < 200);
+    }
+
+    #[test]
+    fn unreachable_uses_highest_contrast() {
+        assert_eq!(
+            (0, 0, 0),
+            with_min_contrast((128, 128, 128), (128, 128, 128), 21f64)
+        );
+    }
+
+    #[test]
+    fn cell_is_shifted() {
+        let background = (0, 0, 0);
+        let config = ConfigBuilder::new()
+            .min_contrast(MinContrast {
+                background,
+                ratio: 4.5,
+            })
+            .build();
+        let cell = correlating_cell(&[Rgba::<u8>::from([5, 5, 15, 255])], &config);
+        assert!(contrast_ratio((cell.red, cell.green, cell.blue), background) >= 4.5);
+        //the char is still chosen based on the original color
+        assert_eq!(
+            correlating_cell(
+                &[Rgba::<u8>::from([5, 5, 15, 255])],
+                &ConfigBuilder::new().build()
+            )
+            .char,
+            cell.char
+        );
+    }
+}
+
+/// Characters for horizontal textures, from dense to light.
+const HORIZONTAL_CHARS: [char; 2] = ['=', '-'];
+/// Characters for vertical textures, from dense to light.
+const VERTICAL_CHARS: [char; 2] = ['|', '!'];
+
+/// Returns a directional character, if the pixel block has a dominant texture direction.
+///
+/// The block has to contain the pixels column by column, so the pixel at `(x, y)` is at
+/// the index `x * tile_height + y`. The brightness changes between neighbouring pixels are summed up
+/// for both directions. If one direction clearly dominates, a character suggesting the structure is returned,
+/// for example a `-` for horizontal stripes, which change their brightness from top to bottom.
+/// Tiles which are too small to compare both directions or are (nearly) uniform return [`None`].
+///
+/// # Examples
+///
+/// ```compile_fail, compile will fail, this is an internal example
+/// //horizontal stripes, white on top of black
+/// let pixels = vec![
+///     Rgba::<u8>::from([255, 255, 255, 255]),
+///     Rgba::<u8>::from([0, 0, 0, 255]),
+///     Rgba::<u8>::from([255, 255, 255, 255]),
+///     Rgba::<u8>::from([0, 0, 0, 255]),
+/// ];
+/// assert_eq!(Some('-'), directional_char(&pixels, 2, 2, &config));
+/// ```
+pub fn directional_char(
+    block: &[Rgba<u8>],
+    tile_width: u32,
+    tile_height: u32,
+    config: &Config,
+) -> Option<char> {
+    //both directions need at least two pixels to be compared
+    if tile_width < 2 || tile_height < 2 {
+        return None;
+    }
+    assert_eq!((tile_width * tile_height) as usize, block.len());
+
+    let brightness = |x: u32, y: u32| {
+        let pixel = block[(x * tile_height + y) as usize];
+        config
+            .luminance_standard
+            .luminance(pixel.0[0], pixel.0[1], pixel.0[2])
+    };
+
+    //average change of the brightness along each axis
+    let mut change_x = 0f32;
+    let mut change_y = 0f32;
+    for x in 0..tile_width {
+        for y in 0..tile_height {
+            if x + 1 < tile_width {
+                change_x += (brightness(x + 1, y) - brightness(x, y)).abs();
+            }
+            if y + 1 < tile_height {
+                change_y += (brightness(x, y + 1) - brightness(x, y)).abs();
+            }
+        }
+    }
+    change_x /= ((tile_width - 1) * tile_height) as f32;
+    change_y /= (tile_width * (tile_height - 1)) as f32;
+
+    //ignore small changes, such as noise or gradients
+    const MIN_CHANGE: f32 = 16f32;
+    //one direction has to change at least twice as much as the other one
+    const DOMINANCE: f32 = 2f32;
+
+    let chars = if change_y >= MIN_CHANGE && change_y >= change_x * DOMINANCE {
+        //changes from top to bottom are horizontal structures
+        HORIZONTAL_CHARS
+    } else if change_x >= MIN_CHANGE && change_x >= change_y * DOMINANCE {
+        VERTICAL_CHARS
+    } else {
+        return None;
+    };
+
+    //use the denser char for bright tiles, like the normal characters
+    let (red, green, blue) = average_color(block);
+    let bright = config.luminance_standard.luminance(red, green, blue) >= 127.5;
+    Some(if bright != config.invert {
+        chars[0]
+    } else {
+        chars[1]
+    })
+}
+
+#[cfg(test)]
+mod test_directional_char {
+    use crate::ConfigBuilder;
+
+    use super::*;
+
+    /// Create a column by column block from a function returning the brightness at (x, y).
+    fn block(width: u32, height: u32, brightness: impl Fn(u32, u32) -> u8) -> Vec<Rgba<u8>> {
+        (0..width)
+            .flat_map(|x| (0..height).map(move |y| (x, y)))
+            .map(|(x, y)| {
+                let value = brightness(x, y);
+                Rgba::<u8>::from([value, value, value, 255])
+            })
+            .collect()
+    }
+
+    #[test]
+    fn horizontal_stripes() {
+        let config = ConfigBuilder::new().build();
+        let pixels = block(4, 4, |_, y| if y % 2 == 0 { 255 } else { 0 });
+        let char = directional_char(&pixels, 4, 4, &config).unwrap();
+        assert!(HORIZONTAL_CHARS.contains(&char));
+    }
+
+    #[test]
+    fn vertical_stripes() {
+        let config = ConfigBuilder::new().build();
+        let pixels = block(4, 4, |x, _| if x % 2 == 0 { 255 } else { 0 });
+        let char = directional_char(&pixels, 4, 4, &config).unwrap();
+        assert!(VERTICAL_CHARS.contains(&char));
+    }
+
+    #[test]
+    fn bright_and_dark_stripes() {
+        let config = ConfigBuilder::new().build();
+        let bright = block(2, 4, |_, y| if y % 2 == 0 { 255 } else { 200 });
+        assert_eq!(Some('='), directional_char(&bright, 2, 4, &config));
+        let dark = block(2, 4, |_, y| if y % 2 == 0 { 60 } else { 0 });
+        assert_eq!(Some('-'), directional_char(&dark, 2, 4, &config));
+        let config = ConfigBuilder::new().invert(true).build();
+        assert_eq!(Some('-'), directional_char(&bright, 2, 4, &config));
+    }
+
+    #[test]
+    fn uniform_tile() {
+        let config = ConfigBuilder::new().build();
+        let pixels = block(4, 4, |_, _| 128);
+        assert_eq!(None, directional_char(&pixels, 4, 4, &config));
+    }
+
+    #[test]
+    fn checkerboard_has_no_direction() {
+        let config = ConfigBuilder::new().build();
+        let pixels = block(4, 4, |x, y| if (x + y) % 2 == 0 { 255 } else { 0 });
+        assert_eq!(None, directional_char(&pixels, 4, 4, &config));
+    }
+
+    #[test]
+    fn too_small_tile() {
+        let config = ConfigBuilder::new().build();
+        let pixels = block(1, 4, |_, y| if y % 2 == 0 { 255 } else { 0 });
+        assert_eq!(None, directional_char(&pixels, 1, 4, &config));
+    }
+}
+
+/// Characters for edges, from no edge to a medium edge, with the minimum edge strength (0.0 - 1.0) they are used for.
+const EDGE_LEVELS: [(f32, char); 3] = [(0f32, ' '), (0.15, '.'), (0.3, ':')];
+/// Minimum edge strength (0.0 - 1.0) for strong edges, which use a character matching the direction of the edge.
+const STRONG_EDGE: f32 = 0.4;
+
+/// Returns a character for a block of an outlined image, graded by the strength of the edge.
+///
+/// The block has to contain the pixels of the outlined image column by column, like for [`directional_char`].
+/// The average brightness of the block is the strength of the edge. Weak edges use faint characters, such as `.`,
+/// while strong edges use a line character matching the direction of the edge: `-`, `|`, `/` or `\`.
+/// If the direction can not be determined, for example because the edge covers the complete tile, a `+` is used.
+///
+/// # Examples
+///
+/// ```compile_fail, compile will fail, this is an internal example
+/// //a strong vertical edge in the right two columns
+/// let pixels = (0..9)
+///     .map(|index| if index / 3 >= 1 { 255 } else { 0 })
+///     .map(|value| Rgba::<u8>::from([value, value, value, 255]))
+///     .collect::<Vec<_>>();
+/// assert_eq!('|', edge_glyph(&pixels, 3, 3));
+/// ```
+pub fn edge_glyph(block: &[Rgba<u8>], tile_width: u32, tile_height: u32) -> char {
+    assert_eq!((tile_width * tile_height) as usize, block.len());
+
+    let strength = |index: usize| {
+        let pixel = block[index];
+        luminosity(pixel.0[0], pixel.0[1], pixel.0[2]) / 255f32
+    };
+    let total = (0..block.len()).map(strength).sum::<f32>();
+    let average = total / block.len() as f32;
+
+    if average < STRONG_EDGE {
+        return EDGE_LEVELS
+            .iter()
+            .rev()
+            .find(|(min, _)| average >= *min)
+            .map_or(' ', |(_, char)| *char);
+    }
+
+    //the direction of the edge is the main axis of the bright pixels, weighted by their strength
+    let position = |index: usize| {
+        (
+            (index as u32 / tile_height) as f32,
+            (index as u32 % tile_height) as f32,
+        )
+    };
+    let (mut mean_x, mut mean_y) = (0f32, 0f32);
+    for index in 0..block.len() {
+        let (x, y) = position(index);
+        mean_x += x * strength(index) / total;
+        mean_y += y * strength(index) / total;
+    }
+    let (mut xx, mut yy, mut xy) = (0f32, 0f32, 0f32);
+    for index in 0..block.len() {
+        let (x, y) = position(index);
+        let weight = strength(index) / total;
+        xx += weight * (x - mean_x) * (x - mean_x);
+        yy += weight * (y - mean_y) * (y - mean_y);
+        xy += weight * (x - mean_x) * (y - mean_y);
+    }
+
+    //without a clear main axis, there is no direction
+    let spread = ((xx - yy) * (xx - yy) + 4f32 * xy * xy).sqrt();
+    if spread < 0.25 * (xx + yy) || xx + yy == 0f32 {
+        return '+';
+    }
+
+    //angle of the main axis, the y axis is pointing down
+    let angle = 0.5 * (2f32 * xy).atan2(xx - yy);
+    let degrees = angle.to_degrees();
+    if degrees.abs() < 22.5 {
+        '-'
+    } else if degrees.abs() > 67.5 {
+        '|'
+    } else if degrees > 0f32 {
+        //down and to the right
+        '\\'
+    } else {
+        '/'
+    }
+}
+
+#[cfg(test)]
+mod test_edge_glyph {
+    use super::*;
+
+    /// Create a block from a function returning the brightness for the position (x, y).
+    fn block(width: u32, height: u32, brightness: impl Fn(u32, u32) -> u8) -> Vec<Rgba<u8>> {
+        (0..width)
+            .flat_map(|x| (0..height).map(move |y| (x, y)))
+            .map(|(x, y)| {
+                let value = brightness(x, y);
+                Rgba::<u8>::from([value, value, value, 255])
+            })
+            .collect()
+    }
+
+    #[test]
+    fn no_edge() {
+        assert_eq!(' ', edge_glyph(&block(4, 4, |_, _| 0), 4, 4));
+    }
+
+    #[test]
+    fn gradual_edge_is_weaker() {
+        //a gradual edge results in weak edge strengths spread over the tile
+        let gradual = edge_glyph(&block(4, 4, |_, _| 40), 4, 4);
+        let medium = edge_glyph(&block(4, 4, |x, _| if x == 1 { 255 } else { 40 }), 4, 4);
+        let sharp = edge_glyph(
+            &block(4, 4, |x, _| if x == 1 || x == 2 { 255 } else { 0 }),
+            4,
+            4,
+        );
+        assert_eq!('.', gradual);
+        assert_eq!(':', medium);
+        assert_eq!('|', sharp);
+    }
+
+    #[test]
+    fn strong_edge_directions() {
+        assert_eq!(
+            '-',
+            edge_glyph(&block(5, 5, |_, y| if y > 1 { 255 } else { 0 }), 5, 5)
+        );
+        assert_eq!(
+            '|',
+            edge_glyph(&block(5, 5, |x, _| if x < 3 { 255 } else { 0 }), 5, 5)
+        );
+        assert_eq!(
+            '\\',
+            edge_glyph(
+                &block(3, 3, |x, y| if x.abs_diff(y) <= 1 { 255 } else { 0 }),
+                3,
+                3
+            )
+        );
+        assert_eq!(
+            '/',
+            edge_glyph(
+                &block(3, 3, |x, y| if x + y >= 1 && x + y <= 3 { 255 } else { 0 }),
+                3,
+                3
+            )
+        );
+    }
+
+    #[test]
+    fn covered_tile_has_no_direction() {
+        assert_eq!('+', edge_glyph(&block(4, 4, |_, _| 255), 4, 4));
+    }
+}
+
+/// Returns a line character matching the direction of the edge in the given tile of the image, if the edge is strong enough.
+///
+/// A Sobel operator is applied to the luminance of each pixel of the tile at `(x, y, width, height)`, using the neighbouring pixels
+/// of the image, so edges at the border of the tile are found as well. The gradients of the tile are combined into a structure tensor,
+/// which results in the dominant gradient direction, even if opposite gradients on both sides of a thin line cancel each other out.
+/// If the strongest normalized gradient magnitude (`0.0` - `1.0`) is at least the threshold, the edge character `|`, `-`, `/` or `\`
+/// is returned, which is perpendicular to the gradient. Otherwise, `None` is returned, so the density characters can be used.
+///
+/// # Examples
+///
+/// ```compile_fail, compile will fail, this is an internal example
+/// //a white left half and a black right half
+/// let img = DynamicImage::ImageRgb8(RgbImage::from_fn(8, 8, |x, _| if x < 4 { Rgb([255; 3]) } else { Rgb([0; 3]) }));
+/// assert_eq!(Some('|'), sobel_char(&img, (2, 2, 4, 4), 0.3, LuminanceStandard::Rec709));
+/// ```
+pub fn sobel_char(
+    image: &DynamicImage,
+    (x, y, width, height): (u32, u32, u32, u32),
+    threshold: f32,
+    standard: LuminanceStandard,
+) -> Option<char> {
+    let (image_width, image_height) = image.dimensions();
+    if image_width == 0 || image_height == 0 || width == 0 || height == 0 {
+        return None;
+    }
+    //the luminance of a pixel, with the coordinates clamped to the image
+    let luminance = |x: i64, y: i64| {
+        let pixel = image.get_pixel(
+            x.clamp(0, image_width as i64 - 1) as u32,
+            y.clamp(0, image_height as i64 - 1) as u32,
+        );
+        standard.luminance(pixel.0[0], pixel.0[1], pixel.0[2]) / 255f32
+    };
+
+    //sums of the structure tensor and the strongest gradient
+    let (mut xx, mut yy, mut xy) = (0f32, 0f32, 0f32);
+    let mut strongest = 0f32;
+    for p_x in x as i64..(x + width) as i64 {
+        for p_y in y as i64..(y + height) as i64 {
+            //differences of the opposite neighbours, weighted by the sobel kernels
+            let difference_x =
+                |offset: i64| luminance(p_x + 1, p_y + offset) - luminance(p_x - 1, p_y + offset);
+            let difference_y =
+                |offset: i64| luminance(p_x + offset, p_y + 1) - luminance(p_x + offset, p_y - 1);
+            let gradient_x = difference_x(-1) + 2f32 * difference_x(0) + difference_x(1);
+            let gradient_y = difference_y(-1) + 2f32 * difference_y(0) + difference_y(1);
+            xx += gradient_x * gradient_x;
+            yy += gradient_y * gradient_y;
+            xy += gradient_x * gradient_y;
+            strongest = strongest.max(gradient_x.hypot(gradient_y));
+        }
+    }
+
+    //a thin edge only covers a few pixels of the tile, so the strongest gradient is used,
+    //the largest gradient of the sobel operator is 4
+    let magnitude = (strongest / 4f32).min(1f32);
+    if magnitude < threshold || magnitude == 0f32 {
+        return None;
+    }
+
+    //dominant direction of the gradient, the y axis is pointing down
+    let degrees = (0.5 * (2f32 * xy).atan2(xx - yy)).to_degrees();
+    //the edge is perpendicular to the gradient
+    Some(if degrees.abs() < 22.5 {
+        '|'
+    } else if degrees.abs() > 67.5 {
+        '-'
+    } else if degrees > 0f32 {
+        '/'
+    } else {
+        '\\'
+    })
+}
+
+#[cfg(test)]
+mod test_sobel_char {
+    use image::{Rgb, RgbImage};
+
+    use super::*;
+
+    fn image(bright: impl Fn(u32, u32) -> bool) -> DynamicImage {
+        DynamicImage::ImageRgb8(RgbImage::from_fn(8, 8, |x, y| {
+            if bright(x, y) {
+                Rgb([255, 255, 255])
+            } else {
+                Rgb([0, 0, 0])
+            }
+        }))
+    }
+
+    const STANDARD: LuminanceStandard = LuminanceStandard::Rec709;
+
+    #[test]
+    fn vertical_edge() {
+        let img = image(|x, _| x < 4);
+        assert_eq!(Some('|'), sobel_char(&img, (2, 2, 4, 4), 0.3, STANDARD));
+    }
+
+    #[test]
+    fn horizontal_edge() {
+        let img = image(|_, y| y < 4);
+        assert_eq!(Some('-'), sobel_char(&img, (2, 2, 4, 4), 0.3, STANDARD));
+    }
+
+    #[test]
+    fn diagonal_edges() {
+        //bright top left corner, the edge goes from the top right to the bottom left
+        let img = image(|x, y| x + y < 8);
+        assert_eq!(Some('/'), sobel_char(&img, (2, 2, 4, 4), 0.3, STANDARD));
+        //bright top right corner
+        let img = image(|x, y| x > y);
+        assert_eq!(Some('\\'), sobel_char(&img, (2, 2, 4, 4), 0.3, STANDARD));
+    }
+
+    #[test]
+    fn thin_line() {
+        //the gradients on both sides of the line point in opposite directions
+        let img = image(|x, _| x == 3);
+        assert_eq!(Some('|'), sobel_char(&img, (2, 2, 4, 4), 0.3, STANDARD));
+    }
+
+    #[test]
+    fn edge_at_tile_border() {
+        //the edge is between the tile and its neighbour
+        let img = image(|x, _| x < 4);
+        assert_eq!(Some('|'), sobel_char(&img, (4, 0, 4, 4), 0.1, STANDARD));
+    }
+
+    #[test]
+    fn below_threshold() {
+        let img = image(|_, _| true);
+        assert_eq!(None, sobel_char(&img, (0, 0, 8, 8), 0f32, STANDARD));
+        //a faint edge between two grays
+        let img = DynamicImage::ImageRgb8(RgbImage::from_fn(8, 8, |x, _| {
+            Rgb([if x < 4 { 100 } else { 120 }; 3])
+        }));
+        assert_eq!(None, sobel_char(&img, (2, 2, 4, 4), 0.3, STANDARD));
+        assert_eq!(Some('|'), sobel_char(&img, (2, 2, 4, 4), 0.05, STANDARD));
+    }
+}
+
+/// Returns the dominant color of the image.
+///
+/// The colors of the image are reduced to 16 levels per channel, the most common of the reduced colors
+/// is the dominant one. The returned color is the average of all pixels with this reduced color,
+/// so it is closer to the original colors. Large images are downscaled first, since the exact pixels do not matter.
+/// Fully transparent pixels are ignored, if there are only transparent pixels, black is returned.
+///
+/// # Examples
+///
+/// ```
+/// let img = image::DynamicImage::ImageRgb8(image::RgbImage::from_pixel(
+///     10,
+///     10,
+///     image::Rgb([0, 0, 255]),
+/// ));
+/// assert_eq!((0, 0, 255), artem::dominant_color(&img));
+/// ```
+pub fn dominant_color(image: &DynamicImage) -> (u8, u8, u8) {
+    let image = if image.width() > 256 || image.height() > 256 {
+        image.thumbnail(256, 256)
+    } else {
+        image.clone()
+    };
+
+    //count and sum up the pixels of each reduced color
+    let mut buckets: HashMap<(u8, u8, u8), (u32, [u32; 3])> = HashMap::new();
+    for (_, _, pixel) in image.pixels() {
+        if pixel.0[3] == 0 {
+            continue;
+        }
+        let (count, sum) = buckets
+            .entry((pixel.0[0] >> 4, pixel.0[1] >> 4, pixel.0[2] >> 4))
+            .or_default();
+        *count += 1;
+        for (sum, channel) in sum.iter_mut().zip(pixel.0) {
+            *sum += channel as u32;
+        }
+    }
+
+    //use the reduced color itself on ties, so the result does not depend on the order of the hashmap
+    buckets
+        .into_iter()
+        .max_by_key(|(color, (count, _))| (*count, *color))
+        .map_or((0, 0, 0), |(_, (count, sum))| {
+            (
+                (sum[0] / count) as u8,
+                (sum[1] / count) as u8,
+                (sum[2] / count) as u8,
+            )
+        })
+}
+
+#[cfg(test)]
+mod test_dominant_color {
+    use image::{ImageBuffer, Rgb, Rgba};
+
+    use super::*;
+
+    #[test]
+    fn mostly_blue() {
+        let img = DynamicImage::ImageRgb8(ImageBuffer::from_fn(100, 100, |x, y| {
+            if x < 20 {
+                Rgb([255, 200, 0])
+            } else if y < 10 {
+                Rgb([255, 255, 255])
+            } else {
+                //slightly varying blue
+                Rgb([10, 20 + (x % 4) as u8, 200 + (y % 8) as u8])
+            }
+        }));
+        let (red, green, blue) = dominant_color(&img);
+        assert!(blue > 190);
+        assert!(blue > red * 2 && blue > green * 2);
+    }
+
+    #[test]
+    fn single_color() {
+        let img = DynamicImage::ImageRgb8(ImageBuffer::from_pixel(3, 3, Rgb([154, 85, 54])));
+        assert_eq!((154, 85, 54), dominant_color(&img));
+    }
+
+    #[test]
+    fn transparent_pixels_are_ignored() {
+        let img = DynamicImage::ImageRgba8(ImageBuffer::from_fn(10, 10, |x, _| {
+            if x < 8 {
+                Rgba([0, 0, 0, 0])
+            } else {
+                Rgba([255, 0, 0, 255])
+            }
+        }));
+        assert_eq!((255, 0, 0), dominant_color(&img));
+    }
+
+    #[test]
+    fn large_image() {
+        let img = DynamicImage::ImageRgb8(ImageBuffer::from_pixel(1000, 600, Rgb([0, 128, 0])));
+        assert_eq!((0, 128, 0), dominant_color(&img));
+    }
+}
+
+/// Limit the number of non-whitespace characters to the given budget.
+///
+/// The cells are expected to be row by row, with the given number of columns. Only the cells
+/// with the highest gradient magnitude, which is calculated from the luminosity of their neighbouring cells,
+/// keep their character, all other characters are replaced with a space. This keeps the most
+/// important structures of the image, such as edges, while removing flat areas.
+/// On equal magnitudes, the earlier cell is kept, so the result is deterministic.
+///
+/// # Examples
+///
+/// ```compile_fail, compile will fail, this is an internal example
+/// apply_ink_budget(&mut cells, columns, 100, LuminanceStandard::Rec709);
+/// assert!(cells.iter().filter(|cell| !cell.char.is_whitespace()).count() <= 100);
+/// ```
+pub fn apply_ink_budget(
+    cells: &mut [Cell],
+    columns: usize,
+    budget: usize,
+    standard: LuminanceStandard,
+) {
+    if columns == 0 {
+        return;
+    }
+    let rows = cells.len() / columns;
+    let luminosity_at = |x: usize, y: usize| {
+        let cell = cells[y * columns + x];
+        standard.luminance(cell.red, cell.green, cell.blue)
+    };
+
+    //gradient magnitude of each cell with a non-whitespace character
+    let mut magnitudes = (0..rows)
+        .flat_map(|y| (0..columns).map(move |x| (x, y)))
+        .filter(|(x, y)| !cells[y * columns + x].char.is_whitespace())
+        .map(|(x, y)| {
+            let gradient_x =
+                luminosity_at((x + 1).min(columns - 1), y) - luminosity_at(x.saturating_sub(1), y);
+            let gradient_y =
+                luminosity_at(x, (y + 1).min(rows - 1)) - luminosity_at(x, y.saturating_sub(1));
+            (y * columns + x, gradient_x.hypot(gradient_y))
+        })
+        .collect::<Vec<_>>();
+
+    if magnitudes.len() <= budget {
+        return;
+    }
+
+    //sort by descending magnitude, keep the order of the cells for equal magnitudes
+    magnitudes.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+    for (index, _) in magnitudes.into_iter().skip(budget) {
+        cells[index].char = ' ';
+    }
+}
+
+#[cfg(test)]
+mod test_ink_budget {
+    use super::*;
+
+    fn cell(value: u8) -> Cell {
+        Cell {
+            char: '#',
+            red: value,
+            green: value,
+            blue: value,
+        }
+    }
+
+    #[test]
+    fn keeps_highest_gradient() {
+        //a single bright cell in the middle of dark cells
+        let mut cells = (0..25)
+            .map(|index| if index == 12 { cell(255) } else { cell(0) })
+            .collect::<Vec<_>>();
+        apply_ink_budget(&mut cells, 5, 4, LuminanceStandard::default());
+
+        let kept = cells
+            .iter()
+            .enumerate()
+            .filter(|(_, cell)| !cell.char.is_whitespace())
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>();
+        //the direct neighbours of the bright cell have the highest gradient
+        assert_eq!(vec![7, 11, 13, 17], kept);
+    }
+
+    #[test]
+    fn budget_is_not_exceeded() {
+        let mut cells = (0..100)
+            .map(|index| cell((index * 37 % 256) as u8))
+            .collect::<Vec<_>>();
+        for budget in [0, 1, 10, 99] {
+            let mut cells = cells.clone();
+            apply_ink_budget(&mut cells, 10, budget, LuminanceStandard::default());
+            assert_eq!(
+                budget,
+                cells
+                    .iter()
+                    .filter(|cell| !cell.char.is_whitespace())
+                    .count()
+            );
+        }
+
+        //a larger budget does not change anything
+        let original = cells.clone();
+        apply_ink_budget(&mut cells, 10, 100, LuminanceStandard::default());
+        assert_eq!(original, cells);
+    }
+
+    #[test]
+    fn whitespace_is_not_counted() {
+        let mut cells = vec![cell(0), cell(255), cell(0), cell(255)];
+        cells[0].char = ' ';
+        cells[1].char = ' ';
+        apply_ink_budget(&mut cells, 2, 2, LuminanceStandard::default());
+        assert_eq!(['#', '#'], [cells[2].char, cells[3].char]);
+    }
+}
+
+/// Stretch the luminances to the full range between `0.0` and `1.0`.
+///
+/// The lowest luminance is mapped to `0.0` and the highest luminance to `1.0`.
+/// If all luminances are equal, there is nothing to stretch, so they are not changed.
+pub fn stretch_contrast(luminances: &mut [f32]) {
+    let min = luminances.iter().copied().fold(f32::INFINITY, f32::min);
+    let max = luminances.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    if max - min <= f32::EPSILON {
+        return;
+    }
+
+    for luminance in luminances.iter_mut() {
+        *luminance = map_range((min, max), (0f32, 1f32), *luminance);
+    }
+}
+
+#[cfg(test)]
+mod test_stretch_contrast {
+    use super::*;
+
+    #[test]
+    fn stretches_to_full_range() {
+        let mut luminances = vec![0.25, 0.5, 0.75];
+        stretch_contrast(&mut luminances);
+        assert_eq!(vec![0.0, 0.5, 1.0], luminances);
+    }
+
+    #[test]
+    fn equal_luminances() {
+        let mut luminances = vec![0.3; 4];
+        stretch_contrast(&mut luminances);
+        assert_eq!(vec![0.3; 4], luminances);
+    }
+}
+
+/// The fraction of the luminances, which is clipped at each end by [`auto_contrast`].
+const AUTO_CONTRAST_CLIP: f32 = 0.01;
+
+/// Stretch the luminances between their 1st and 99th percentile to the full range between `0.0` and `1.0`.
+///
+/// The percentiles are taken from a histogram of the luminances with 256 bins. Luminances below the 1st percentile
+/// are clamped to `0.0` and above the 99th percentile to `1.0`, so a few outliers do not prevent the stretching,
+/// like they do for [`stretch_contrast`]. If both percentiles are equal, the luminances are not changed.
+pub fn auto_contrast(luminances: &mut [f32]) {
+    let mut histogram = [0usize; 256];
+    for luminance in luminances.iter() {
+        histogram[(luminance.clamp(0f32, 1f32) * 255f32).round() as usize] += 1;
+    }
+    //the first bin, where the number of luminances up to it reaches the count
+    let percentile = |count: f32| {
+        let mut sum = 0;
+        histogram
+            .iter()
+            .position(|bin| {
+                sum += bin;
+                sum as f32 >= count
+            })
+            .unwrap_or(255) as f32
+            / 255f32
+    };
+    let total = luminances.len() as f32;
+    let low = percentile((total * AUTO_CONTRAST_CLIP).max(1f32));
+    let high = percentile(total * (1f32 - AUTO_CONTRAST_CLIP));
+    log::debug!("Auto contrast between {low} and {high}");
+    if high - low <= f32::EPSILON {
+        return;
+    }
+
+    for luminance in luminances.iter_mut() {
+        *luminance = map_range_clamped((low, high), (0f32, 1f32), *luminance);
+    }
+}
+
+#[cfg(test)]
+mod test_auto_contrast {
+    use super::*;
+
+    #[test]
+    fn stretches_to_full_range() {
+        let mut luminances = vec![0.2, 0.3, 0.4];
+        auto_contrast(&mut luminances);
+        assert_eq!(0f32, luminances[0]);
+        assert!((luminances[1] - 0.5).abs() < 0.01, "{}", luminances[1]);
+        assert_eq!(1f32, luminances[2]);
+    }
+
+    #[test]
+    fn clips_outliers() {
+        //a single white outlier among 200 dark luminances
+        let mut luminances = (0..200)
+            .map(|index| 0.1 + (index % 2) as f32 * 0.1)
+            .collect::<Vec<_>>();
+        luminances.push(1.0);
+        auto_contrast(&mut luminances);
+        assert_eq!(0f32, luminances[0]);
+        assert_eq!(1f32, luminances[1]);
+        assert_eq!(1f32, luminances[200]);
+
+        //the outlier prevents the stretching by the minimum and maximum
+        let mut stretched = vec![0.1, 0.2, 1.0];
+        stretch_contrast(&mut stretched);
+        assert!(stretched[1] < 0.2);
+    }
+
+    #[test]
+    fn equal_luminances() {
+        let mut luminances = vec![0.3; 4];
+        auto_contrast(&mut luminances);
+        assert_eq!(vec![0.3; 4], luminances);
+    }
+}
+
+/// Round the color channel to the nearest multiple of the step.
+///
+/// Values, which would be rounded above 255, use the largest multiple of the step instead,
+/// so the result is always a multiple of the step. A step of 0 or 1 does not change the value.
+///
+/// # Examples
+///
+/// ```compile_fail, compile will fail, this is an internal example
+/// assert_eq!(32, quantize_channel(30, 16));
+/// assert_eq!(240, quantize_channel(255, 16));
+/// ```
+pub fn quantize_channel(value: u8, step: u8) -> u8 {
+    if step <= 1 {
+        return value;
+    }
+    let step = step as u16;
+    let rounded = (value as u16 + step / 2) / step * step;
+    //the largest multiple of the step, which fits into a channel
+    rounded.min(u8::MAX as u16 / step * step) as u8
+}
+
+#[cfg(test)]
+mod test_quantize_channel {
+    use super::*;
+
+    #[test]
+    fn rounds_to_nearest_multiple() {
+        assert_eq!(0, quantize_channel(7, 16));
+        assert_eq!(16, quantize_channel(8, 16));
+        assert_eq!(32, quantize_channel(30, 16));
+        assert_eq!(100, quantize_channel(120, 100));
+    }
+
+    #[test]
+    fn results_are_multiples() {
+        for step in 1..=u8::MAX {
+            for value in 0..=u8::MAX {
+                assert_eq!(0, quantize_channel(value, step) % step);
+            }
+        }
+    }
+
+    #[test]
+    fn no_change() {
+        for value in 0..=u8::MAX {
+            assert_eq!(value, quantize_channel(value, 0));
+            assert_eq!(value, quantize_channel(value, 1));
+        }
+    }
+}
+
+/// Darken the luminances towards the edges with the given strength between `0.0` and `1.0`.
+///
+/// The luminances are row by row, with the given number of columns and rows. Each luminance is multiplied with
+/// `1 - strength * distance²`, where the distance to the center of the grid is normalized to `1.0` in the corners,
+/// so the falloff follows the shape of the image.
+pub fn apply_vignette(luminances: &mut [f32], (columns, rows): (usize, usize), strength: f32) {
+    for (index, luminance) in luminances.iter_mut().enumerate() {
+        //distance of the center of the cell to the center of the grid, from -0.5 to 0.5
+        let x = ((index % columns) as f32 + 0.5) / columns as f32 - 0.5;
+        let y = ((index / columns) as f32 + 0.5) / rows as f32 - 0.5;
+        //the squared distance to a corner is 0.5
+        let distance = (x * x + y * y) * 2f32;
+        *luminance *= (1f32 - strength * distance).clamp(0f32, 1f32);
+    }
+}
+
+/// Multiply the luminances with the brightness of the given mask, which has to have one pixel per luminance.
+///
+/// The luminances are row by row, like the pixels of the mask. A white pixel keeps the luminance,
+/// while a black pixel sets it to `0.0`, which results in the lightest character.
+pub fn apply_mask(luminances: &mut [f32], mask: &GrayImage) {
+    for (luminance, weight) in luminances.iter_mut().zip(mask.pixels()) {
+        *luminance *= weight.0[0] as f32 / 255f32;
+    }
+}
+
+#[cfg(test)]
+mod test_apply_mask {
+    use super::*;
+
+    #[test]
+    fn multiplies_brightness() {
+        let mut luminances = vec![1.0, 0.5, 0.8, 1.0];
+        let mask = GrayImage::from_raw(2, 2, vec![255, 255, 0, 51]).unwrap();
+        apply_mask(&mut luminances, &mask);
+        assert_eq!(vec![1.0, 0.5, 0.0, 0.2], luminances);
+    }
+}
+
+#[cfg(test)]
+mod test_apply_vignette {
+    use super::*;
+
+    #[test]
+    fn darker_towards_edges() {
+        let mut luminances = vec![1.0; 25];
+        apply_vignette(&mut luminances, (5, 5), 1.0);
+        //center, edge and corner
+        assert_eq!(1.0, luminances[12]);
+        assert!(luminances[10] < luminances[11]);
+        assert!(luminances[0] < luminances[10]);
+        assert!(luminances[0] < 0.4);
+    }
+
+    #[test]
+    fn zero_strength() {
+        let mut luminances = vec![0.7; 6];
+        apply_vignette(&mut luminances, (3, 2), 0.0);
+        assert_eq!(vec![0.7; 6], luminances);
+    }
+}
+
+/// Returns the characters for the luminances using Floyd–Steinberg dithering.
+///
+/// The luminances are expected to be between `0.0` and `1.0` and row by row, with the given number of columns.
+/// The cells are processed from left to right and top to bottom. The difference between the luminance of a cell
+/// and the luminance represented by its character is distributed to the following neighbouring cells,
+/// so a smooth gradient alternates between characters instead of showing visible bands.
+///
+/// # Examples
+///
+/// ```compile_fail, compile will fail, this is an internal example
+/// let config = ConfigBuilder::new().characters("# ".to_string()).build();
+/// let chars = dithered_glyphs(&[0.5; 4], 2, &config);
+/// assert_eq!(vec!['#', ' ', ' ', '#'], chars);
+/// ```
+pub fn dithered_glyphs(luminances: &[f32], columns: usize, config: &Config) -> Vec<char> {
+    diffuse_errors(luminances, columns, config)
+        .into_iter()
+        .map(|(char, _)| char)
+        .collect()
+}
+
+/// Returns the dithered character of each cell, together with the error, which the cell received from its neighbours.
+///
+/// The cells have to be processed in order, since each cell depends on the errors of the previous cells,
+/// so this can not be parallelized. See [`dithered_glyphs`] for more information.
+fn diffuse_errors(luminances: &[f32], columns: usize, config: &Config) -> Vec<(char, f32)> {
+    let rows = luminances.len().div_ceil(columns);
+    let levels = config_glyph_levels(config);
+    let mut glyphs = Vec::with_capacity(luminances.len());
+
+    //errors of the current and the next row, with an additional column on both sides
+    let mut current = vec![0f32; columns + 2];
+    let mut next = vec![0f32; columns + 2];
+    for (row, cells) in luminances.chunks(columns).enumerate() {
+        for (column, luminance) in cells.iter().enumerate() {
+            let levels = match &config.ramp_gradient {
+                Some(gradient) if uses_gradient_end(gradient, (column, row), (columns, rows)) => {
+                    &levels[1]
+                }
+                _ => &levels[0],
+            };
+            let value = (luminance + current[column + 1]).clamp(0f32, 1f32);
+            let (char, level) = levels[(value * 255f32).round() as usize];
+            glyphs.push((char, current[column + 1]));
+
+            let error = value - level;
+            current[column + 2] += error * 7f32 / 16f32;
+            next[column] += error * 3f32 / 16f32;
+            next[column + 1] += error * 5f32 / 16f32;
+            next[column + 2] += error / 16f32;
+        }
+        std::mem::swap(&mut current, &mut next);
+        next.fill(0f32);
+    }
+    glyphs
+}
+
+/// Returns the characters for the luminances using ordered dithering with a Bayer matrix of the given size (4 or 8).
+///
+/// The luminances are expected to be between `0.0` and `1.0` and row by row, with the given number of columns.
+/// The luminance of each cell is offset by the threshold of the matrix at its position, by at most half
+/// the distance between two characters, so equal luminances between two characters alternate between them in a fixed pattern.
+/// Unlike [`dithered_glyphs`], each cell only depends on its own position, so the result is the same for any order.
+///
+/// # Examples
+///
+/// ```compile_fail, compile will fail, this is an internal example
+/// let config = ConfigBuilder::new().characters("# ".to_string()).build();
+/// let chars = ordered_glyphs(&[0.5; 4], 2, 4, &config);
+/// assert_eq!(vec![' ', '#', '#', ' '], chars);
+/// ```
+pub fn ordered_glyphs(
+    luminances: &[f32],
+    columns: usize,
+    size: usize,
+    config: &Config,
+) -> Vec<char> {
+    let rows = luminances.len().div_ceil(columns);
+    let levels = config_glyph_levels(config);
+    //the distance between two characters, which are spread evenly over the luminances
+    let steps = levels
+        .iter()
+        .map(|levels| {
+            let chars = levels
+                .iter()
+                .map(|(char, _)| *char)
+                .collect::<std::collections::HashSet<_>>()
+                .len();
+            1f32 / chars as f32
+        })
+        .collect::<Vec<_>>();
+
+    luminances
+        .iter()
+        .enumerate()
+        .map(|(index, luminance)| {
+            let (column, row) = (index % columns, index / columns);
+            let set = match &config.ramp_gradient {
+                Some(gradient) if uses_gradient_end(gradient, (column, row), (columns, rows)) => 1,
+                _ => 0,
+            };
+            let value =
+                (luminance + bayer_threshold(size, (column, row)) * steps[set]).clamp(0f32, 1f32);
+            levels[set][(value * 255f32).round() as usize].0
+        })
+        .collect()
+}
+
+/// Returns the threshold (`-0.5` - `0.5`) of the Bayer matrix with the given size (4 or 8) at the (column, row) position.
+///
+/// The matrix is repeated over the whole image, so the position is taken modulo its size.
+fn bayer_threshold(size: usize, (column, row): (usize, usize)) -> f32 {
+    let value = if size == 4 {
+        BAYER_4[row % 4][column % 4]
+    } else {
+        BAYER_8[row % 8][column % 8]
+    };
+    (value as f32 + 0.5) / (size * size) as f32 - 0.5
+}
+
+/// Returns the characters for the luminances using random dithering, which looks like a stipple drawing.
+///
+/// The luminances are expected to be between `0.0` and `1.0` and row by row, with the given number of columns.
+/// The characters are spread evenly over the luminances and each cell randomly uses one of the two characters next to its luminance.
+/// The closer the luminance is to the denser character, the higher is its chance, so on average the cells have their luminance,
+/// but without a visible pattern. The random numbers are generated from the seed, so the same seed always results in the same characters.
+///
+/// # Examples
+///
+/// ```compile_fail, compile will fail, this is an internal example
+/// let config = ConfigBuilder::new().characters("# ".to_string()).build();
+/// assert_eq!(
+///     stippled_glyphs(&[0.5; 16], 4, 42, &config),
+///     stippled_glyphs(&[0.5; 16], 4, 42, &config)
+/// );
+/// ```
+pub fn stippled_glyphs(
+    luminances: &[f32],
+    columns: usize,
+    seed: u64,
+    config: &Config,
+) -> Vec<char> {
+    let rows = luminances.len().div_ceil(columns);
+    //the characters of each set, from the lowest to the highest luminance
+    let ramps = config_glyph_levels(config)
+        .into_iter()
+        .map(|levels| {
+            let mut chars = levels.into_iter().map(|(char, _)| char).collect::<Vec<_>>();
+            chars.dedup();
+            chars
+        })
+        .collect::<Vec<_>>();
+    let mut rng = SplitMix64::new(seed);
+
+    luminances
+        .iter()
+        .enumerate()
+        .map(|(index, luminance)| {
+            let (column, row) = (index % columns, index / columns);
+            let set = match &config.ramp_gradient {
+                Some(gradient) if uses_gradient_end(gradient, (column, row), (columns, rows)) => 1,
+                _ => 0,
+            };
+            let chars = &ramps[set];
+            let position = luminance.clamp(0f32, 1f32) * (chars.len() - 1) as f32;
+            let lower = position.floor() as usize;
+            //a random number is used for every cell, so the following cells do not depend on the luminance
+            if (rng.next_f64() as f32) < position - lower as f32 {
+                chars[lower + 1]
+            } else {
+                chars[lower]
+            }
+        })
+        .collect()
+}
+
+/// Returns the levels of the characters of the config, see [`glyph_levels`].
+///
+/// A ramp gradient needs the levels of both sets of characters, the start is the first and the end the second set.
+fn config_glyph_levels(config: &Config) -> Vec<Vec<(char, f32)>> {
+    match &config.ramp_gradient {
+        Some(gradient) => vec![
+            glyph_levels(|luminance| ramp_glyph(luminance, &gradient.start, config)),
+            glyph_levels(|luminance| ramp_glyph(luminance, &gradient.end, config)),
+        ],
+        None => vec![glyph_levels(|luminance| {
+            glyph_for_luminance(luminance, config)
+        })],
+    }
+}
+
+/// Returns the character and the luminance it represents for each of the 256 luminance values.
+///
+/// The represented luminance is the average of all luminance values, which are mapped to the same character
+/// by the given glyph function.
+fn glyph_levels(glyph: impl Fn(f64) -> char) -> Vec<(char, f32)> {
+    let chars = (0..=u8::MAX)
+        .map(|value| glyph(value as f64 / 255f64))
+        .collect::<Vec<_>>();
+
+    let mut sums: HashMap<char, (f32, u32)> = HashMap::new();
+    for (value, char) in chars.iter().enumerate() {
+        let (sum, count) = sums.entry(*char).or_default();
+        *sum += value as f32 / 255f32;
+        *count += 1;
+    }
+
+    chars
+        .into_iter()
+        .map(|char| {
+            let (sum, count) = sums[&char];
+            (char, sum / count as f32)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test_dithered_glyphs {
+    use crate::ConfigBuilder;
+
+    use super::*;
+
+    #[test]
+    fn mid_gray_alternates() {
+        let config = ConfigBuilder::new().characters("# ".to_string()).build();
+        let chars = dithered_glyphs(&[0.5; 16], 4, &config);
+        let spaces = chars.iter().filter(|char| **char == ' ').count();
+        assert_eq!(8, spaces);
+    }
+
+    #[test]
+    fn black_and_white_are_exact() {
+        let config = ConfigBuilder::new().characters("#k. ".to_string()).build();
+        assert_eq!(vec![' '; 6], dithered_glyphs(&[0.0; 6], 3, &config));
+        assert_eq!(vec!['#'; 6], dithered_glyphs(&[1.0; 6], 3, &config));
+    }
+
+    #[test]
+    fn gradient_carries_errors() {
+        let config = ConfigBuilder::new().characters("#k. ".to_string()).build();
+        //a smooth horizontal gradient over 4 rows
+        let luminances = (0..4)
+            .flat_map(|_| (0..32).map(|column| column as f32 / 31f32))
+            .collect::<Vec<_>>();
+        let cells = diffuse_errors(&luminances, 32, &config);
+        //the first cell does not have any previous cells
+        assert_eq!(0f32, cells[0].1);
+        //the middle of the image received errors from its neighbours
+        let (_, error) = cells[2 * 32 + 15];
+        assert!(error.abs() > 0.01, "{error} is too small");
+        //the errors are only distributed, the average luminance stays about the same
+        let levels = glyph_levels(|luminance| glyph_for_luminance(luminance, &config));
+        let average = cells
+            .iter()
+            .map(|(char, _)| {
+                levels
+                    .iter()
+                    .find(|(level_char, _)| level_char == char)
+                    .unwrap()
+                    .1
+            })
+            .sum::<f32>()
+            / cells.len() as f32;
+        assert!(
+            (average - 0.5).abs() < 0.05,
+            "{average} is not close to 0.5"
+        );
+    }
+
+    #[test]
+    fn levels_respect_invert() {
+        let config = ConfigBuilder::new()
+            .characters("# ".to_string())
+            .invert(true)
+            .build();
+        let levels = glyph_levels(|luminance| glyph_for_luminance(luminance, &config));
+        assert_eq!('#', levels[0].0);
+        assert_eq!(' ', levels[255].0);
+        assert!(levels[0].1 < 0.5 && levels[255].1 > 0.5);
+    }
+}
+
+#[cfg(test)]
+mod test_ordered_glyphs {
+    use crate::ConfigBuilder;
+
+    use super::*;
+
+    #[test]
+    fn adjacent_equal_tiles_differ() {
+        let config = ConfigBuilder::new().characters("# ".to_string()).build();
+        for size in [4, 8] {
+            let chars = ordered_glyphs(&[0.5; 2], 2, size, &config);
+            assert_ne!(chars[0], chars[1], "{size}");
+        }
+    }
+
+    #[test]
+    fn mid_gray_is_half_filled() {
+        let config = ConfigBuilder::new().characters("# ".to_string()).build();
+        for size in [4, 8] {
+            let chars = ordered_glyphs(&vec![0.5; size * size], size, size, &config);
+            let spaces = chars.iter().filter(|char| **char == ' ').count();
+            assert_eq!(size * size / 2, spaces);
+        }
+    }
+
+    #[test]
+    fn matrix_is_repeated() {
+        let config = ConfigBuilder::new().characters("#k. ".to_string()).build();
+        let luminances = vec![0.4; 16 * 8];
+        let chars = ordered_glyphs(&luminances, 16, 4, &config);
+        for (index, char) in chars.iter().enumerate() {
+            let (column, row) = (index % 16, index / 16);
+            assert_eq!(chars[(row % 4) * 16 + column % 4], *char);
+        }
+    }
+
+    #[test]
+    fn black_and_white_are_exact() {
+        let config = ConfigBuilder::new().characters("#k. ".to_string()).build();
+        assert_eq!(vec![' '; 64], ordered_glyphs(&[0.0; 64], 8, 8, &config));
+        assert_eq!(vec!['#'; 64], ordered_glyphs(&[1.0; 64], 8, 8, &config));
+    }
+
+    #[test]
+    fn thresholds_are_centered() {
+        for size in [4, 8] {
+            let thresholds = (0..size)
+                .flat_map(|row| (0..size).map(move |column| bayer_threshold(size, (column, row))))
+                .collect::<Vec<_>>();
+            assert!(thresholds.iter().all(|threshold| threshold.abs() < 0.5));
+            assert!(thresholds.iter().sum::<f32>().abs() < 1e-5);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_stippled_glyphs {
+    use crate::ConfigBuilder;
+
+    use super::*;
+
+    #[test]
+    fn same_seed_same_glyphs() {
+        let config = ConfigBuilder::new().characters("#k. ".to_string()).build();
+        let luminances = (0..400)
+            .map(|index| index as f32 / 400f32)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            stippled_glyphs(&luminances, 20, 7, &config),
+            stippled_glyphs(&luminances, 20, 7, &config)
+        );
+        assert_ne!(
+            stippled_glyphs(&luminances, 20, 7, &config),
+            stippled_glyphs(&luminances, 20, 8, &config)
+        );
+    }
+
+    #[test]
+    fn density_follows_luminance() {
+        let config = ConfigBuilder::new().characters("# ".to_string()).build();
+        let filled = |luminance: f32| {
+            stippled_glyphs(&[luminance; 1000], 40, 0, &config)
+                .iter()
+                .filter(|char| **char == '#')
+                .count()
+        };
+        //the chance of the dense character is roughly the luminance
+        let (quarter, half, three_quarters) = (filled(0.25), filled(0.5), filled(0.75));
+        assert!((200..300).contains(&quarter), "{quarter}");
+        assert!((450..550).contains(&half), "{half}");
+        assert!((700..800).contains(&three_quarters), "{three_quarters}");
+    }
+
+    #[test]
+    fn no_regular_pattern() {
+        //unlike ordered dithering, the rows of a uniform gray do not repeat
+        let config = ConfigBuilder::new().characters("# ".to_string()).build();
+        let chars = stippled_glyphs(&[0.5; 64], 8, 0, &config);
+        assert!(chars.chunks(8).any(|row| row != &chars[..8]));
+        assert_ne!(chars, ordered_glyphs(&[0.5; 64], 8, 8, &config));
+    }
+
+    #[test]
+    fn black_and_white_are_exact() {
+        let config = ConfigBuilder::new().characters("#k. ".to_string()).build();
+        assert_eq!(vec![' '; 64], stippled_glyphs(&[0.0; 64], 8, 3, &config));
+        assert_eq!(vec!['#'; 64], stippled_glyphs(&[1.0; 64], 8, 3, &config));
+    }
+}
+
+/// The 4x4 Bayer matrix, which orders the values `0` - `15`, so that consecutive values are spread evenly.
+const BAYER_4: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+/// The 8x8 Bayer matrix with the values `0` - `63`, see [`BAYER_4`].
+const BAYER_8: [[u8; 8]; 8] = [
+    [0, 32, 8, 40, 2, 34, 10, 42],
+    [48, 16, 56, 24, 50, 18, 58, 26],
+    [12, 44, 4, 36, 14, 46, 6, 38],
+    [60, 28, 52, 20, 62, 30, 54, 22],
+    [3, 35, 11, 43, 1, 33, 9, 41],
+    [51, 19, 59, 27, 49, 17, 57, 25],
+    [15, 47, 7, 39, 13, 45, 5, 37],
+    [63, 31, 55, 23, 61, 29, 53, 21],
+];
+
+/// Threshold map for blending the two sets of characters of a [`RampGradient`].
+///
+/// It is the 4x4 Bayer matrix, which spreads the cells of both sets evenly, instead of creating a hard edge.
+const BLEND_THRESHOLDS: [[u8; 4]; 4] = BAYER_4;
+
+/// Returns the character for the luminance of the cell at the given (column, row) position.
+///
+/// If the config has a [`RampGradient`], the characters of the gradient are used depending on the position,
+/// otherwise this is the same as [`glyph_for_luminance`].
+///
+/// # Examples
+///
+/// ```compile_fail, compile will fail, this is an internal example
+/// let config = ConfigBuilder::new().characters("#k. ".to_string()).build();
+/// assert_eq!('#', cell_glyph(1.0, (0, 0), (10, 10), &config));
+/// ```
+pub fn cell_glyph(
+    luminance: f64,
+    position: (usize, usize),
+    size: (usize, usize),
+    config: &Config,
+) -> char {
+    match &config.ramp_gradient {
+        Some(gradient) => ramp_glyph(
+            luminance,
+            if uses_gradient_end(gradient, position, size) {
+                &gradient.end
+            } else {
+                &gradient.start
+            },
+            config,
+        ),
+        None => glyph_for_luminance(luminance, config),
+    }
+}
+
+/// Returns whether the cell at the given (column, row) position uses the end characters of the gradient.
+///
+/// The further the cell is in the direction of the gradient, the more likely it uses the end characters.
+/// The first column (or row) always uses the start characters and the last one always uses the end characters.
+fn uses_gradient_end(
+    gradient: &RampGradient,
+    (column, row): (usize, usize),
+    (columns, rows): (usize, usize),
+) -> bool {
+    let (position, length) = match gradient.direction {
+        GradientDirection::Horizontal => (column, columns),
+        GradientDirection::Vertical => (row, rows),
+    };
+    let progress = position as f32 / length.saturating_sub(1).max(1) as f32;
+    let threshold = (BLEND_THRESHOLDS[row % 4][column % 4] as f32 + 0.5) / 16f32;
+    progress > threshold
+}
+
+#[cfg(test)]
+mod test_cell_glyph {
+    use crate::{
+        config::{GradientDirection, RampGradient},
+        ConfigBuilder,
+    };
+
+    use super::*;
+
+    fn gradient_config(direction: GradientDirection) -> Config {
+        ConfigBuilder::new()
+            .ramp_gradient(RampGradient {
+                start: "#k. ".to_string(),
+                end: "@o- ".to_string(),
+                direction,
+            })
+            .build()
+    }
+
+    #[test]
+    fn horizontal_start_and_end() {
+        let config = gradient_config(GradientDirection::Horizontal);
+        for row in 0..8 {
+            assert_eq!('k', cell_glyph(0.6, (0, row), (20, 8), &config));
+            assert_eq!('o', cell_glyph(0.6, (19, row), (20, 8), &config));
+        }
+    }
+
+    #[test]
+    fn vertical_start_and_end() {
+        let config = gradient_config(GradientDirection::Vertical);
+        for column in 0..20 {
+            assert_eq!('#', cell_glyph(1.0, (column, 0), (20, 8), &config));
+            assert_eq!('@', cell_glyph(1.0, (column, 7), (20, 8), &config));
+        }
+    }
+
+    #[test]
+    fn middle_blends_both() {
+        let config = gradient_config(GradientDirection::Horizontal);
+        let chars = (0..4)
+            .flat_map(|row| (0..4).map(move |column| (9 + column, row)))
+            .map(|position| cell_glyph(0.6, position, (21, 4), &config))
+            .collect::<Vec<_>>();
+        assert!(chars.contains(&'k'));
+        assert!(chars.contains(&'o'));
+    }
+
+    #[test]
+    fn without_gradient() {
+        let config = ConfigBuilder::new().characters("#k. ".to_string()).build();
+        assert_eq!(
+            glyph_for_luminance(0.6, &config),
+            cell_glyph(0.6, (5, 5), (10, 10), &config)
+        );
+    }
+}
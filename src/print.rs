@@ -0,0 +1,368 @@
+use std::{
+    fs::File,
+    io::{self, Read, Write},
+    path::Path,
+};
+
+use image::{DynamicImage, ImageError, ImageFormat};
+
+use crate::{
+    config::{ArtemError, Config, TargetType},
+    target,
+};
+
+/// The source of an image, which is printed by [`print_image`].
+///
+/// It can be created from a path or from the encoded bytes of an image, such as the content of a png file.
+///
+/// # Examples
+/// ```
+/// use std::path::Path;
+/// use artem::ImageSource;
+///
+/// let source = ImageSource::from(Path::new("examples/abraham_lincoln.jpg"));
+/// assert!(matches!(source, ImageSource::Path(_)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageSource<'a> {
+    /// Path to an image file.
+    Path(&'a Path),
+    /// Encoded bytes of an image, the format is guessed from the content.
+    Bytes(&'a [u8]),
+}
+
+impl ImageSource<'_> {
+    /// Load and decode the image.
+    ///
+    /// # Errors
+    /// Returns [`ArtemError::Io`], if the file can not be read, or [`ArtemError::UnsupportedFormat`],
+    /// if the image can not be decoded.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::{config::ArtemError, ImageSource};
+    ///
+    /// let source = ImageSource::from("examples/abraham_lincoln.jpg");
+    /// assert!(source.load().is_ok());
+    /// let source = ImageSource::from([1u8, 2, 3].as_slice());
+    /// assert!(matches!(source.load(), Err(ArtemError::UnsupportedFormat(_))));
+    /// ```
+    pub fn load(&self) -> Result<DynamicImage, ArtemError> {
+        match self {
+            ImageSource::Path(path) => {
+                //only the first bytes are needed to detect the formats, which require a feature
+                let mut header = Vec::with_capacity(HEADER_LENGTH);
+                File::open(path)
+                    .and_then(|file| file.take(HEADER_LENGTH as u64).read_to_end(&mut header))
+                    .map_err(|err| ArtemError::Io(err.to_string()))?;
+                match optional_format(&header)? {
+                    Some(format) => {
+                        let mut reader = image::io::Reader::open(path)
+                            .map_err(|err| ArtemError::Io(err.to_string()))?;
+                        reader.set_format(format);
+                        reader.decode()
+                    }
+                    None => image::open(path),
+                }
+            }
+            ImageSource::Bytes(bytes) => match optional_format(bytes)? {
+                Some(format) => image::load_from_memory_with_format(bytes, format),
+                None => image::load_from_memory(bytes),
+            },
+        }
+        .map_err(|err| match err {
+            ImageError::IoError(err) => ArtemError::Io(err.to_string()),
+            err => ArtemError::UnsupportedFormat(err.to_string()),
+        })
+    }
+
+    /// Load and decode the image, rotated and flipped according to its EXIF orientation.
+    ///
+    /// Photos are often stored sideways and only shown upright by image viewers, which apply the orientation
+    /// from the metadata, see [`crate::exif`]. Images without an orientation are returned like [`ImageSource::load`] does.
+    ///
+    /// # Errors
+    /// Returns the same errors as [`ImageSource::load`].
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::ImageSource;
+    ///
+    /// let source = ImageSource::from("examples/abraham_lincoln.jpg");
+    /// assert_eq!(source.load().unwrap(), source.load_oriented().unwrap());
+    /// ```
+    #[cfg(feature = "exif")]
+    pub fn load_oriented(&self) -> Result<DynamicImage, ArtemError> {
+        let image = self.load()?;
+        let orientation = match self {
+            ImageSource::Path(path) => {
+                //the metadata is stored at the start of the file
+                let mut header = Vec::with_capacity(EXIF_LENGTH);
+                File::open(path)
+                    .and_then(|file| file.take(EXIF_LENGTH as u64).read_to_end(&mut header))
+                    .map_err(|err| ArtemError::Io(err.to_string()))?;
+                crate::exif::orientation(&header)
+            }
+            ImageSource::Bytes(bytes) => crate::exif::orientation(bytes),
+        };
+        Ok(match orientation {
+            Some(orientation) => crate::exif::orient(image, orientation),
+            None => image,
+        })
+    }
+}
+
+/// Number of bytes, which are read to find the EXIF orientation.
+///
+/// A jpeg segment is at most 64 KiB long, so this fits the EXIF data, even if another segment comes before it.
+#[cfg(feature = "exif")]
+const EXIF_LENGTH: usize = 128 * 1024;
+
+/// Number of bytes, which are needed by [`optional_format`] to detect the format.
+const HEADER_LENGTH: usize = 12;
+
+/// Returns the format of the image, if it is one of the formats, which are only decoded with a feature.
+///
+/// The format is detected from the magic bytes at the start of the image. WebP files are RIFF containers
+/// of the `WEBP` type, AVIF files start with a `ftyp` box of the `avif` or `avis` (image sequence) brand.
+/// The extension of a file is ignored, so a file with a wrong extension is still decoded with the right decoder.
+///
+/// # Errors
+/// Returns [`ArtemError::MissingFormatFeature`], if the format is detected, but its feature is not enabled.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// assert_eq!(Ok(Some(ImageFormat::WebP)), optional_format(b"RIFF\0\0\0\0WEBPVP8L"));
+/// assert_eq!(Ok(None), optional_format(b"\x89PNG\r\n\x1a\n"));
+/// ```
+fn optional_format(header: &[u8]) -> Result<Option<ImageFormat>, ArtemError> {
+    let (format, name, feature, enabled) = match header {
+        [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'E', b'B', b'P', ..] => {
+            (ImageFormat::WebP, "WebP", "webp", cfg!(feature = "webp"))
+        }
+        [_, _, _, _, b'f', b't', b'y', b'p', b'a', b'v', b'i', b'f' | b's', ..] => {
+            (ImageFormat::Avif, "AVIF", "avif", cfg!(feature = "avif"))
+        }
+        _ => return Ok(None),
+    };
+    if enabled {
+        Ok(Some(format))
+    } else {
+        Err(ArtemError::MissingFormatFeature(name, feature))
+    }
+}
+
+impl<'a> From<&'a Path> for ImageSource<'a> {
+    fn from(path: &'a Path) -> Self {
+        Self::Path(path)
+    }
+}
+
+impl<'a> From<&'a str> for ImageSource<'a> {
+    fn from(path: &'a str) -> Self {
+        Self::Path(Path::new(path))
+    }
+}
+
+impl<'a> From<&'a [u8]> for ImageSource<'a> {
+    fn from(bytes: &'a [u8]) -> Self {
+        Self::Bytes(bytes)
+    }
+}
+
+/// Resets the colors of the terminal when it is dropped.
+///
+/// Since it is dropped even if the conversion panics or an error occurs while writing,
+/// the terminal is never left with the colors of the image.
+struct ResetGuard<W: Write> {
+    writer: W,
+    colored: bool,
+}
+
+impl<W: Write> Drop for ResetGuard<W> {
+    fn drop(&mut self) {
+        if self.colored {
+            //errors can not be handled while dropping, the output is most likely closed anyway
+            let _ = self.writer.write_all(target::ansi::RESET.as_bytes());
+        }
+        let _ = self.writer.flush();
+    }
+}
+
+/// Load, convert and print an image to the terminal.
+///
+/// This is a convenience function, which combines loading the image, converting it with [`crate::convert`]
+/// and printing the result to stdout. Colors are only used, if they are enabled in the config and stdout is a terminal
+/// (see [`crate::should_use_color`]), so the output can be redirected to a file without any escape sequences.
+/// After printing, the colors of the terminal are reset, even if an error occurs.
+///
+/// Use [`print_image_to`] to print to something else than stdout.
+///
+/// # Errors
+/// Returns an [`ArtemError`], if the image can not be loaded, it can not be converted (see [`crate::convert`])
+/// or the output can not be written.
+///
+/// # Examples
+/// ```no_run
+/// use artem::config::ConfigBuilder;
+///
+/// artem::print_image("examples/abraham_lincoln.jpg", &ConfigBuilder::new().build()).unwrap();
+/// ```
+pub fn print_image<'a>(
+    source: impl Into<ImageSource<'a>>,
+    config: &Config,
+) -> Result<(), ArtemError> {
+    let stdout = io::stdout();
+    if !config.color() || crate::should_use_color(None) {
+        print_image_to(stdout.lock(), source, config)
+    } else {
+        log::debug!("Stdout is not a terminal, disabling colors");
+        let config = Config {
+            color: false,
+            ..config.clone()
+        };
+        print_image_to(stdout.lock(), source, &config)
+    }
+}
+
+/// Load, convert and print an image to the given writer.
+///
+/// In contrast to [`print_image`], colors are used as configured, regardless of whether the writer is a terminal.
+/// If colors are used, the output always ends with a reset sequence, even if an error occurs.
+///
+/// # Errors
+/// Returns an [`ArtemError`], if the image can not be loaded, it can not be converted (see [`crate::convert`])
+/// or the output can not be written.
+///
+/// # Examples
+/// ```
+/// use artem::config::ConfigBuilder;
+///
+/// let mut output = Vec::new();
+/// let config = ConfigBuilder::new().color(false).target_size(std::num::NonZeroU32::new(20).unwrap()).build();
+/// artem::print_image_to(&mut output, "examples/abraham_lincoln.jpg", &config).unwrap();
+/// assert!(!output.is_empty());
+/// ```
+pub fn print_image_to<'a, W: Write>(
+    writer: W,
+    source: impl Into<ImageSource<'a>>,
+    config: &Config,
+) -> Result<(), ArtemError> {
+    let source = source.into();
+    #[cfg(feature = "exif")]
+    let image = source.load_oriented()?;
+    #[cfg(not(feature = "exif"))]
+    let image = source.load()?;
+
+    let ascii_art = crate::convert(image, config)?;
+    let mut guard = ResetGuard {
+        writer,
+        colored: config.color()
+            && matches!(config.target, TargetType::Shell | TargetType::AnsiFile),
+    };
+    guard
+        .writer
+        .write_all(ascii_art.as_bytes())
+        .map_err(|err| ArtemError::Io(err.to_string()))
+}
+
+#[cfg(test)]
+mod test_print_image_to {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::ConfigBuilder;
+
+    fn png_bytes() -> Vec<u8> {
+        let mut bytes = Cursor::new(Vec::new());
+        DynamicImage::new_rgb8(4, 4)
+            .write_to(&mut bytes, ImageFormat::Png)
+            .unwrap();
+        bytes.into_inner()
+    }
+
+    #[test]
+    fn from_bytes() {
+        let mut output = Vec::new();
+        let config = ConfigBuilder::new()
+            .color(false)
+            .target_size(std::num::NonZeroU32::new(20).unwrap())
+            .build();
+        print_image_to(&mut output, png_bytes().as_slice(), &config).unwrap();
+        assert!(!String::from_utf8(output).unwrap().contains('\x1b'));
+    }
+
+    #[test]
+    fn invalid_bytes() {
+        let config = ConfigBuilder::new().build();
+        let result = print_image_to(Vec::new(), [1u8, 2, 3].as_slice(), &config);
+        assert!(matches!(result, Err(ArtemError::UnsupportedFormat(_))));
+    }
+
+    #[test]
+    fn empty_characters() {
+        let mut output = Vec::new();
+        let mut config = ConfigBuilder::new().color(true).build();
+        config.characters = String::new();
+        let result = print_image_to(&mut output, png_bytes().as_slice(), &config);
+        assert_eq!(Err(ArtemError::EmptyCharacterSet), result);
+        //nothing is written, not even the reset sequence
+        assert!(output.is_empty());
+    }
+}
+
+#[cfg(test)]
+mod test_optional_format {
+    use super::*;
+
+    const WEBP: &[u8] = b"RIFF\xce\x08\0\0WEBPVP8L";
+    const AVIF: &[u8] = b"\0\0\0\x18ftypavif\0\0\0\0";
+
+    #[test]
+    fn other_formats() {
+        assert_eq!(Ok(None), optional_format(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR"));
+        //a RIFF container of another type, like a wave file
+        assert_eq!(Ok(None), optional_format(b"RIFF\0\0\0\0WAVEfmt "));
+        assert_eq!(Ok(None), optional_format(b"\0\0\0\x18ftypmp42"));
+        assert_eq!(Ok(None), optional_format(b"RIFF"));
+    }
+
+    #[test]
+    #[cfg(feature = "webp")]
+    fn webp() {
+        assert_eq!(Ok(Some(ImageFormat::WebP)), optional_format(WEBP));
+    }
+
+    #[test]
+    #[cfg(not(feature = "webp"))]
+    fn webp_disabled() {
+        assert_eq!(
+            Err(ArtemError::MissingFormatFeature("WebP", "webp")),
+            optional_format(WEBP)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "avif")]
+    fn avif() {
+        assert_eq!(Ok(Some(ImageFormat::Avif)), optional_format(AVIF));
+        //the box size differs between encoders
+        assert_eq!(
+            Ok(Some(ImageFormat::Avif)),
+            optional_format(b"\0\0\0\x20ftypavis")
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "avif"))]
+    fn avif_disabled() {
+        assert_eq!(
+            Err(ArtemError::MissingFormatFeature("AVIF", "avif")),
+            optional_format(AVIF)
+        );
+        assert_eq!(
+            Err(ArtemError::MissingFormatFeature("AVIF", "avif")),
+            optional_format(b"\0\0\0\x20ftypavis")
+        );
+    }
+}
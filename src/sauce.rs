@@ -0,0 +1,175 @@
+//! Support for SAUCE metadata records.
+//!
+//! SAUCE (Standard Architecture for Universal Comment Extensions) is the conventional way to
+//! describe ANSI art files. The record is appended to the end of a `.ans` file and contains information
+//! such as the title and the dimensions of the art, which is used by ANSI art viewers.
+//! The specification can be found at <https://www.acid.org/info/sauce/sauce.htm>.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The character marking the end of the file content, before the SAUCE record.
+pub const EOF_CHAR: u8 = 0x1A;
+
+/// The length of a SAUCE record in bytes, without the [`EOF_CHAR`].
+pub const RECORD_LENGTH: usize = 128;
+
+/// A SAUCE record for a character based ANSI art file.
+///
+/// # Examples
+/// ```
+/// use artem::sauce::Sauce;
+///
+/// let sauce = Sauce::new("Lincoln", 80, 45);
+/// let bytes = sauce.to_bytes(1234);
+/// assert_eq!(129, bytes.len());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sauce {
+    /// Title of the art, at most 35 characters are used.
+    pub title: String,
+    /// Name of the author, at most 20 characters are used.
+    pub author: String,
+    /// Name of the group of the author, at most 20 characters are used.
+    pub group: String,
+    /// Creation date in the `CCYYMMDD` format.
+    pub date: String,
+    /// Width of the art in characters.
+    pub columns: u16,
+    /// Number of lines of the art.
+    pub rows: u16,
+}
+
+impl Sauce {
+    /// Create a new record with the given title and dimensions, dated today.
+    pub fn new(title: &str, columns: u16, rows: u16) -> Self {
+        Self {
+            title: title.to_string(),
+            author: String::new(),
+            group: String::new(),
+            date: today(),
+            columns,
+            rows,
+        }
+    }
+
+    /// Returns the bytes of the record, including the leading [`EOF_CHAR`].
+    ///
+    /// The `file_size` is the size of the file content in bytes, without the record.
+    /// The bytes should be appended directly to the end of the file content.
+    pub fn to_bytes(&self, file_size: u32) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(RECORD_LENGTH + 1);
+        bytes.push(EOF_CHAR);
+        bytes.extend_from_slice(b"SAUCE00");
+        push_padded(&mut bytes, &self.title, 35);
+        push_padded(&mut bytes, &self.author, 20);
+        push_padded(&mut bytes, &self.group, 20);
+        push_padded(&mut bytes, &self.date, 8);
+        bytes.extend_from_slice(&file_size.to_le_bytes());
+        //data type character
+        bytes.push(1);
+        //file type ansi
+        bytes.push(1);
+        //width and number of lines, the other two infos are unused for ansi files
+        bytes.extend_from_slice(&self.columns.to_le_bytes());
+        bytes.extend_from_slice(&self.rows.to_le_bytes());
+        bytes.extend_from_slice(&[0; 4]);
+        //no comments and no flags
+        bytes.push(0);
+        bytes.push(0);
+        //no font name
+        bytes.extend_from_slice(&[0; 22]);
+        debug_assert_eq!(RECORD_LENGTH + 1, bytes.len());
+        bytes
+    }
+}
+
+/// Push the ascii characters of the value, padded with spaces to the given length.
+///
+/// Characters which are not ascii are replaced with a `?`, longer values are cut off.
+fn push_padded(bytes: &mut Vec<u8>, value: &str, length: usize) {
+    let value = value
+        .chars()
+        .map(|char| if char.is_ascii() { char as u8 } else { b'?' })
+        .chain(std::iter::repeat(b' '))
+        .take(length);
+    bytes.extend(value);
+}
+
+/// Returns the current (UTC) date in the `CCYYMMDD` format.
+fn today() -> String {
+    let days = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs() / 86_400);
+    let (year, month, day) = civil_from_days(days as i64);
+    format!("{year:04}{month:02}{day:02}")
+}
+
+/// Convert the days since the unix epoch to a (year, month, day) date.
+///
+/// The algorithm is from <https://howardhinnant.github.io/date_algorithms.html#civil_from_days>
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_part = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_part + 2) / 5 + 1) as u32;
+    let month = if month_part < 10 {
+        month_part + 3
+    } else {
+        month_part - 9
+    } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod test_sauce {
+    use super::*;
+
+    #[test]
+    fn record_layout() {
+        let sauce = Sauce {
+            title: "Test".to_string(),
+            author: "Artem".to_string(),
+            group: String::new(),
+            date: "20240327".to_string(),
+            columns: 80,
+            rows: 26,
+        };
+        let bytes = sauce.to_bytes(2105);
+        assert_eq!(RECORD_LENGTH + 1, bytes.len());
+        assert_eq!(EOF_CHAR, bytes[0]);
+
+        let record = &bytes[1..];
+        assert_eq!(b"SAUCE00", &record[0..7]);
+        assert_eq!(format!("{:35}", "Test").as_bytes(), &record[7..42]);
+        assert_eq!(format!("{:20}", "Artem").as_bytes(), &record[42..62]);
+        assert_eq!(&[b' '; 20], &record[62..82]);
+        assert_eq!(b"20240327", &record[82..90]);
+        assert_eq!(2105u32.to_le_bytes(), record[90..94]);
+        assert_eq!([1, 1], record[94..96]);
+        assert_eq!(80u16.to_le_bytes(), record[96..98]);
+        assert_eq!(26u16.to_le_bytes(), record[98..100]);
+    }
+
+    #[test]
+    fn long_and_non_ascii_title() {
+        let sauce = Sauce::new(
+            "Ä very long title, which does not fit into the record",
+            1,
+            1,
+        );
+        let bytes = sauce.to_bytes(0);
+        assert_eq!(b"? very long title, which does not f", &bytes[8..43]);
+    }
+
+    #[test]
+    fn dates() {
+        assert_eq!((1970, 1, 1), civil_from_days(0));
+        assert_eq!((2000, 2, 29), civil_from_days(11_016));
+        assert_eq!((2024, 3, 27), civil_from_days(19_809));
+    }
+}
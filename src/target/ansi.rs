@@ -1,28 +1,94 @@
-use colored::{ColoredString, Colorize};
+use colored::{Color, ColoredString, Colorize};
+use once_cell::sync::Lazy;
+
+use crate::config::{ColorMode, DistanceMetric};
+use crate::palette;
 
 /// Returns an colored string with the given colors.
 ///
-/// Checks if true_colors are supported, by checking the `COLORTERM` environnement variable,
-/// it then returns the given char as a colored string, either using true colors or ansi colors as a fallback.
-/// Background colors are only supported when true colors are enabled.
+/// Depending on the color mode, the given char is returned as a colored string using either true colors,
+/// the 256 colors of the xterm palette or the 16 basic ansi colors as a fallback.
+/// If `background_color` is true, the color is used as the background color instead.
 /// # Examples
 /// ```compile_fail, compile will fail, this is an internal example
-/// println!("{}", get_colored_string(100, 100, 100, 'x', false));
+/// println!("{}", colored_char(100, 100, 100, 'x', false, ColorMode::Truecolor, DistanceMetric::Rgb));
 /// ```
-pub fn colored_char(red: u8, green: u8, blue: u8, char: char, background_color: bool) -> String {
-    if *crate::SUPPORTS_TRUECOLOR {
+pub fn colored_char(
+    red: u8,
+    green: u8,
+    blue: u8,
+    char: char,
+    background_color: bool,
+    color_mode: ColorMode,
+    distance_metric: DistanceMetric,
+) -> String {
+    match color_mode {
         //return true color string
-        if background_color {
+        ColorMode::Truecolor if background_color => {
             char.to_string().on_truecolor(red, green, blue).to_string()
-        } else {
-            char.to_string().truecolor(red, green, blue).to_string()
         }
-    } else {
+        ColorMode::Truecolor => char.to_string().truecolor(red, green, blue).to_string(),
+        ColorMode::Ansi256 if background_color => {
+            with_ansi256(&char.to_string(), 48, ansi256_index(red, green, blue))
+        }
+        ColorMode::Ansi256 => rgb_to_ansi256(&char.to_string(), red, green, blue),
         //otherwise use basic (8 color) ansi color
-        rgb_to_ansi(&char.to_string(), red, green, blue).to_string()
+        ColorMode::Ansi16 if background_color => {
+            rgb_to_ansi_background(&char.to_string(), red, green, blue, distance_metric).to_string()
+        }
+        ColorMode::Ansi16 => {
+            rgb_to_ansi(&char.to_string(), red, green, blue, distance_metric).to_string()
+        }
+    }
+}
+
+/// Returns an upper half block with the upper color as the foreground and the lower color as the background.
+///
+/// Like [`colored_char`], the colors are converted to the given color mode.
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// println!("{}", half_block((255, 0, 0), (0, 0, 255), ColorMode::Truecolor, DistanceMetric::Rgb));
+/// ```
+pub fn half_block(
+    (red, green, blue): (u8, u8, u8),
+    (lower_red, lower_green, lower_blue): (u8, u8, u8),
+    color_mode: ColorMode,
+    distance_metric: DistanceMetric,
+) -> String {
+    match color_mode {
+        ColorMode::Truecolor => HALF_BLOCK
+            .to_string()
+            .truecolor(red, green, blue)
+            .on_truecolor(lower_red, lower_green, lower_blue)
+            .to_string(),
+        ColorMode::Ansi256 => with_ansi256(
+            &with_ansi256(
+                &HALF_BLOCK.to_string(),
+                48,
+                ansi256_index(lower_red, lower_green, lower_blue),
+            ),
+            38,
+            ansi256_index(red, green, blue),
+        ),
+        ColorMode::Ansi16 => HALF_BLOCK
+            .to_string()
+            .color(nearest_ansi_color(red, green, blue, distance_metric))
+            .on_color(nearest_ansi_color(
+                lower_red,
+                lower_green,
+                lower_blue,
+                distance_metric,
+            ))
+            .to_string(),
     }
 }
 
+/// The upper half block, which is used to show two pixels in a single character.
+pub const HALF_BLOCK: char = '▀';
+
+/// The full block, which only shows the color of a tile.
+pub const FULL_BLOCK: char = '█';
+
 #[cfg(test)]
 mod test_colored_string {
     use std::env;
@@ -30,124 +96,406 @@ mod test_colored_string {
     use super::*;
 
     #[test]
-    #[ignore = "Requires truecolor support"]
     fn rust_color_no_background() {
         //ensure that colors will be used
-        env::set_var("COLORTERM", "truecolor");
         env::set_var("CLICOLOR_FORCE", "1");
         assert_eq!(
             "x".truecolor(154, 85, 54).to_string(),
-            colored_char(154, 85, 54, 'x', false)
+            colored_char(
+                154,
+                85,
+                54,
+                'x',
+                false,
+                ColorMode::Truecolor,
+                DistanceMetric::Rgb
+            )
         );
     }
 
     #[test]
-    #[ignore = "Requires truecolor support"]
     fn rust_color_with_background() {
         //ensure that colors will be used
-        env::set_var("COLORTERM", "truecolor");
         env::set_var("CLICOLOR_FORCE", "1");
         assert_eq!(
             "x".on_truecolor(154, 85, 54).to_string(),
-            colored_char(154, 85, 54, 'x', true)
+            colored_char(
+                154,
+                85,
+                54,
+                'x',
+                true,
+                ColorMode::Truecolor,
+                DistanceMetric::Rgb
+            )
         );
     }
 
     #[test]
     fn rust_color_ansi_no_background() {
-        //set true color support to false
-        env::set_var("COLORTERM", "false");
         //ensure that colors will be used
         env::set_var("CLICOLOR_FORCE", "1");
         assert_eq!(
             "\u{1b}[33mx\u{1b}[0m",
-            colored_char(154, 85, 54, 'x', false)
+            colored_char(
+                154,
+                85,
+                54,
+                'x',
+                false,
+                ColorMode::Ansi16,
+                DistanceMetric::Rgb
+            )
         );
     }
 
     #[test]
     fn rust_color_ansi_with_background() {
-        //set true color support to false
-        env::set_var("COLORTERM", "false");
         //ensure that colors will be used
         env::set_var("CLICOLOR_FORCE", "1");
-        //ansi does not support background, so it is the same as without
-        assert_eq!("\u{1b}[33mx\u{1b}[0m", colored_char(154, 85, 54, 'x', true));
+        //the nearest background color is used instead of the foreground color
+        assert_eq!(
+            "\u{1b}[43mx\u{1b}[0m",
+            colored_char(
+                154,
+                85,
+                54,
+                'x',
+                true,
+                ColorMode::Ansi16,
+                DistanceMetric::Rgb
+            )
+        );
+    }
+
+    #[test]
+    fn half_block_colors() {
+        //ensure that colors will be used
+        env::set_var("CLICOLOR_FORCE", "1");
+        assert_eq!(
+            "▀".truecolor(255, 0, 0).on_truecolor(0, 0, 255).to_string(),
+            half_block(
+                (255, 0, 0),
+                (0, 0, 255),
+                ColorMode::Truecolor,
+                DistanceMetric::Rgb
+            )
+        );
+        assert_eq!(
+            "\u{1b}[38;5;196m\u{1b}[48;5;21m▀\u{1b}[0m\u{1b}[0m",
+            half_block(
+                (255, 0, 0),
+                (0, 0, 255),
+                ColorMode::Ansi256,
+                DistanceMetric::Rgb
+            )
+        );
+        assert_eq!(
+            "\u{1b}[104;91m▀\u{1b}[0m",
+            half_block(
+                (255, 0, 0),
+                (0, 0, 255),
+                ColorMode::Ansi16,
+                DistanceMetric::Rgb
+            )
+        );
+    }
+
+    #[test]
+    fn rust_color_ansi256() {
+        //ensure that colors will be used
+        env::set_var("CLICOLOR_FORCE", "1");
+        assert_eq!(
+            "\u{1b}[38;5;95mx\u{1b}[0m",
+            colored_char(
+                154,
+                85,
+                54,
+                'x',
+                false,
+                ColorMode::Ansi256,
+                DistanceMetric::Rgb
+            )
+        );
+        assert_eq!(
+            "\u{1b}[48;5;95mx\u{1b}[0m",
+            colored_char(
+                154,
+                85,
+                54,
+                'x',
+                true,
+                ColorMode::Ansi256,
+                DistanceMetric::Rgb
+            )
+        );
+    }
+}
+
+/// Escape sequence, which resets all colors and styles.
+pub const RESET: &str = "\x1b[0m";
+
+/// Terminates a line of ansi colored output.
+///
+/// To keep the output compatible with tools, which naively capture or concatenate
+/// the output line by line (similar to `chafa` or `img2sixel`), every line that uses escape sequences
+/// has to end with a reset, before the line break is added. The output never
+/// relies on cursor movement sequences, such as `\x1b[K`.
+///
+/// If the last escape sequence of the line is already a reset, nothing is added.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// let mut line = String::from("\x1b[31mx");
+/// terminate_line(&mut line);
+/// assert_eq!("\x1b[31mx\x1b[0m\n", line);
+/// ```
+pub fn terminate_line(line: &mut String) {
+    if let Some(index) = line.rfind('\x1b') {
+        if !line[index..].starts_with(RESET) {
+            line.push_str(RESET);
+        }
+    }
+    line.push('\n');
+}
+
+#[cfg(test)]
+mod test_terminate_line {
+    use super::*;
+
+    #[test]
+    fn plain_line() {
+        let mut line = String::from("xyz");
+        terminate_line(&mut line);
+        assert_eq!("xyz\n", line);
     }
+
+    #[test]
+    fn already_reset() {
+        let mut line = String::from("\x1b[31mx\x1b[0m");
+        terminate_line(&mut line);
+        assert_eq!("\x1b[31mx\x1b[0m\n", line);
+    }
+
+    #[test]
+    fn reset_before_border() {
+        let mut line = String::from("\x1b[31mx\x1b[0m║");
+        terminate_line(&mut line);
+        assert_eq!("\x1b[31mx\x1b[0m║\n", line);
+    }
+
+    #[test]
+    fn missing_reset() {
+        let mut line = String::from("\x1b[31mx");
+        terminate_line(&mut line);
+        assert_eq!("\x1b[31mx\x1b[0m\n", line);
+    }
+}
+
+/// Returns the number of visible characters of the line, without escape sequences.
+///
+/// Colored output contains escape sequences, such as `\x1b[31m` or `\x1b[38;2;255;0;0m`, which do not take up any space
+/// in the terminal. Control sequences (`ESC [`, followed by parameters and a final byte between `@` and `~`) are skipped
+/// completely, other escape sequences only consist of the escape character and a single following character.
+/// This is useful to pad, center or truncate colored lines.
+///
+/// # Examples
+/// ```
+/// assert_eq!(3, artem::visible_len("abc"));
+/// assert_eq!(1, artem::visible_len("\x1b[38;5;196mx\x1b[0m"));
+/// ```
+pub fn visible_len(line: &str) -> usize {
+    let mut chars = line.chars();
+    let mut len = 0;
+    while let Some(char) = chars.next() {
+        if char != '\x1b' {
+            len += 1;
+            continue;
+        }
+        match chars.next() {
+            //the parameters and intermediate bytes are followed by a single final byte
+            Some('[') => {
+                for char in chars.by_ref() {
+                    if ('@'..='~').contains(&char) {
+                        break;
+                    }
+                }
+            }
+            //a two character escape sequence, or an escape character at the end of the line
+            _ => continue,
+        }
+    }
+    len
+}
+
+#[cfg(test)]
+mod test_visible_len {
+    use super::*;
+
+    #[test]
+    fn plain_line() {
+        assert_eq!(0, visible_len(""));
+        assert_eq!(5, visible_len("ab cd"));
+        //characters, not bytes, are counted
+        assert_eq!(3, visible_len("░▒▓"));
+    }
+
+    #[test]
+    fn single_colored_char() {
+        assert_eq!(1, visible_len("\x1b[31mx"));
+        assert_eq!(1, visible_len("\x1b[38;5;196mx"));
+        assert_eq!(1, visible_len("\x1b[48;2;0;0;255;38;2;255;0;0m▀"));
+    }
+
+    #[test]
+    fn with_resets() {
+        assert_eq!(3, visible_len("\x1b[31ma\x1b[0mb\x1b[32mc\x1b[0m"));
+        //a bright 16 color code, followed by an uncolored border
+        assert_eq!(4, visible_len("\x1b[91mxyz\x1b[0m║"));
+    }
+
+    #[test]
+    fn unfinished_sequence() {
+        assert_eq!(1, visible_len("a\x1b[31"));
+        assert_eq!(1, visible_len("a\x1b"));
+    }
+}
+
+/// VGA colors as example ansi colors, in the order of the ansi color codes.
+///
+/// From <https://en.wikipedia.org/wiki/ANSI_escape_code#Colors>
+const VGA_COLORS: [[u8; 3]; 16] = [
+    [0, 0, 0],       //black
+    [170, 0, 0],     //red
+    [0, 170, 0],     //green
+    [170, 85, 0],    //yellow
+    [0, 0, 170],     //blue
+    [170, 0, 170],   //magenta
+    [0, 170, 170],   //cyan
+    [170, 170, 170], //white
+    [128, 128, 128], //bright black/gray
+    [255, 0, 0],     //bright red
+    [0, 255, 0],     //bright green
+    [255, 255, 0],   //bright yellow
+    [0, 0, 255],     //bright blue
+    [255, 0, 255],   //bright magenta
+    [0, 255, 255],   //bright cyan
+    [255, 255, 255], //bright white
+];
+
+/// The [`VGA_COLORS`] as points of the rgb distance, so they only have to be converted once.
+static VGA_COLORS_RGB: Lazy<[[f32; 3]; 16]> =
+    Lazy::new(|| VGA_COLORS.map(|color| color.map(f32::from)));
+
+/// The [`VGA_COLORS`] in the CIELAB color space, so they only have to be converted once.
+static VGA_COLORS_LAB: Lazy<[[f32; 3]; 16]> = Lazy::new(|| VGA_COLORS.map(rgb_to_lab));
+
+/// The linear value of each sRGB channel value, since the transfer function is expensive.
+static LINEAR_CHANNELS: Lazy<[f32; 256]> =
+    Lazy::new(|| std::array::from_fn(|channel| linear_channel(channel as u8)));
+
+/// Returns the linear value (`0.0` - `1.0`) of a sRGB channel, using the sRGB transfer function.
+fn linear_channel(channel: u8) -> f32 {
+    let channel = channel as f32 / 255f32;
+    if channel <= 0.04045 {
+        channel / 12.92
+    } else {
+        ((channel + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Converts a sRGB color to the CIELAB color space, using the D65 white point.
+///
+/// The formulas come from <https://en.wikipedia.org/wiki/SRGB> and <https://en.wikipedia.org/wiki/CIELAB_color_space>.
+pub(crate) fn rgb_to_lab([red, green, blue]: [u8; 3]) -> [f32; 3] {
+    let linear = &*LINEAR_CHANNELS;
+    let (red, green, blue) = (
+        linear[red as usize],
+        linear[green as usize],
+        linear[blue as usize],
+    );
+
+    //xyz color space, relative to the D65 white point
+    let x = (0.4124 * red + 0.3576 * green + 0.1805 * blue) / 0.95047;
+    let y = 0.2126 * red + 0.7152 * green + 0.0722 * blue;
+    let z = (0.0193 * red + 0.1192 * green + 0.9505 * blue) / 1.08883;
+
+    const DELTA: f32 = 6f32 / 29f32;
+    let f = |t: f32| {
+        if t > DELTA.powi(3) {
+            t.cbrt()
+        } else {
+            t / (3f32 * DELTA * DELTA) + 4f32 / 29f32
+        }
+    };
+    let (x, y, z) = (f(x), f(y), f(z));
+    [116f32 * y - 16f32, 500f32 * (x - y), 200f32 * (y - z)]
 }
 
 ///Converts the given input string to an ansi colored string
 ///
 /// It tries to match the ANSI-Color as closely as possible by calculating the distance between all
-/// 8 colors and the given input color from `r`, `b` and `b`, then returning the nearest.
+/// 16 colors and the given input color from `r`, `b` and `b`, then returning the nearest.
+/// The distance is calculated using the given metric, see [`DistanceMetric`].
 /// It will not be 100% accurate, since every terminal has slightly different
 /// ANSI-Colors. It used the VGA-Colors as ANSI-Color.
 ///
 /// # Examples
 /// ```compile_fail, compile will fail, this is an internal example
 /// //convert black to ansi black color
-/// assert_eq!("input".black(), rgb_to_ansi("input", 0, 0, 0));
+/// assert_eq!("input".black(), rgb_to_ansi("input", 0, 0, 0, DistanceMetric::Rgb));
 /// ```
-fn rgb_to_ansi(input: &str, r: u8, g: u8, b: u8) -> ColoredString {
-    //get rgb values and convert them to i32, since later on the could negative when subtracting
-    let r = r as i32;
-    let g = g as i32;
-    let b = b as i32;
-
-    //vga colors as example ansi color
-    //from https://en.wikipedia.org/wiki/ANSI_escape_code#Colors
-    let vga_colors = [
-        [0, 0, 0],       //black
-        [170, 0, 0],     //red
-        [0, 170, 0],     //green
-        [170, 85, 0],    //yellow
-        [0, 0, 170],     //blue
-        [170, 0, 170],   //magenta
-        [0, 170, 170],   //cyan
-        [170, 170, 170], //white
-        [128, 128, 128], //bright black/gray
-        [255, 0, 0],     //bright red
-        [0, 255, 0],     //bright green
-        [255, 255, 0],   //bright yellow
-        [0, 0, 255],     //bright blue
-        [255, 0, 255],   //bright magenta
-        [0, 255, 255],   //bright cyan
-        [255, 255, 255], //bright white
-    ];
-
-    //find nearest color
-    let mut smallest_distance = i32::MAX;
-    let mut smallest_distance_index: u8 = 7;
-    //maybe there is a better method for this
-    for (index, vga_color) in vga_colors.iter().enumerate() {
-        let distance =
-            (r - vga_color[0]).pow(2) + (g - vga_color[1]).pow(2) + (b - vga_color[2]).pow(2);
-
-        if distance < smallest_distance {
-            smallest_distance = distance;
-            smallest_distance_index = index as u8;
-        }
-    }
+fn rgb_to_ansi(input: &str, r: u8, g: u8, b: u8, metric: DistanceMetric) -> ColoredString {
+    input.color(nearest_ansi_color(r, g, b, metric))
+}
+
+/// Converts the given input string to a string with the nearest ansi background color.
+///
+/// The color is chosen like the foreground color of [`rgb_to_ansi`].
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// assert_eq!("input".on_bright_red(), rgb_to_ansi_background("input", 255, 0, 0, DistanceMetric::Rgb));
+/// ```
+fn rgb_to_ansi_background(
+    input: &str,
+    r: u8,
+    g: u8,
+    b: u8,
+    metric: DistanceMetric,
+) -> ColoredString {
+    input.on_color(nearest_ansi_color(r, g, b, metric))
+}
+
+/// Returns the nearest of the 16 ansi colors, see [`rgb_to_ansi`].
+///
+/// The colors are matched like any other palette, see [`crate::palette::nearest_color`],
+/// but the VGA colors are only converted once, so only the given color has to be converted for each call.
+fn nearest_ansi_color(r: u8, g: u8, b: u8, metric: DistanceMetric) -> Color {
+    let vga_colors: &[[f32; 3]; 16] = match metric {
+        DistanceMetric::Rgb => &VGA_COLORS_RGB,
+        DistanceMetric::CieLab => &VGA_COLORS_LAB,
+    };
+    let nearest = palette::nearest_center(&palette::color_point((r, g, b), metric), vga_colors);
 
-    //convert string to matching color
-    match smallest_distance_index {
-        0 => input.black(),
-        1 => input.red(),
-        2 => input.green(),
-        3 => input.yellow(),
-        4 => input.blue(),
-        5 => input.magenta(),
-        6 => input.cyan(),
-        7 => input.white(),
-        8 => input.bright_black(),
-        9 => input.bright_red(),
-        10 => input.bright_green(),
-        11 => input.bright_yellow(),
-        12 => input.bright_blue(),
-        13 => input.bright_magenta(),
-        14 => input.bright_cyan(),
-        15 => input.bright_white(),
-        _ => input.normal(),
+    //convert index to matching color
+    match nearest {
+        0 => Color::Black,
+        1 => Color::Red,
+        2 => Color::Green,
+        3 => Color::Yellow,
+        4 => Color::Blue,
+        5 => Color::Magenta,
+        6 => Color::Cyan,
+        8 => Color::BrightBlack,
+        9 => Color::BrightRed,
+        10 => Color::BrightGreen,
+        11 => Color::BrightYellow,
+        12 => Color::BrightBlue,
+        13 => Color::BrightMagenta,
+        14 => Color::BrightCyan,
+        15 => Color::BrightWhite,
+        _ => Color::White,
     }
 }
 
@@ -158,46 +506,308 @@ mod test_convert_rgb_ansi {
     #[test]
     fn convert_vga_normal_values() {
         //convert black to ansi black color
-        assert_eq!("input".black(), rgb_to_ansi("input", 0, 0, 0));
+        assert_eq!(
+            "input".black(),
+            rgb_to_ansi("input", 0, 0, 0, DistanceMetric::Rgb)
+        );
         //convert red to ansi red color
-        assert_eq!("input".red(), rgb_to_ansi("input", 170, 0, 0));
+        assert_eq!(
+            "input".red(),
+            rgb_to_ansi("input", 170, 0, 0, DistanceMetric::Rgb)
+        );
         //convert green to ansi green color
-        assert_eq!("input".green(), rgb_to_ansi("input", 0, 170, 0));
+        assert_eq!(
+            "input".green(),
+            rgb_to_ansi("input", 0, 170, 0, DistanceMetric::Rgb)
+        );
         //convert yellow to ansi yellow color
-        assert_eq!("input".yellow(), rgb_to_ansi("input", 170, 85, 0));
+        assert_eq!(
+            "input".yellow(),
+            rgb_to_ansi("input", 170, 85, 0, DistanceMetric::Rgb)
+        );
         //convert blue to ansi blue color
-        assert_eq!("input".blue(), rgb_to_ansi("input", 0, 0, 170));
+        assert_eq!(
+            "input".blue(),
+            rgb_to_ansi("input", 0, 0, 170, DistanceMetric::Rgb)
+        );
         //convert magenta to ansi magenta color
-        assert_eq!("input".magenta(), rgb_to_ansi("input", 170, 0, 170));
+        assert_eq!(
+            "input".magenta(),
+            rgb_to_ansi("input", 170, 0, 170, DistanceMetric::Rgb)
+        );
         //convert cyan to ansi cyan color
-        assert_eq!("input".cyan(), rgb_to_ansi("input", 0, 170, 170));
+        assert_eq!(
+            "input".cyan(),
+            rgb_to_ansi("input", 0, 170, 170, DistanceMetric::Rgb)
+        );
         //convert white to ansi white color
-        assert_eq!("input".white(), rgb_to_ansi("input", 170, 170, 170));
+        assert_eq!(
+            "input".white(),
+            rgb_to_ansi("input", 170, 170, 170, DistanceMetric::Rgb)
+        );
     }
 
     #[test]
     fn convert_vga_bright_values() {
         //convert bright black to ansi bright black color
-        assert_eq!("input".bright_black(), rgb_to_ansi("input", 128, 128, 128));
+        assert_eq!(
+            "input".bright_black(),
+            rgb_to_ansi("input", 128, 128, 128, DistanceMetric::Rgb)
+        );
         //convert bright red to ansi bright red color
-        assert_eq!("input".bright_red(), rgb_to_ansi("input", 255, 0, 0));
+        assert_eq!(
+            "input".bright_red(),
+            rgb_to_ansi("input", 255, 0, 0, DistanceMetric::Rgb)
+        );
         //convert bright green to ansi bright green color
-        assert_eq!("input".bright_green(), rgb_to_ansi("input", 0, 255, 0));
+        assert_eq!(
+            "input".bright_green(),
+            rgb_to_ansi("input", 0, 255, 0, DistanceMetric::Rgb)
+        );
         //convert bright yellow to ansi bright yellow color
-        assert_eq!("input".bright_yellow(), rgb_to_ansi("input", 255, 255, 0));
+        assert_eq!(
+            "input".bright_yellow(),
+            rgb_to_ansi("input", 255, 255, 0, DistanceMetric::Rgb)
+        );
         //convert bright blue to ansi bright blue color
-        assert_eq!("input".bright_blue(), rgb_to_ansi("input", 0, 0, 255));
+        assert_eq!(
+            "input".bright_blue(),
+            rgb_to_ansi("input", 0, 0, 255, DistanceMetric::Rgb)
+        );
         //convert bright magenta to ansi bright magenta color
-        assert_eq!("input".bright_magenta(), rgb_to_ansi("input", 255, 0, 255));
+        assert_eq!(
+            "input".bright_magenta(),
+            rgb_to_ansi("input", 255, 0, 255, DistanceMetric::Rgb)
+        );
         //convert bright cyan to ansi bright cyan color
-        assert_eq!("input".bright_cyan(), rgb_to_ansi("input", 0, 255, 255));
+        assert_eq!(
+            "input".bright_cyan(),
+            rgb_to_ansi("input", 0, 255, 255, DistanceMetric::Rgb)
+        );
         //convert bright white to ansi bright white color
-        assert_eq!("input".bright_white(), rgb_to_ansi("input", 255, 255, 255));
+        assert_eq!(
+            "input".bright_white(),
+            rgb_to_ansi("input", 255, 255, 255, DistanceMetric::Rgb)
+        );
     }
 
     #[test]
     fn rgb_blue() {
         //convert a blue rgb tone to ansi blue
-        assert_eq!("input".blue(), rgb_to_ansi("input", 0, 0, 88));
+        assert_eq!(
+            "input".blue(),
+            rgb_to_ansi("input", 0, 0, 88, DistanceMetric::Rgb)
+        );
+    }
+
+    #[test]
+    fn lab_vga_values() {
+        //the vga colors are still matched exactly
+        for (index, [r, g, b]) in VGA_COLORS.into_iter().enumerate() {
+            assert_eq!(
+                rgb_to_ansi("input", r, g, b, DistanceMetric::Rgb),
+                rgb_to_ansi("input", r, g, b, DistanceMetric::CieLab),
+                "vga color {index}"
+            );
+        }
+    }
+
+    #[test]
+    fn lab_brown() {
+        //a mid-tone brown is closer to the (brownish) vga yellow, but rgb picks red
+        assert_eq!(
+            "input".red(),
+            rgb_to_ansi("input", 100, 40, 20, DistanceMetric::Rgb)
+        );
+        assert_eq!(
+            "input".yellow(),
+            rgb_to_ansi("input", 100, 40, 20, DistanceMetric::CieLab)
+        );
+    }
+
+    #[test]
+    fn lab_dark_blue() {
+        //dark blues do not collapse into black
+        assert_eq!(
+            "input".black(),
+            rgb_to_ansi("input", 0, 0, 75, DistanceMetric::Rgb)
+        );
+        assert_eq!(
+            "input".blue(),
+            rgb_to_ansi("input", 0, 0, 75, DistanceMetric::CieLab)
+        );
+    }
+
+    #[test]
+    fn lab_values() {
+        let [l, a, b] = rgb_to_lab([255, 255, 255]);
+        assert!(
+            (l - 100f32).abs() < 0.1 && a.abs() < 0.1 && b.abs() < 0.1,
+            "{l} {a} {b}"
+        );
+        assert_eq!([0f32, 0f32, 0f32], rgb_to_lab([0, 0, 0]));
+        //red, with the values from the CIELAB article
+        let [l, a, b] = rgb_to_lab([255, 0, 0]);
+        assert!((l - 53.24).abs() < 0.1, "{l}");
+        assert!((a - 80.09).abs() < 0.2, "{a}");
+        assert!((b - 67.20).abs() < 0.2, "{b}");
+    }
+
+    #[test]
+    fn linear_channels_match_transfer_function() {
+        for channel in 0..=255u8 {
+            assert_eq!(
+                linear_channel(channel).to_bits(),
+                LINEAR_CHANNELS[channel as usize].to_bits()
+            );
+        }
+    }
+
+    #[test]
+    fn cached_colors_match_direct_calculation() {
+        //the distances to all colors, calculated without any precomputed values
+        let direct = |[r, g, b]: [u8; 3], metric: DistanceMetric| -> [f32; 16] {
+            match metric {
+                DistanceMetric::Rgb => VGA_COLORS.map(|[vga_r, vga_g, vga_b]| {
+                    ((r as i32 - vga_r as i32).pow(2)
+                        + (g as i32 - vga_g as i32).pow(2)
+                        + (b as i32 - vga_b as i32).pow(2)) as f32
+                }),
+                DistanceMetric::CieLab => {
+                    let [l, a, b] = rgb_to_lab([r, g, b]);
+                    VGA_COLORS.map(|vga| {
+                        let [vga_l, vga_a, vga_b] = rgb_to_lab(vga);
+                        (l - vga_l).powi(2) + (a - vga_a).powi(2) + (b - vga_b).powi(2)
+                    })
+                }
+            }
+        };
+        let values = (0..=255u8).step_by(5);
+        for r in values.clone() {
+            for g in values.clone() {
+                for b in values.clone() {
+                    for metric in [DistanceMetric::Rgb, DistanceMetric::CieLab] {
+                        let distances = direct([r, g, b], metric);
+                        //the first of the nearest colors is used
+                        let nearest = (0..16)
+                            .reduce(|nearest, index| {
+                                if distances[index] < distances[nearest] {
+                                    index
+                                } else {
+                                    nearest
+                                }
+                            })
+                            .unwrap();
+                        let expected = nearest_ansi_color(
+                            VGA_COLORS[nearest][0],
+                            VGA_COLORS[nearest][1],
+                            VGA_COLORS[nearest][2],
+                            DistanceMetric::Rgb,
+                        );
+                        assert_eq!(
+                            expected,
+                            nearest_ansi_color(r, g, b, metric),
+                            "{r} {g} {b} {metric:?}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Colors within this distance of each other are considered gray, when converting to the 256 colors of the xterm palette.
+const GRAYSCALE_TOLERANCE: u8 = 8;
+
+/// Returns the index of the nearest color in the 256 colors of the xterm palette.
+///
+/// Grays, whose channels are within [`GRAYSCALE_TOLERANCE`] of each other, use the 24 step grayscale ramp (232 - 255),
+/// which is finer than the gray colors of the color cube. All other colors are quantized to the 6 levels
+/// (0, 95, 135, 175, 215 and 255) of each channel of the 6×6×6 color cube (16 - 231).
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// assert_eq!(196, ansi256_index(255, 0, 0));
+/// ```
+fn ansi256_index(r: u8, g: u8, b: u8) -> u8 {
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    if max - min <= GRAYSCALE_TOLERANCE {
+        let gray = ((r as u16 + g as u16 + b as u16) / 3) as u8;
+        //the ramp goes from 8 to 238 in steps of 10, black and white are part of the cube
+        return match gray {
+            0..=3 => 16,
+            247..=255 => 231,
+            gray => 232 + ((gray.saturating_sub(8) as u16 + 5) / 10).min(23) as u8,
+        };
+    }
+
+    //levels of the color cube are 0, 95, 135, 175, 215, 255, the first step is larger than the others
+    let level = |value: u8| match value {
+        0..=47 => 0,
+        48..=114 => 1,
+        value => (value - 35) / 40,
+    };
+    16 + 36 * level(r) + 6 * level(g) + level(b)
+}
+
+/// Returns the input wrapped in a 256 color escape sequence of the given type (38 for the foreground, 48 for the background).
+///
+/// Like the other colored strings, no escape sequences are used if colors are disabled, for example by `NO_COLOR`.
+fn with_ansi256(input: &str, kind: u8, index: u8) -> String {
+    if colored::control::SHOULD_COLORIZE.should_colorize() {
+        format!("\x1b[{kind};5;{index}m{input}{RESET}")
+    } else {
+        input.to_string()
+    }
+}
+
+/// Converts the given input string to a string colored with the nearest of the 256 colors of the xterm palette.
+///
+/// This is a lot closer to the original color than the 16 basic ansi colors of [`rgb_to_ansi`],
+/// while still working on terminals without truecolor support. See [`ansi256_index`] for how the color is chosen.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// assert_eq!("\x1b[38;5;196minput\x1b[0m", rgb_to_ansi256("input", 255, 0, 0));
+/// ```
+pub fn rgb_to_ansi256(input: &str, r: u8, g: u8, b: u8) -> String {
+    with_ansi256(input, 38, ansi256_index(r, g, b))
+}
+
+#[cfg(test)]
+mod test_convert_rgb_ansi256 {
+    use super::*;
+
+    #[test]
+    fn color_cube() {
+        assert_eq!(196, ansi256_index(255, 0, 0));
+        assert_eq!(46, ansi256_index(0, 255, 0));
+        assert_eq!(21, ansi256_index(0, 0, 255));
+        assert_eq!(208, ansi256_index(255, 135, 0));
+        //the first level is reached at 48
+        assert_eq!(16 + 36, ansi256_index(48, 0, 20));
+        assert_eq!(16, ansi256_index(47, 0, 20));
+    }
+
+    #[test]
+    fn grayscale_ramp() {
+        assert_eq!(16, ansi256_index(0, 0, 0));
+        assert_eq!(231, ansi256_index(255, 255, 255));
+        assert_eq!(232, ansi256_index(8, 8, 8));
+        assert_eq!(244, ansi256_index(128, 128, 128));
+        assert_eq!(255, ansi256_index(238, 238, 238));
+        //close to each other, but not exactly gray
+        assert_eq!(244, ansi256_index(124, 128, 132));
+    }
+
+    #[test]
+    fn escape_sequence() {
+        //ensure that colors will be used
+        std::env::set_var("CLICOLOR_FORCE", "1");
+        assert_eq!(
+            "\x1b[38;5;196minput\x1b[0m",
+            rgb_to_ansi256("input", 255, 0, 0)
+        );
     }
 }
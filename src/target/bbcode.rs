@@ -0,0 +1,117 @@
+use crate::pixel::Cell;
+
+/// Start of the code block, which keeps the characters aligned in a monospace font.
+pub const CODE_START: &str = "[code]\n";
+
+/// End of the code block.
+pub const CODE_END: &str = "[/code]\n";
+
+/// Returns a string with the given character wrapped in a color tag.
+///
+/// White spaces don't have a visible color, so they are returned without a tag.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// assert_eq!("[color=#ff0000]x[/color]", colored_char(255, 0, 0, 'x'));
+/// ```
+pub fn colored_char(red: u8, green: u8, blue: u8, char: char) -> String {
+    colored_line(&[Cell {
+        char,
+        red,
+        green,
+        blue,
+    }])
+}
+
+/// Returns the cells of a line as a string with color tags.
+///
+/// Consecutive characters with the same color share a single tag, which keeps the output small enough
+/// for the length limits of most forums. White spaces are never wrapped in a tag, since they don't have a visible color.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// let red = Cell { char: 'x', red: 255, green: 0, blue: 0 };
+/// assert_eq!("[color=#ff0000]xx[/color] ", colored_line(&[red, red, Cell { char: ' ', ..red }]));
+/// ```
+pub fn colored_line(cells: &[Cell]) -> String {
+    let mut line = String::with_capacity(cells.len() * 2);
+    //color of the currently open tag
+    let mut open = None;
+    for cell in cells {
+        let color = (cell.red, cell.green, cell.blue);
+        if cell.char.is_whitespace() || open != Some(color) {
+            if open.take().is_some() {
+                line.push_str("[/color]");
+            }
+            if !cell.char.is_whitespace() {
+                line.push_str(&format!(
+                    "[color=#{:02x}{:02x}{:02x}]",
+                    color.0, color.1, color.2
+                ));
+                open = Some(color);
+            }
+        }
+        line.push(cell.char);
+    }
+    if open.is_some() {
+        line.push_str("[/color]");
+    }
+    line
+}
+
+#[cfg(test)]
+mod test_bbcode {
+    use super::*;
+
+    fn cell(char: char, red: u8, green: u8, blue: u8) -> Cell {
+        Cell {
+            char,
+            red,
+            green,
+            blue,
+        }
+    }
+
+    #[test]
+    fn red_char() {
+        assert_eq!("[color=#ff0000]x[/color]", colored_char(255, 0, 0, 'x'));
+    }
+
+    #[test]
+    fn whitespace_is_not_colored() {
+        assert_eq!(" ", colored_char(255, 0, 0, ' '));
+    }
+
+    #[test]
+    fn same_colors_are_merged() {
+        assert_eq!(
+            "[color=#0a0b0c]ab[/color][color=#ffffff]c[/color]",
+            colored_line(&[
+                cell('a', 10, 11, 12),
+                cell('b', 10, 11, 12),
+                cell('c', 255, 255, 255)
+            ])
+        );
+    }
+
+    #[test]
+    fn whitespace_runs() {
+        assert_eq!(
+            "  [color=#ff0000]x[/color]  [color=#ff0000]y[/color] ",
+            colored_line(&[
+                cell(' ', 0, 0, 0),
+                cell(' ', 0, 0, 0),
+                cell('x', 255, 0, 0),
+                cell(' ', 255, 0, 0),
+                cell(' ', 0, 0, 0),
+                cell('y', 255, 0, 0),
+                cell(' ', 255, 0, 0),
+            ])
+        );
+    }
+
+    #[test]
+    fn empty_line() {
+        assert_eq!("", colored_line(&[]));
+    }
+}
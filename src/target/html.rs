@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 ///Returns the top part of the output html file.
 ///
 /// This contains the html elements needed for a correct html file.
@@ -12,19 +14,43 @@
 /// string.push_str(&html_top())
 /// ```
 pub fn html_top() -> String {
-    r#"<!DOCTYPE html>
+    html_top_with_style("", None)
+}
+
+///Returns the top part of the output html file, with an additional style in the head.
+///
+/// This is the same as [`html_top`], but the given style, for example a `<style>` element,
+/// is added at the end of the head element. If a background color is given, it is used
+/// for the pre element, which contains the ascii art.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// use artem::target::html;
+///
+/// let string = String::new();
+/// string.push_str(&html_top_with_style("<style>.c0 { color: #000000 }</style>", Some((255, 255, 255))))
+/// ```
+pub fn html_top_with_style(style: &str, background: Option<(u8, u8, u8)>) -> String {
+    let pre = match background {
+        Some((red, green, blue)) => {
+            format!(r#"<pre style="background-color: #{red:02X?}{green:02X?}{blue:02X?}">"#)
+        }
+        None => String::from("<pre>"),
+    };
+    format!(
+        r#"<!DOCTYPE html>
     <html lang="en">
     
     <head>
         <meta charset="UTF-8">
         <meta http-equiv="X-UA-Compatible" content="IE=edge">
         <meta name="viewport" content="width=device-width, initial-scale=1.0">
-        <title>Artem Ascii Image</title>
+        <title>Artem Ascii Image</title>{style}
     </head>
     
     <body>
-        <pre>"#
-        .to_string()
+        {pre}"#
+    )
 }
 
 #[cfg(test)]
@@ -48,6 +74,18 @@ mod test_push_html_top {
             html_top()
         )
     }
+
+    #[test]
+    fn style_is_added_to_head() {
+        let top = html_top_with_style("<style></style>", None);
+        assert!(top.contains("<title>Artem Ascii Image</title><style></style>\n    </head>"));
+    }
+
+    #[test]
+    fn background_is_added_to_pre() {
+        let top = html_top_with_style("", Some((255, 250, 10)));
+        assert!(top.ends_with("<body>\n        <pre style=\"background-color: #FFFA0A\">"));
+    }
 }
 
 ///Returns the bottom part of the output html file.
@@ -106,10 +144,37 @@ pub fn colored_char(red: u8, green: u8, blue: u8, char: char, background_color:
     }
 }
 
+/// Returns an upper half block with the upper color as the foreground and the lower color as the background.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// assert_eq!(
+///     "<span style=\"color: #FF0000; background-color: #0000FF\">▀</span>",
+///     half_block((255, 0, 0), (0, 0, 255))
+/// );
+/// ```
+pub fn half_block(
+    (red, green, blue): (u8, u8, u8),
+    (lower_red, lower_green, lower_blue): (u8, u8, u8),
+) -> String {
+    format!(
+        "<span style=\"color: #{:02X?}{:02X?}{:02X?}; background-color: #{:02X?}{:02X?}{:02X?}\">{}</span>",
+        red, green, blue, lower_red, lower_green, lower_blue, super::ansi::HALF_BLOCK
+    )
+}
+
 #[cfg(test)]
 mod test_html_string {
     use super::*;
 
+    #[test]
+    fn half_block_colors() {
+        assert_eq!(
+            "<span style=\"color: #FF0000; background-color: #0000FF\">▀</span>",
+            half_block((255, 0, 0), (0, 0, 255))
+        );
+    }
+
     #[test]
     fn whitespace_no_tag() {
         assert_eq!(" ", colored_char(0, 0, 0, ' ', false))
@@ -147,3 +212,120 @@ mod test_html_string {
         )
     }
 }
+
+/// A palette of css classes, with one class for each distinct color.
+///
+/// Instead of using an inline style for every character, like [`colored_char`], each span only
+/// references the class of its color, which are defined once in a `<style>` element.
+/// This drastically reduces the size of html files with only a few colors.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// let palette = CssPalette::new([((255, 0, 0), 'x'), ((255, 0, 0), 'y')].into_iter(), false);
+/// assert_eq!("<span class=\"c0\">x</span>", palette.colored_char(255, 0, 0, 'x'));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CssPalette {
+    colors: Vec<(u8, u8, u8)>,
+    classes: HashMap<(u8, u8, u8), usize>,
+    background_color: bool,
+}
+
+impl CssPalette {
+    /// Create a palette from the colors and characters, which will be converted.
+    ///
+    /// The classes are numbered in the order of their first appearance. Like [`colored_char`],
+    /// whitespace without a background color does not need a class.
+    pub fn new(
+        colored_chars: impl Iterator<Item = ((u8, u8, u8), char)>,
+        background_color: bool,
+    ) -> Self {
+        let mut colors = Vec::new();
+        let mut classes = HashMap::new();
+        for (color, char) in colored_chars {
+            if (background_color || !char.is_whitespace()) && !classes.contains_key(&color) {
+                classes.insert(color, colors.len());
+                colors.push(color);
+            }
+        }
+        Self {
+            colors,
+            classes,
+            background_color,
+        }
+    }
+
+    /// Returns a `<style>` element, which defines the css class of each color.
+    pub fn style(&self) -> String {
+        let property = if self.background_color {
+            "background-color"
+        } else {
+            "color"
+        };
+        let mut style = String::from("<style>");
+        for (index, (red, green, blue)) in self.colors.iter().enumerate() {
+            style.push_str(&format!(
+                ".c{index} {{ {property}: #{red:02X?}{green:02X?}{blue:02X?} }}"
+            ));
+        }
+        style.push_str("</style>");
+        style
+    }
+
+    /// Returns an html string of the given char, which references the css class of its color.
+    ///
+    /// If the color is not part of the palette, the char is returned without a span.
+    pub fn colored_char(&self, red: u8, green: u8, blue: u8, char: char) -> String {
+        match self.classes.get(&(red, green, blue)) {
+            Some(index) if self.background_color || !char.is_whitespace() => {
+                format!("<span class=\"c{index}\">{char}</span>")
+            }
+            _ => String::from(char),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_css_palette {
+    use super::*;
+
+    #[test]
+    fn one_class_per_color() {
+        let palette = CssPalette::new(
+            [((255, 0, 0), 'x'), ((0, 0, 255), 'y'), ((255, 0, 0), 'z')].into_iter(),
+            false,
+        );
+        assert_eq!(
+            "<style>.c0 { color: #FF0000 }.c1 { color: #0000FF }</style>",
+            palette.style()
+        );
+        assert_eq!(
+            "<span class=\"c0\">z</span>",
+            palette.colored_char(255, 0, 0, 'z')
+        );
+        assert_eq!(
+            "<span class=\"c1\">y</span>",
+            palette.colored_char(0, 0, 255, 'y')
+        );
+    }
+
+    #[test]
+    fn whitespace_no_class() {
+        let palette = CssPalette::new([((255, 0, 0), ' ')].into_iter(), false);
+        assert_eq!("<style></style>", palette.style());
+        assert_eq!(" ", palette.colored_char(255, 0, 0, ' '));
+    }
+
+    #[test]
+    fn whitespace_with_background() {
+        let palette = CssPalette::new([((255, 0, 0), ' ')].into_iter(), true);
+        assert_eq!(
+            "<style>.c0 { background-color: #FF0000 }</style>",
+            palette.style()
+        );
+        assert_eq!(
+            "<span class=\"c0\"> </span>",
+            palette.colored_char(255, 0, 0, ' ')
+        );
+    }
+}
@@ -0,0 +1,90 @@
+use serde::{Deserialize, Serialize};
+
+use crate::pixel::Cell;
+
+/// The json output of a converted image.
+///
+/// It contains the dimensions of the conversion, as calculated by
+/// [`ResizingDimension::calculate_dimensions`](crate::config::ResizingDimension::calculate_dimensions),
+/// and the cells row by row. The colors of the cells are always the sampled colors of the image,
+/// independent of the color support of the terminal.
+///
+/// # Examples
+/// ```
+/// use artem::{config::{ConfigBuilder, TargetType}, JsonGrid};
+///
+/// let img = image::DynamicImage::new_rgb8(512, 512);
+/// let config = ConfigBuilder::new().target(TargetType::Json).build();
+/// let json = artem::convert(img, &config).unwrap();
+/// let grid: JsonGrid = serde_json::from_str(&json).unwrap();
+/// assert_eq!(80, grid.columns);
+/// assert_eq!(grid.rows as usize, grid.cells.len());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JsonGrid {
+    /// Number of columns of the ascii art.
+    pub columns: u32,
+    /// Number of rows of the ascii art.
+    pub rows: u32,
+    /// Width of the part of the resized image, which is converted to a single cell, in pixels.
+    pub tile_width: u32,
+    /// Height of the part of the resized image, which is converted to a single cell, in pixels.
+    pub tile_height: u32,
+    /// The cells, as an array of rows.
+    pub cells: Vec<Vec<Cell>>,
+}
+
+impl JsonGrid {
+    /// Returns the grid as a json string, followed by a line break.
+    ///
+    /// # Examples
+    /// ```compile_fail, compile will fail, this is an internal example
+    /// let red = Cell { char: 'x', red: 255, green: 0, blue: 0 };
+    /// let grid = JsonGrid { columns: 1, rows: 1, tile_width: 2, tile_height: 4, cells: vec![vec![red]] };
+    /// assert_eq!(
+    ///     "{\"columns\":1,\"rows\":1,\"tile_width\":2,\"tile_height\":4,\"cells\":[[{\"char\":\"x\",\"r\":255,\"g\":0,\"b\":0}]]}\n",
+    ///     grid.to_json()
+    /// );
+    /// ```
+    pub fn to_json(&self) -> String {
+        //the grid only consists of numbers and strings, which can always be serialized
+        let mut json = serde_json::to_string(self).expect("Failed to serialize the grid");
+        json.push('\n');
+        json
+    }
+}
+
+#[cfg(test)]
+mod test_json_grid {
+    use super::*;
+
+    #[test]
+    fn cell_names() {
+        let grid = JsonGrid {
+            columns: 2,
+            rows: 1,
+            tile_width: 2,
+            tile_height: 4,
+            cells: vec![vec![
+                Cell {
+                    char: 'x',
+                    red: 12,
+                    green: 34,
+                    blue: 56,
+                },
+                Cell {
+                    char: '"',
+                    red: 0,
+                    green: 0,
+                    blue: 0,
+                },
+            ]],
+        };
+        assert_eq!(
+            "{\"columns\":2,\"rows\":1,\"tile_width\":2,\"tile_height\":4,\"cells\":[[\
+            {\"char\":\"x\",\"r\":12,\"g\":34,\"b\":56},{\"char\":\"\\\"\",\"r\":0,\"g\":0,\"b\":0}]]}\n",
+            grid.to_json()
+        );
+        assert_eq!(grid, serde_json::from_str(&grid.to_json()).unwrap());
+    }
+}
@@ -11,3 +11,20 @@ pub mod html;
 /// Ansi formatted colors. This includes the shell/terminal as well as `.ans`/`.ansi`
 /// files.
 pub mod ansi;
+
+/// Contains methods for BBCode, which is used by many forums.
+/// These wrap the characters in color tags and the output in a code block.
+pub mod bbcode;
+
+/// Contains methods for svg files.
+/// These place each row of characters as a text element in a grid.
+pub mod svg;
+
+/// Contains methods for json output.
+/// These write the characters and colors of the cells, instead of a rendered text.
+pub mod json;
+
+/// Contains methods for png images.
+/// These draw the characters with a bundled font, instead of writing them as text.
+#[cfg(feature = "png")]
+pub mod png;
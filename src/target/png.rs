@@ -0,0 +1,203 @@
+use ab_glyph::{point, Font, FontRef, PxScale, ScaleFont};
+use image::{Rgba, RgbaImage};
+
+/// Font, which is used to draw the characters.
+///
+/// DejaVu Sans Mono is bundled, since it also contains the block and braille characters, see `fonts/LICENSE`.
+const FONT: &[u8] = include_bytes!("fonts/DejaVuSansMono.ttf");
+
+/// Size of the font in pixels.
+pub const FONT_SIZE: f32 = 16.0;
+
+/// Color of the characters, if they are not colored, and of the border.
+pub const FOREGROUND: (u8, u8, u8) = (255, 255, 255);
+
+/// Color of the background, if it is not transparent.
+pub const BACKGROUND: (u8, u8, u8) = (0, 0, 0);
+
+/// A character of the image, with the color of the character and an optional background color of its cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PngCell {
+    pub char: char,
+    pub color: (u8, u8, u8),
+    pub background: Option<(u8, u8, u8)>,
+}
+
+/// Returns the bundled font.
+fn font() -> FontRef<'static> {
+    FontRef::try_from_slice(FONT).expect("The bundled font is valid")
+}
+
+/// Returns the width and height of a character cell in pixels.
+///
+/// The width is the advance of the monospace font, the height includes the ascenders and descenders,
+/// so block characters fill the whole cell.
+pub fn cell_size() -> (u32, u32) {
+    let font = font();
+    let font = font.as_scaled(PxScale::from(FONT_SIZE));
+    (
+        font.h_advance(font.glyph_id('M')).ceil() as u32,
+        font.height().ceil() as u32,
+    )
+}
+
+/// Returns the rows of cells drawn as an image.
+///
+/// Each row may have a different number of cells, the image is as wide as the longest row.
+/// The background of the cells without a background color is [`BACKGROUND`], or fully transparent
+/// if `transparent` is true, so the image can be placed on top of other graphics. The characters are
+/// drawn with their alpha coverage, so their anti-aliased edges blend with the background.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// let cell = PngCell { char: '█', color: (255, 0, 0), background: None };
+/// let image = render(&[vec![cell]], true);
+/// assert_eq!(cell_size(), image.dimensions());
+/// ```
+pub fn render(rows: &[Vec<PngCell>], transparent: bool) -> RgbaImage {
+    let (cell_width, cell_height) = cell_size();
+    let columns = rows.iter().map(Vec::len).max().unwrap_or_default() as u32;
+    let background = if transparent {
+        Rgba([BACKGROUND.0, BACKGROUND.1, BACKGROUND.2, 0])
+    } else {
+        Rgba([BACKGROUND.0, BACKGROUND.1, BACKGROUND.2, u8::MAX])
+    };
+    let mut image = RgbaImage::from_pixel(
+        columns * cell_width,
+        rows.len() as u32 * cell_height,
+        background,
+    );
+
+    let font = font();
+    let scale = PxScale::from(FONT_SIZE);
+    let ascent = font.as_scaled(scale).ascent();
+    for (row_index, row) in (0..).zip(rows) {
+        for (column, cell) in (0..).zip(row) {
+            let (x, y) = (column * cell_width, row_index * cell_height);
+            if let Some((red, green, blue)) = cell.background {
+                for p_y in y..y + cell_height {
+                    for p_x in x..x + cell_width {
+                        image.put_pixel(p_x, p_y, Rgba([red, green, blue, u8::MAX]));
+                    }
+                }
+            }
+            if cell.char.is_whitespace() {
+                continue;
+            }
+            let glyph = font
+                .glyph_id(cell.char)
+                .with_scale_and_position(scale, point(x as f32, y as f32 + ascent));
+            let Some(outline) = font.outline_glyph(glyph) else {
+                continue;
+            };
+            let bounds = outline.px_bounds();
+            outline.draw(|g_x, g_y, coverage| {
+                let p_x = bounds.min.x as i64 + g_x as i64;
+                let p_y = bounds.min.y as i64 + g_y as i64;
+                //glyphs, which are larger than their cell, are clipped at the edges of the image
+                if (0..image.width() as i64).contains(&p_x)
+                    && (0..image.height() as i64).contains(&p_y)
+                {
+                    blend(
+                        image.get_pixel_mut(p_x as u32, p_y as u32),
+                        cell.color,
+                        coverage,
+                    );
+                }
+            });
+        }
+    }
+    image
+}
+
+/// Draw the color with the given coverage (`0.0` - `1.0`) on top of the pixel.
+///
+/// The alpha of the pixel is kept, where the color does not cover it, so a transparent
+/// background stays transparent around the characters.
+fn blend(pixel: &mut Rgba<u8>, (red, green, blue): (u8, u8, u8), coverage: f32) {
+    let coverage = coverage.clamp(0f32, 1f32);
+    let alpha = pixel.0[3] as f32 / u8::MAX as f32;
+    let blended_alpha = coverage + alpha * (1f32 - coverage);
+    if blended_alpha == 0f32 {
+        return;
+    }
+    let channel = |color: u8, background: u8| {
+        ((color as f32 * coverage + background as f32 * alpha * (1f32 - coverage)) / blended_alpha)
+            .round() as u8
+    };
+    *pixel = Rgba([
+        channel(red, pixel.0[0]),
+        channel(green, pixel.0[1]),
+        channel(blue, pixel.0[2]),
+        (blended_alpha * u8::MAX as f32).round() as u8,
+    ]);
+}
+
+#[cfg(test)]
+mod test_png {
+    use super::*;
+
+    fn cell(char: char) -> PngCell {
+        PngCell {
+            char,
+            color: (255, 0, 0),
+            background: None,
+        }
+    }
+
+    #[test]
+    fn size_of_the_rows() {
+        let (cell_width, cell_height) = cell_size();
+        let image = render(&[vec![cell('a'); 3], vec![cell('b')]], false);
+        assert_eq!((3 * cell_width, 2 * cell_height), image.dimensions());
+    }
+
+    #[test]
+    fn transparent_background() {
+        let (cell_width, cell_height) = cell_size();
+        let image = render(&[vec![cell('█'), cell(' ')]], true);
+        //the full block covers the middle of its cell
+        assert_eq!(
+            &Rgba([255, 0, 0, 255]),
+            image.get_pixel(cell_width / 2, cell_height / 2)
+        );
+        //the space and the area around the characters are transparent
+        assert!((cell_width..2 * cell_width)
+            .all(|x| (0..cell_height).all(|y| image.get_pixel(x, y).0[3] == 0)));
+    }
+
+    #[test]
+    fn opaque_background() {
+        let image = render(&[vec![cell(' ')]], false);
+        assert!(image.pixels().all(|pixel| pixel == &Rgba([0, 0, 0, 255])));
+    }
+
+    #[test]
+    fn background_color() {
+        let (cell_width, cell_height) = cell_size();
+        let image = render(
+            &[vec![PngCell {
+                char: ' ',
+                color: FOREGROUND,
+                background: Some((0, 0, 255)),
+            }]],
+            true,
+        );
+        assert_eq!((cell_width, cell_height), image.dimensions());
+        assert!(image.pixels().all(|pixel| pixel == &Rgba([0, 0, 255, 255])));
+    }
+
+    #[test]
+    fn blend_on_transparent() {
+        let mut pixel = Rgba([0, 0, 0, 0]);
+        blend(&mut pixel, (255, 0, 0), 0.5);
+        assert_eq!(Rgba([255, 0, 0, 128]), pixel);
+    }
+
+    #[test]
+    fn blend_on_opaque() {
+        let mut pixel = Rgba([0, 0, 0, 255]);
+        blend(&mut pixel, (255, 0, 0), 0.5);
+        assert_eq!(Rgba([128, 0, 0, 255]), pixel);
+    }
+}
@@ -0,0 +1,190 @@
+use crate::{config::SvgFont, pixel::Cell};
+
+/// Width of a character cell, relative to the font size.
+pub const GLYPH_WIDTH: f32 = 0.6;
+
+/// Height of a character cell (the line height), relative to the font size.
+pub const LINE_HEIGHT: f32 = 1.2;
+
+/// Returns the start of the svg file, with the size of the given number of columns and rows.
+///
+/// The width and height, as well as the view box, are calculated from the columns and rows
+/// and the size of a character cell, see [`SvgFont`].
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// let top = svg_top(10, 2, &SvgFont::default());
+/// assert!(top.contains(r#"viewBox="0 0 84 33.6""#));
+/// ```
+pub fn svg_top(columns: u32, rows: u32, font: &SvgFont) -> String {
+    let width = number(columns as f32 * font.size * GLYPH_WIDTH);
+    let height = number(rows as f32 * font.size * LINE_HEIGHT);
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" viewBox=\"0 0 {width} {height}\" \
+        font-family=\"{}\" font-size=\"{}\" xml:space=\"preserve\">\n",
+        escape(&font.family),
+        number(font.size)
+    )
+}
+
+/// End of the svg file.
+pub const SVG_BOTTOM: &str = "</svg>\n";
+
+/// Returns a single row of the svg, which starts at the given row index.
+///
+/// The row consists of an optional prefix and suffix, for example for the border, which are not colored, and the cells.
+/// Consecutive cells with the same color share a `tspan` element, white spaces are not colored. If `background_color` is true, the colors are
+/// drawn as rectangles behind the characters instead. Without color, the characters are not wrapped in any element.
+/// The text is stretched to exactly fill the columns, so the characters stay aligned, even if the font is a bit narrower or wider.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// let red = Cell { char: 'x', red: 255, green: 0, blue: 0 };
+/// assert_eq!(
+///     "<text x=\"0\" y=\"14\" textLength=\"8.4\" lengthAdjust=\"spacing\"><tspan fill=\"#FF0000\">x</tspan></text>\n",
+///     text_row(0, ("", &[red], ""), &SvgFont::default(), true, false)
+/// );
+/// ```
+pub fn text_row(
+    row: u32,
+    (prefix, cells, suffix): (&str, &[Cell], &str),
+    font: &SvgFont,
+    color: bool,
+    background_color: bool,
+) -> String {
+    let cell_width = font.size * GLYPH_WIDTH;
+    let line_height = font.size * LINE_HEIGHT;
+    let prefix_columns = prefix.chars().count();
+    let columns = prefix_columns + cells.len() + suffix.chars().count();
+    let mut svg = String::with_capacity(cells.len() * 2);
+
+    //group the cells into runs of the same color
+    let mut runs: Vec<(usize, &[Cell])> = Vec::new();
+    let mut start = 0;
+    for index in 1..=cells.len() {
+        let same_color = cells.get(index).is_some_and(|cell| {
+            let first = cells[start];
+            (cell.red, cell.green, cell.blue) == (first.red, first.green, first.blue)
+        });
+        if !same_color {
+            runs.push((start, &cells[start..index]));
+            start = index;
+        }
+    }
+
+    if color && background_color {
+        for (start, run) in &runs {
+            svg.push_str(&format!(
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>\n",
+                number((prefix_columns + start) as f32 * cell_width),
+                number(row as f32 * line_height),
+                number(run.len() as f32 * cell_width),
+                number(line_height),
+                hex_color(&run[0])
+            ));
+        }
+    }
+
+    //the baseline is placed one font size below the top of the row, the rest of the line height is for the descenders
+    svg.push_str(&format!(
+        "<text x=\"0\" y=\"{}\" textLength=\"{}\" lengthAdjust=\"spacing\">",
+        number(row as f32 * line_height + font.size),
+        number(columns as f32 * cell_width)
+    ));
+    svg.push_str(&escape(prefix));
+    for (_, run) in runs {
+        let text = escape(&run.iter().map(|cell| cell.char).collect::<String>());
+        //white spaces don't have a visible color
+        if color && !background_color && !run.iter().all(|cell| cell.char.is_whitespace()) {
+            svg.push_str(&format!(
+                "<tspan fill=\"{}\">{text}</tspan>",
+                hex_color(&run[0])
+            ));
+        } else {
+            svg.push_str(&text);
+        }
+    }
+    svg.push_str(&escape(suffix));
+    svg.push_str("</text>\n");
+    svg
+}
+
+/// Returns the color of the cell as a hex color.
+fn hex_color(cell: &Cell) -> String {
+    format!("#{:02X}{:02X}{:02X}", cell.red, cell.green, cell.blue)
+}
+
+/// Returns the number rounded to two decimals, without trailing zeros.
+fn number(value: f32) -> String {
+    ((value * 100f32).round() / 100f32).to_string()
+}
+
+/// Escape the characters, which have a special meaning in xml.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod test_svg {
+    use super::*;
+
+    fn cell(char: char, red: u8, green: u8, blue: u8) -> Cell {
+        Cell {
+            char,
+            red,
+            green,
+            blue,
+        }
+    }
+
+    #[test]
+    fn top_size() {
+        let font = SvgFont {
+            family: String::from("Fira Code"),
+            size: 10f32,
+        };
+        assert_eq!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"48\" height=\"36\" viewBox=\"0 0 48 36\" \
+            font-family=\"Fira Code\" font-size=\"10\" xml:space=\"preserve\">\n",
+            svg_top(8, 3, &font)
+        );
+    }
+
+    #[test]
+    fn colored_runs() {
+        let cells = [
+            cell('a', 255, 0, 0),
+            cell('b', 255, 0, 0),
+            cell(' ', 0, 255, 0),
+            cell('c', 0, 0, 255),
+        ];
+        assert_eq!(
+            "<text x=\"0\" y=\"30.8\" textLength=\"50.4\" lengthAdjust=\"spacing\">║<tspan fill=\"#FF0000\">ab</tspan> \
+            <tspan fill=\"#0000FF\">c</tspan>║</text>\n",
+            text_row(1, ("║", &cells, "║"), &SvgFont::default(), true, false)
+        );
+    }
+
+    #[test]
+    fn background_rects() {
+        let cells = [cell('a', 255, 0, 0), cell('b', 0, 0, 255)];
+        assert_eq!(
+            "<rect x=\"0\" y=\"0\" width=\"8.4\" height=\"16.8\" fill=\"#FF0000\"/>\n\
+            <rect x=\"8.4\" y=\"0\" width=\"8.4\" height=\"16.8\" fill=\"#0000FF\"/>\n\
+            <text x=\"0\" y=\"14\" textLength=\"16.8\" lengthAdjust=\"spacing\">ab</text>\n",
+            text_row(0, ("", &cells, ""), &SvgFont::default(), true, true)
+        );
+    }
+
+    #[test]
+    fn without_color_is_escaped() {
+        let cells = [cell('<', 255, 0, 0), cell('&', 0, 0, 255)];
+        assert_eq!(
+            "<text x=\"0\" y=\"14\" textLength=\"16.8\" lengthAdjust=\"spacing\">&lt;&amp;</text>\n",
+            text_row(0, ("", &cells, ""), &SvgFont::default(), false, false)
+        );
+    }
+}
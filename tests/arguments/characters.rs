@@ -15,6 +15,20 @@ pub mod characters {
         ));
     }
 
+    #[test]
+    fn arg_is_empty() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["-c", ""]);
+        cmd.assert()
+            .failure()
+            .code(65)
+            .stderr(predicate::str::contains(
+                "The characters are empty, at least one character is required",
+            ));
+    }
+
     #[test]
     fn arg_is_number() {
         let mut cmd = Command::cargo_bin("artem").unwrap();
@@ -74,4 +88,579 @@ pub mod characters {
             ));
         }
     }
+
+    #[test]
+    fn arg_preset_detailed_is_long() {
+        let convert = |arg: &str| {
+            let mut cmd = Command::cargo_bin("artem").unwrap();
+            cmd.arg("assets/images/standard_test_img.png")
+                .args(["-c", arg]);
+            cmd.assert().success().get_output().stdout.clone()
+        };
+        assert_eq!(convert("long"), convert("detailed"));
+        assert_eq!(convert("flat"), convert("standard"));
+    }
+
+    #[test]
+    fn arg_named_presets() {
+        for (arg, characters) in [("blocks", "█▓▒░ "), ("minimal", "#:. "), ("binary", "# ")]
+        {
+            let mut cmd = Command::cargo_bin("artem").unwrap();
+            cmd.arg("assets/images/standard_test_img.png")
+                .args(["-c", arg]);
+            cmd.assert()
+                .success()
+                .stdout(predicate::function(|output: &str| {
+                    output
+                        .chars()
+                        .all(|char| char == '\n' || characters.contains(char))
+                }));
+        }
+    }
+}
+
+pub mod mapping_file {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::{fs, process::Command};
+
+    #[test]
+    fn arg_is_none() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .arg("--mapping-file");
+        cmd.assert().failure().stderr(predicate::str::contains(
+            "error: a value is required for '--mapping-file <mapping-file>' but none was supplied",
+        ));
+    }
+
+    #[test]
+    fn file_does_not_exist() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--mapping-file", "/tmp/artem_non_existing_mapping.txt"]);
+        cmd.assert()
+            .failure()
+            .stderr(predicate::str::contains("Could not read mapping file"));
+    }
+
+    #[test]
+    //windows does not like this test, it can not create the file
+    #[cfg(not(target_os = "windows"))]
+    fn invalid_mapping() {
+        fs::write("/tmp/artem_invalid_mapping.txt", "0: \n200:#\n100:.").unwrap();
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--mapping-file", "/tmp/artem_invalid_mapping.txt"]);
+        cmd.assert()
+            .failure()
+            .stderr(predicate::str::contains("Invalid mapping file"));
+        fs::remove_file("/tmp/artem_invalid_mapping.txt").unwrap();
+    }
+
+    #[test]
+    //windows does not like this test, it can not create the file
+    #[cfg(not(target_os = "windows"))]
+    fn mapped_chars() {
+        fs::write("/tmp/artem_mapping.txt", "0:.\n128:#").unwrap();
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--mapping-file", "/tmp/artem_mapping.txt"]);
+        //only the brighter bars of the test image are above the threshold
+        cmd.assert().success().stdout(predicate::str::starts_with(
+            "..........##################################....................................",
+        ));
+        fs::remove_file("/tmp/artem_mapping.txt").unwrap();
+    }
+
+    #[test]
+    fn conflicts_characters() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--mapping-file", "/tmp/artem_mapping.txt"])
+            .args(["-c", "#. "]);
+        cmd.assert().failure().stderr(predicate::str::contains(
+            "error: the argument '--mapping-file <mapping-file>' cannot be used with '--characters <characters>'",
+        ));
+    }
+}
+
+pub mod ramp_gradient {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    //windows does not like this test, it can not create the file
+    #[cfg(not(target_os = "windows"))]
+    fn start_and_end_characters() {
+        use std::fs;
+
+        //the same brightness everywhere, so only the position changes the characters
+        let path = "/tmp/artem_ramp_gradient_gray.png";
+        image::RgbImage::from_pixel(40, 40, image::Rgb([153, 153, 153]))
+            .save(path)
+            .unwrap();
+
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg(path)
+            .args(["--ramp-gradient", "#k. |@o- ", "--no-color", "-s", "20"]);
+        let output = cmd.assert().success().get_output().stdout.clone();
+        let output = String::from_utf8(output).unwrap();
+        for line in output.lines() {
+            assert!(line.starts_with('k'));
+            assert!(line.ends_with('o'));
+        }
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn invalid_ramps() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--ramp-gradient", "#k. "]);
+        cmd.assert()
+            .failure()
+            .stderr(predicate::str::contains("Invalid ramp gradient"));
+    }
+
+    #[test]
+    fn invalid_direction() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png").args([
+            "--ramp-gradient",
+            "#k. |@o- ",
+            "diagonal",
+        ]);
+        cmd.assert().failure().stderr(predicate::str::contains(
+            "diagonal is not a valid direction",
+        ));
+    }
+
+    #[test]
+    fn conflicts_characters() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--ramp-gradient", "#k. |@o- "])
+            .args(["-c", "#. "]);
+        cmd.assert()
+            .failure()
+            .stderr(predicate::str::contains("cannot be used with"));
+    }
+}
+
+pub mod ramp_rounding {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    fn arg_is_correct() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png").args([
+            "--ramp-rounding",
+            "ceil",
+            "-s",
+            "40",
+            "--no-color",
+        ]);
+        cmd.assert().success().stdout(predicate::str::starts_with(
+            ";;;;;kkkkxxxxxoooollll'........    ;;;;;",
+        ));
+    }
+
+    #[test]
+    fn floor_is_default() {
+        let mut default = Command::cargo_bin("artem").unwrap();
+        default.arg("assets/images/standard_test_img.png");
+        let mut floor = Command::cargo_bin("artem").unwrap();
+        floor
+            .arg("assets/images/standard_test_img.png")
+            .args(["--ramp-rounding", "floor"]);
+        assert_eq!(
+            default.assert().success().get_output().stdout,
+            floor.assert().success().get_output().stdout
+        );
+    }
+
+    #[test]
+    fn arg_is_invalid() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--ramp-rounding", "truncate"]);
+        cmd.assert()
+            .failure()
+            .stderr(predicate::str::contains("invalid value 'truncate'"));
+    }
+}
+
+pub mod background_char {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    fn replaces_spaces() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png").args([
+            "--background-char",
+            "_",
+            "-s",
+            "40",
+            "--no-color",
+        ]);
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::starts_with(
+                ":::::OOOOkkkkkddddoooo,........____:::::",
+            ))
+            .stdout(predicate::str::contains(" ").not());
+    }
+
+    #[test]
+    fn arg_is_not_a_char() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--background-char", "ab"]);
+        cmd.assert()
+            .failure()
+            .stderr(predicate::str::contains("ab is not a single character"));
+    }
+
+    #[test]
+    fn conflicts_threshold() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png").args([
+            "--background-char",
+            "_",
+            "--threshold",
+            "0.5",
+        ]);
+        cmd.assert()
+            .failure()
+            .stderr(predicate::str::contains("cannot be used with"));
+    }
+}
+
+pub mod tonal_ramps {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    fn band_characters() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png").args([
+            "--tonal-ramps",
+            ":. |o+-|@#%",
+            "-s",
+            "40",
+            "--no-color",
+        ]);
+        cmd.assert().success().stdout(predicate::str::starts_with(
+            "-----%%%%%oooooooo++++:::::....    -----",
+        ));
+    }
+
+    #[test]
+    fn arg_is_invalid() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--tonal-ramps", "#|."]);
+        cmd.assert().failure().stderr(predicate::str::contains(
+            "expected three sets of characters separated by a '|'",
+        ));
+    }
+
+    #[test]
+    fn empty_band() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--tonal-ramps", "#||."]);
+        cmd.assert().failure().stderr(predicate::str::contains(
+            "each band needs at least one character",
+        ));
+    }
+
+    #[test]
+    fn conflicts_characters() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--tonal-ramps", "a|b|c"])
+            .args(["-c", "#. "]);
+        cmd.assert().failure().stderr(predicate::str::contains(
+            "cannot be used with '--characters <characters>'",
+        ));
+    }
+}
+
+pub mod braille {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    fn only_braille_characters() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png").args([
+            "--braille",
+            "-s",
+            "20",
+            "--no-color",
+        ]);
+        cmd.assert()
+            .success()
+            .stdout(predicate::function(|output: &str| {
+                output
+                    .chars()
+                    .all(|char| char == '\n' || ('\u{2800}'..='\u{28FF}').contains(&char))
+            }))
+            .stdout(predicate::str::contains("⣿"));
+    }
+
+    #[test]
+    fn threshold_is_optional() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png").args([
+            "--braille",
+            "1",
+            "-s",
+            "20",
+            "--no-color",
+        ]);
+        //only pure white sets the dots
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::contains("⣿").not());
+    }
+
+    #[test]
+    fn invalid_threshold() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--braille", "1.5"]);
+        cmd.assert().failure().stderr(predicate::str::contains(
+            "The braille threshold has to be between 0.0 and 1.0, but is 1.5",
+        ));
+    }
+
+    #[test]
+    fn conflicts_directional() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--braille", "--directional"]);
+        cmd.assert().failure().stderr(predicate::str::contains(
+            "cannot be used with '--directional'",
+        ));
+    }
+}
+
+pub mod threshold {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    fn only_two_characters() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png").args([
+            "--threshold",
+            "-s",
+            "20",
+            "--no-color",
+            "--on-char",
+            "@",
+            "--off-char",
+            ".",
+        ]);
+        cmd.assert()
+            .success()
+            .stdout(predicate::function(|output: &str| {
+                output.chars().all(|char| matches!(char, '\n' | '@' | '.'))
+            }))
+            .stdout(predicate::str::contains("@"))
+            .stdout(predicate::str::contains("."));
+    }
+
+    #[test]
+    fn invalid_threshold() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--threshold", "1.5"]);
+        cmd.assert().failure().stderr(predicate::str::contains(
+            "The threshold has to be between 0.0 and 1.0, but is 1.5",
+        ));
+    }
+
+    #[test]
+    fn on_char_is_not_single_char() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--threshold", "--on-char", "ab"]);
+        cmd.assert()
+            .failure()
+            .stderr(predicate::str::contains("ab is not a single character"));
+    }
+
+    #[test]
+    fn on_char_requires_threshold() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--on-char", "@"]);
+        cmd.assert()
+            .failure()
+            .stderr(predicate::str::contains("--threshold"));
+    }
+}
+
+pub mod dither {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    //windows does not like this test, it can not create the file
+    #[cfg(not(target_os = "windows"))]
+    fn threshold_alternates_in_gradient() {
+        use std::fs;
+
+        //a smooth horizontal gradient from black to white
+        let path = "/tmp/artem_dither_gradient.png";
+        image::GrayImage::from_fn(80, 40, |x, _| image::Luma([(x * 255 / 79) as u8]))
+            .save(path)
+            .unwrap();
+        let convert = |args: &[&str]| {
+            let mut cmd = Command::cargo_bin("artem").unwrap();
+            cmd.arg(path)
+                .args(["--threshold", "--no-color", "-s", "40"])
+                .args(args);
+            String::from_utf8(cmd.assert().success().get_output().stdout.clone()).unwrap()
+        };
+
+        //without dithering, each line switches exactly once between the characters
+        let plain = convert(&[]);
+        let switches = |line: &str| {
+            line.chars()
+                .zip(line.chars().skip(1))
+                .filter(|(a, b)| a != b)
+                .count()
+        };
+        assert!(plain.lines().all(|line| switches(line) == 1));
+        //with dithering, the middle of the gradient alternates between them
+        let dithered = convert(&["--dither"]);
+        assert!(dithered.lines().any(|line| switches(line) > 1));
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn conflicts_with_braille() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--dither", "--braille"]);
+        cmd.assert()
+            .failure()
+            .stderr(predicate::str::contains("cannot be used with '--braille"));
+    }
+
+    #[test]
+    //windows does not like this test, it can not create the file
+    #[cfg(not(target_os = "windows"))]
+    fn bayer_repeats_pattern() {
+        use std::fs;
+
+        //a uniform gray between the two characters
+        let path = "/tmp/artem_dither_bayer.png";
+        image::RgbImage::from_pixel(80, 80, image::Rgb([128, 128, 128]))
+            .save(path)
+            .unwrap();
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg(path).args([
+            "--dither",
+            "bayer4",
+            "--characters",
+            "# ",
+            "--no-color",
+            "-s",
+            "20",
+        ]);
+        let output = String::from_utf8(cmd.assert().success().get_output().stdout.clone()).unwrap();
+        let lines = output.lines().collect::<Vec<_>>();
+        //adjacent parts of the same brightness alternate, the pattern is repeated every 4 lines and columns
+        assert!(lines[0].starts_with(" # #"));
+        for (index, line) in lines.iter().enumerate().skip(4) {
+            assert_eq!(lines[index - 4], *line);
+            assert_eq!(line[..4], line[4..8]);
+        }
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn stipple_depends_on_seed() {
+        let convert = |seed: &str| {
+            let mut cmd = Command::cargo_bin("artem").unwrap();
+            cmd.arg("assets/images/standard_test_img.png").args([
+                "--dither",
+                "stipple",
+                "--seed",
+                seed,
+                "--no-color",
+                "-s",
+                "40",
+            ]);
+            cmd.assert().success().get_output().stdout.clone()
+        };
+        assert_eq!(convert("1"), convert("1"));
+        assert_ne!(convert("1"), convert("2"));
+    }
+
+    #[test]
+    fn invalid_mode() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--dither", "bayer2"]);
+        cmd.assert()
+            .failure()
+            .stderr(predicate::str::contains("invalid value 'bayer2'"));
+    }
+}
+
+pub mod edges {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    fn edges_use_line_characters() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--edges", "-s", "40", "--no-color"]);
+        //the vertical color bars have strong vertical edges, while the rest uses the characters
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::contains("|"))
+            .stdout(predicate::str::contains("W"));
+    }
+
+    #[test]
+    fn invalid_threshold() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--edges", "2"]);
+        cmd.assert().failure().stderr(predicate::str::contains(
+            "The edge threshold has to be between 0.0 and 1.0, but is 2",
+        ));
+    }
+
+    #[test]
+    fn conflicts_outline() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--edges", "--outline"]);
+        cmd.assert()
+            .failure()
+            .stderr(predicate::str::contains("cannot be used with '--outline'"));
+    }
 }
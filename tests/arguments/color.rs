@@ -25,6 +25,58 @@ pub mod invert {
     }
 }
 
+pub mod negative {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    fn arg_is_correct() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .arg("--negative");
+        //similar to --invert, but the luminance of the inverted pixels is not exactly the inverted luminance
+        cmd.assert().success().stdout(predicate::str::starts_with(
+            "dddddddddd'''''''',,,,,,,,,::::::::ccccccccc000000000KKKKKKKKNNNNNNNNNdddddddddd",
+        ));
+    }
+
+    #[test]
+    //windows does not like this test, it can not create the file
+    #[cfg(not(target_os = "windows"))]
+    fn colors_are_inverted() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png").args([
+            "--negative",
+            "-o",
+            "/tmp/ascii_negative.html",
+        ]);
+        cmd.assert().success();
+        //the yellow bar of the test image is blue
+        let output = std::fs::read_to_string("/tmp/ascii_negative.html").unwrap();
+        assert!(output.contains("color: #4B4BEF"));
+        assert!(!output.contains("color: #B4B410"));
+        std::fs::remove_file("/tmp/ascii_negative.html").unwrap();
+    }
+}
+
+pub mod silhouette {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    fn opaque_image() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--silhouette", "--no-color"]);
+        //the image has no transparent pixels, so the silhouette is completely filled
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::is_match("^M+(\nM+)*\n$").unwrap());
+    }
+}
+
 pub mod no_color {
     use assert_cmd::prelude::*;
     use predicates::prelude::*;
@@ -64,6 +116,109 @@ pub mod no_color {
     }
 }
 
+pub mod force_color {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    fn pipe_is_not_colored() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        //the output of the test is not a terminal, the 256 colors do not depend on the colored crate
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--color-mode", "256", "-s", "20"])
+            .env_remove("CLICOLOR_FORCE");
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::contains("\x1b").not());
+    }
+
+    #[test]
+    fn color_alias() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--color-mode", "256", "-s", "20", "--color"])
+            .env_remove("CLICOLOR_FORCE");
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::contains("\x1b[38;5;"));
+    }
+
+    #[test]
+    fn conflicts_no_color() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--force-color", "--no-color"]);
+        cmd.assert().failure().stderr(predicate::str::starts_with(
+            "error: the argument '--force-color' cannot be used with '--no-color'",
+        ));
+    }
+}
+
+pub mod pin_color_mode {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::process::Command;
+
+    /// Returns the output with the pinned color mode in the given environment.
+    fn output(mode: &str, env: &[(&str, &str)]) -> Vec<u8> {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--pin-color-mode", mode, "-s", "20"])
+            .env_remove("CLICOLOR_FORCE")
+            .env_remove("NO_COLOR")
+            .envs(env.iter().copied());
+        cmd.assert().success().get_output().stdout.clone()
+    }
+
+    #[test]
+    fn independent_of_environment() {
+        let environments: [&[(&str, &str)]; 4] = [
+            &[],
+            &[("COLORTERM", "truecolor"), ("TERM", "xterm-256color")],
+            &[("COLORTERM", ""), ("TERM", "dumb"), ("NO_COLOR", "1")],
+            &[("CLICOLOR_FORCE", "1"), ("CLICOLOR", "0")],
+        ];
+        for (mode, escape) in [
+            ("truecolor", "\x1b[38;2;"),
+            ("256", "\x1b[38;5;"),
+            ("16", "\x1b[3"),
+        ] {
+            let expected = output(mode, environments[0]);
+            assert!(
+                String::from_utf8_lossy(&expected).contains(escape),
+                "{mode}"
+            );
+            for env in environments {
+                assert_eq!(expected, output(mode, env), "{mode} {env:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn none_is_not_colored() {
+        let output = output(
+            "none",
+            &[("CLICOLOR_FORCE", "1"), ("COLORTERM", "truecolor")],
+        );
+        assert!(!output.contains(&b'\x1b'));
+    }
+
+    #[test]
+    fn conflicts_color_mode() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png").args([
+            "--pin-color-mode",
+            "256",
+            "--color-mode",
+            "16",
+        ]);
+        cmd.assert().failure().stderr(predicate::str::starts_with(
+            "error: the argument '--pin-color-mode <pin-color-mode>' cannot be used with '--color-mode <color-mode>'",
+        ));
+    }
+}
+
 pub mod background_color {
     use assert_cmd::prelude::*;
     use predicates::prelude::*;
@@ -102,3 +257,938 @@ pub mod background_color {
             .stdout(predicate::str::starts_with(load_correct_file()));
     }
 }
+
+pub mod min_contrast {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    fn arg_is_none() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .arg("--min-contrast");
+        cmd.assert().failure().stderr(predicate::str::starts_with(
+            "error: a value is required for '--min-contrast <min-contrast>' but none was supplied",
+        ));
+    }
+
+    #[test]
+    fn ratio_out_of_range() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--min-contrast", "0.5"]);
+        cmd.assert().failure().stderr(predicate::str::starts_with(
+            "[ERROR] The minimum contrast has to be between 1.0 and 21.0",
+        ));
+    }
+
+    #[test]
+    fn background_requires_min_contrast() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--contrast-background", "#FFFFFF"]);
+        cmd.assert().failure().stderr(predicate::str::starts_with(
+            "error: the following required arguments were not provided",
+        ));
+    }
+
+    #[test]
+    fn invalid_background() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png").args([
+            "--min-contrast",
+            "4.5",
+            "--contrast-background",
+            "white",
+        ]);
+        cmd.assert().failure().stderr(predicate::str::contains(
+            "white is not a hex color like #FF8800",
+        ));
+    }
+
+    #[test]
+    fn arg_does_not_change_chars() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--min-contrast", "21"]);
+        //only check first line
+        cmd.assert().success().stdout(predicate::str::starts_with(
+            "::::::::::OOOOOOOOkkkkkkkkkxddddddddoooooooo;.................        ::::::::::",
+        ));
+    }
+}
+
+pub mod dominant_color {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::{fs, process::Command};
+
+    #[test]
+    fn arg_is_correct() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .arg("--dominant-color");
+        cmd.assert().success().stdout("#666666     \n");
+    }
+
+    #[test]
+    fn arg_no_color() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--dominant-color", "--no-color"]);
+        cmd.assert().success().stdout("#666666\n");
+    }
+
+    #[test]
+    //windows does not like this test, it can not create the file
+    #[cfg(not(target_os = "windows"))]
+    fn mostly_blue() {
+        let path = "/tmp/artem_mostly_blue.png";
+        image::RgbImage::from_fn(40, 40, |x, _| {
+            if x < 10 {
+                image::Rgb([250, 250, 250])
+            } else {
+                image::Rgb([20, 40, 220])
+            }
+        })
+        .save(path)
+        .unwrap();
+
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg(path).args(["--dominant-color", "--no-color"]);
+        let output = cmd.assert().success().get_output().stdout.clone();
+        let hex = String::from_utf8(output).unwrap();
+        let channel = |index: usize| u8::from_str_radix(&hex[index..index + 2], 16).unwrap();
+        //blue is clearly the strongest channel
+        assert!(channel(5) > 200);
+        assert!(channel(5) > channel(1) * 2 && channel(5) > channel(3) * 2);
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn multiple_inputs_are_prefixed() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.args([
+            "assets/images/standard_test_img.png",
+            "assets/images/standard_test_img.png",
+            "--dominant-color",
+            "--no-color",
+        ]);
+        cmd.assert().success().stdout(predicate::str::starts_with(
+            "assets/images/standard_test_img.png: #666666\n",
+        ));
+    }
+}
+
+pub mod color_mode {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    fn ansi256_escape_sequences() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--color-mode", "256", "-s", "20"])
+            .env("CLICOLOR_FORCE", "1");
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::contains("\x1b[38;5;"))
+            .stdout(predicate::str::contains("\x1b[38;2;").not());
+    }
+
+    #[test]
+    fn ansi256_background() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--color-mode", "256", "--background", "-s", "20"])
+            .env("CLICOLOR_FORCE", "1");
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::contains("\x1b[48;5;"));
+    }
+
+    #[test]
+    fn ansi16_background() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--color-mode", "16", "--background", "-s", "20"])
+            .env("CLICOLOR_FORCE", "1");
+        //the background codes of the 16 colors are 40-47 and 100-107
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::is_match("\x1b\\[(4[0-7]|10[0-7])m").unwrap())
+            .stderr(predicate::str::contains("Background flag will be ignored").not());
+    }
+
+    #[test]
+    fn half_blocks() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--half-blocks", "--color-mode", "256", "-s", "20"])
+            .env("CLICOLOR_FORCE", "1");
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::contains("▀"))
+            .stdout(predicate::str::contains("\x1b[48;5;"));
+    }
+
+    #[test]
+    #[cfg(not(target_os = "windows"))]
+    fn half_blocks_partial_block() {
+        //with a ratio of 0.5 each row is 2 pixels high, so the 5 pixel rows end in the upper half of the third row
+        image::RgbImage::from_pixel(4, 5, image::Rgb([255, 0, 0]))
+            .save("/tmp/artem_partial_block.png")
+            .unwrap();
+        for (partial_block, rows) in [("render", 2), ("pad", 3)] {
+            let mut cmd = Command::cargo_bin("artem").unwrap();
+            cmd.arg("/tmp/artem_partial_block.png").args([
+                "--half-blocks",
+                "--partial-block",
+                partial_block,
+                "--ratio",
+                "0.5",
+                "-s",
+                "4",
+            ]);
+            let output = cmd.assert().success().get_output().stdout.clone();
+            assert_eq!(rows, String::from_utf8(output).unwrap().lines().count());
+        }
+    }
+
+    #[test]
+    fn partial_block_requires_half_blocks() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--partial-block", "pad"]);
+        cmd.assert().failure().stderr(predicate::str::contains(
+            "the following required arguments were not provided",
+        ));
+    }
+
+    #[test]
+    fn half_blocks_conflict_no_color() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--half-blocks", "--no-color"]);
+        cmd.assert()
+            .failure()
+            .stderr(predicate::str::contains("cannot be used with"));
+    }
+
+    #[test]
+    fn only_color() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--only-color", "--color-mode", "256", "-s", "20"])
+            .env("CLICOLOR_FORCE", "1");
+        //every character is a block, which is preceded by its color
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::is_match(r"^(\x1b\[38;5;\d+m█\x1b\[0m)+\n").unwrap());
+    }
+
+    #[test]
+    fn only_color_conflict_no_color() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--only-color", "--no-color"]);
+        cmd.assert()
+            .failure()
+            .stderr(predicate::str::contains("cannot be used with"));
+    }
+
+    #[test]
+    fn capabilities_use_color_mode() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.args(["--capabilities", "--color-mode", "256"]);
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::contains("Color mode: ansi256\n"));
+    }
+
+    #[test]
+    fn ansi16_lab_distance() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--color-mode", "16", "--color-distance", "lab", "-s", "20"])
+            .env("CLICOLOR_FORCE", "1");
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::contains("\x1b[3"))
+            .stdout(predicate::str::contains("\x1b[38;").not());
+    }
+
+    #[test]
+    fn invalid_color_distance() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--color-distance", "hsv"]);
+        cmd.assert().failure().stderr(predicate::str::contains(
+            "invalid value 'hsv' for '--color-distance <color-distance>'",
+        ));
+    }
+}
+
+pub mod capabilities {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    fn without_input() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("--capabilities");
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::contains("Truecolor support: "))
+            .stdout(predicate::str::contains("Terminal width: "));
+    }
+
+    #[test]
+    fn reflects_truecolor_env() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("--capabilities")
+            .env("TERM", "xterm-256color")
+            .env("COLORTERM", "truecolor");
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::contains("Truecolor support: yes\n"))
+            .stdout(predicate::str::contains("TERM: xterm-256color\n"))
+            .stdout(predicate::str::contains("COLORTERM: truecolor\n"))
+            .stdout(predicate::str::contains("Color mode: truecolor\n"));
+    }
+
+    #[test]
+    fn reflects_missing_env() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("--capabilities")
+            .env("TERM", "dumb")
+            .env_remove("COLORTERM");
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::contains("Truecolor support: no\n"))
+            .stdout(predicate::str::contains("256 color support: no\n"))
+            .stdout(predicate::str::contains("TERM: dumb\n"))
+            .stdout(predicate::str::contains("COLORTERM: not set\n"))
+            .stdout(predicate::str::contains("Color mode: ansi\n"));
+    }
+
+    #[test]
+    fn detects_xterm_256color() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("--capabilities")
+            .env("TERM", "xterm-256color")
+            .env_remove("COLORTERM");
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::contains("256 color support: yes\n"))
+            .stdout(predicate::str::contains("Color mode: ansi256\n"));
+    }
+
+    #[test]
+    fn detects_screen_256color() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("--capabilities")
+            .env("TERM", "screen-256color")
+            .env_remove("COLORTERM");
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::contains("256 color support: yes\n"))
+            .stdout(predicate::str::contains("Color mode: ansi256\n"));
+    }
+
+    #[test]
+    fn detects_image_protocol() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("--capabilities")
+            .env("TERM", "xterm-256color")
+            .env("TERM_PROGRAM", "iTerm.app");
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::contains("TERM_PROGRAM: iTerm.app\n"))
+            .stdout(predicate::str::contains("Image protocol: iterm\n"));
+    }
+
+    #[test]
+    fn truecolor_before_256color() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("--capabilities")
+            .env("TERM", "xterm-256color")
+            .env("COLORTERM", "24bit");
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::contains("Color mode: truecolor\n"));
+    }
+
+    #[test]
+    fn conversion_uses_256color() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["-s", "20"])
+            .env("TERM", "xterm-256color")
+            .env_remove("COLORTERM")
+            .env("CLICOLOR_FORCE", "1");
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::contains("\x1b[38;5;"))
+            .stderr(predicate::str::contains("Using 256 colors"));
+    }
+
+    #[test]
+    fn no_color_mode() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.args(["--capabilities", "--no-color"]);
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::contains("Color mode: none\n"));
+    }
+}
+
+pub mod palette {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    fn arg_is_correct() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--palette", "4", "--no-color"]);
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::is_match("^(#[0-9A-F]{6}\n){4}$").unwrap());
+    }
+
+    #[test]
+    fn same_seed_same_palette() {
+        let run = || {
+            let mut cmd = Command::cargo_bin("artem").unwrap();
+            cmd.arg("assets/images/standard_test_img.png")
+                .args(["--palette", "6", "--seed", "42"]);
+            cmd.assert().success().get_output().stdout.clone()
+        };
+        assert_eq!(run(), run());
+    }
+
+    #[test]
+    fn zero_colors() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--palette", "0"]);
+        cmd.assert().failure().stderr(predicate::str::contains(
+            "The palette must have at least one color",
+        ));
+    }
+
+    #[test]
+    fn seed_invalid() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--palette", "4", "--seed", "-1"]);
+        cmd.assert()
+            .failure()
+            .stderr(predicate::str::contains("error"));
+    }
+}
+
+pub mod mono_optimize {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    fn no_color_escapes() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .arg("--mono-optimize");
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::contains("\x1b").not());
+    }
+
+    #[test]
+    //windows does not like this test, it can not create the file
+    #[cfg(not(target_os = "windows"))]
+    fn dithering_is_active() {
+        use std::fs;
+
+        //a uniform gray between the two characters can only be shown by alternating them
+        let path = "/tmp/artem_mono_optimize_gray.png";
+        image::RgbImage::from_pixel(40, 40, image::Rgb([128, 128, 128]))
+            .save(path)
+            .unwrap();
+
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg(path)
+            .args(["--mono-optimize", "--characters", "# ", "-s", "20"]);
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::contains("#").and(predicate::str::contains(" ")));
+
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg(path)
+            .args(["--no-color", "--characters", "# ", "-s", "20"]);
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::contains(" ").not());
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn conflicts_with_background() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--mono-optimize", "--background"]);
+        cmd.assert().failure().stderr(predicate::str::contains(
+            "cannot be used with '--background'",
+        ));
+    }
+}
+
+pub mod vignette {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    fn arg_is_out_of_range() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--vignette", "1.5"]);
+        cmd.assert().failure().stderr(predicate::str::contains(
+            "The vignette strength has to be between 0.0 and 1.0",
+        ));
+    }
+
+    #[test]
+    fn corners_fade_out() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png").args([
+            "--vignette",
+            "1",
+            "--no-color",
+            "-s",
+            "40",
+        ]);
+        //the corners are darkened completely, while the bright center stays visible
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::starts_with("   "))
+            .stdout(predicate::str::contains("okOOOOO00"));
+    }
+}
+
+pub mod mask {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    fn file_does_not_exist() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--mask", "does_not_exist.png"]);
+        cmd.assert()
+            .failure()
+            .stderr(predicate::str::contains("Could not read mask image"));
+    }
+
+    #[test]
+    //windows does not like this test, it can not create the file
+    #[cfg(not(target_os = "windows"))]
+    fn blanks_dark_half() {
+        //white on the left half and black on the right half
+        image::GrayImage::from_fn(64, 48, |x, _| image::Luma([if x < 32 { 255 } else { 0 }]))
+            .save("/tmp/artem_half_mask.png")
+            .unwrap();
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png").args([
+            "--mask",
+            "/tmp/artem_half_mask.png",
+            "--no-color",
+            "-s",
+            "40",
+        ]);
+        let output = cmd.assert().success().get_output().stdout.clone();
+        let output = String::from_utf8(output).unwrap();
+        for line in output.lines() {
+            assert_eq!(" ".repeat(20), line[20..]);
+            assert!(!line[..20].trim().is_empty());
+        }
+    }
+}
+
+pub mod color_step {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::{fs, process::Command};
+
+    #[test]
+    fn arg_is_zero() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--color-step", "0"]);
+        cmd.assert()
+            .failure()
+            .stderr(predicate::str::contains("invalid value '0'"));
+    }
+
+    #[test]
+    //windows does not like this test, it can not create the file
+    #[cfg(not(target_os = "windows"))]
+    fn colors_are_multiples() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("examples/abraham_lincoln.jpg").args([
+            "--color-step",
+            "64",
+            "-o",
+            "/tmp/ascii_color_step.html",
+        ]);
+        cmd.assert().success();
+        let output = fs::read_to_string("/tmp/ascii_color_step.html").unwrap();
+        for (index, _) in output.match_indices("color: #") {
+            let color = &output[index + 8..index + 14];
+            assert!(
+                ["00", "40", "80", "C0"].contains(&&color[0..2]),
+                "{color} is not a multiple of 64"
+            );
+        }
+        fs::remove_file("/tmp/ascii_color_step.html").unwrap();
+    }
+}
+
+pub mod color_palette {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::{fs, process::Command};
+
+    #[test]
+    fn arg_is_invalid() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--color-palette", "#000000,white"]);
+        cmd.assert()
+            .failure()
+            .stderr(predicate::str::contains("invalid value 'white'"));
+    }
+
+    #[test]
+    //windows does not like this test, it can not create the file
+    #[cfg(not(target_os = "windows"))]
+    fn colors_are_from_palette() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("examples/abraham_lincoln.jpg").args([
+            "--color-palette",
+            "#000000,#FF8800,#FFFFFF",
+            "-o",
+            "/tmp/ascii_color_palette.html",
+        ]);
+        cmd.assert().success();
+        let output = fs::read_to_string("/tmp/ascii_color_palette.html").unwrap();
+        for (index, _) in output.match_indices("color: #") {
+            let color = &output[index + 8..index + 14];
+            assert!(
+                ["000000", "FF8800", "FFFFFF"].contains(&color),
+                "{color} is not in the palette"
+            );
+        }
+        fs::remove_file("/tmp/ascii_color_palette.html").unwrap();
+    }
+}
+
+#[cfg(feature = "lut")]
+pub mod lut {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::{fs, process::Command};
+
+    #[test]
+    fn file_does_not_exist() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--lut", "/tmp/artem_non_existing_lut.cube"]);
+        cmd.assert()
+            .failure()
+            .stderr(predicate::str::contains("Could not read lut file"));
+    }
+
+    #[test]
+    //windows does not like this test, it can not create the file
+    #[cfg(not(target_os = "windows"))]
+    fn invalid_lut() {
+        fs::write("/tmp/artem_invalid_lut.cube", "LUT_3D_SIZE 2\n0 0 0").unwrap();
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--lut", "/tmp/artem_invalid_lut.cube"]);
+        cmd.assert()
+            .failure()
+            .stderr(predicate::str::contains("Invalid lut file"));
+        fs::remove_file("/tmp/artem_invalid_lut.cube").unwrap();
+    }
+
+    #[test]
+    //windows does not like this test, it can not create the file
+    #[cfg(not(target_os = "windows"))]
+    fn identity_lut() {
+        fs::write(
+            "/tmp/artem_identity_lut.cube",
+            "TITLE \"identity\"\nLUT_3D_SIZE 2\n0 0 0\n1 0 0\n0 1 0\n1 1 0\n0 0 1\n1 0 1\n0 1 1\n1 1 1",
+        )
+        .unwrap();
+        let expected = Command::cargo_bin("artem")
+            .unwrap()
+            .arg("assets/images/standard_test_img.png")
+            .output()
+            .unwrap()
+            .stdout;
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--lut", "/tmp/artem_identity_lut.cube"]);
+        cmd.assert()
+            .success()
+            .stdout(predicate::eq(String::from_utf8(expected).unwrap()));
+        fs::remove_file("/tmp/artem_identity_lut.cube").unwrap();
+    }
+}
+
+pub mod background_fill {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    fn arg_is_invalid() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--background-fill", "white"]);
+        cmd.assert().failure().stderr(predicate::str::contains(
+            "white is not a hex color like #FF8800",
+        ));
+    }
+
+    #[test]
+    //windows does not like this test, it can not create the file
+    #[cfg(not(target_os = "windows"))]
+    fn transparent_parts_are_spaces() {
+        use std::fs;
+
+        //a transparent left half, which is black without a background, so inverted it would be dense
+        let path = "/tmp/artem_background_fill.png";
+        image::RgbaImage::from_fn(80, 40, |x, _| {
+            image::Rgba(if x < 40 {
+                [0, 0, 0, 0]
+            } else {
+                [255, 0, 0, 255]
+            })
+        })
+        .save(path)
+        .unwrap();
+
+        let convert = |args: &[&str]| {
+            let mut cmd = Command::cargo_bin("artem").unwrap();
+            cmd.arg(path)
+                .args(["--no-color", "--invert", "-s", "20"])
+                .args(args);
+            String::from_utf8(cmd.assert().success().get_output().stdout.clone()).unwrap()
+        };
+        let filled = convert(&["--background-fill", "#FFFFFF"]);
+        assert!(filled
+            .lines()
+            .all(|line| line.starts_with(&" ".repeat(10)) && !line.trim().is_empty()));
+        let plain = convert(&[]);
+        assert!(plain.lines().all(|line| !line.starts_with(' ')));
+
+        fs::remove_file(path).unwrap();
+    }
+}
+
+pub mod brightness {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    fn arg_is_invalid() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--brightness", "2"]);
+        cmd.assert().failure().stderr(predicate::str::contains(
+            "The brightness has to be between -1.0 and 1.0, but is 2",
+        ));
+    }
+
+    #[test]
+    fn negative_darkens() {
+        let convert = |args: &[&str]| {
+            let mut cmd = Command::cargo_bin("artem").unwrap();
+            cmd.arg("assets/images/standard_test_img.png")
+                .args(["--no-color", "-s", "40"])
+                .args(args);
+            cmd.assert().success().get_output().stdout.clone()
+        };
+        //a fully darkened image only uses the lightest character
+        let dark = String::from_utf8(convert(&["--brightness", "-1"])).unwrap();
+        assert!(dark.lines().all(|line| line.trim().is_empty()));
+        assert_ne!(convert(&[]), convert(&["--brightness", "0.2"]));
+    }
+}
+
+pub mod contrast {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    fn arg_is_invalid() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--contrast", "-1.5"]);
+        cmd.assert().failure().stderr(predicate::str::contains(
+            "The contrast has to be between -1.0 and 1.0, but is -1.5",
+        ));
+    }
+
+    #[test]
+    fn changes_output() {
+        let convert = |args: &[&str]| {
+            let mut cmd = Command::cargo_bin("artem").unwrap();
+            cmd.arg("assets/images/standard_test_img.png")
+                .args(["--no-color", "-s", "40"])
+                .args(args);
+            cmd.assert().success().get_output().stdout.clone()
+        };
+        assert_eq!(convert(&[]), convert(&["--contrast", "0"]));
+        assert_ne!(convert(&[]), convert(&["--contrast", "0.5"]));
+    }
+}
+
+pub mod auto_contrast {
+    use assert_cmd::prelude::*;
+    use std::{collections::HashSet, process::Command};
+
+    #[test]
+    //windows does not like this test, it can not create the file
+    #[cfg(not(target_os = "windows"))]
+    fn widens_characters() {
+        //a dark gradient with a narrow range of brightness
+        let path = "/tmp/artem_auto_contrast_dark.png";
+        image::GrayImage::from_fn(200, 40, |x, _| image::Luma([10 + (x / 10) as u8]))
+            .save(path)
+            .unwrap();
+
+        let distinct_chars = |args: &[&str]| {
+            let mut cmd = Command::cargo_bin("artem").unwrap();
+            cmd.arg(path).args(["--no-color", "-s", "40"]).args(args);
+            let output = cmd.assert().success().get_output().stdout.clone();
+            String::from_utf8(output)
+                .unwrap()
+                .lines()
+                .flat_map(str::chars)
+                .collect::<HashSet<_>>()
+                .len()
+        };
+        assert!(distinct_chars(&[]) <= 3);
+        assert!(distinct_chars(&["--auto-contrast"]) >= 10);
+    }
+}
+
+pub mod gamma {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    fn arg_is_invalid() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--gamma", "0"]);
+        cmd.assert().failure().stderr(predicate::str::contains(
+            "The gamma has to be a positive number, but is 0",
+        ));
+    }
+
+    #[test]
+    fn changes_output() {
+        let convert = |args: &[&str]| {
+            let mut cmd = Command::cargo_bin("artem").unwrap();
+            cmd.arg("assets/images/standard_test_img.png")
+                .args(["--no-color", "-s", "40"])
+                .args(args);
+            cmd.assert().success().get_output().stdout.clone()
+        };
+        //the output changes, since the brightness is calculated differently
+        assert_ne!(convert(&[]), convert(&["--gamma"]));
+    }
+}
+
+pub mod luminance {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    fn arg_is_invalid() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--luminance", "rec2020"]);
+        cmd.assert()
+            .failure()
+            .stderr(predicate::str::contains("invalid value 'rec2020'"));
+    }
+
+    #[test]
+    fn standards_differ() {
+        let convert = |standard: &str| {
+            let mut cmd = Command::cargo_bin("artem").unwrap();
+            cmd.arg("assets/images/standard_test_img.png").args([
+                "--no-color",
+                "-s",
+                "40",
+                "--luminance",
+                standard,
+            ]);
+            cmd.assert().success().get_output().stdout.clone()
+        };
+        assert_ne!(convert("rec709"), convert("rec601"));
+        assert_ne!(convert("rec709"), convert("average"));
+    }
+}
+
+pub mod channel {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    fn arg_is_invalid() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--channel", "alpha"]);
+        cmd.assert()
+            .failure()
+            .stderr(predicate::str::contains("invalid value 'alpha'"));
+    }
+
+    #[test]
+    fn channels_differ() {
+        let convert = |channel: &str| {
+            let mut cmd = Command::cargo_bin("artem").unwrap();
+            cmd.arg("assets/images/standard_test_img.png").args([
+                "--no-color",
+                "-s",
+                "40",
+                "--channel",
+                channel,
+            ]);
+            cmd.assert().success().get_output().stdout.clone()
+        };
+        let luminance = convert("luminance");
+        for channel in ["red", "green", "blue"] {
+            assert_ne!(luminance, convert(channel));
+        }
+    }
+}
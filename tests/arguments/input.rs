@@ -1,4 +1,4 @@
-///! Test the input argument, including url and file inputs
+//! Test the input argument, including url and file inputs
 
 pub mod input {
     use assert_cmd::prelude::*; // Add methods on commands
@@ -60,7 +60,7 @@ pub mod input {
         cmd.arg("--help");
         cmd.assert().success().stdout(predicate::str::contains(
             //only test beginning, since different formatting would break the rest
-            "Paths to the target image. The original image is NOT altered.",
+            "Paths to the target image. Use - to read the image from the standard input.",
         ));
     }
 
@@ -86,16 +86,206 @@ pub mod input {
             "assets/images/standard_test_img.png",
         ]);
 
-        let mut ascii_img = String::new();
-        //add img twice, since it was given twice as an input
-        ascii_img.push_str(&load_correct_file());
-        ascii_img.push('\n');
-        ascii_img.push_str(&load_correct_file());
+        //add img twice, since it was given twice as an input, each with a header
+        let header = "==> assets/images/standard_test_img.png <==\n";
+        let ascii_img = format!(
+            "{header}{}\n{header}{}",
+            load_correct_file(),
+            load_correct_file()
+        );
         //check only the first line, the rest is likely to be correct as well
         cmd.assert()
             .success()
             .stdout(predicate::str::starts_with(ascii_img));
     }
+
+    #[test]
+    fn failed_input_does_not_abort() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+
+        cmd.args(["Cargo.toml", "assets/images/standard_test_img.png"]);
+        //the other image is still converted, but the error is reported with its exit code
+        cmd.assert()
+            .failure()
+            .code(66)
+            .stdout(predicate::str::contains(load_correct_file()))
+            .stderr(predicate::str::contains("Failed to decode the image"))
+            .stderr(predicate::str::contains(
+                "1 of the inputs could not be converted",
+            ));
+    }
+
+    #[test]
+    fn fail_fast() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+
+        cmd.args([
+            "Cargo.toml",
+            "assets/images/standard_test_img.png",
+            "--fail-fast",
+        ]);
+        cmd.assert()
+            .failure()
+            .code(66)
+            .stdout(predicate::str::is_empty())
+            .stderr(predicate::str::contains("Failed to decode the image"))
+            .stderr(predicate::str::contains("inputs could not be converted").not());
+    }
+
+    #[test]
+    fn glob_pattern() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+
+        //the pattern is quoted, so it is not expanded by a shell
+        cmd.arg("assets/images/standard_test_im?.png");
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::starts_with(load_correct_file()));
+    }
+
+    #[test]
+    fn glob_without_matches() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+
+        cmd.arg("assets/images/*.non_existing");
+        cmd.assert()
+            .failure()
+            .code(66)
+            .stderr(predicate::str::contains(
+                "No files match assets/images/*.non_existing",
+            ));
+    }
+
+    #[test]
+    //windows does not like this test, it can not create the file
+    #[cfg(not(target_os = "windows"))]
+    fn animated_gif_piped() {
+        use image::{codecs::gif::GifEncoder, Delay, Frame, Rgba, RgbaImage};
+
+        let frames = [Rgba([255, 0, 0, 255]), Rgba([0, 0, 255, 255])].map(|color| {
+            Frame::from_parts(
+                RgbaImage::from_pixel(16, 16, color),
+                0,
+                0,
+                Delay::from_numer_denom_ms(10, 1),
+            )
+        });
+        let file = std::fs::File::create("/tmp/artem_animation.gif").unwrap();
+        GifEncoder::new(file).encode_frames(frames).unwrap();
+
+        RgbaImage::from_pixel(16, 16, Rgba([255, 0, 0, 255]))
+            .save("/tmp/artem_animation_first_frame.png")
+            .unwrap();
+
+        //the output of the tests is piped, so only the first frame is converted, without clearing the screen
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("/tmp/artem_animation.gif");
+        let output = cmd.assert().success().get_output().stdout.clone();
+        let mut first_frame = Command::cargo_bin("artem").unwrap();
+        first_frame.arg("/tmp/artem_animation_first_frame.png");
+        assert_eq!(first_frame.assert().success().get_output().stdout, output);
+        assert!(!String::from_utf8(output).unwrap().contains("\x1b[2J"));
+        std::fs::remove_file("/tmp/artem_animation_first_frame.png").unwrap();
+        std::fs::remove_file("/tmp/artem_animation.gif").unwrap();
+    }
+
+    #[test]
+    fn stdin_input() {
+        //the assert_cmd command can write to the stdin of the process
+        let mut cmd = assert_cmd::Command::cargo_bin("artem").unwrap();
+
+        cmd.arg("-")
+            .write_stdin(std::fs::read("assets/images/standard_test_img.png").unwrap());
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::starts_with(load_correct_file()));
+    }
+
+    #[test]
+    fn stdin_is_empty() {
+        let mut cmd = assert_cmd::Command::cargo_bin("artem").unwrap();
+
+        cmd.arg("-").write_stdin("");
+        cmd.assert()
+            .failure()
+            .code(66)
+            .stderr(predicate::str::contains(
+                "No image data was received from stdin",
+            ));
+    }
+
+    #[test]
+    fn stdin_is_not_an_image() {
+        let mut cmd = assert_cmd::Command::cargo_bin("artem").unwrap();
+
+        cmd.arg("-").write_stdin("not an image");
+        cmd.assert()
+            .failure()
+            .code(66)
+            .stderr(predicate::str::contains("Failed to decode the image"));
+    }
+
+    #[test]
+    #[cfg(feature = "webp")]
+    fn webp_input() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+
+        cmd.args(["assets/images/abraham_lincoln.webp", "--no-color"]);
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::is_empty().not());
+    }
+
+    #[test]
+    #[cfg(not(feature = "webp"))]
+    fn webp_disabled_input() {
+        let mut cmd = assert_cmd::Command::cargo_bin("artem").unwrap();
+
+        //the format is detected from the bytes, not the extension
+        cmd.arg("-")
+            .write_stdin(std::fs::read("assets/images/abraham_lincoln.webp").unwrap());
+        cmd.assert()
+            .failure()
+            .code(66)
+            .stderr(predicate::str::contains(
+                "WebP images require enabling the `webp` feature",
+            ));
+    }
+
+    #[test]
+    #[cfg(feature = "avif")]
+    fn avif_input() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+
+        cmd.args(["assets/images/abraham_lincoln.avif", "--no-color"]);
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::is_empty().not());
+    }
+
+    #[test]
+    #[cfg(not(feature = "avif"))]
+    fn avif_disabled_input() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+
+        cmd.arg("assets/images/abraham_lincoln.avif");
+        cmd.assert()
+            .failure()
+            .code(66)
+            .stderr(predicate::str::contains(
+            "AVIF images require enabling the `avif` feature, rebuild artem with `--features avif`",
+        ));
+    }
+
+    #[test]
+    fn stdin_used_twice() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+
+        cmd.args(["-", "-"]);
+        cmd.assert()
+            .failure()
+            .stderr(predicate::str::contains("can only be used once"));
+    }
 }
 
 #[cfg(feature = "web_image")]
@@ -116,6 +306,17 @@ pub mod url_input {
         ));
     }
 
+    #[test]
+    fn file_starting_with_http() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+
+        //only http:// and https:// are URLs, other inputs are still files
+        cmd.arg("http_image.png");
+        cmd.assert().failure().stderr(predicate::str::contains(
+            "[ERROR] File http_image.png does not exist",
+        ));
+    }
+
     #[test]
     fn correct_input() {
         let mut cmd = Command::cargo_bin("artem").unwrap();
@@ -152,11 +353,13 @@ pub mod url_input {
             "https://raw.githubusercontent.com/FineFindus/artem/master/assets/images/standard_test_img.png",
         ]);
 
-        let mut ascii_img = String::new();
-        //add img twice, since it was given twice as an input
-        ascii_img.push_str(&load_correct_file());
-        ascii_img.push('\n');
-        ascii_img.push_str(&load_correct_file());
+        //add img twice, since it was given twice as an input, each with a header
+        let header = "==> https://raw.githubusercontent.com/FineFindus/artem/master/assets/images/standard_test_img.png <==\n";
+        let ascii_img = format!(
+            "{header}{}\n{header}{}",
+            load_correct_file(),
+            load_correct_file()
+        );
         //check only the first line, the rest is likely to be correct as well
         cmd.assert()
             .success()
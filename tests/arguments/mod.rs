@@ -1,6 +1,10 @@
-///! Tests for the different arguments.
-///! Some of the them are bundled into the same file, since they are similar.
-///! For example all color arguments.
+//! Tests for the different arguments.
+//! Some of the them are bundled into the same file, since they are similar.
+//! For example all color arguments.
+//!
+//! Some tests intentionally pass the argument and value as a single string,
+//! to test how the argument parsing handles spaces.
+#![allow(clippy::module_inception, clippy::suspicious_command_arg_space)]
 pub mod characters;
 pub mod color;
 pub mod input;
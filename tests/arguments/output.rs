@@ -27,6 +27,126 @@ pub mod output_file {
         fs::remove_file("/tmp/ascii.ans").unwrap();
     }
 
+    #[test]
+    //windows does not like this test, it can not create the file
+    #[cfg(not(target_os = "windows"))]
+    fn multiple_files() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png").args([
+            "-o",
+            "/tmp/ascii_multiple.txt",
+            "-o",
+            "/tmp/ascii_multiple.html",
+            "--verbose",
+            "info",
+        ]);
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::starts_with(
+                "Written 2105 bytes to /tmp/ascii_multiple.txt\nWritten 62626 bytes to /tmp/ascii_multiple.html",
+            ))
+            //the image is only converted once
+            .stderr(predicate::str::contains("Starting conversion to ascii").count(1));
+
+        let text = fs::read_to_string("/tmp/ascii_multiple.txt").unwrap();
+        let desired_output =
+            fs::read_to_string("assets/standard_test_img/standard_test_img.txt").unwrap();
+        assert_eq!(desired_output.trim_end_matches('\n'), text);
+        let html = fs::read_to_string("/tmp/ascii_multiple.html").unwrap();
+        assert!(html.starts_with("<!DOCTYPE html>"));
+
+        //delete output files
+        fs::remove_file("/tmp/ascii_multiple.txt").unwrap();
+        fs::remove_file("/tmp/ascii_multiple.html").unwrap();
+    }
+
+    #[test]
+    //windows does not like this test, it can not create the file
+    #[cfg(not(target_os = "windows"))]
+    fn file_is_html_css_classes() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png").args([
+            "-o",
+            "/tmp/ascii_classes.html",
+            "--css-classes",
+        ]);
+        //only check first line
+        cmd.assert().success().stdout(predicate::str::starts_with(
+            "Written 46604 bytes to /tmp/ascii_classes.html",
+        ));
+        let html = fs::read_to_string("/tmp/ascii_classes.html").unwrap();
+        assert!(html.contains("<style>.c0 { color: #"));
+        assert!(!html.contains("<span style="));
+        //delete output file
+        fs::remove_file("/tmp/ascii_classes.html").unwrap();
+    }
+
+    #[test]
+    //windows does not like this test, it can not create the file
+    #[cfg(not(target_os = "windows"))]
+    fn file_is_html_background() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png").args([
+            "-o",
+            "/tmp/ascii_background.html",
+            "--html-background",
+            "#1e1e1e",
+        ]);
+        cmd.assert().success();
+        let html = fs::read_to_string("/tmp/ascii_background.html").unwrap();
+        assert!(html.contains("<pre style=\"background-color: #1E1E1E\"><span style=\"color: #"));
+        //delete output file
+        fs::remove_file("/tmp/ascii_background.html").unwrap();
+    }
+
+    #[test]
+    //windows does not like this test, it can not create the file
+    #[cfg(not(target_os = "windows"))]
+    fn file_is_svg() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png").args([
+            "-o",
+            "/tmp/ascii.svg",
+            "--svg-font-family",
+            "Fira Code",
+            "--svg-font-size",
+            "10",
+        ]);
+        cmd.assert().success();
+        let svg = fs::read_to_string("/tmp/ascii.svg").unwrap();
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.contains("font-family=\"Fira Code\" font-size=\"10\""));
+        assert!(svg.contains("<tspan fill=\"#"));
+        //the svg does not contain any ansi escape codes
+        assert!(!svg.contains('\x1b'));
+        //delete output file
+        fs::remove_file("/tmp/ascii.svg").unwrap();
+    }
+
+    #[test]
+    fn svg_font_size_is_zero() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png").args([
+            "-o",
+            "/tmp/ascii_zero.svg",
+            "--svg-font-size",
+            "0",
+        ]);
+        cmd.assert().failure().stderr(predicate::str::contains(
+            "The font size has to be a positive number",
+        ));
+    }
+
+    #[test]
+    fn html_background_without_html() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--html-background", "#ffffff"]);
+        cmd.assert().success().stderr(predicate::str::contains(
+            "The --html-background argument is only used for .html output files",
+        ));
+    }
+
     #[test]
     //windows does not like this test, it can not create the file
     #[cfg(not(target_os = "windows"))]
@@ -56,6 +176,33 @@ pub mod output_file {
         //delete output file
         fs::remove_file("/tmp/test.txt").unwrap();
     }
+
+    #[test]
+    //windows does not like this test, it can not create the file
+    #[cfg(not(target_os = "windows"))]
+    fn file_plain_text_force_color() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png").args([
+            "-o",
+            "/tmp/test_force_color.txt",
+            "--force-color",
+        ]);
+        cmd.assert().success();
+        let output = fs::read_to_string("/tmp/test_force_color.txt").unwrap();
+        fs::remove_file("/tmp/test_force_color.txt").unwrap();
+        assert!(output.contains('\x1b'));
+    }
+
+    #[test]
+    fn file_can_not_be_created() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["-o", "does_not_exist/test.txt"]);
+        cmd.assert()
+            .failure()
+            .code(73)
+            .stderr(predicate::str::contains("Could not create output file"));
+    }
 }
 
 pub mod verbosity {
@@ -106,3 +253,680 @@ pub mod verbosity {
             .stderr(predicate::str::contains("ERROR"));
     }
 }
+
+pub mod stats {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::{fs, process::Command};
+
+    #[test]
+    fn no_stats_by_default() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png");
+        cmd.assert()
+            .success()
+            .stderr(predicate::str::contains("Columns").not());
+    }
+
+    #[test]
+    fn stats_dimensions() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .arg("--stats");
+        let rows = fs::read_to_string("assets/standard_test_img/standard_test_img.txt")
+            .unwrap()
+            .lines()
+            .count();
+        cmd.assert().success().stderr(
+            predicate::str::contains("Source dimensions: 640x512")
+                .and(predicate::str::contains("Columns: 80"))
+                .and(predicate::str::contains(format!("Rows: {rows}")))
+                .and(predicate::str::contains("Tile size: 8x19"))
+                .and(predicate::str::contains("Elapsed time:")),
+        );
+    }
+
+    #[test]
+    fn stats_do_not_change_output() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .arg("--stats");
+        let desired_output =
+            fs::read_to_string("assets/standard_test_img/standard_test_img.txt").unwrap();
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::starts_with(desired_output));
+    }
+}
+
+pub mod output_dir {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::{fs, process::Command};
+
+    #[test]
+    fn conflicts_output_file() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png").args([
+            "--output-dir",
+            "/tmp",
+            "-o",
+            "/tmp/ascii.txt",
+        ]);
+        cmd.assert().failure().stderr(predicate::str::starts_with(
+            "error: the argument '--output-dir <output-dir>' cannot be used with '--output <output-file>'",
+        ));
+    }
+
+    #[test]
+    fn jobs_requires_output_dir() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--jobs", "2"]);
+        cmd.assert().failure().stderr(predicate::str::starts_with(
+            "error: the following required arguments were not provided",
+        ));
+    }
+
+    #[test]
+    //windows does not like this test, it can not create the file
+    #[cfg(not(target_os = "windows"))]
+    fn many_tiny_images() {
+        let input_dir = "/tmp/artem_batch_input";
+        let output_dir = "/tmp/artem_batch_output";
+        let _ = fs::remove_dir_all(output_dir);
+        fs::create_dir_all(input_dir).unwrap();
+
+        let count = 64;
+        let inputs = (0..count)
+            .map(|index| {
+                let path = format!("{input_dir}/tiny_{index}.png");
+                image::RgbImage::from_pixel(4, 4, image::Rgb([index * 4, 0, 255 - index * 4]))
+                    .save(&path)
+                    .unwrap();
+                path
+            })
+            .collect::<Vec<_>>();
+
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.args(&inputs).args([
+            "--output-dir",
+            output_dir,
+            "--jobs",
+            "4",
+            "-s",
+            "20",
+            "--verbose",
+            "info",
+        ]);
+        //each file is written on its own, the ascii art is not printed
+        let assert = cmd
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("Written").count(count as usize));
+
+        //each worker drops its image and output, before it loads the next image
+        let stderr = String::from_utf8(assert.get_output().stderr.clone()).unwrap();
+        let peak_held = stderr
+            .lines()
+            .find_map(|line| line.split("At most ").nth(1))
+            .and_then(|line| line.split(' ').next())
+            .and_then(|count| count.parse::<usize>().ok())
+            .unwrap();
+        assert!((1..=4).contains(&peak_held));
+
+        for index in 0..count {
+            let output = fs::read_to_string(format!("{output_dir}/tiny_{index}.txt")).unwrap();
+            assert!(!output.is_empty());
+        }
+
+        fs::remove_dir_all(input_dir).unwrap();
+        fs::remove_dir_all(output_dir).unwrap();
+    }
+
+    #[test]
+    //windows does not like this test, it can not create the file
+    #[cfg(not(target_os = "windows"))]
+    fn same_file_names() {
+        let input_dir = "/tmp/artem_same_names_input";
+        let output_dir = "/tmp/artem_same_names_output";
+        let _ = fs::remove_dir_all(output_dir);
+        let inputs = ["black", "white"].map(|name| {
+            fs::create_dir_all(format!("{input_dir}/{name}")).unwrap();
+            let path = format!("{input_dir}/{name}/image.png");
+            let value = if name == "black" { 0 } else { 255 };
+            image::RgbImage::from_pixel(4, 4, image::Rgb([value; 3]))
+                .save(&path)
+                .unwrap();
+            path
+        });
+
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.args(&inputs)
+            .args(["--output-dir", output_dir, "--jobs", "2", "-s", "20"]);
+        cmd.assert()
+            .success()
+            .stderr(predicate::str::contains(format!(
+                "{}/white/image.png is written to image_2 instead",
+                input_dir
+            )));
+
+        //both images are kept, instead of overwriting each other
+        let black = fs::read_to_string(format!("{output_dir}/image.txt")).unwrap();
+        let white = fs::read_to_string(format!("{output_dir}/image_2.txt")).unwrap();
+        assert!(black.chars().all(|char| char.is_whitespace()));
+        assert!(white.chars().any(|char| !char.is_whitespace()));
+
+        fs::remove_dir_all(input_dir).unwrap();
+        fs::remove_dir_all(output_dir).unwrap();
+    }
+}
+
+pub mod sauce {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::{fs, process::Command};
+
+    #[test]
+    fn title_requires_sauce() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--sauce-title", "Test"]);
+        cmd.assert().failure().stderr(predicate::str::starts_with(
+            "error: the following required arguments were not provided",
+        ));
+    }
+
+    #[test]
+    fn only_for_ansi_files() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .arg("--sauce");
+        cmd.assert().success().stderr(predicate::str::contains(
+            "The --sauce argument is only used for .ans/.ansi output files",
+        ));
+    }
+
+    #[test]
+    //windows does not like this test, it can not create the file
+    #[cfg(not(target_os = "windows"))]
+    fn record_is_appended() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png").args([
+            "-o",
+            "/tmp/ascii_sauce.ans",
+            "--sauce",
+            "--sauce-title",
+            "Test image",
+        ]);
+        cmd.assert().success().stdout(predicate::str::starts_with(
+            "Written 2234 bytes to /tmp/ascii_sauce.ans",
+        ));
+
+        let bytes = fs::read("/tmp/ascii_sauce.ans").unwrap();
+        let (content, record) = bytes.split_at(bytes.len() - 129);
+        assert_eq!(0x1A, record[0]);
+        assert_eq!(b"SAUCE00", &record[1..8]);
+        assert_eq!(format!("{:35}", "Test image").as_bytes(), &record[8..43]);
+        //file size, width and number of lines
+        assert_eq!((content.len() as u32).to_le_bytes(), record[91..95]);
+        assert_eq!(80u16.to_le_bytes(), record[97..99]);
+        let rows = fs::read_to_string("assets/standard_test_img/standard_test_img.txt")
+            .unwrap()
+            .lines()
+            .count();
+        assert_eq!((rows as u16).to_le_bytes(), record[99..101]);
+        //delete output file
+        fs::remove_file("/tmp/ascii_sauce.ans").unwrap();
+    }
+}
+
+pub mod markdown {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    fn wraps_in_fenced_block() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--markdown", "-s", "30"]);
+        let output = cmd.assert().success().get_output().stdout.clone();
+        let output = String::from_utf8(output).unwrap();
+        let lines = output.lines().collect::<Vec<_>>();
+        assert_eq!(Some(&"```text"), lines.first());
+        assert_eq!(Some(&"```"), lines.last());
+        //the art itself does not use colors
+        assert!(!output.contains('\x1b'));
+    }
+
+    #[test]
+    fn with_border() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--markdown", "--border", "-s", "30"]);
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::contains("╝\n```"));
+    }
+
+    #[test]
+    fn conflicts_background() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--markdown", "--background"]);
+        cmd.assert()
+            .failure()
+            .stderr(predicate::str::contains("cannot be used with"));
+    }
+}
+
+pub mod whitespace {
+    use assert_cmd::prelude::*;
+    use std::process::Command;
+
+    fn convert(whitespace: &str) -> String {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("examples/abraham_lincoln.jpg").args([
+            "--whitespace",
+            whitespace,
+            "--no-color",
+            "-c",
+            "#. ",
+            "-s",
+            "60",
+        ]);
+        String::from_utf8(cmd.assert().success().get_output().stdout.clone()).unwrap()
+    }
+
+    /// Expands the tabs of a line to the next tab stop, as a terminal would.
+    fn expand_tabs(line: &str) -> String {
+        line.chars().fold(String::new(), |mut expanded, char| {
+            if char == '\t' {
+                expanded.push_str(&" ".repeat(8 - expanded.chars().count() % 8));
+            } else {
+                expanded.push(char);
+            }
+            expanded
+        })
+    }
+
+    #[test]
+    fn compact_keeps_alignment() {
+        let preserved = convert("preserve");
+        let compact = convert("compact");
+        assert!(compact.len() < preserved.len());
+        assert!(compact.contains('\t'));
+        assert!(!preserved.contains('\t'));
+        for (preserved, compact) in preserved.lines().zip(compact.lines()) {
+            assert!(!compact.ends_with(' '));
+            assert_eq!(preserved.trim_end(), expand_tabs(compact));
+        }
+    }
+
+    #[test]
+    fn compact_disables_color() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png").args([
+            "--whitespace",
+            "compact",
+            "-s",
+            "30",
+        ]);
+        let output = cmd.assert().success().get_output().stdout.clone();
+        assert!(!String::from_utf8(output).unwrap().contains('\x1b'));
+    }
+}
+
+pub mod line_ending {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::{fs, process::Command};
+
+    #[test]
+    fn crlf_file() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png").args([
+            "--line-ending",
+            "crlf",
+            "--no-color",
+            "-o",
+            "/tmp/ascii_crlf.txt",
+        ]);
+        cmd.assert().success();
+        let output = fs::read_to_string("/tmp/ascii_crlf.txt").unwrap();
+        let expected =
+            fs::read_to_string("assets/standard_test_img/standard_test_img.txt").unwrap();
+        //the rows are separated by the line ending, but there is none after the last row
+        assert_eq!(expected.replace('\n', "\r\n"), output);
+        assert!(!output.ends_with('\n'));
+        assert_eq!(output.lines().count() - 1, output.matches("\r\n").count());
+        assert_eq!(output.matches('\n').count(), output.matches("\r\n").count());
+    }
+
+    #[test]
+    fn crlf_stdout() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png").args([
+            "--line-ending",
+            "crlf",
+            "--no-color",
+        ]);
+        let output = cmd.assert().success().get_output().stdout.clone();
+        let output = String::from_utf8(output).unwrap();
+        //the last line ends with the line ending as well
+        assert!(output.ends_with("\r\n"));
+        assert_eq!(output.matches('\n').count(), output.matches("\r\n").count());
+    }
+
+    #[test]
+    fn lf_is_default() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--line-ending", "lf", "--no-color"]);
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::contains("\r").not());
+    }
+
+    #[test]
+    fn arg_is_invalid() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--line-ending", "cr"]);
+        cmd.assert()
+            .failure()
+            .stderr(predicate::str::contains("invalid value 'cr'"));
+    }
+}
+
+pub mod bbcode {
+    use assert_cmd::prelude::*;
+    use std::{fs, process::Command};
+
+    #[test]
+    //windows does not like this test, it can not create the file
+    #[cfg(not(target_os = "windows"))]
+    fn code_block_with_colors() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["-o", "/tmp/ascii_forum.bbcode"]);
+        cmd.assert().success();
+
+        let content = fs::read_to_string("/tmp/ascii_forum.bbcode").unwrap();
+        assert!(content.starts_with("[code]\n"));
+        //the last line break is removed when writing the file
+        assert!(content.ends_with("[/code]"));
+        assert!(content.contains("[color=#"));
+        assert_eq!(
+            content.matches("[color=").count(),
+            content.matches("[/color]").count()
+        );
+    }
+}
+
+pub mod sidecar {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::{fs, process::Command};
+
+    /// Returns the number of the first occurrence of `"key":` in the json.
+    fn json_number(json: &str, key: &str) -> usize {
+        let start = json.find(&format!("\"{key}\":")).unwrap() + key.len() + 3;
+        json[start..]
+            .chars()
+            .take_while(char::is_ascii_digit)
+            .collect::<String>()
+            .parse()
+            .unwrap()
+    }
+
+    #[test]
+    fn only_for_output_files() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .arg("--sidecar");
+        cmd.assert().success().stderr(predicate::str::contains(
+            "The --sidecar argument is only used for output files",
+        ));
+    }
+
+    #[test]
+    //windows does not like this test, it can not create the file
+    #[cfg(not(target_os = "windows"))]
+    fn dimensions_match_output() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png").args([
+            "-o",
+            "/tmp/ascii_sidecar.txt",
+            "--sidecar",
+            "--border",
+            "-s",
+            "50",
+        ]);
+        cmd.assert().success();
+
+        let output = fs::read_to_string("/tmp/ascii_sidecar.txt").unwrap();
+        let sidecar = fs::read_to_string("/tmp/ascii_sidecar.txt.meta").unwrap();
+        assert_eq!(
+            output
+                .lines()
+                .map(|line| line.chars().count())
+                .max()
+                .unwrap(),
+            json_number(&sidecar, "columns")
+        );
+        assert_eq!(output.lines().count(), json_number(&sidecar, "rows"));
+        assert_eq!(640, json_number(&sidecar, "source_width"));
+        assert_eq!(512, json_number(&sidecar, "source_height"));
+        assert!(sidecar.contains("\"color_mode\":\"none\""));
+
+        fs::remove_file("/tmp/ascii_sidecar.txt").unwrap();
+        fs::remove_file("/tmp/ascii_sidecar.txt.meta").unwrap();
+    }
+
+    #[test]
+    //windows does not like this test, it can not create the file
+    #[cfg(not(target_os = "windows"))]
+    fn failed_sidecar_continues_batch() {
+        let input_dir = "/tmp/artem_sidecar_input";
+        let output_dir = "/tmp/artem_sidecar_output";
+        let _ = fs::remove_dir_all(output_dir);
+        fs::create_dir_all(input_dir).unwrap();
+        let inputs = ["first", "second"].map(|name| {
+            let path = format!("{input_dir}/{name}.png");
+            image::RgbImage::from_pixel(4, 4, image::Rgb([255; 3]))
+                .save(&path)
+                .unwrap();
+            path
+        });
+        //a directory can not be overwritten by the sidecar file of the first image
+        fs::create_dir_all(format!("{output_dir}/first.txt.meta")).unwrap();
+
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.args(&inputs)
+            .args(["--output-dir", output_dir, "--sidecar", "-s", "20"]);
+        cmd.assert()
+            .failure()
+            .code(74)
+            .stderr(predicate::str::contains(format!(
+                "Could not write to sidecar file {output_dir}/first.txt.meta"
+            )))
+            .stderr(predicate::str::contains(
+                "1 of the inputs could not be converted",
+            ));
+
+        //the other image is still converted with its sidecar file
+        assert!(fs::metadata(format!("{output_dir}/first.txt")).is_ok());
+        assert!(fs::metadata(format!("{output_dir}/second.txt")).is_ok());
+        assert!(fs::metadata(format!("{output_dir}/second.txt.meta")).is_ok());
+
+        fs::remove_dir_all(input_dir).unwrap();
+        fs::remove_dir_all(output_dir).unwrap();
+    }
+}
+
+pub mod format {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    fn json_stdout() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--format", "json", "-s", "20"]);
+        let output = cmd.assert().success().get_output().stdout.clone();
+
+        let json: artem::JsonGrid = serde_json::from_slice(&output).unwrap();
+        assert_eq!((20, 6), (json.columns, json.rows));
+        assert_eq!(6, json.cells.len());
+        assert!(json.cells.iter().all(|row| row.len() == 20));
+    }
+
+    #[test]
+    fn invalid_format() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--format", "xml"]);
+        cmd.assert()
+            .failure()
+            .stderr(predicate::str::contains("invalid value 'xml'"));
+    }
+
+    #[test]
+    //windows does not like this test, it can not create the file
+    #[cfg(not(target_os = "windows"))]
+    fn json_extension() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png").args([
+            "-o",
+            "/tmp/ascii_format.json",
+            "-s",
+            "20",
+        ]);
+        cmd.assert().success();
+
+        let json: artem::JsonGrid =
+            serde_json::from_str(&std::fs::read_to_string("/tmp/ascii_format.json").unwrap())
+                .unwrap();
+        assert_eq!(20, json.columns);
+        std::fs::remove_file("/tmp/ascii_format.json").unwrap();
+    }
+}
+
+pub mod image_protocol {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    fn kitty() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png").args([
+            "--image-protocol",
+            "kitty",
+            "-s",
+            "20",
+        ]);
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::starts_with("\x1b_Ga=T,f=100,q=2,c=20,"))
+            .stdout(predicate::str::ends_with("\x1b\\\n"));
+    }
+
+    #[test]
+    fn iterm() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png").args([
+            "--image-protocol",
+            "iterm",
+            "-s",
+            "20",
+        ]);
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::starts_with("\x1b]1337;File=inline=1;"))
+            .stdout(predicate::str::contains(";width=20;"))
+            .stdout(predicate::str::ends_with("\x07\n"));
+    }
+
+    #[test]
+    fn auto_falls_back_to_ascii() {
+        //the output of the tests is not a terminal, so the protocol is not used, even in a supporting terminal
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--image-protocol", "auto", "-s", "20"])
+            .env("TERM", "xterm-kitty");
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::contains("\x1b_G").not())
+            .stdout(predicate::str::contains("dOkkk"));
+    }
+
+    #[test]
+    fn output_file_conflict() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png").args([
+            "--image-protocol",
+            "kitty",
+            "-o",
+            "/tmp/ascii.txt",
+        ]);
+        cmd.assert().failure().stderr(predicate::str::contains(
+            "the argument '--image-protocol <image-protocol>' cannot be used with",
+        ));
+    }
+}
+
+#[cfg(feature = "png")]
+pub mod png {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::{fs, process::Command};
+
+    #[test]
+    //windows does not like this test, it can not create the file
+    #[cfg(not(target_os = "windows"))]
+    fn transparent_background() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png").args([
+            "-o",
+            "/tmp/ascii_transparent.png",
+            "--transparent",
+        ]);
+        cmd.assert().success();
+
+        let image = image::open("/tmp/ascii_transparent.png")
+            .unwrap()
+            .to_rgba8();
+        //the background around the characters is transparent, while the inside of the characters is opaque
+        assert!(image.pixels().any(|pixel| pixel.0[3] == 0));
+        assert!(image.pixels().any(|pixel| pixel.0[3] == u8::MAX));
+        fs::remove_file("/tmp/ascii_transparent.png").unwrap();
+    }
+
+    #[test]
+    //windows does not like this test, it can not create the file
+    #[cfg(not(target_os = "windows"))]
+    fn opaque_background() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["-o", "/tmp/ascii_opaque.png"]);
+        cmd.assert().success();
+
+        let image = image::open("/tmp/ascii_opaque.png").unwrap().to_rgba8();
+        assert!(image.pixels().all(|pixel| pixel.0[3] == u8::MAX));
+        fs::remove_file("/tmp/ascii_opaque.png").unwrap();
+    }
+
+    #[test]
+    fn transparent_without_png() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .arg("--transparent");
+        cmd.assert().success().stderr(predicate::str::contains(
+            "The --transparent argument is only used for .png output files",
+        ));
+    }
+}
@@ -28,34 +28,44 @@ pub mod scale {
     #[test]
     fn arg_is_negative() {
         let mut cmd = Command::cargo_bin("artem").unwrap();
-        //should panic when trying to convert the arg
+        //is rejected by the validation of the config
         cmd.arg("assets/images/standard_test_img.png")
             .args(["--ratio", "-6"]);
-        cmd.assert().failure().stderr(predicate::str::starts_with(
-            "error: unexpected argument '-6' found",
-        ));
+        cmd.assert()
+            .failure()
+            .code(65)
+            .stderr(predicate::str::starts_with(
+                "[ERROR] The scale has to be a positive number, but is -6",
+            ));
     }
 
     #[test]
     fn arg_is_larger_max() {
         let mut cmd = Command::cargo_bin("artem").unwrap();
-        //should panic when trying to convert the arg
+        //is rejected by the validation of the config
         cmd.arg("assets/images/standard_test_img.png")
             .args(["--ratio", &f64::MAX.to_string()]);
-        cmd.assert().success().stdout(predicate::str::starts_with(
-            "::::::::::OOOOOOOOkkkkkkkkkxddddddddoooooooo;.................        ::::::::::",
-        ));
+        //the value is too large for a f32
+        cmd.assert()
+            .failure()
+            .code(65)
+            .stderr(predicate::str::starts_with(
+                "[ERROR] The scale has to be a positive number, but is inf",
+            ));
     }
 
     #[test]
     fn arg_is_zero() {
         let mut cmd = Command::cargo_bin("artem").unwrap();
-        //should panic when trying to convert the arg
+        //is rejected by the validation of the config
         cmd.arg("assets/images/standard_test_img.png")
             .args(["--ratio", "0"]);
-        cmd.assert().success().stdout(predicate::str::starts_with(
-            "::::::::::OOOOOOOOkkkkkkkkkxddddddddoooooooo;.................        ::::::::::",
-        ));
+        cmd.assert()
+            .failure()
+            .code(65)
+            .stderr(predicate::str::starts_with(
+                "[ERROR] The scale has to be a positive number, but is 0",
+            ));
     }
 
     #[test]
@@ -68,4 +78,119 @@ pub mod scale {
             "::::::::::OOOOOOOOkkkkkkkkkxddddddddoooooooo;.................        ::::::::::",
         ));
     }
+
+    #[test]
+    fn different_rows() {
+        let rows = |ratio: &str| {
+            let mut cmd = Command::cargo_bin("artem").unwrap();
+            cmd.arg("assets/images/standard_test_img.png")
+                .args(["--ratio", ratio, "--no-color"]);
+            let output = cmd.assert().success().get_output().stdout.clone();
+            String::from_utf8(output).unwrap().lines().count()
+        };
+        assert!(rows("0.5") > rows("0.42"));
+        //values above 1 are no longer clamped
+        assert!(rows("2") > rows("1"));
+    }
+}
+
+pub mod filter {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    fn arg_is_invalid() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--filter", "bicubic"]);
+        cmd.assert()
+            .failure()
+            .stderr(predicate::str::contains("invalid value 'bicubic'"));
+    }
+
+    #[test]
+    fn nearest_changes_output() {
+        let convert = |filter: &str| {
+            let mut cmd = Command::cargo_bin("artem").unwrap();
+            cmd.arg("assets/images/standard_test_img.png").args([
+                "--no-color",
+                "-s",
+                "40",
+                "--filter",
+                filter,
+            ]);
+            cmd.assert().success().get_output().stdout.clone()
+        };
+        assert_ne!(convert("thumbnail"), convert("nearest"));
+    }
+}
+
+pub mod char_height_compensation {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    fn arg_is_below_one() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        //is rejected by the validation of the config
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--char-height-compensation", "0.5"]);
+        cmd.assert()
+            .failure()
+            .code(65)
+            .stderr(predicate::str::starts_with(
+                "[ERROR] The char height compensation has to be at least 1.0, but is 0.5",
+            ));
+    }
+
+    #[test]
+    fn rows_require_factor() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--compensation-rows", "blank"]);
+        cmd.assert().failure().stderr(predicate::str::contains(
+            "the following required arguments were not provided",
+        ));
+    }
+
+    #[test]
+    fn doubles_rows() {
+        let convert = |args: &[&str]| {
+            let mut cmd = Command::cargo_bin("artem").unwrap();
+            cmd.arg("assets/images/standard_test_img.png")
+                .args(["-s", "40", "--ratio", "1"])
+                .args(args);
+            String::from_utf8(cmd.assert().success().get_output().stdout.clone()).unwrap()
+        };
+        let converted = convert(&[]);
+        let stretched = convert(&["--char-height-compensation", "2"]);
+        let lines = stretched.lines().collect::<Vec<_>>();
+        assert_eq!(converted.lines().count() * 2, lines.len());
+        for (row, pair) in converted.lines().zip(lines.chunks(2)) {
+            assert_eq!([row, row], pair);
+        }
+    }
+
+    #[test]
+    fn blank_rows() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png").args([
+            "-s",
+            "40",
+            "--no-color",
+            "--char-height-compensation",
+            "2",
+            "--compensation-rows",
+            "blank",
+        ]);
+        let output = cmd.assert().success().get_output().stdout.clone();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output
+            .lines()
+            .skip(1)
+            .step_by(2)
+            .all(|line| line == " ".repeat(40)));
+    }
 }
@@ -86,9 +86,41 @@ pub mod size {
             .args(["-s", "75"]);
         //only check first line
         cmd.assert().success().stdout(predicate::str::starts_with(
-            ":::::::::dOOOOOOOkkkkkkkkxdddddddoooooooo:................       ':::::::::",
+            ":::::::::xOOOOOOOkkkkkkkkxdddddddoooooooo:................       ':::::::::",
         ));
     }
+
+    #[test]
+    fn default_without_terminal() {
+        //the output of the tests is not a terminal, so the width of the terminal can not be used
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .arg("--no-color");
+        let output = cmd.assert().success().get_output().stdout.clone();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.lines().all(|line| line.chars().count() == 80));
+    }
+
+    #[test]
+    //windows does not like this test, it can not create the file
+    #[cfg(not(target_os = "windows"))]
+    fn default_output_file() {
+        //output files do not use the width of the terminal, even if stdout is a terminal
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png").args([
+            "-o",
+            "/tmp/ascii_default_size.txt",
+            "--verbose",
+            "trace",
+        ]);
+        cmd.assert().success().stderr(
+            predicate::str::contains("Using default target size for output files")
+                .and(predicate::str::contains("Using terminal width").not()),
+        );
+        let text = std::fs::read_to_string("/tmp/ascii_default_size.txt").unwrap();
+        assert!(text.lines().all(|line| line.chars().count() == 80));
+        std::fs::remove_file("/tmp/ascii_default_size.txt").unwrap();
+    }
 }
 
 pub mod width {
@@ -194,3 +226,132 @@ pub mod height {
         ));
     }
 }
+
+pub mod fit {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    fn arg_is_invalid() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--fit", "80"]);
+        cmd.assert()
+            .failure()
+            .stderr(predicate::str::contains("80 is not a size like 120x40"));
+    }
+
+    #[test]
+    fn arg_conflict_size() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--fit", "80x24", "-s", "75"]);
+        cmd.assert().failure().stderr(predicate::str::contains(
+            "the argument '--fit [<COLUMNSxROWS>]' cannot be used with '--size <size>'",
+        ));
+    }
+
+    #[test]
+    fn requires_terminal_without_value() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png").arg("--fit");
+        cmd.assert()
+            .failure()
+            .stderr(predicate::str::contains("Failed to read terminal size"));
+    }
+
+    #[test]
+    fn fits_into_both_dimensions() {
+        for size in ["80x24", "200x10", "80x1"] {
+            let (columns, rows) = size.split_once('x').unwrap();
+            let (columns, rows) = (columns.parse().unwrap(), rows.parse().unwrap());
+            let mut cmd = Command::cargo_bin("artem").unwrap();
+            cmd.arg("assets/images/standard_test_img.png")
+                .args(["--no-color", "--fit", size]);
+            let output = cmd.assert().success().get_output().stdout.clone();
+            let output = String::from_utf8(output).unwrap();
+            assert!(output.lines().count() <= rows);
+            assert!(output.lines().all(|line| line.chars().count() <= columns));
+        }
+    }
+
+    #[test]
+    fn zero_rows() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--fit", "80x0"]);
+        cmd.assert()
+            .failure()
+            .stderr(predicate::str::contains("The target size is 0"));
+    }
+}
+
+pub mod interactive {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    fn requires_terminal() {
+        //the output of the test command is not a terminal
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .arg("--interactive");
+        cmd.assert().failure().stderr(predicate::str::contains(
+            "The --interactive argument requires a terminal",
+        ));
+    }
+}
+
+pub mod stretch {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    fn exact_size() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--stretch", "50x10", "--no-color"]);
+        let output = cmd.assert().success().get_output().stdout.clone();
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(10, output.lines().count());
+        assert!(output.lines().all(|line| line.chars().count() == 50));
+    }
+
+    #[test]
+    fn zero_rows() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--stretch", "50x0"]);
+        cmd.assert()
+            .failure()
+            .code(65)
+            .stderr(predicate::str::contains(
+                "The stretched size has to be between 1x1 and 4096x4096, but is 50x0",
+            ));
+    }
+
+    #[test]
+    fn zero_columns() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--stretch", "0x10"]);
+        cmd.assert().failure().code(65);
+    }
+
+    #[test]
+    fn arg_conflict_fit() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png").args([
+            "--stretch",
+            "50x10",
+            "--fit",
+            "80x24",
+        ]);
+        cmd.assert()
+            .failure()
+            .stderr(predicate::str::contains("cannot be used with"));
+    }
+}
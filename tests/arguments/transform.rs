@@ -136,6 +136,36 @@ pub mod hysteresis {
     }
 }
 
+pub mod graded_edges {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    fn outline_is_required() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .arg("--graded-edges");
+        cmd.assert()
+            .failure()
+            .stderr(predicate::str::starts_with(
+                "error: the following required arguments were not provided:",
+            ))
+            .stderr(predicate::str::contains("--outline"));
+    }
+
+    #[test]
+    fn arg_is_correct() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--outline", "--graded-edges"]);
+        //only check first line
+        cmd.assert().success().stdout(predicate::str::starts_with(
+            "         ..                .                :                .       ..         ",
+        ));
+    }
+}
+
 pub mod border {
     use assert_cmd::prelude::*;
     use predicates::prelude::*;
@@ -165,6 +195,61 @@ pub mod border {
                 "╚══════════════════════════════════════════════════════════════════════════════╝\n",
             ));
     }
+
+    #[test]
+    fn ascii_style() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png").args([
+            "--border",
+            "--border-style",
+            "ascii",
+            "-s",
+            "30",
+        ]);
+        let output = cmd.assert().success().get_output().stdout.clone();
+        let output = String::from_utf8(output).unwrap();
+        let lines = output.lines().collect::<Vec<_>>();
+
+        //the border fits exactly into the size
+        assert_eq!(format!("+{}+", "-".repeat(28)), lines[0]);
+        assert_eq!(format!("+{}+", "-".repeat(28)), lines[lines.len() - 1]);
+        for line in &lines[1..lines.len() - 1] {
+            assert_eq!(30, line.chars().count());
+            assert!(line.starts_with('|') && line.ends_with('|'), "{line}");
+        }
+    }
+
+    #[test]
+    fn single_style() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png").args([
+            "--border",
+            "--border-style",
+            "single",
+            "-s",
+            "30",
+        ]);
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::starts_with(format!(
+                "┌{}┐\n│",
+                "─".repeat(28)
+            )))
+            .stdout(predicate::str::ends_with(format!(
+                "│\n└{}┘\n",
+                "─".repeat(28)
+            )));
+    }
+
+    #[test]
+    fn style_requires_border() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--border-style", "ascii"]);
+        cmd.assert()
+            .failure()
+            .stderr(predicate::str::contains("--border"));
+    }
 }
 
 pub mod center_x {
@@ -195,6 +280,24 @@ pub mod center_x {
     }
 }
 
+pub mod center {
+    use assert_cmd::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    fn no_terminal_is_noop() {
+        let convert = |args: &[&str]| {
+            let mut cmd = Command::cargo_bin("artem").unwrap();
+            cmd.arg("assets/images/standard_test_img.png")
+                .args(["--force-color", "-s", "20"])
+                .args(args);
+            cmd.assert().success().get_output().stdout.clone()
+        };
+        //the output of the test command is not a terminal, so its width is unknown
+        assert_eq!(convert(&[]), convert(&["--center"]));
+    }
+}
+
 pub mod center_y {
     use assert_cmd::prelude::*;
     use predicates::prelude::*;
@@ -223,3 +326,245 @@ pub mod center_y {
             .stdout(predicate::str::contains("\n"));
     }
 }
+
+pub mod rtl {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    fn arg_is_correct() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png").arg("--rtl");
+        //only check first line
+        cmd.assert().success().stdout(predicate::str::starts_with(
+            "::::::::::        .................;ooooooooddddddddxkkkkkkkkkOOOOOOOO::::::::::",
+        ));
+    }
+}
+
+pub mod reverse_lines {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    fn arg_is_correct() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .arg("--reverse-lines");
+        //only check first line
+        cmd.assert().success().stdout(predicate::str::starts_with(
+            "..........            cWWWWWWWWWWWWWWWWW                              ..........",
+        ));
+    }
+}
+
+pub mod crop {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    fn region_is_converted() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png").args([
+            "--crop",
+            "320x256+320+256",
+            "--stats",
+            "-s",
+            "30",
+        ]);
+        cmd.assert()
+            .success()
+            .stderr(predicate::str::contains("Source dimensions: 320x256"));
+    }
+
+    #[test]
+    fn invalid_region() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--crop", "320x256+20"]);
+        cmd.assert().failure().stderr(predicate::str::contains(
+            "320x256+20 is not a region like 200x100+50+20",
+        ));
+    }
+
+    #[test]
+    fn empty_region() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--crop", "0x256"]);
+        cmd.assert()
+            .failure()
+            .code(65)
+            .stderr(predicate::str::contains(
+                "The cropped region has to be at least 1x1 pixels, but is 0x256",
+            ));
+    }
+
+    #[test]
+    fn outside_of_image_is_clamped() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png").args([
+            "--crop",
+            "5x5+10000+10000",
+            "--stats",
+        ]);
+        //only the last pixel of the image is left
+        cmd.assert()
+            .success()
+            .stderr(predicate::str::contains("Source dimensions: 1x1"));
+    }
+}
+
+pub mod trim {
+    use predicates::prelude::*;
+
+    /// Returns a 40x20 png, which is transparent except for a white rectangle in the middle.
+    fn png() -> Vec<u8> {
+        let mut encoded = Vec::new();
+        image::DynamicImage::ImageRgba8(image::ImageBuffer::from_fn(40, 20, |x, y| {
+            if (10..30).contains(&x) && (8..16).contains(&y) {
+                image::Rgba([255, 255, 255, 255])
+            } else {
+                image::Rgba([0, 0, 0, 0])
+            }
+        }))
+        .write_to(
+            &mut std::io::Cursor::new(&mut encoded),
+            image::ImageFormat::Png,
+        )
+        .unwrap();
+        encoded
+    }
+
+    fn convert(args: &[&str]) -> String {
+        let mut cmd = assert_cmd::Command::cargo_bin("artem").unwrap();
+        cmd.args(["-", "-s", "20", "--ratio", "0.5", "--no-color"])
+            .args(args)
+            .write_stdin(png());
+        String::from_utf8(cmd.assert().success().get_output().stdout.clone()).unwrap()
+    }
+
+    #[test]
+    fn removes_margins() {
+        let untrimmed = convert(&[]);
+        assert!(untrimmed.lines().next().unwrap().trim().is_empty());
+
+        let trimmed = convert(&["--trim"]);
+        assert!(trimmed.lines().count() < untrimmed.lines().count());
+        assert!(trimmed
+            .lines()
+            .all(|line| line.chars().count() == 10 && !line.contains(' ')));
+    }
+
+    #[test]
+    fn before_border() {
+        let output = convert(&["--trim", "--border"]);
+        let lines = output.lines().collect::<Vec<_>>();
+        assert_eq!(12, lines[0].chars().count());
+        //the border is added right next to the first and last column
+        assert!(lines[1].starts_with('║') && !lines[1].starts_with("║ "));
+        assert!(lines[1].ends_with('║') && !lines[1].ends_with(" ║"));
+    }
+
+    #[test]
+    fn stats_use_trimmed_size() {
+        let mut cmd = assert_cmd::Command::cargo_bin("artem").unwrap();
+        cmd.args(["-", "-s", "20", "--ratio", "0.5", "--trim", "--stats"])
+            .write_stdin(png());
+        cmd.assert()
+            .success()
+            .stderr(predicate::str::contains("Columns: 10"));
+    }
+}
+
+pub mod rotate {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    fn swaps_dimensions() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--rotate", "90", "--stats"]);
+        cmd.assert()
+            .success()
+            .stderr(predicate::str::contains("Source dimensions: 512x640"));
+    }
+
+    #[test]
+    fn invalid_degrees() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--rotate", "45"]);
+        cmd.assert()
+            .failure()
+            .stderr(predicate::str::contains("invalid value '45' for '--rotate"));
+    }
+}
+
+#[cfg(feature = "exif")]
+pub mod auto_orient {
+    use predicates::prelude::*;
+
+    /// Returns a 40x10 jpeg, which has the given EXIF orientation.
+    fn jpeg(orientation: u8) -> Vec<u8> {
+        let mut encoded = Vec::new();
+        image::DynamicImage::new_rgb8(40, 10)
+            .write_to(
+                &mut std::io::Cursor::new(&mut encoded),
+                image::ImageFormat::Jpeg,
+            )
+            .unwrap();
+        //big endian EXIF data with a single orientation entry
+        let mut segment = b"Exif\0\0MM\0\x2a\0\0\0\x08\0\x01\x01\x12\0\x03\0\0\0\x01\0".to_vec();
+        segment.extend([orientation, 0, 0, 0, 0, 0, 0]);
+        let mut jpeg = vec![0xFF, 0xD8, 0xFF, 0xE1];
+        jpeg.extend((segment.len() as u16 + 2).to_be_bytes());
+        jpeg.extend(segment);
+        //skip the start of image marker of the encoded image
+        jpeg.extend(&encoded[2..]);
+        jpeg
+    }
+
+    #[test]
+    fn rotates_image() {
+        let mut cmd = assert_cmd::Command::cargo_bin("artem").unwrap();
+        cmd.args(["-", "--stats"]).write_stdin(jpeg(6));
+        cmd.assert()
+            .success()
+            .stderr(predicate::str::contains("Source dimensions: 10x40"));
+    }
+
+    #[test]
+    fn mirrored_image_keeps_dimensions() {
+        let mut cmd = assert_cmd::Command::cargo_bin("artem").unwrap();
+        cmd.args(["-", "--stats"]).write_stdin(jpeg(2));
+        cmd.assert()
+            .success()
+            .stderr(predicate::str::contains("Source dimensions: 40x10"));
+    }
+
+    #[test]
+    fn disabled() {
+        let mut cmd = assert_cmd::Command::cargo_bin("artem").unwrap();
+        cmd.args(["-", "--stats", "--no-auto-orient"])
+            .write_stdin(jpeg(6));
+        cmd.assert()
+            .success()
+            .stderr(predicate::str::contains("Source dimensions: 40x10"));
+    }
+
+    #[test]
+    fn before_rotate() {
+        let mut cmd = assert_cmd::Command::cargo_bin("artem").unwrap();
+        cmd.args(["-", "--stats", "--rotate", "90"])
+            .write_stdin(jpeg(6));
+        cmd.assert()
+            .success()
+            .stderr(predicate::str::contains("Source dimensions: 40x10"));
+    }
+}
@@ -1,6 +1,6 @@
+//! Utilities and common function between tests.
+//! It includes functions to help loading expected results to compare against.
 use std::fs;
-///! Utilities and common function between tests.
-///! It includes functions to help loading expected results to compare against.
 
 /// Load the correct files.
 ///
@@ -193,3 +193,33 @@ fn full_file_compare_html_background_color() {
 
     assert_str_eq!(desired_output, file_output);
 }
+
+#[test]
+fn print_image_to_ends_with_reset() {
+    let mut output = Vec::new();
+    //a short output of only a few characters still ends with a reset
+    let config = artem::config::ConfigBuilder::new()
+        .target_size(std::num::NonZeroU32::new(20).unwrap())
+        .build();
+    artem::print_image_to(&mut output, "assets/images/standard_test_img.png", &config).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.ends_with("\x1b[0m"));
+}
+
+#[test]
+fn library_checkerboard() {
+    //an 8x8 checkerboard with squares of 8x8 pixels, so each square is a single character
+    let img = image::DynamicImage::ImageLuma8(image::ImageBuffer::from_fn(64, 64, |x, y| {
+        image::Luma([if (x / 8 + y / 8) % 2 == 0 { 255 } else { 0 }])
+    }));
+    let config = artem::config::ConfigBuilder::new()
+        .target_size(std::num::NonZeroU32::new(8).unwrap())
+        .scale(1.0)
+        .color(false)
+        .build();
+
+    assert_str_eq!(
+        "M M M M \n M M M M\nM M M M \n M M M M\nM M M M \n M M M M\nM M M M \n M M M M\n",
+        artem::convert(img, &config).unwrap()
+    );
+}